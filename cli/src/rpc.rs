@@ -113,6 +113,11 @@ pub fn request(config: Config, debug: bool, dry_run: bool, rpc: Rpc) -> ! {
 		CollectionPerf(x)         => req_resp!(x, debug, rpc::resp::CollectionPerf),
 		CollectionHealth(x)       => req_resp!(x, debug, rpc::resp::CollectionHealth),
 		CollectionResourceSize(x) => req_resp!(x, debug, rpc::resp::CollectionResourceSize),
+		CollectionDuplicates(x)   => req_resp!(x, debug, rpc::resp::CollectionDuplicates),
+		CollectionMistags(x)      => req_resp!(x, debug, rpc::resp::CollectionMistags),
+		CollectionExport(x)       => req_resp!(x, debug, rpc::resp::CollectionExport),
+		CollectionDiffLast(x)     => req_resp!(x, debug, rpc::resp::CollectionDiffLast),
+		CollectionRecent(x)       => req_resp!(x, debug, rpc::resp::CollectionRecent),
 
 		DaemonConfig(x)      => req_resp!(x, debug, rpc::resp::DaemonConfig),
 		DaemonMethods(x)     => req_resp!(x, debug, rpc::resp::DaemonMethods),
@@ -132,6 +137,7 @@ pub fn request(config: Config, debug: bool, dry_run: bool, rpc: Rpc) -> ! {
 		StateRepeat(x)     => req_resp!(x, debug, rpc::resp::StateRepeat),
 		StateRuntime(x)    => req_resp!(x, debug, rpc::resp::StateRuntime),
 		StateVolume(x)     => req_resp!(x, debug, rpc::resp::StateVolume),
+		StateSleepTimer(x) => req_resp!(x, debug, rpc::resp::StateSleepTimer),
 
 		KeyArtist(x)        => req_resp!(x, debug, rpc::resp::KeyArtist),
 		KeyAlbum(x)         => req_resp!(x, debug, rpc::resp::KeyAlbum),
@@ -182,14 +188,20 @@ pub fn request(config: Config, debug: bool, dry_run: bool, rpc: Rpc) -> ! {
 		Stop(x)        => req_resp!(x, debug, rpc::resp::Stop),
 		Next(x)        => req_resp!(x, debug, rpc::resp::Status),
 		Previous(x)    => req_resp!(x, debug, rpc::resp::Status),
+		NextChapter(x)     => req_resp!(x, debug, rpc::resp::Status),
+		PreviousChapter(x) => req_resp!(x, debug, rpc::resp::Status),
 		Skip(x)        => req_resp!(x, debug, rpc::resp::Status),
 		Back(x)        => req_resp!(x, debug, rpc::resp::Status),
 		Seek(x)        => req_resp!(x, debug, rpc::resp::Status),
 		Shuffle(x)     => req_resp!(x, debug, rpc::resp::Status),
 		Repeat(x)      => req_resp!(x, debug, rpc::resp::Repeat),
+		AutoDj(x)      => req_resp!(x, debug, rpc::resp::AutoDj),
+		SleepTimer(x)  => req_resp!(x, debug, rpc::resp::SleepTimer),
 		Volume(x)      => req_resp!(x, debug, rpc::resp::Volume),
 		VolumeUp(x)    => req_resp!(x, debug, rpc::resp::VolumeUp),
 		VolumeDown(x)  => req_resp!(x, debug, rpc::resp::VolumeDown),
+		VolumeDb(x)    => req_resp!(x, debug, rpc::resp::VolumeDb),
+		ChannelMix(x)  => req_resp!(x, debug, rpc::resp::ChannelMix),
 
 		QueueAddKeyArtist(x)  => req_resp!(x, debug, rpc::resp::Status),
 		QueueAddKeyAlbum(x)   => req_resp!(x, debug, rpc::resp::Status),
@@ -204,6 +216,12 @@ pub fn request(config: Config, debug: bool, dry_run: bool, rpc: Rpc) -> ! {
 		QueueAddPlaylist(x)   => req_resp!(x, debug, rpc::resp::Status),
 		QueueSetIndex(x)      => req_resp!(x, debug, rpc::resp::QueueSetIndex),
 		QueueRemoveRange(x)   => req_resp!(x, debug, rpc::resp::QueueRemoveRange),
+		QueueMove(x)          => req_resp!(x, debug, rpc::resp::QueueMove),
+		QueueInsert(x)        => req_resp!(x, debug, rpc::resp::QueueInsert),
+		QueueSave(x)          => req_resp!(x, debug, rpc::resp::QueueSave),
+		QueueRestore(x)       => req_resp!(x, debug, rpc::resp::QueueRestore),
+		QueueSnapshots(x)     => req_resp!(x, debug, rpc::resp::QueueSnapshots),
+		QueueSnapshotDelete(x) => req_resp!(x, debug, rpc::resp::QueueSnapshotDelete),
 
 		PlaylistNew(x)          => req_resp!(x, debug, rpc::resp::PlaylistNew),
 		PlaylistRemove(x)       => req_resp!(x, debug, rpc::resp::PlaylistRemove),
@@ -219,6 +237,31 @@ pub fn request(config: Config, debug: bool, dry_run: bool, rpc: Rpc) -> ! {
 		PlaylistSingle(x)       => req_resp!(x, debug, rpc::resp::PlaylistSingle),
 		PlaylistBrief(x)        => req_resp!(x, debug, rpc::resp::PlaylistBrief),
 		PlaylistFull(x)         => req_resp!(x, debug, rpc::resp::PlaylistFull),
+		PlaylistImport(x)       => req_resp!(x, debug, rpc::resp::PlaylistImport),
+		PlaylistExport(x)       => req_resp!(x, debug, rpc::resp::PlaylistExport),
+		PlaylistRename(x)       => req_resp!(x, debug, rpc::resp::PlaylistRename),
+		PlaylistMoveIndex(x)    => req_resp!(x, debug, rpc::resp::PlaylistMoveIndex),
+		PlaylistInsertKeys(x)   => req_resp!(x, debug, rpc::resp::PlaylistInsertKeys),
+		PlaylistDedup(x)        => req_resp!(x, debug, rpc::resp::PlaylistDedup),
+		PlaylistSort(x)         => req_resp!(x, debug, rpc::resp::PlaylistSort),
+		PlaylistRepair(x)       => req_resp!(x, debug, rpc::resp::PlaylistRepair),
+		PlaylistRepairApply(x)  => req_resp!(x, debug, rpc::resp::PlaylistRepairApply),
+		PlaylistMetadataSet(x)  => req_resp!(x, debug, rpc::resp::PlaylistMetadataSet),
+
+		SmartPlaylistSet(x)     => req_resp!(x, debug, rpc::resp::SmartPlaylistSet),
+		SmartPlaylistRemove(x)  => req_resp!(x, debug, rpc::resp::SmartPlaylistRemove),
+		SmartPlaylistRefresh(x) => req_resp!(x, debug, rpc::resp::SmartPlaylistRefresh),
+		SmartPlaylistFull(x)    => req_resp!(x, debug, rpc::resp::SmartPlaylistFull),
+
+		History(x) => req_resp!(x, debug, rpc::resp::History),
+
+		RateSong(x)  => req_resp!(x, debug, rpc::resp::RateSong),
+		RateAlbum(x) => req_resp!(x, debug, rpc::resp::RateAlbum),
+		Favorites(x) => req_resp!(x, debug, rpc::resp::Favorites),
+
+		ResumePolicy(x) => req_resp!(x, debug, rpc::resp::ResumePolicy),
+
+		AudioErrors(x) => req_resp!(x, debug, rpc::resp::AudioErrors),
 	}
 }
 
@@ -284,11 +327,31 @@ mod tests {
 			// Assert it is the same as expected output.
 			if !$expected_json_output.is_empty() {
 				println!("================================================= Assert Output for [{:#?}]", $method);
-				assert_eq!(string, $expected_json_output);
+				assert_eq!(mask_timestamps(&string), mask_timestamps($expected_json_output));
 			}
 		)* }}
 	}
 
+	// Playlist `created`/`modified` are the time the test ran,
+	// so replace their values with `0` before comparing.
+	fn mask_timestamps(json: &str) -> String {
+		let mut out = String::with_capacity(json.len());
+		let mut rest = json;
+
+		while let Some(i) = ["\"created\": ", "\"modified\": "]
+			.iter()
+			.filter_map(|k| rest.find(k).map(|i| i + k.len()))
+			.min()
+		{
+			out.push_str(&rest[..i]);
+			rest = rest[i..].trim_start_matches(|c: char| c.is_ascii_digit());
+			out.push('0');
+		}
+
+		out.push_str(rest);
+		out
+	}
+
 	#[test]
 	#[ignore]
 	// This launches `festivald`, and tests every
@@ -505,7 +568,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -516,7 +584,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -527,7 +600,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "flac",
@@ -538,7 +616,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "m4a",
@@ -549,7 +632,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "song_6",
@@ -560,7 +648,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -571,7 +664,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       }
     ],
     "sort_artist_lexi": [
@@ -1055,7 +1153,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -1066,7 +1169,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -1077,7 +1185,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "flac",
@@ -1088,7 +1201,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "m4a",
@@ -1099,7 +1217,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "song_6",
@@ -1110,7 +1233,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -1121,7 +1249,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       }
     ]
   },
@@ -1159,6 +1292,7 @@ r#"{
     ],
     "sleep_on_fail": 3000,
     "collection_paths": [],
+    "fingerprint_seconds": 0,
     "tls": false,
     "certificate": null,
     "key": null,
@@ -1172,6 +1306,15 @@ r#"{
     "cache_time": 3600,
     "restore_audio_state": false,
     "previous_threshold": 3,
+    "previous_history": false,
+    "auto_dj": false,
+    "resume_threshold": 1200,
+    "volume_db_curve": false,
+    "volume_step": 5,
+    "limiter": true,
+    "max_decode_errors": 100,
+    "resampler_quality": "balanced",
+    "bit_perfect": false,
     "media_controls": false,
     "authorization": false,
     "confirm_no_tls_auth": false,
@@ -1187,7 +1330,7 @@ r#"{
 r#"{
   "jsonrpc": "2.0",
   "result": {
-    "len": 109,
+    "len": 147,
     "methods": [
       "collection_new",
       "collection_brief",
@@ -1202,6 +1345,11 @@ r#"{
       "collection_perf",
       "collection_health",
       "collection_resource_size",
+      "collection_duplicates",
+      "collection_mistags",
+      "collection_export",
+      "collection_diff_last",
+      "collection_recent",
       "daemon_config",
       "daemon_methods",
       "daemon_no_auth_rpc",
@@ -1219,6 +1367,7 @@ r#"{
       "state_repeat",
       "state_runtime",
       "state_volume",
+      "state_sleep_timer",
       "key_artist",
       "key_album",
       "key_song",
@@ -1262,15 +1411,21 @@ r#"{
       "next",
       "stop",
       "previous",
+      "next_chapter",
+      "previous_chapter",
       "clear",
       "seek",
       "skip",
       "back",
       "shuffle",
       "repeat",
+      "auto_dj",
+      "sleep_timer",
       "volume",
       "volume_up",
       "volume_down",
+      "volume_db",
+      "channel_mix",
       "queue_add_key_artist",
       "queue_add_key_album",
       "queue_add_key_song",
@@ -1284,6 +1439,12 @@ r#"{
       "queue_add_playlist",
       "queue_set_index",
       "queue_remove_range",
+      "queue_move",
+      "queue_insert",
+      "queue_save",
+      "queue_restore",
+      "queue_snapshots",
+      "queue_snapshot_delete",
       "playlist_new",
       "playlist_remove",
       "playlist_clone",
@@ -1297,7 +1458,27 @@ r#"{
       "playlist_add_map_song",
       "playlist_single",
       "playlist_brief",
-      "playlist_full"
+      "playlist_full",
+      "playlist_import",
+      "playlist_export",
+      "playlist_rename",
+      "playlist_move_index",
+      "playlist_insert_keys",
+      "playlist_dedup",
+      "playlist_sort",
+      "playlist_repair",
+      "playlist_repair_apply",
+      "playlist_metadata_set",
+      "smart_playlist_set",
+      "smart_playlist_remove",
+      "smart_playlist_refresh",
+      "smart_playlist_full",
+      "history",
+      "rate_song",
+      "rate_album",
+      "favorites",
+      "resume_policy",
+      "audio_errors"
     ]
   },
   "id": 0
@@ -1363,6 +1544,18 @@ r#"{
     "runtime": 0,
     "repeat": "off",
     "volume": 25,
+    "auto_dj": false,
+    "auto_dj_span": null,
+    "sleep": "off",
+    "chapter": null,
+    "chapters": [],
+    "channel_mix": {
+      "mono": false,
+      "balance": 0,
+      "swap": false
+    },
+    "output_path": "none",
+    "output_rate": 0,
     "song": null
   },
   "id": 0
@@ -1444,6 +1637,17 @@ r#"{
   "id": 0
 }"#,
 
+			StateSleepTimer => rpc::resp::StateSleepTimer,
+			"",
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "timer": "off",
+    "remaining": null
+  },
+  "id": 0
+}"#,
+
 			KeyArtist => rpc::resp::KeyArtist,
 			ureq::json!({"key":0}),
 r#"{
@@ -1506,7 +1710,12 @@ r#"{
       "track": 1,
       "disc": null,
       "mime": "",
-      "extension": ""
+      "extension": "",
+      "play_stats": {
+        "play_count": 0,
+        "skip_count": 0,
+        "last_played": null
+      }
     }
   },
   "id": 0
@@ -1574,7 +1783,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -1585,7 +1799,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -1596,7 +1815,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "flac",
@@ -1607,7 +1831,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       }
     ]
   },
@@ -1658,7 +1887,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -1669,7 +1903,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       }
     ]
   },
@@ -1786,7 +2025,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -1797,7 +2041,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       }
     ]
   },
@@ -1870,7 +2119,12 @@ r#"{
       "track": 2,
       "disc": null,
       "mime": "",
-      "extension": ""
+      "extension": "",
+      "play_stats": {
+        "play_count": 0,
+        "skip_count": 0,
+        "last_played": null
+      }
     }
   },
   "id": 0
@@ -1938,7 +2192,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -1949,7 +2208,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -1960,7 +2224,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "flac",
@@ -1971,7 +2240,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       }
     ]
   },
@@ -1998,7 +2272,12 @@ r#"{
         "track": 1,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       },
       {
         "title": "mp3",
@@ -2009,7 +2288,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       }
     ]
   },
@@ -2093,7 +2377,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       }
     ]
   },
@@ -2167,7 +2456,12 @@ r#"{
         "track": 2,
         "disc": null,
         "mime": "",
-        "extension": ""
+        "extension": "",
+        "play_stats": {
+          "play_count": 0,
+          "skip_count": 0,
+          "last_played": null
+        }
       }
     ]
   },
@@ -2238,6 +2532,22 @@ r#"{
   "id": 0
 }"#,
 
+			NextChapter => rpc::resp::Status,
+			"",
+r#"{
+  "jsonrpc": "2.0",
+  "result": null,
+  "id": 0
+}"#,
+
+			PreviousChapter => rpc::resp::Status,
+			"",
+r#"{
+  "jsonrpc": "2.0",
+  "result": null,
+  "id": 0
+}"#,
+
 			Skip => rpc::resp::Status,
 			ureq::json!({"skip":0}),
 r#"{
@@ -2263,7 +2573,7 @@ r#"{
 }"#,
 
 			Shuffle => rpc::resp::Status,
-			"",
+			ureq::json!({"mode":"albums"}),
 r#"{
   "jsonrpc": "2.0",
   "result": null,
@@ -2281,6 +2591,44 @@ r#"{
   "id": 0
 }"#,
 
+			Repeat => rpc::resp::Repeat,
+			ureq::json!({"mode":"album"}),
+			"",
+
+			Repeat => rpc::resp::Repeat,
+			ureq::json!({"mode":{"count":0},"count":2}),
+			"",
+
+			Repeat => rpc::resp::Repeat,
+			ureq::json!({"mode":{"ab":{"a":1,"b":2}}}),
+			"",
+
+			Repeat => rpc::resp::Repeat,
+			ureq::json!({"mode":"queue"}),
+			"",
+
+			AutoDj => rpc::resp::AutoDj,
+			ureq::json!({"on":false}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "previous": false,
+    "current": false
+  },
+  "id": 0
+}"#,
+
+			SleepTimer => rpc::resp::SleepTimer,
+			ureq::json!({"mode":"off"}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "previous": "off",
+    "current": "off"
+  },
+  "id": 0
+}"#,
+
 			// Volume + Queue + Any other operation that depends on `shukusai::audio::Audio`.
 			//
 			// `Audio` is going to be stuck in an infinitely loop trying to get a handle
@@ -2302,6 +2650,14 @@ r#"{
 			ureq::json!({"down":5}),
 			"",
 
+			VolumeDb => rpc::resp::VolumeDb,
+			ureq::json!({"db":-6.0}),
+			"",
+
+			ChannelMix => rpc::resp::ChannelMix,
+			ureq::json!({"mono":false}),
+			"",
+
 			QueueAddKeyArtist => rpc::resp::Status,
 			ureq::json!({"key":0,"append":"back","clear":false,"play":false}),
 r#"{
@@ -2359,11 +2715,11 @@ r#"{
 			"",
 
 			QueueAddRandSong => rpc::resp::QueueAddRandSong,
-			ureq::json!({"append":"back","clear":false,"play":false}),
+			ureq::json!({"append":"back","clear":false,"play":false,"mode":"spread"}),
 			"",
 
 			QueueAddRandEntry => rpc::resp::QueueAddRandEntry,
-			ureq::json!({"append":"back","clear":false,"play":false}),
+			ureq::json!({"append":"back","clear":false,"play":false,"mode":"weighted"}),
 			"",
 
 			// Skipped, no playlists object.
@@ -2379,6 +2735,30 @@ r#"{
 			ureq::json!({"start":0,"end":1,"skip":false}),
 			"",
 
+			QueueMove => rpc::resp::QueueMove,
+			ureq::json!({"from":0,"to":0}),
+			"",
+
+			QueueInsert => rpc::resp::QueueInsert,
+			ureq::json!({"index":0,"keys":[0]}),
+			"",
+
+			QueueSave => rpc::resp::QueueSave,
+			ureq::json!({"name":"hello"}),
+			"",
+
+			QueueRestore => rpc::resp::QueueRestore,
+			ureq::json!({"name":"hello"}),
+			"",
+
+			QueueSnapshots => rpc::resp::QueueSnapshots,
+			"",
+			"", // Contains timestamps, not reliable on CI.
+
+			QueueSnapshotDelete => rpc::resp::QueueSnapshotDelete,
+			ureq::json!({"name":"hello"}),
+			"", // Contains timestamps, not reliable on CI.
+
 			// Playlists.
 			//
 			// Unlike `Audio`/`Queue`, the playlists are directly
@@ -2654,7 +3034,17 @@ r#"{
     "len": 1,
     "playlists": [
       "hello"
-    ]
+    ],
+    "metadata": {
+      "hello": {
+        "description": "",
+        "created": 0,
+        "modified": 0,
+        "art": null,
+        "pinned": false,
+        "folder": ""
+      }
+    }
   },
   "id": 0
 }"#,
@@ -2812,6 +3202,16 @@ r#"{
           }
         }
       ]
+    },
+    "metadata": {
+      "hello": {
+        "description": "",
+        "created": 0,
+        "modified": 0,
+        "art": null,
+        "pinned": false,
+        "folder": ""
+      }
     }
   },
   "id": 0
@@ -2855,6 +3255,273 @@ r#"{
   "id": 0
 }"#,
 
+			PlaylistExport => rpc::resp::PlaylistExport,
+			ureq::json!({"playlist":"hello","format":"m3u8","relative_to":null}),
+			"", // Skipped, contains PATHs.
+
+			PlaylistMoveIndex => rpc::resp::PlaylistMoveIndex,
+			ureq::json!({"playlist":"hello","from":0,"to":1}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "out_of_bounds": false,
+    "from": 0,
+    "to": 1,
+    "playlist_len": 13
+  },
+  "id": 0
+}"#,
+
+			PlaylistInsertKeys => rpc::resp::PlaylistInsertKeys,
+			ureq::json!({"playlist":"hello","index":13,"keys":[0]}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "out_of_bounds": false,
+    "index": 13,
+    "len": 1,
+    "playlist_len": 14
+  },
+  "id": 0
+}"#,
+
+			PlaylistDedup => rpc::resp::PlaylistDedup,
+			ureq::json!({"playlist":"hello"}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "removed": 10,
+    "len": 4
+  },
+  "id": 0
+}"#,
+
+			PlaylistSort => rpc::resp::PlaylistSort,
+			ureq::json!({"playlist":"hello","sort":"title"}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "len": 4
+  },
+  "id": 0
+}"#,
+
+			PlaylistRepair => rpc::resp::PlaylistRepair,
+			ureq::json!({"playlist":"hello","threshold":null}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "applied": 0,
+    "review": 0,
+    "repairs": []
+  },
+  "id": 0
+}"#,
+
+			PlaylistRepairApply => rpc::resp::PlaylistRepairApply,
+			ureq::json!({"playlist":"hello","index":0,"key":0}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "applied": false
+  },
+  "id": 0
+}"#,
+
+			PlaylistMetadataSet => rpc::resp::PlaylistMetadataSet,
+			ureq::json!({"playlist":"hello","description":"desc","folder":"Rock","art":null,"pinned":true}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "metadata": {
+      "description": "desc",
+      "created": 0,
+      "modified": 0,
+      "art": null,
+      "pinned": true,
+      "folder": "Rock"
+    }
+  },
+  "id": 0
+}"#,
+
+			PlaylistRename => rpc::resp::PlaylistRename,
+			ureq::json!({"from":"hello","to":"hello2"}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "len": null,
+    "entries": null
+  },
+  "id": 0
+}"#,
+
+			PlaylistRename => rpc::resp::PlaylistRename,
+			ureq::json!({"from":"hello2","to":"hello"}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "len": null,
+    "entries": null
+  },
+  "id": 0
+}"#,
+
+			History => rpc::resp::History,
+			ureq::json!({"offset":0,"len":10}),
+			"", // Skipped, contains timestamps.
+
+			SmartPlaylistSet => rpc::resp::SmartPlaylistSet,
+			ureq::json!({"playlist":"smart","rules":[{"genre":"does_not_exist"}],"limit":null,"sort":null}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "existed": false,
+    "len": 0
+  },
+  "id": 0
+}"#,
+
+			SmartPlaylistRefresh => rpc::resp::SmartPlaylistRefresh,
+			ureq::json!({"playlist":"smart"}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "len": 0
+  },
+  "id": 0
+}"#,
+
+			SmartPlaylistFull => rpc::resp::SmartPlaylistFull,
+			"",
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "len": 1,
+    "smart_playlists": {
+      "smart": {
+        "rules": [
+          {
+            "genre": "does_not_exist"
+          }
+        ],
+        "limit": null,
+        "sort": "lexi"
+      }
+    }
+  },
+  "id": 0
+}"#,
+
+			SmartPlaylistRemove => rpc::resp::SmartPlaylistRemove,
+			ureq::json!({"playlist":"smart"}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "smart_playlist": {
+      "rules": [
+        {
+          "genre": "does_not_exist"
+        }
+      ],
+      "limit": null,
+      "sort": "lexi"
+    }
+  },
+  "id": 0
+}"#,
+
+			RateSong => rpc::resp::RateSong,
+			ureq::json!({"key":0,"rating":5}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "previous": 0,
+    "rating": 5
+  },
+  "id": 0
+}"#,
+
+			RateAlbum => rpc::resp::RateAlbum,
+			ureq::json!({"key":0,"rating":4}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "previous": 0,
+    "rating": 4
+  },
+  "id": 0
+}"#,
+
+			RateSong => rpc::resp::RateSong,
+			ureq::json!({"key":0,"rating":0}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "previous": 5,
+    "rating": 0
+  },
+  "id": 0
+}"#,
+
+			RateAlbum => rpc::resp::RateAlbum,
+			ureq::json!({"key":0,"rating":0}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "previous": 4,
+    "rating": 0
+  },
+  "id": 0
+}"#,
+
+			Favorites => rpc::resp::Favorites,
+			"",
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "album_len": 0,
+    "albums": [],
+    "song_len": 0,
+    "songs": []
+  },
+  "id": 0
+}"#,
+
+			ResumePolicy => rpc::resp::ResumePolicy,
+			ureq::json!({"key":0,"policy":"always"}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "previous": "auto",
+    "current": "always"
+  },
+  "id": 0
+}"#,
+
+			ResumePolicy => rpc::resp::ResumePolicy,
+			ureq::json!({"key":0,"policy":"auto"}),
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "previous": "always",
+    "current": "auto"
+  },
+  "id": 0
+}"#,
+
+			AudioErrors => rpc::resp::AudioErrors,
+			"",
+r#"{
+  "jsonrpc": "2.0",
+  "result": {
+    "len": 0,
+    "error_len": 0,
+    "songs": []
+  },
+  "id": 0
+}"#,
+
 			// Saved until last.
 			DaemonShutdown => rpc::resp::DaemonShutdown,
 			"",
@@ -5,6 +5,8 @@ use shukusai::signal::{
 	Volume,Toggle,Pause,Play,Skip,Back,
 	Previous,Next,Stop,Shuffle,Index,
 	RepeatSong,RepeatQueue,RepeatOff,
+	RepeatAlbum,RepeatArtist,RepeatCount,RepeatAb,
+	SleepSong,SleepAlbum,SleepQueue,SleepOff,SleepMinutes,
 	Clear,Seek,SeekForward,SeekBackward,
 };
 use crate::constants::{
@@ -242,6 +244,34 @@ pub struct Cli {
 	/// Example: `festivald --collection-path /my/path/1 --collection-path /my/path/2`
 	collection_path: Vec<PathBuf>,
 
+	#[arg(long, verbatim_doc_comment, value_name = "SECONDS")]
+	/// Acoustically fingerprint the first SECONDS of each `Song`
+	///
+	/// If this is non-zero, `collection_new` will decode the
+	/// beginning of each `Song` and create an acoustic fingerprint
+	/// used by the `collection_duplicates` & `collection_mistags` methods.
+	///
+	/// Fingerprints are cached, so only new or modified
+	/// files are decoded on subsequent `collection_new` calls.
+	///
+	/// Setting this to `0` disables fingerprinting.
+	fingerprint_seconds: Option<u32>,
+
+	#[arg(long, verbatim_doc_comment, value_name = "ARTICLE")]
+	/// Leading articles to ignore when sorting the `Collection`
+	///
+	/// With `The`, `The Beatles` will sort under `B`.
+	///
+	/// Articles are matched case-insensitively, and only
+	/// if followed by a space, so `Theory` is not affected.
+	///
+	/// The defaults are `The`, `A`, `Die` and `Le`.
+	///
+	/// To set multiple articles, use this flag per article.
+	///
+	/// Example: `festivald --sort-article The --sort-article Les`
+	sort_article: Vec<String>,
+
 	#[arg(long, verbatim_doc_comment)]
 	/// Enable/disable inlined resources for the REST API.
 	///
@@ -330,6 +360,118 @@ pub struct Cli {
 	/// always go back if `threshold` is not specified.
 	previous_threshold: Option<u32>,
 
+	#[arg(long, verbatim_doc_comment)]
+	/// Make the `previous` method follow the play history
+	///
+	/// By default, `previous` goes back to the previous `Song` in the queue.
+	///
+	/// With this option, `previous` goes back to the `Song` that
+	/// actually played before the current one (even after a shuffle
+	/// or queue clear), re-adding it to the queue at the current index.
+	///
+	/// Repeated `previous`'s keep walking back through the history,
+	/// see the `history` method for what it contains.
+	previous_history: bool,
+
+	#[arg(long, verbatim_doc_comment)]
+	/// Turn on Auto-DJ upon startup
+	///
+	/// When the queue ends, Auto-DJ appends `Song`'s related
+	/// to what just played (same artist, genre, release year,
+	/// shared playlists), avoiding recently played ones.
+	///
+	/// This can be changed while running with the `auto_dj` method.
+	auto_dj: bool,
+
+	#[arg(long, verbatim_doc_comment, value_name = "SECONDS")]
+	/// Remember the playback position of songs at least this long
+	///
+	/// Songs with a runtime of at least `SECONDS` (or with
+	/// the genre "Audiobook") continue where they were left
+	/// off when played again, instead of from the start.
+	///
+	/// The default is 1200 seconds (20 minutes).
+	///
+	/// This can be overridden per song with the `resume_policy` method.
+	resume_threshold: Option<u32>,
+
+	#[arg(long, verbatim_doc_comment)]
+	/// Make the volume follow a decibel curve
+	///
+	/// By default, volume `0..100` is linear, which is
+	/// too coarse at low volumes.
+	///
+	/// With this option, volume `1..100` is spread evenly
+	/// over `-60dB..0dB` (`0.6dB` per step), `0` is muted.
+	///
+	/// The `volume_db` method sets the volume in decibels
+	/// following whichever curve is picked.
+	volume_db_curve: bool,
+
+	#[arg(long, verbatim_doc_comment, value_name = "STEP", value_parser = clap::value_parser!(u8).range(0..=100))]
+	/// How much `volume_up` and `volume_down` change the volume by
+	///
+	/// This is used when the methods are
+	/// called without the `up` or `down` field.
+	///
+	/// The default is 5.
+	volume_step: Option<u8>,
+
+	#[arg(long, verbatim_doc_comment)]
+	/// Disable the peak limiter
+	///
+	/// `festivald` limits peaks right before audio is sent
+	/// to the device, so loud masters don't clip.
+	///
+	/// This is transparent, audio that doesn't
+	/// peak above `~-0.1dBFS` is not touched.
+	///
+	/// `--disable-limiter` disables this.
+	disable_limiter: bool,
+
+	#[arg(long, verbatim_doc_comment, value_name = "ERRORS")]
+	/// How many corrupt packets a song can have before it is skipped
+	///
+	/// Packets that fail to decode are skipped, and if the
+	/// file can't be read further, `festivald` seeks a second
+	/// past the corrupt data and continues playing.
+	///
+	/// Once a song has more errors than `ERRORS`, it is skipped
+	/// (or playback is paused, if the song would be repeated).
+	/// Setting this to `0` skips the song on the first error.
+	///
+	/// Each error is recorded, see the `audio_errors` method.
+	///
+	/// The default is 100.
+	max_decode_errors: Option<u32>,
+
+	#[arg(long, verbatim_doc_comment, value_name = "FAST|BALANCED|BEST")]
+	/// The quality of the resampler
+	///
+	/// If the audio device doesn't support the sample rate
+	/// of a song, it is resampled. This is only done on
+	/// Windows, Linux/macOS always play the native sample rate.
+	///
+	/// `fast` uses the least CPU, `best` sounds the cleanest.
+	///
+	/// The default is `balanced`.
+	resampler_quality: Option<shukusai::audio::ResamplerQuality>,
+
+	#[arg(long, verbatim_doc_comment)]
+	/// Send samples to the audio device untouched
+	///
+	/// The audio device is re-opened at each song's
+	/// native sample rate and sample format, so no
+	/// resampling or conversion happens.
+	///
+	/// Formats the device can't play as-is are still converted.
+	///
+	/// Volume, channel mixing and the peak limiter
+	/// do nothing while this is enabled. The `sleep_timer`
+	/// fade-out does nothing either, playback still pauses
+	/// when the timer goes off, but without fading out.
+	bit_perfect: bool,
+
 	#[arg(long, verbatim_doc_comment, default_value_t = false)]
 	/// Disable audio state restoration
 	///
@@ -508,6 +650,42 @@ pub struct Signal {
 	/// Turn off repeating
 	repeat_off: bool,
 
+	#[arg(long)]
+	/// Turn on repeat of the current album's songs in the queue
+	repeat_album: bool,
+
+	#[arg(long)]
+	/// Turn on repeat of the current artist's songs in the queue
+	repeat_artist: bool,
+
+	#[arg(long, value_name = "NUMBER")]
+	/// Repeat the current song `NUMBER` more times, then turn off repeating
+	repeat_count: Option<u8>,
+
+	#[arg(long, value_name = "A-B")]
+	/// Loop between second `A` and `B` of the current song, e.g. `30-45`
+	repeat_ab: Option<String>,
+
+	#[arg(long)]
+	/// Pause playback after the current song finishes
+	sleep_song: bool,
+
+	#[arg(long)]
+	/// Pause playback after the current album's songs in the queue finish
+	sleep_album: bool,
+
+	#[arg(long)]
+	/// Pause playback when the queue finishes
+	sleep_queue: bool,
+
+	#[arg(long, value_name = "MINUTES")]
+	/// Pause playback after `MINUTES` minutes
+	sleep_minutes: Option<u32>,
+
+	#[arg(long)]
+	/// Turn off the sleep timer
+	sleep_off: bool,
+
 	#[arg(long)]
 	#[arg(value_parser = clap::value_parser!(u8).range(0..=100), value_name = "VOLUME")]
 	/// Set the volume to `VOLUME` (0-100)
@@ -699,6 +877,12 @@ impl Cli {
 		if s.repeat_song  { handle(RepeatSong::touch())  }
 		if s.repeat_queue { handle(RepeatQueue::touch()) }
 		if s.repeat_off   { handle(RepeatOff::touch())   }
+		if s.repeat_album { handle(RepeatAlbum::touch()) }
+		if s.repeat_artist { handle(RepeatArtist::touch()) }
+		if s.sleep_song   { handle(SleepSong::touch())   }
+		if s.sleep_album  { handle(SleepAlbum::touch())  }
+		if s.sleep_queue  { handle(SleepQueue::touch())  }
+		if s.sleep_off    { handle(SleepOff::touch())    }
 
 		// Content signals.
 		if let Some(volume) = s.volume        { handle(Volume(shukusai::audio::Volume::new(volume)).save()) }
@@ -708,6 +892,9 @@ impl Cli {
 		if let Some(index)  = s.index         { handle(Index(index.into()).save()) }
 		if let Some(skip)   = s.skip          { handle(Skip(skip).save())          }
 		if let Some(back)   = s.back          { handle(Back(back).save())          }
+		if let Some(count)  = s.repeat_count  { handle(RepeatCount(count).save())  }
+		if let Some(ab)     = &s.repeat_ab    { handle(RepeatAb(ab.clone()).save()) }
+		if let Some(min)    = s.sleep_minutes { handle(SleepMinutes(min).save())   }
 
 		exit(0);
 	}
@@ -720,6 +907,10 @@ impl Cli {
 		let mut direct_download     = self.direct_download.then_some(self.direct_download);
 		let mut confirm_no_tls_auth = self.confirm_no_tls_auth.then_some(self.confirm_no_tls_auth);
 		let mut no_auth_docs        = self.no_auth_docs.then_some(self.no_auth_docs);
+		let mut previous_history    = self.previous_history.then_some(self.previous_history);
+		let mut auto_dj             = self.auto_dj.then_some(self.auto_dj);
+		let mut volume_db_curve     = self.volume_db_curve.then_some(self.volume_db_curve);
+		let mut bit_perfect         = self.bit_perfect.then_some(self.bit_perfect);
 
 		// `disable_*` negation.
 		let mut docs                = self.disable_docs.then_some(!self.disable_docs);
@@ -728,6 +919,7 @@ impl Cli {
 		let mut rest                = self.disable_rest.then_some(!self.disable_rest);
 		let mut watch               = self.disable_watch.then_some(!self.disable_watch);
 		let mut cache_clean         = self.disable_cache_clean.then_some(!self.disable_cache_clean);
+		let mut limiter             = self.disable_limiter.then_some(!self.disable_limiter);
 
 		// Special-case conversions.
 		macro_rules! vec_to_some_hashset {
@@ -765,6 +957,12 @@ impl Cli {
 			Some(std::mem::take(&mut self.collection_path))
 		};
 
+		let mut sort_articles = if self.sort_article.is_empty() {
+			None
+		} else {
+			Some(std::mem::take(&mut self.sort_article))
+		};
+
 		let mut log_level = self.log_level.clone();
 
 		macro_rules! if_some {
@@ -787,6 +985,8 @@ impl Cli {
 			exclusive_ips           => cb.exclusive_ips,
 			self.sleep_on_fail      => cb.sleep_on_fail,
 			collection_paths        => cb.collection_paths,
+			self.fingerprint_seconds => cb.fingerprint_seconds,
+			sort_articles           => cb.sort_articles,
 			tls                     => cb.tls,
 			self.certificate        => cb.certificate,
 			self.key                => cb.key,
@@ -800,6 +1000,15 @@ impl Cli {
 			self.cache_time         => cb.cache_time,
 			restore_audio_state     => cb.restore_audio_state,
 			self.previous_threshold => cb.previous_threshold,
+			previous_history        => cb.previous_history,
+			auto_dj                 => cb.auto_dj,
+			self.resume_threshold   => cb.resume_threshold,
+			volume_db_curve         => cb.volume_db_curve,
+			self.volume_step        => cb.volume_step,
+			limiter                 => cb.limiter,
+			self.max_decode_errors  => cb.max_decode_errors,
+			self.resampler_quality  => cb.resampler_quality,
+			bit_perfect             => cb.bit_perfect,
 			media_controls          => cb.media_controls,
 			self.authorization      => cb.authorization,
 			confirm_no_tls_auth     => cb.confirm_no_tls_auth,
@@ -60,6 +60,8 @@ pub struct ConfigBuilder {
 	pub exclusive_ips:       Option<BTreeSet<Ipv4Addr>>,
 	pub sleep_on_fail:       Option<u64>,
 	pub collection_paths:    Option<Vec<PathBuf>>,
+	pub fingerprint_seconds: Option<u32>,
+	pub sort_articles:       Option<Vec<String>>,
 	pub tls:                 Option<bool>,
 	pub certificate:         Option<PathBuf>,
 	pub key:                 Option<PathBuf>,
@@ -70,6 +72,15 @@ pub struct ConfigBuilder {
 	pub log_level:           Option<log::LevelFilter>,
 	pub restore_audio_state: Option<bool>,
 	pub previous_threshold:  Option<u32>,
+	pub previous_history:    Option<bool>,
+	pub auto_dj:             Option<bool>,
+	pub resume_threshold:    Option<u32>,
+	pub volume_db_curve:     Option<bool>,
+	pub volume_step:         Option<u8>,
+	pub limiter:             Option<bool>,
+	pub max_decode_errors:   Option<u32>,
+	pub resampler_quality:   Option<shukusai::audio::ResamplerQuality>,
+	pub bit_perfect:         Option<bool>,
 	pub watch:               Option<bool>,
 	pub cache_clean:         Option<bool>,
 	pub cache_time:          Option<u64>,
@@ -90,6 +101,8 @@ impl Default for ConfigBuilder {
 			exclusive_ips:       Some(BTreeSet::new()),
 			sleep_on_fail:       Some(3000),
 			collection_paths:    Some(vec![]),
+			fingerprint_seconds: Some(0),
+			sort_articles:       Some(shukusai::sort::SORT_ARTICLES_DEFAULT.iter().map(|s| s.to_string()).collect()),
 			tls:                 Some(false),
 			certificate:         Some(PathBuf::from("")),
 			key:                 Some(PathBuf::from("")),
@@ -100,6 +113,15 @@ impl Default for ConfigBuilder {
 			log_level:           Some(LOG_DEFAULT),
 			restore_audio_state: Some(true),
 			previous_threshold:  Some(3),
+			previous_history:    Some(false),
+			auto_dj:             Some(false),
+			resume_threshold:    Some(shukusai::collection::RESUME_THRESHOLD),
+			volume_db_curve:     Some(false),
+			volume_step:         Some(5),
+			limiter:             Some(true),
+			max_decode_errors:   Some(shukusai::audio::DECODE_ERROR_THRESHOLD_DEFAULT),
+			resampler_quality:   Some(shukusai::audio::ResamplerQuality::new()),
+			bit_perfect:         Some(false),
 			watch:               Some(true),
 			cache_clean:         Some(true),
 			cache_time:          Some(3600),
@@ -124,6 +146,8 @@ impl ConfigBuilder {
 			exclusive_ips,
 			sleep_on_fail,
 			collection_paths,
+			fingerprint_seconds,
+			sort_articles,
 			tls,
 			certificate,
 			key,
@@ -134,6 +158,15 @@ impl ConfigBuilder {
 			log_level,
 			restore_audio_state,
 			previous_threshold,
+			previous_history,
+			auto_dj,
+			resume_threshold,
+			volume_db_curve,
+			volume_step,
+			limiter,
+			max_decode_errors,
+			resampler_quality,
+			bit_perfect,
 			watch,
 			cache_clean,
 			cache_time,
@@ -176,6 +209,8 @@ impl ConfigBuilder {
 			exclusive_ips:       sum!(exclusive_ips,       "exclusive_ips",       None::<BTreeSet<Ipv4Addr>>),
 			sleep_on_fail:       sum!(sleep_on_fail,       "sleep_on_fail",       Some(3000)),
 			collection_paths:    get!(collection_paths,    "collection_paths",    if let Some(p) = dirs::audio_dir() { vec![p] } else { Vec::<PathBuf>::with_capacity(0) }),
+			fingerprint_seconds: get!(fingerprint_seconds, "fingerprint_seconds", 0),
+			sort_articles:       get!(sort_articles,       "sort_articles",       shukusai::sort::SORT_ARTICLES_DEFAULT.iter().map(|s| s.to_string()).collect::<Vec<String>>()),
 			tls:                 get!(tls,                 "tls",                 false),
 			certificate:         sum!(certificate,         "certificate",         None::<PathBuf>),
 			key:                 sum!(key,                 "key",                 None::<PathBuf>),
@@ -186,6 +221,15 @@ impl ConfigBuilder {
 			log_level:           get!(log_level,           "log_level",           LOG_DEFAULT),
 			restore_audio_state: get!(restore_audio_state, "restore_audio_state", true),
 			previous_threshold:  get!(previous_threshold,  "previous_threshold",  3),
+			previous_history:    get!(previous_history,    "previous_history",    false),
+			auto_dj:             get!(auto_dj,             "auto_dj",             false),
+			resume_threshold:    get!(resume_threshold,    "resume_threshold",    shukusai::collection::RESUME_THRESHOLD),
+			volume_db_curve:     get!(volume_db_curve,     "volume_db_curve",     false),
+			volume_step:         get!(volume_step,         "volume_step",         5),
+			limiter:             get!(limiter,             "limiter",             true),
+			max_decode_errors:   get!(max_decode_errors,   "max_decode_errors",   shukusai::audio::DECODE_ERROR_THRESHOLD_DEFAULT),
+			resampler_quality:   get!(resampler_quality,   "resampler_quality",   shukusai::audio::ResamplerQuality::new()),
+			bit_perfect:         get!(bit_perfect,         "bit_perfect",         false),
 			watch:               get!(watch,               "watch",               true),
 			cache_clean:         get!(cache_clean,         "cache_clean",         true),
 			cache_time:          get!(cache_time,          "cache_time",          3600),
@@ -204,6 +248,11 @@ impl ConfigBuilder {
 			c.sleep_on_fail = None;
 		}
 
+		if c.volume_step > 100 {
+			warn!("config [volume_step] is over 100, using 100");
+			c.volume_step = 100;
+		}
+
 		if c.filename_separator.contains("/") {
 			crate::exit!("[filename_separator] cannot contain '/', it is the PATH separator for ZIPs");
 		}
@@ -356,6 +405,8 @@ impl ConfigBuilder {
 			cmd.exclusive_ips       => self.exclusive_ips,
 			cmd.sleep_on_fail       => self.sleep_on_fail,
 			cmd.collection_paths    => self.collection_paths,
+			cmd.fingerprint_seconds => self.fingerprint_seconds,
+			cmd.sort_articles       => self.sort_articles,
 			cmd.tls                 => self.tls,
 			cmd.certificate         => self.certificate,
 			cmd.key                 => self.key,
@@ -366,6 +417,15 @@ impl ConfigBuilder {
 			cmd.log_level           => self.log_level,
 			cmd.restore_audio_state => self.restore_audio_state,
 			cmd.previous_threshold  => self.previous_threshold,
+			cmd.previous_history    => self.previous_history,
+			cmd.auto_dj             => self.auto_dj,
+			cmd.resume_threshold    => self.resume_threshold,
+			cmd.volume_db_curve     => self.volume_db_curve,
+			cmd.volume_step         => self.volume_step,
+			cmd.limiter             => self.limiter,
+			cmd.max_decode_errors   => self.max_decode_errors,
+			cmd.resampler_quality   => self.resampler_quality,
+			cmd.bit_perfect         => self.bit_perfect,
 			cmd.watch               => self.watch,
 			cmd.cache_clean         => self.cache_clean,
 			cmd.cache_time          => self.cache_time,
@@ -390,6 +450,8 @@ pub struct Config {
 	pub exclusive_ips:       Option<BTreeSet<Ipv4Addr>>,
 	pub sleep_on_fail:       Option<u64>,
 	pub collection_paths:    Vec<PathBuf>,
+	pub fingerprint_seconds: u32,
+	pub sort_articles:       Vec<String>,
 	pub tls:                 bool,
 	pub certificate:         Option<PathBuf>,
 	pub key:                 Option<PathBuf>,
@@ -400,6 +462,15 @@ pub struct Config {
 	pub log_level:           log::LevelFilter,
 	pub restore_audio_state: bool,
 	pub previous_threshold:  u32,
+	pub previous_history:    bool,
+	pub auto_dj:             bool,
+	pub resume_threshold:    u32,
+	pub volume_db_curve:     bool,
+	pub volume_step:         u8,
+	pub limiter:             bool,
+	pub max_decode_errors:   u32,
+	pub resampler_quality:   shukusai::audio::ResamplerQuality,
+	pub bit_perfect:         bool,
 	pub watch:               bool,
 	pub cache_clean:         bool,
 	pub cache_time:          u64,
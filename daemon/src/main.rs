@@ -66,6 +66,23 @@ fn main() {
 	}
 	// Set the default `previous` audio threshold.
 	benri::atomic_store!(shukusai::audio::PREVIOUS_THRESHOLD, CONFIG.previous_threshold);
+	benri::atomic_store!(shukusai::audio::PREVIOUS_HISTORY, CONFIG.previous_history);
+	// Set the volume curve and limiter.
+	benri::atomic_store!(shukusai::audio::VOLUME_DB_CURVE, CONFIG.volume_db_curve);
+	benri::atomic_store!(shukusai::audio::LIMITER, CONFIG.limiter);
+	// Set how many corrupt packets a `Song` can have.
+	benri::atomic_store!(shukusai::audio::DECODE_ERROR_THRESHOLD, CONFIG.max_decode_errors);
+	// Set the resampler quality and bit-perfect output.
+	benri::atomic_store!(shukusai::audio::RESAMPLER_QUALITY, CONFIG.resampler_quality as u8);
+	benri::atomic_store!(shukusai::audio::BIT_PERFECT, CONFIG.bit_perfect);
+	// Turn Auto-DJ on/off.
+	benri::send!(TO_KERNEL, shukusai::kernel::FrontendToKernel::AutoDj(CONFIG.auto_dj));
+	// Set which `Song`'s remember their position.
+	benri::send!(TO_KERNEL, shukusai::kernel::FrontendToKernel::ResumeThreshold(CONFIG.resume_threshold));
+	// Set the acoustic fingerprinting length.
+	benri::atomic_store!(shukusai::collection::FINGERPRINT_SECONDS, CONFIG.fingerprint_seconds);
+	// Set the articles ignored when sorting.
+	*benri::lockw!(shukusai::sort::SORT_ARTICLES) = CONFIG.sort_articles.clone();
 
 	// Create documentation.
 	if CONFIG.docs {
@@ -22,7 +22,9 @@ use mime::{
 	APPLICATION_JSON,
 };
 use std::borrow::Cow;
+use std::io::Write;
 use serde::Serialize;
+use log::debug;
 use crate::config::config;
 use crate::constants::FESTIVALD_SERVER;
 
@@ -33,6 +35,8 @@ const INLINE: &str = "inline";
 const ATTACHMENT: &str = "attachment";
 // Zip file MIME.
 const MIME_ZIP: &str = "application/zip";
+// Send streamed bodies in chunks of this many bytes.
+const STREAM_CHUNK: usize = 64 * 1024;
 
 //---------------------------------------------------------------------------------------------------- REST Responses
 pub fn rest_ok(bytes: Vec<u8>, name: &str, mime: &str) -> Response<Body> {
@@ -339,6 +343,135 @@ pub fn result_cache<'a>(string: &str, id: Option<json_rpc::Id<'a>>) -> Response<
 	}
 }
 
+// A JSON-RPC result whose `data` string field is streamed.
+//
+// `t` must have an empty `data` field, it is filled
+// with everything `f` writes, escaped as a JSON string.
+pub fn result_stream<'a, T, F>(t: T, id: Option<json_rpc::Id<'a>>, f: F) -> Response<Body>
+where
+	T: Clone + Serialize,
+	F: FnOnce(&mut BodyWriter) -> std::io::Result<()> + Send + 'static,
+{
+	const DATA: &[u8] = br#""data": """#;
+
+	let r = json_rpc::Response::result(Cow::Borrowed(&t), id.clone());
+	let mut r = match serde_json::to_vec_pretty(&r) {
+		Ok(r)  => r,
+		Err(e) => return internal_error(id),
+	};
+
+	// Split the envelope in between the `""` of `data`.
+	let Some(i) = r.windows(DATA.len()).position(|w| w == DATA) else {
+		return internal_error(id);
+	};
+	let suffix = r.split_off(i + DATA.len() - 1);
+	let prefix = r;
+
+	let body = stream(move |w| {
+		w.write_all(&prefix)?;
+		w.escape = true;
+		f(w)?;
+		w.escape = false;
+		w.write_all(&suffix)
+	});
+
+	match Builder::new()
+		.status(StatusCode::OK)
+		.header(SERVER, FESTIVALD_SERVER)
+		.header(CONTENT_TYPE, APPLICATION_JSON.essence_str())
+		.body(body)
+	{
+		Ok(r)  => r,
+		Err(_) => internal_error(id),
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Streaming
+// A blocking `Write` into a streaming `Body`, see `stream()`.
+pub struct BodyWriter {
+	sender: hyper::body::Sender,
+	handle: tokio::runtime::Handle,
+	buf:    Vec<u8>,
+	// Escape written bytes as the inside of a JSON string.
+	escape: bool,
+}
+
+impl Write for BodyWriter {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		if self.escape {
+			json_escape(buf, &mut self.buf);
+		} else {
+			self.buf.extend_from_slice(buf);
+		}
+
+		if self.buf.len() >= STREAM_CHUNK {
+			self.flush()?;
+		}
+
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		if self.buf.is_empty() {
+			return Ok(());
+		}
+
+		let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(STREAM_CHUNK));
+		match self.handle.block_on(self.sender.send_data(chunk.into())) {
+			Ok(()) => Ok(()),
+			// The client disconnected.
+			Err(_) => Err(std::io::ErrorKind::BrokenPipe.into()),
+		}
+	}
+}
+
+// Create a `Body` that is written to by `f` on a blocking thread.
+//
+// The `Body` is sent as `f` writes, in chunks of `STREAM_CHUNK`,
+// so large outputs are never fully held in memory.
+//
+// If `f` fails, the `Body` is aborted, so the client sees
+// an incomplete response instead of a truncated but valid one.
+pub fn stream<F>(f: F) -> Body
+where
+	F: FnOnce(&mut BodyWriter) -> std::io::Result<()> + Send + 'static,
+{
+	let (sender, body) = Body::channel();
+	let mut writer = BodyWriter {
+		sender,
+		handle: tokio::runtime::Handle::current(),
+		buf:    Vec::with_capacity(STREAM_CHUNK),
+		escape: false,
+	};
+
+	tokio::task::spawn_blocking(move || {
+		if let Err(e) = f(&mut writer).and_then(|_| writer.flush()) {
+			debug!("Stream - aborting body: {e}");
+			writer.sender.abort();
+		}
+	});
+
+	body
+}
+
+// Escape `bytes` as the inside of a JSON string.
+//
+// This works on bytes so chunks may split a UTF-8 character,
+// all multi-byte UTF-8 bytes are `>= 0x80` and are passed through.
+fn json_escape(bytes: &[u8], out: &mut Vec<u8>) {
+	for b in bytes {
+		match b {
+			b'"'  => out.extend_from_slice(b"\\\""),
+			b'\\' => out.extend_from_slice(b"\\\\"),
+			b'\n' => out.extend_from_slice(b"\\n"),
+			b'\r' => out.extend_from_slice(b"\\r"),
+			b'\t' => out.extend_from_slice(b"\\t"),
+			0..=0x1F => out.extend_from_slice(format!("\\u{b:04x}").as_bytes()),
+			_ => out.push(*b),
+		}
+	}
+}
+
 //---------------------------------------------------------------------------------------------------- JSON-RPC specific error response
 pub fn parse_error<'a>(id: Option<json_rpc::Id<'a>>) -> Response<Body> {
 	// SAFETY: These `.unwraps()` are safe. The content is static.
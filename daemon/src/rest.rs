@@ -40,7 +40,9 @@ use crate::config::AUTH;
 use std::str::FromStr;
 use benri::debug_panic;
 use std::collections::VecDeque;
-use shukusai::state::PLAYLISTS;
+use shukusai::state::{PLAYLISTS,Playlists,PlaylistFormat};
+use shukusai::export::ExportFormat;
+use shukusai::sort::SongSort;
 use std::collections::btree_set::BTreeSet;
 
 //---------------------------------------------------------------------------------------------------- Const
@@ -301,13 +303,25 @@ pub async fn handle(
 			None => return Ok(resp::not_found("Missing playlist name")),
 		};
 
-		// Return error if more than 2 endpoints.
+		// Optional playlist file format.
+		let format = match split.next() {
+			Some(s) if !s.is_empty() => match PlaylistFormat::from_str(s) {
+				Ok(f)  => Some(f),
+				Err(_) => return Ok(resp::not_found("Unknown playlist format")),
+			},
+			_ => None,
+		};
+
+		// Return error if more than 3 endpoints.
 		match split.next() {
 			Some(s) if !s.is_empty() => return Ok(resp::not_found(ERR_END)),
 			_ => (),
 		}
 
-		playlist_fn(playlist_name, collection.arc()).await
+		match format {
+			Some(format) => playlist_format_fn(playlist_name, format, collection.arc()).await,
+			None         => playlist_fn(playlist_name, collection.arc()).await,
+		}
 	//-------------------------------------------------- `/collection` endpoint.
 	} else if ep1 == "collection" {
 		// Auth.
@@ -315,12 +329,25 @@ pub async fn handle(
 			return Ok(resp);
 		}
 
+		// Optional export format.
+		let format = match split.next() {
+			Some(s) if !s.is_empty() => match ExportFormat::from_str(s) {
+				Ok(f)  => Some(f),
+				Err(_) => return Ok(resp::not_found("Unknown export format")),
+			},
+			_ => None,
+		};
+
+		// Return error if more than 2 endpoints.
 		match split.next() {
 			Some(s) if !s.is_empty() => return Ok(resp::not_found(ERR_END)),
 			_ => (),
 		}
 
-		collection_fn(collection.arc()).await
+		match format {
+			Some(format) => collection_export_fn(format, collection.arc()).await,
+			None         => collection_fn(collection.arc()).await,
+		}
 	//-------------------------------------------------- unknown endpoint.
 	} else {
 		Ok(resp::not_found(ERR_END))
@@ -1040,6 +1067,30 @@ pub async fn playlist_fn(
 	impl_playlist(playlist_name, &collection).await
 }
 
+pub async fn playlist_format_fn(
+	playlist_name: &str,
+	format:        PlaylistFormat,
+	collection:    Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	trace!("REST - playlist_format_fn(): {playlist_name}, {format}");
+
+	let mut vec = vec![];
+	{
+		let playlists = PLAYLISTS.read();
+		let Some(playlist) = playlists.get(playlist_name) else {
+			return Ok(resp::server_err("Playlist was not found"));
+		};
+
+		if Playlists::export(playlist_name, playlist, format, None, &collection, &mut vec).is_err() {
+			return Ok(resp::server_err("Failed to create playlist file"));
+		}
+	}
+
+	let name = format!("{playlist_name}.{}", format.extension());
+
+	Ok(resp::rest_ok(vec, &name, format.mime()))
+}
+
 //---------------------------------------------------------------------------------------------------- `/collection`
 pub async fn collection_fn(collection: Arc<Collection>) -> Result<Response<Body>, anyhow::Error> {
 	// Zip name.
@@ -1098,6 +1149,22 @@ pub async fn collection_fn(collection: Arc<Collection>) -> Result<Response<Body>
 	Ok(resp::rest_zip(body, &zip_name, len))
 }
 
+pub async fn collection_export_fn(
+	format:     ExportFormat,
+	collection: Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	trace!("REST - collection_export_fn(): {format}");
+
+	let name = format!("Collection{}{}.{}", config().filename_separator, collection.timestamp, format.extension());
+
+	// Streamed as it is exported, the length isn't known upfront.
+	let body = resp::stream(move |w| {
+		shukusai::export::export(&collection, format, SongSort::default(), w)
+	});
+
+	Ok(resp::rest_stream(body, &name, format.mime(), None))
+}
+
 //---------------------------------------------------------------------------------------------------- TESTS
 //#[cfg(test)]
 //mod tests {
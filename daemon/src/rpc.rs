@@ -20,15 +20,28 @@ use shukusai::{
 	state::{
 		AUDIO_STATE,
 		PLAYLISTS,
+		Playlists,
+		PlaylistFormat,
+		PlaylistMetadata,
+		REPAIR_THRESHOLD,
+		SMART_PLAYLISTS,
+		HISTORY,
+		QUEUE_SNAPSHOTS,
+		AUDIO_ERRORS,
 	},
 	collection::{
 		Collection,
+		CollectionDiff,
 		Artist,
 		Album,
 		Song,
 		ArtistKey,
 		AlbumKey,
 		SongKey,
+		FIRST_SEEN,
+		RATINGS,
+		RESUME,
+		CHAPTERS,
 		json::{
 			CollectionJson,
 			ArtistJson,
@@ -41,6 +54,10 @@ use shukusai::{
 		COMMIT,
 	},
 	search::SearchKind,
+	sort::{
+		AlbumSort,
+		SongSort,
+	},
 };
 use crate::{
 	resp,
@@ -100,7 +117,9 @@ impl_err! {
 	ERR_INDEX,          15, "Bad index, greater or equal to queue length",
 	ERR_OFFSET,         16, "Bad offset, greater or equal to amount of songs",
 	ERR_PLAYLIST,       17, "Playlist doesn't exist",
-	ERR_INDEX_PLAYLIST, 18, "Bad index, greater or equal to playlist length"
+	ERR_INDEX_PLAYLIST, 18, "Bad index, greater or equal to playlist length",
+	ERR_PLAYLIST_FORMAT, 19, "Unknown playlist file format",
+	ERR_QUEUE_SNAPSHOT, 20, "Queue snapshot doesn't exist"
 }
 
 //---------------------------------------------------------------------------------------------------- Response "Cache"
@@ -120,6 +139,12 @@ pub static CACHE_COLLECTION_ENTRIES:       tokio::sync::RwLock<String> = tokio::
 pub static CACHE_PLAYLIST_BRIEF:           tokio::sync::RwLock<String> = tokio::sync::RwLock::const_new(String::new());
 pub static CACHE_PLAYLIST_FULL:            tokio::sync::RwLock<String> = tokio::sync::RwLock::const_new(String::new());
 
+// The `CollectionDiff` of the last `Collection` reset, if any.
+//
+// INVARIANT: This must be set before `TO_ROUTER_C` gets
+// the new `Collection`, so the keys are always valid.
+pub static COLLECTION_DIFF: tokio::sync::RwLock<Option<Arc<CollectionDiff>>> = tokio::sync::RwLock::const_new(None);
+
 // We pre-serialize the above response, but we
 // need to pop off the `"id": null\n}` at the end.
 // That string is `14` bytes.
@@ -431,6 +456,11 @@ pub async fn handle(
 		CollectionPerf         => lac!(method, request, collection_perf).await,
 		CollectionHealth       => lac!(method, request, collection_health, collection.arc()).await,
 		CollectionResourceSize => lac!(method, request, collection_resource_size, collection.arc()).await,
+		CollectionDuplicates   => lac!(method, request, collection_duplicates, collection.arc()).await,
+		CollectionMistags      => lac!(method, request, collection_mistags, collection.arc()).await,
+		CollectionExport       => ppacor!(method, request, collection_export, rpc::param::CollectionExport, collection.arc()).await,
+		CollectionDiffLast     => lac!(method, request, collection_diff_last, collection.arc()).await,
+		CollectionRecent       => ppacor!(method, request, collection_recent, rpc::param::CollectionRecent, collection.arc()).await,
 
 		//-------------------------------------------------- Daemon
 		DaemonConfig      => lac!(method, request, daemon_config).await,
@@ -452,6 +482,7 @@ pub async fn handle(
 		StateRepeat     => lac!(method, request, state_repeat).await,
 		StateRuntime    => lac!(method, request, state_runtime).await,
 		StateVolume     => lac!(method, request, state_volume).await,
+		StateSleepTimer => lac!(method, request, state_sleep_timer).await,
 
 		//-------------------------------------------------- Key
 		KeyArtist        => ppacor!(method, request, key_artist, rpc::param::KeyArtist, collection.arc()).await,
@@ -507,15 +538,25 @@ pub async fn handle(
 		Next               => lac!(method, request, next, TO_KERNEL).await,
 		Stop               => lac!(method, request, stop, TO_KERNEL).await,
 		Previous           => ppacor!(method, request, previous, rpc::param::Previous, TO_KERNEL).await,
-		Shuffle            => lac!(method, request, shuffle, TO_KERNEL).await,
+		NextChapter        => lac!(method, request, next_chapter, TO_KERNEL).await,
+		PreviousChapter    => lac!(method, request, previous_chapter, TO_KERNEL).await,
+		Shuffle            => match request.params {
+			// No params is the same as `mode: null`.
+			None => shuffle(rpc::param::Shuffle { mode: None }, request.id, TO_KERNEL).await,
+			_    => ppacor!(method, request, shuffle, rpc::param::Shuffle, TO_KERNEL).await,
+		},
 		Clear              => ppacor!(method, request, clear, rpc::param::Clear, TO_KERNEL).await,
 		Seek               => ppacor!(method, request, seek, rpc::param::Seek, TO_KERNEL).await,
 		Skip               => ppacor!(method, request, skip, rpc::param::Skip, TO_KERNEL).await,
 		Back               => ppacor!(method, request, back, rpc::param::Back, TO_KERNEL).await,
 		Repeat             => ppacor!(method, request, repeat, rpc::param::Repeat, TO_KERNEL).await,
+		AutoDj             => ppacor!(method, request, auto_dj, rpc::param::AutoDj, TO_KERNEL).await,
+		SleepTimer         => ppacor!(method, request, sleep_timer, rpc::param::SleepTimer, TO_KERNEL).await,
 		Volume             => ppacor!(method, request, volume, rpc::param::Volume, TO_KERNEL).await,
 		VolumeUp           => ppacor!(method, request, volume_up, rpc::param::VolumeUp, TO_KERNEL).await,
 		VolumeDown         => ppacor!(method, request, volume_down, rpc::param::VolumeDown, TO_KERNEL).await,
+		VolumeDb           => ppacor!(method, request, volume_db, rpc::param::VolumeDb, TO_KERNEL).await,
+		ChannelMix         => ppacor!(method, request, channel_mix, rpc::param::ChannelMix, TO_KERNEL).await,
 
 		//-------------------------------------------------- Queue
 		QueueAddKeyArtist  => ppacor!(method, request, queue_add_key_artist, rpc::param::QueueAddKeyArtist, collection.arc(), TO_KERNEL).await,
@@ -531,6 +572,12 @@ pub async fn handle(
 		QueueAddPlaylist   => ppacor!(method, request, queue_add_playlist, rpc::param::QueueAddPlaylist, collection.arc(), TO_KERNEL).await,
 		QueueSetIndex      => ppacor!(method, request, queue_set_index, rpc::param::QueueSetIndex, TO_KERNEL).await,
 		QueueRemoveRange   => ppacor!(method, request, queue_remove_range, rpc::param::QueueRemoveRange, TO_KERNEL).await,
+		QueueMove          => ppacor!(method, request, queue_move, rpc::param::QueueMove, TO_KERNEL).await,
+		QueueInsert        => ppacor!(method, request, queue_insert, rpc::param::QueueInsert, collection.arc(), TO_KERNEL).await,
+		QueueSave          => ppacor!(method, request, queue_save, rpc::param::QueueSave, collection.arc()).await,
+		QueueRestore       => ppacor!(method, request, queue_restore, rpc::param::QueueRestore, TO_KERNEL).await,
+		QueueSnapshots     => lac!(method, request, queue_snapshots).await,
+		QueueSnapshotDelete => ppacor!(method, request, queue_snapshot_delete, rpc::param::QueueSnapshotDelete).await,
 
 		//-------------------------------------------------- Playlist
 		PlaylistNew          => ppacor!(method, request, playlist_new, rpc::param::PlaylistNew, collection.arc()).await,
@@ -547,6 +594,36 @@ pub async fn handle(
 		PlaylistSingle       => ppacor!(method, request, playlist_single, rpc::param::PlaylistSingle, collection.arc()).await,
 		PlaylistBrief        => lac!(method, request, playlist_brief).await,
 		PlaylistFull         => lac!(method, request, playlist_full).await,
+		PlaylistImport       => ppacor!(method, request, playlist_import, rpc::param::PlaylistImport, collection.arc()).await,
+		PlaylistExport       => ppacor!(method, request, playlist_export, rpc::param::PlaylistExport, collection.arc()).await,
+		PlaylistRename       => ppacor!(method, request, playlist_rename, rpc::param::PlaylistRename, collection.arc()).await,
+		PlaylistMoveIndex    => ppacor!(method, request, playlist_move_index, rpc::param::PlaylistMoveIndex, collection.arc()).await,
+		PlaylistInsertKeys   => ppacor!(method, request, playlist_insert_keys, rpc::param::PlaylistInsertKeys, collection.arc()).await,
+		PlaylistDedup        => ppacor!(method, request, playlist_dedup, rpc::param::PlaylistDedup, collection.arc()).await,
+		PlaylistSort         => ppacor!(method, request, playlist_sort, rpc::param::PlaylistSort, collection.arc()).await,
+		PlaylistRepair       => ppacor!(method, request, playlist_repair, rpc::param::PlaylistRepair, collection.arc()).await,
+		PlaylistRepairApply  => ppacor!(method, request, playlist_repair_apply, rpc::param::PlaylistRepairApply, collection.arc()).await,
+		PlaylistMetadataSet  => ppacor!(method, request, playlist_metadata_set, rpc::param::PlaylistMetadataSet).await,
+
+		//-------------------------------------------------- Smart Playlists
+		SmartPlaylistSet     => ppacor!(method, request, smart_playlist_set, rpc::param::SmartPlaylistSet, collection.arc()).await,
+		SmartPlaylistRemove  => ppacor!(method, request, smart_playlist_remove, rpc::param::SmartPlaylistRemove).await,
+		SmartPlaylistRefresh => ppacor!(method, request, smart_playlist_refresh, rpc::param::SmartPlaylistRefresh, collection.arc()).await,
+		SmartPlaylistFull    => lac!(method, request, smart_playlist_full).await,
+
+		//-------------------------------------------------- History
+		History => ppacor!(method, request, history, rpc::param::History).await,
+
+		//-------------------------------------------------- Ratings
+		RateSong  => ppacor!(method, request, rate_song, rpc::param::RateSong, collection.arc()).await,
+		RateAlbum => ppacor!(method, request, rate_album, rpc::param::RateAlbum, collection.arc()).await,
+		Favorites => lac!(method, request, favorites, collection.arc()).await,
+
+		//-------------------------------------------------- Resume
+		ResumePolicy => ppacor!(method, request, resume_policy, rpc::param::ResumePolicy, collection.arc(), TO_KERNEL).await,
+
+		//-------------------------------------------------- Audio errors
+		AudioErrors => lac!(method, request, audio_errors, collection.arc()).await,
 	}
 }
 
@@ -630,6 +707,7 @@ async fn collection_new<'a>(
 		let collection = loop {
 			match recv!(FROM_KERNEL) {
 				KernelToFrontend::NewCollection(c) => break c,
+				KernelToFrontend::CollectionDiff(d) => *COLLECTION_DIFF.write().await = Some(d),
 				_ => (),
 			}
 		};
@@ -722,9 +800,14 @@ async fn collection_perf<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyho
 
 async fn collection_health<'a>(id: Option<Id<'a>>, collection: Arc<Collection>) -> Result<Response<Body>, anyhow::Error> {
 	let mut vec = vec![];
+	let mut audio_error = vec![];
+	let audio_errors = AUDIO_ERRORS.read();
 
 	for song in collection.songs.iter() {
-		if !song.path.exists() {
+		let missing = !song.path.exists();
+		let errored = !audio_errors.get(&song.path).is_empty();
+
+		if missing || errored {
 			let album  = &collection.albums[song.album];
 			let artist = &collection.artists[album.artist];
 
@@ -737,7 +820,13 @@ async fn collection_health<'a>(id: Option<Id<'a>>, collection: Arc<Collection>)
 				key_song: SongKey::from(song.key),
 				path: Cow::Borrowed(song.path.as_path()),
 			};
-			vec.push(json);
+
+			if errored {
+				audio_error.push(json.clone());
+			}
+			if missing {
+				vec.push(json);
+			}
 		}
 	}
 
@@ -746,6 +835,8 @@ async fn collection_health<'a>(id: Option<Id<'a>>, collection: Arc<Collection>)
 		"song_len": collection.songs.len(),
 		"missing_len": vec.len(),
 		"missing": vec,
+		"audio_error_len": audio_error.len(),
+		"audio_error": audio_error,
 	});
 
 	Ok(resp::result(resp, id))
@@ -783,6 +874,131 @@ async fn collection_resource_size<'a>(
 	}).await
 }
 
+async fn collection_duplicates<'a>(id: Option<Id<'a>>, collection: Arc<Collection>) -> Result<Response<Body>, anyhow::Error> {
+	tokio::task::block_in_place(move || async move {
+		let groups = song_key_groups(&collection.acoustic_duplicates(), &collection);
+
+		let resp = rpc::resp::CollectionDuplicates {
+			len: groups.len(),
+			groups: Cow::Owned(groups),
+		};
+
+		Ok(resp::result(resp, id))
+	}).await
+}
+
+async fn collection_mistags<'a>(id: Option<Id<'a>>, collection: Arc<Collection>) -> Result<Response<Body>, anyhow::Error> {
+	tokio::task::block_in_place(move || async move {
+		let groups = song_key_groups(&collection.acoustic_mistags(), &collection);
+
+		let resp = rpc::resp::CollectionMistags {
+			len: groups.len(),
+			groups: Cow::Owned(groups),
+		};
+
+		Ok(resp::result(resp, id))
+	}).await
+}
+
+async fn collection_export<'a>(
+	params:     rpc::param::CollectionExport,
+	id:         Option<Id<'a>>,
+	collection: Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let rpc::param::CollectionExport { format, sort } = params;
+
+	// `data` is streamed in as it is exported, the
+	// whole `Collection` is never held in memory at once.
+	let resp = rpc::resp::CollectionExport {
+		format,
+		len: collection.songs.len(),
+		data: Cow::Borrowed(""),
+	};
+
+	Ok(resp::result_stream(resp, id, move |w| {
+		shukusai::export::export(&collection, format, sort.unwrap_or_default(), w)
+	}))
+}
+
+async fn collection_diff_last<'a>(id: Option<Id<'a>>, collection: Arc<Collection>) -> Result<Response<Body>, anyhow::Error> {
+	let diff = match &*COLLECTION_DIFF.read().await {
+		Some(d) => Arc::clone(d),
+		None    => return Ok(resp::result(rpc::resp::CollectionDiffLast(None), id)),
+	};
+
+	let resp = rpc::resp::CollectionDiffLastInner {
+		timestamp_old:    diff.timestamp_old,
+		timestamp_new:    diff.timestamp_new,
+		artists_added:    Cow::Borrowed(&diff.artists_added),
+		artists_removed:  diff.artists_removed.iter().map(|a| Cow::Borrowed(&**a)).collect(),
+		artists_modified: Cow::Borrowed(&diff.artists_modified),
+		albums_added:     Cow::Borrowed(&diff.albums_added),
+		albums_removed:   Cow::Borrowed(&diff.albums_removed),
+		albums_modified:  Cow::Borrowed(&diff.albums_modified),
+		songs_added:      Cow::Owned(song_key_entries(&diff.songs_added, &collection)),
+		songs_removed:    Cow::Borrowed(&diff.songs_removed),
+		songs_modified:   Cow::Owned(song_key_entries(&diff.songs_modified, &collection)),
+	};
+
+	Ok(resp::result(rpc::resp::CollectionDiffLast(Some(resp)), id))
+}
+
+async fn collection_recent<'a>(
+	params:     rpc::param::CollectionRecent,
+	id:         Option<Id<'a>>,
+	collection: Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let rpc::param::CollectionRecent { since } = params;
+
+	tokio::task::block_in_place(move || async move {
+		let first_seen = FIRST_SEEN.read();
+
+		// The `DateAddedRev` sorts are newest first,
+		// so stop at the first one older than `since`.
+		let albums: Vec<&Album> = collection
+			.album_iter(AlbumSort::DateAddedRev)
+			.take_while(|k| first_seen.album(**k, &collection) >= since)
+			.map(|k| &collection.albums[k])
+			.collect();
+
+		let songs: Vec<SongKey> = collection
+			.song_iter(SongSort::DateAddedRev)
+			.take_while(|k| first_seen.song(*k, &collection) >= since)
+			.collect();
+		let songs = song_key_entries(&songs, &collection);
+
+		Ok(resp::result(serde_json::json!({
+			"since": since,
+			"album_len": albums.len(),
+			"albums": albums,
+			"song_len": songs.len(),
+			"songs": songs,
+		}), id))
+	}).await
+}
+
+// Map groups of `SongKey`'s into `EntryJson`'s.
+fn song_key_groups<'a>(
+	groups: &[Box<[SongKey]>],
+	collection: &'a Arc<Collection>,
+) -> Vec<Vec<shukusai::collection::EntryJson<'a>>> {
+	groups
+		.iter()
+		.map(|g| song_key_entries(g, collection))
+		.collect()
+}
+
+// Map `SongKey`'s into `EntryJson`'s.
+fn song_key_entries<'a>(
+	keys: &[SongKey],
+	collection: &'a Arc<Collection>,
+) -> Vec<shukusai::collection::EntryJson<'a>> {
+	keys
+		.iter()
+		.map(|k| shukusai::collection::EntryJson::from_song(*k, collection))
+		.collect()
+}
+
 //---------------------------------------------------------------------------------------------------- State
 async fn state_audio<'a>(id: Option<Id<'a>>, collection: Arc<Collection>) -> Result<Response<Body>, anyhow::Error> {
 	let shukusai::state::AudioState {
@@ -794,6 +1010,13 @@ async fn state_audio<'a>(id: Option<Id<'a>>, collection: Arc<Collection>) -> Res
 		runtime,
 		repeat,
 		volume,
+		auto_dj,
+		auto_dj_span,
+		sleep,
+		chapter,
+		channel_mix,
+		output_path,
+		output_rate,
 	} = audio_state_low_priority_lock().await.clone();
 
 	let song_key = song;
@@ -804,6 +1027,11 @@ async fn state_audio<'a>(id: Option<Id<'a>>, collection: Arc<Collection>) -> Res
 		None
 	};
 
+	let chapters = match song {
+		Some(song) => CHAPTERS.read().get(&song.path).to_vec(),
+		None       => vec![],
+	};
+
 	let resp = serde_json::json!({
 		"queue": queue,
 		"queue_len": queue.len(),
@@ -814,6 +1042,14 @@ async fn state_audio<'a>(id: Option<Id<'a>>, collection: Arc<Collection>) -> Res
 		"runtime": runtime.inner(),
 		"repeat": repeat,
 		"volume": volume.inner(),
+		"auto_dj": auto_dj,
+		"auto_dj_span": auto_dj_span,
+		"sleep": sleep,
+		"chapter": chapter,
+		"chapters": chapters,
+		"channel_mix": channel_mix,
+		"output_path": output_path,
+		"output_rate": output_rate,
 		"song": song,
 	});
 
@@ -914,6 +1150,16 @@ async fn state_volume<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyhow::
 	Ok(resp::result(resp, id))
 }
 
+async fn state_sleep_timer<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyhow::Error> {
+	let timer = audio_state_low_priority_lock().await.sleep;
+	let resp = rpc::resp::StateSleepTimer {
+		timer,
+		remaining: timer.remaining(benri::unix!()),
+	};
+
+	Ok(resp::result(resp, id))
+}
+
 //---------------------------------------------------------------------------------------------------- Daemon
 async fn daemon_config<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyhow::Error> {
 	let c = config();
@@ -925,6 +1171,8 @@ async fn daemon_config<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyhow:
 		"exclusive_ips":       c.exclusive_ips.as_ref().map(|h| Cow::Borrowed(h)),
 		"sleep_on_fail":       c.sleep_on_fail.clone(),
 		"collection_paths":    Cow::Borrowed(&c.collection_paths),
+		"fingerprint_seconds": c.fingerprint_seconds,
+		"sort_articles":       Cow::Borrowed(&c.sort_articles),
 		"tls":                 c.tls,
 		"certificate":         c.certificate.as_ref().map(|p| Cow::Borrowed(p.as_path())),
 		"key":                 c.key.as_ref().map(|p| Cow::Borrowed(p.as_path())),
@@ -938,6 +1186,15 @@ async fn daemon_config<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyhow:
 		"cache_time":          c.cache_time,
 		"restore_audio_state": c.restore_audio_state,
 		"previous_threshold":  c.previous_threshold,
+		"previous_history":    c.previous_history,
+		"auto_dj":             c.auto_dj,
+		"resume_threshold":    c.resume_threshold,
+		"volume_db_curve":     c.volume_db_curve,
+		"volume_step":         c.volume_step,
+		"limiter":             c.limiter,
+		"max_decode_errors":   c.max_decode_errors,
+		"resampler_quality":   c.resampler_quality,
+		"bit_perfect":         c.bit_perfect,
 		"media_controls":      c.media_controls,
 		"authorization":       AUTH.get().is_some(),
 		"confirm_no_tls_auth": c.confirm_no_tls_auth,
@@ -962,6 +1219,11 @@ async fn daemon_save<'a>(
 			return Ok(resp::error(ERR_FS.0, ERR_FS.1, id));
 		}
 
+		// Save `SmartPlaylists`.
+		if SMART_PLAYLISTS.read().save_atomic().is_err() {
+			return Ok(resp::error(ERR_FS.0, ERR_FS.1, id));
+		}
+
 		// Save `AudioState`.
 		if AUDIO_STATE.read().save_atomic().is_err() {
 			return Ok(resp::error(ERR_FS.0, ERR_FS.1, id));
@@ -1713,11 +1975,21 @@ macro_rules! impl_signal {
 }
 
 impl_signal! {
-	toggle,       FrontendToKernel::Toggle,
-	play,         FrontendToKernel::Play,
-	pause,        FrontendToKernel::Pause,
-	next,         FrontendToKernel::Next,
-	shuffle,      FrontendToKernel::Shuffle
+	toggle,           FrontendToKernel::Toggle,
+	play,             FrontendToKernel::Play,
+	pause,            FrontendToKernel::Pause,
+	next,             FrontendToKernel::Next,
+	next_chapter,     FrontendToKernel::NextChapter,
+	previous_chapter, FrontendToKernel::PreviousChapter
+}
+
+async fn shuffle<'a>(
+	params:    rpc::param::Shuffle,
+	id:        Option<Id<'a>>,
+	TO_KERNEL: &Sender<FrontendToKernel>,
+) -> Result<Response<Body>, anyhow::Error> {
+	send!(TO_KERNEL, FrontendToKernel::Shuffle(params.mode.unwrap_or_default()));
+	Ok(resp::result_ok(id))
 }
 
 async fn previous<'a>(
@@ -1788,7 +2060,16 @@ async fn repeat<'a>(
 	id:        Option<Id<'a>>,
 	TO_KERNEL: &Sender<FrontendToKernel>
 ) -> Result<Response<Body>, anyhow::Error> {
-	let current  = params.mode;
+	// `count`, `a` and `b` override the values inside `mode`.
+	let current = match params.mode {
+		shukusai::audio::Repeat::Count(count) => shukusai::audio::Repeat::Count(params.count.unwrap_or(count)),
+		shukusai::audio::Repeat::Ab { a, b }  => shukusai::audio::Repeat::Ab { a: params.a.unwrap_or(a), b: params.b.unwrap_or(b) },
+		mode => mode,
+	};
+	if !current.is_valid() {
+		return Ok(resp::invalid_params(id));
+	}
+
 	let previous = audio_state_low_priority_lock().await.repeat;
 	let resp = rpc::resp::Repeat {
 		previous,
@@ -1800,6 +2081,54 @@ async fn repeat<'a>(
 	Ok(resp::result(resp, id))
 }
 
+async fn auto_dj<'a>(
+	params:    rpc::param::AutoDj,
+	id:        Option<Id<'a>>,
+	TO_KERNEL: &Sender<FrontendToKernel>
+) -> Result<Response<Body>, anyhow::Error> {
+	let current  = params.on;
+	let previous = audio_state_low_priority_lock().await.auto_dj;
+	let resp = rpc::resp::AutoDj {
+		previous,
+		current,
+	};
+	if previous != current {
+		send!(TO_KERNEL, FrontendToKernel::AutoDj(current));
+	}
+	Ok(resp::result(resp, id))
+}
+
+async fn sleep_timer<'a>(
+	params:    rpc::param::SleepTimer,
+	id:        Option<Id<'a>>,
+	TO_KERNEL: &Sender<FrontendToKernel>
+) -> Result<Response<Body>, anyhow::Error> {
+	use shukusai::audio::SleepTimer;
+	let now = benri::unix!();
+
+	// `minutes` and `fade` override the values inside `mode`.
+	let current = match (params.mode, params.minutes) {
+		(SleepTimer::Time { fade, .. }, Some(minutes)) => SleepTimer::minutes(minutes, params.fade.unwrap_or(fade), now),
+		(SleepTimer::Time { at, fade }, None)          => SleepTimer::Time { at, fade: params.fade.unwrap_or(fade) },
+		(mode, _) => mode,
+	};
+	if let SleepTimer::Time { at, .. } = current {
+		if at <= now {
+			return Ok(resp::invalid_params(id));
+		}
+	}
+
+	let previous = audio_state_low_priority_lock().await.sleep;
+	let resp = rpc::resp::SleepTimer {
+		previous,
+		current,
+	};
+	if previous != current {
+		send!(TO_KERNEL, FrontendToKernel::Sleep(current));
+	}
+	Ok(resp::result(resp, id))
+}
+
 async fn volume<'a>(
 	params:    rpc::param::Volume,
 	id:        Option<Id<'a>>,
@@ -1821,7 +2150,7 @@ async fn volume_up<'a>(
 	TO_KERNEL: &Sender<FrontendToKernel>,
 ) -> Result<Response<Body>, anyhow::Error> {
 	let previous = shukusai::audio::Volume::new(atomic_load!(shukusai::state::VOLUME));
-	let current  = previous.inner() + params.up;
+	let current  = previous.inner().saturating_add(params.up.unwrap_or(config().volume_step));
 	let current  = shukusai::audio::Volume::new(if current > 100 { 100 } else { current });
 	send!(TO_KERNEL, FrontendToKernel::Volume(current));
 	let resp = rpc::resp::VolumeUp {
@@ -1837,7 +2166,7 @@ async fn volume_down<'a>(
 	TO_KERNEL: &Sender<FrontendToKernel>,
 ) -> Result<Response<Body>, anyhow::Error> {
 	let previous = shukusai::audio::Volume::new(atomic_load!(shukusai::state::VOLUME));
-	let current  = shukusai::audio::Volume::new(previous.inner().saturating_sub(params.down));
+	let current  = shukusai::audio::Volume::new(previous.inner().saturating_sub(params.down.unwrap_or(config().volume_step)));
 	send!(TO_KERNEL, FrontendToKernel::Volume(current));
 	let resp = rpc::resp::VolumeDown {
 		previous,
@@ -1846,6 +2175,45 @@ async fn volume_down<'a>(
 	Ok(resp::result(resp, id))
 }
 
+async fn volume_db<'a>(
+	params:    rpc::param::VolumeDb,
+	id:        Option<Id<'a>>,
+	TO_KERNEL: &Sender<FrontendToKernel>,
+) -> Result<Response<Body>, anyhow::Error> {
+	// `-inf` (muted) is `null`.
+	let db = |v: shukusai::audio::Volume| Some(v.gain_db()).filter(|db| db.is_finite());
+
+	let previous = shukusai::audio::Volume::new(atomic_load!(shukusai::state::VOLUME));
+	let current  = shukusai::audio::Volume::from_gain_db(params.db);
+	send!(TO_KERNEL, FrontendToKernel::Volume(current));
+	let resp = rpc::resp::VolumeDb {
+		previous: db(previous),
+		current:  db(current),
+	};
+	Ok(resp::result(resp, id))
+}
+
+async fn channel_mix<'a>(
+	params:    rpc::param::ChannelMix,
+	id:        Option<Id<'a>>,
+	TO_KERNEL: &Sender<FrontendToKernel>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let previous = audio_state_low_priority_lock().await.channel_mix;
+	let current  = shukusai::audio::ChannelMix {
+		mono:    params.mono.unwrap_or(previous.mono),
+		balance: params.balance.unwrap_or(previous.balance),
+		swap:    params.swap.unwrap_or(previous.swap),
+	}.check();
+	let resp = rpc::resp::ChannelMix {
+		previous,
+		current,
+	};
+	if previous != current {
+		send!(TO_KERNEL, FrontendToKernel::ChannelMix(current));
+	}
+	Ok(resp::result(resp, id))
+}
+
 //---------------------------------------------------------------------------------------------------- Queue
 macro_rules! get_append {
 	($params:expr, $id:expr) => {
@@ -1996,7 +2364,9 @@ async fn queue_add_rand_artist<'a>(
 	collection: Arc<Collection>,
 	TO_KERNEL:  &Sender<FrontendToKernel>
 ) -> Result<Response<Body>, anyhow::Error> {
-	if let Some(key) = collection.rand_artist(None) {
+	let current = audio_state_low_priority_lock().await.song;
+
+	if let Some(key) = params.mode.unwrap_or_default().rand_artist(&collection, current) {
 		let x = &collection.artists[key];
 
 		let append = get_append!(params, id);
@@ -2016,7 +2386,9 @@ async fn queue_add_rand_album<'a>(
 	collection: Arc<Collection>,
 	TO_KERNEL:  &Sender<FrontendToKernel>
 ) -> Result<Response<Body>, anyhow::Error> {
-	if let Some(key) = collection.rand_album(None) {
+	let current = audio_state_low_priority_lock().await.song;
+
+	if let Some(key) = params.mode.unwrap_or_default().rand_album(&collection, current) {
 		let x = &collection.albums[key];
 
 		let append = get_append!(params, id);
@@ -2036,7 +2408,9 @@ async fn queue_add_rand_song<'a>(
 	collection: Arc<Collection>,
 	TO_KERNEL:  &Sender<FrontendToKernel>
 ) -> Result<Response<Body>, anyhow::Error> {
-	if let Some(key) = collection.rand_song(None) {
+	let current = audio_state_low_priority_lock().await.song;
+
+	if let Some(key) = params.mode.unwrap_or_default().rand_song(&collection, current) {
 		let x = &collection.songs[key];
 
 		let append = get_append!(params, id);
@@ -2055,7 +2429,9 @@ async fn queue_add_rand_entry<'a>(
 	collection: Arc<Collection>,
 	TO_KERNEL:  &Sender<FrontendToKernel>
 ) -> Result<Response<Body>, anyhow::Error> {
-	if let Some(key) = collection.rand_song(None) {
+	let current = audio_state_low_priority_lock().await.song;
+
+	if let Some(key) = params.mode.unwrap_or_default().rand_song(&collection, current) {
 		let x = &collection.songs[key];
 
 		let append = get_append!(params, id);
@@ -2118,6 +2494,98 @@ async fn queue_remove_range<'a>(
 	}
 }
 
+async fn queue_move<'a>(
+	params:    rpc::param::QueueMove,
+	id:        Option<Id<'a>>,
+	TO_KERNEL: &Sender<FrontendToKernel>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let queue_len = audio_state_low_priority_lock().await.queue.len();
+
+	if params.from >= queue_len || params.to >= queue_len {
+		Ok(resp::result(rpc::resp::QueueMove { out_of_bounds: true, from: params.from, to: params.to, queue_len }, id))
+	} else {
+		send!(TO_KERNEL, FrontendToKernel::QueueMove((params.from, params.to)));
+		Ok(resp::result(rpc::resp::QueueMove { out_of_bounds: false, from: params.from, to: params.to, queue_len }, id))
+	}
+}
+
+async fn queue_insert<'a>(
+	params:     rpc::param::QueueInsert,
+	id:         Option<Id<'a>>,
+	collection: Arc<Collection>,
+	TO_KERNEL:  &Sender<FrontendToKernel>,
+) -> Result<Response<Body>, anyhow::Error> {
+	if params.keys.iter().any(|k| collection.songs.get(SongKey::from(*k)).is_none()) {
+		return Ok(resp::error(ERR_KEY_SONG.0, ERR_KEY_SONG.1, id));
+	}
+
+	let queue_len = audio_state_low_priority_lock().await.queue.len();
+	let len       = params.keys.len();
+
+	if params.index > queue_len {
+		Ok(resp::result(rpc::resp::QueueInsert { out_of_bounds: true, index: params.index, len, queue_len }, id))
+	} else {
+		let keys: Box<[SongKey]> = params.keys.into_iter().map(SongKey::from).collect();
+		send!(TO_KERNEL, FrontendToKernel::QueueInsert((params.index, keys)));
+		Ok(resp::result(rpc::resp::QueueInsert { out_of_bounds: false, index: params.index, len, queue_len }, id))
+	}
+}
+
+async fn queue_save<'a>(
+	params:     rpc::param::QueueSave<'a>,
+	id:         Option<Id<'a>>,
+	collection: Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let state = audio_state_low_priority_lock().await;
+	let len   = state.queue.len();
+
+	let existed = QUEUE_SNAPSHOTS.write().insert(params.name.into(), &state, benri::unix!(), &collection).is_some();
+
+	Ok(resp::result(rpc::resp::QueueSave { existed, len }, id))
+}
+
+async fn queue_restore<'a>(
+	params:    rpc::param::QueueRestore<'a>,
+	id:        Option<Id<'a>>,
+	TO_KERNEL: &Sender<FrontendToKernel>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let (len, valid_len, index, elapsed) = match QUEUE_SNAPSHOTS.read().get(&*params.name) {
+		Some(s) => {
+			let (queue, index, elapsed) = s.restore();
+			(s.queue.len(), queue.len(), index, elapsed)
+		},
+		None => return Ok(resp::error(ERR_QUEUE_SNAPSHOT.0, ERR_QUEUE_SNAPSHOT.1, id)),
+	};
+
+	send!(TO_KERNEL, FrontendToKernel::QueueRestore(params.name.into()));
+
+	Ok(resp::result(rpc::resp::QueueRestore { len, valid_len, index, elapsed }, id))
+}
+
+async fn queue_snapshots<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyhow::Error> {
+	let snapshots = QUEUE_SNAPSHOTS.read();
+
+	let resp = serde_json::json!({
+		"len":       snapshots.len(),
+		"snapshots": &*snapshots,
+	});
+
+	Ok(resp::result(resp, id))
+}
+
+async fn queue_snapshot_delete<'a>(
+	params: rpc::param::QueueSnapshotDelete<'a>,
+	id:     Option<Id<'a>>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let snapshot = QUEUE_SNAPSHOTS.write().remove(&params.name);
+
+	let resp = serde_json::json!({
+		"snapshot": snapshot,
+	});
+
+	Ok(resp::result(resp, id))
+}
+
 //---------------------------------------------------------------------------------------------------- Playlists
 async fn playlist_new<'a>(
 	params:      rpc::param::PlaylistNew<'a>,
@@ -2135,7 +2603,11 @@ async fn playlist_remove<'a>(
 	id:          Option<Id<'a>>,
 	collection:  Arc<Collection>,
 ) -> Result<Response<Body>, anyhow::Error> {
-	match PLAYLISTS.write().playlist_remove(params.playlist.into()) {
+	let mut playlists = PLAYLISTS.write();
+	// Smart playlist rules go with the playlist.
+	SMART_PLAYLISTS.write().remove(&*params.playlist);
+
+	match playlists.playlist_remove(params.playlist.into()) {
 		Some(v) => Ok(resp::result(serde_json::json!({ "len": v.len(), "entries": v }), id)),
 		None    => Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id)),
 	}
@@ -2343,11 +2815,13 @@ async fn playlist_single<'a>(
 }
 
 async fn playlist_brief<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyhow::Error> {
-	let playlists: Cow<Vec<Arc<str>>> = Cow::Owned(PLAYLISTS.read().name_arcs());
+	let lock = PLAYLISTS.read();
+	let playlists: Cow<Vec<Arc<str>>> = Cow::Owned(lock.name_arcs());
 
 	let resp = serde_json::json!({
 		"len": playlists.len(),
 		"playlists": playlists,
+		"metadata": lock.1,
 	});
 
 	Ok(resp::result(resp, id))
@@ -2378,7 +2852,425 @@ async fn playlist_full<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyhow:
 		"entry_len":    entry_len,
 		"valid_len":        valid,
 		"invalid_len":      invalid,
-		"playlists":    playlists,
+		"playlists":    playlists.0,
+		"metadata":     playlists.1,
+	});
+
+	Ok(resp::result(resp, id))
+}
+
+async fn playlist_import<'a>(
+	params:      rpc::param::PlaylistImport<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let Some(format) = params.format.or_else(|| PlaylistFormat::from_path(&params.path)) else {
+		return Ok(resp::error(ERR_PLAYLIST_FORMAT.0, ERR_PLAYLIST_FORMAT.1, id));
+	};
+
+	let Ok(bytes) = tokio::fs::read(&params.path).await else {
+		return Ok(resp::error(ERR_FS.0, ERR_FS.1, id));
+	};
+	let string = String::from_utf8_lossy(&bytes);
+
+	let playlist    = Playlists::import(&string, format, params.path.parent(), &collection);
+	let len         = playlist.len();
+	let invalid_len = Playlists::invalid_len(&playlist);
+	let existed     = PLAYLISTS.write().playlist_set(params.playlist.into(), playlist).is_some();
+
+	let resp = rpc::resp::PlaylistImport {
+		existed,
+		len,
+		valid_len: len - invalid_len,
+		invalid_len,
+	};
+
+	Ok(resp::result(resp, id))
+}
+
+async fn playlist_export<'a>(
+	params:      rpc::param::PlaylistExport<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let playlists = PLAYLISTS.read();
+
+	let Some(playlist) = playlists.get(&*params.playlist) else {
+		return Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id));
+	};
+
+	// `Invalid` entries are only written in `XSPF`.
+	let len = match params.format {
+		PlaylistFormat::Xspf => playlist.len(),
+		_ => Playlists::valid_len(playlist),
+	};
+	let mut vec = vec![];
+	if Playlists::export(&params.playlist, playlist, params.format, params.relative_to.as_deref(), &collection, &mut vec).is_err() {
+		return Ok(resp::error(ERR_SERDE.0, ERR_SERDE.1, id));
+	}
+
+	// All exports are written from `str`'s, this is always valid UTF-8.
+	let data = String::from_utf8_lossy(&vec);
+
+	let resp = rpc::resp::PlaylistExport {
+		format: params.format,
+		len,
+		data,
+	};
+
+	Ok(resp::result(resp, id))
+}
+
+async fn playlist_rename<'a>(
+	params:      rpc::param::PlaylistRename<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let mut playlists = PLAYLISTS.write();
+
+	let result = playlists.playlist_rename(Arc::from(&*params.from), &params.to);
+
+	// Smart playlist rules go with the playlist.
+	if result.is_ok() {
+		SMART_PLAYLISTS.write().rename(&params.from, &params.to);
+	}
+
+	match result {
+		Ok(Some(v)) => Ok(resp::result(serde_json::json!({ "len": v.len(), "entries": v }), id)),
+		Ok(None)    => Ok(resp::result(rpc::resp::PlaylistRename { len: None, entries: None }, id)),
+		Err(_)      => Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id)),
+	}
+}
+
+async fn playlist_move_index<'a>(
+	params:      rpc::param::PlaylistMoveIndex<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let mut playlists = PLAYLISTS.write();
+	let name: Arc<str> = params.playlist.into();
+
+	let Ok(moved) = playlists.playlist_move_index(params.from, params.to, Arc::clone(&name)) else {
+		return Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id));
+	};
+
+	let playlist_len = playlists.get(&name).map_or(0, |v| v.len());
+
+	Ok(resp::result(rpc::resp::PlaylistMoveIndex { out_of_bounds: !moved, from: params.from, to: params.to, playlist_len }, id))
+}
+
+async fn playlist_insert_keys<'a>(
+	params:      rpc::param::PlaylistInsertKeys<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	if params.keys.iter().any(|k| collection.songs.get(SongKey::from(*k)).is_none()) {
+		return Ok(resp::error(ERR_KEY_SONG.0, ERR_KEY_SONG.1, id));
+	}
+
+	let mut playlists = PLAYLISTS.write();
+	let name: Arc<str> = params.playlist.into();
+	let keys: Box<[SongKey]> = params.keys.into_iter().map(SongKey::from).collect();
+	let len = keys.len();
+
+	let Ok(inserted) = playlists.playlist_insert_songs(params.index, &keys, Arc::clone(&name), &collection) else {
+		return Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id));
+	};
+
+	let playlist_len = playlists.get(&name).map_or(0, |v| v.len());
+
+	Ok(resp::result(rpc::resp::PlaylistInsertKeys { out_of_bounds: !inserted, index: params.index, len, playlist_len }, id))
+}
+
+async fn playlist_dedup<'a>(
+	params:      rpc::param::PlaylistDedup<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let mut playlists = PLAYLISTS.write();
+	let name: Arc<str> = params.playlist.into();
+
+	let Some(removed) = playlists.playlist_dedup(Arc::clone(&name)) else {
+		return Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id));
+	};
+
+	let len = playlists.get(&name).map_or(0, |v| v.len());
+
+	Ok(resp::result(rpc::resp::PlaylistDedup { removed, len }, id))
+}
+
+async fn playlist_sort<'a>(
+	params:      rpc::param::PlaylistSort<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let mut playlists = PLAYLISTS.write();
+	let name: Arc<str> = params.playlist.into();
+
+	if !playlists.playlist_sort(params.sort, Arc::clone(&name), &collection) {
+		return Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id));
+	}
+
+	let len = playlists.get(&name).map_or(0, |v| v.len());
+
+	Ok(resp::result(rpc::resp::PlaylistSort { len }, id))
+}
+
+async fn playlist_repair<'a>(
+	params:      rpc::param::PlaylistRepair<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let threshold = match params.threshold {
+		Some(t) => f64::from(t.min(100)) / 100.0,
+		None    => REPAIR_THRESHOLD,
+	};
+
+	// There's no old `Collection` to get hints from here,
+	// only `Kernel`'s repair after a reset has those.
+	let hints = std::collections::HashMap::new();
+
+	let mut playlists = PLAYLISTS.write();
+
+	let repairs = match params.playlist {
+		Some(playlist) => {
+			if !playlists.contains_key(&*playlist) {
+				return Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id));
+			}
+			playlists.repair_playlist(threshold, &hints, playlist.into(), &collection)
+		},
+		None => playlists.repair(threshold, &hints, &collection),
+	};
+
+	let applied = repairs.iter().filter(|r| r.applied).count();
+
+	let resp = rpc::resp::PlaylistRepair {
+		applied,
+		review: repairs.len() - applied,
+		repairs,
+	};
+
+	Ok(resp::result(resp, id))
+}
+
+async fn playlist_repair_apply<'a>(
+	params:      rpc::param::PlaylistRepairApply<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let key = SongKey::from(params.key);
+	if collection.songs.get(key).is_none() {
+		return Ok(resp::error(ERR_KEY_SONG.0, ERR_KEY_SONG.1, id));
+	}
+
+	match PLAYLISTS.write().repair_apply(params.index, key, params.playlist.into(), &collection) {
+		Ok(applied) => Ok(resp::result(rpc::resp::PlaylistRepairApply { applied }, id)),
+		Err(_)      => Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id)),
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Smart Playlists
+async fn playlist_metadata_set<'a>(
+	params: rpc::param::PlaylistMetadataSet<'a>,
+	id:     Option<Id<'a>>,
+) -> Result<Response<Body>, anyhow::Error> {
+	// The art must be an existing file, an empty PATH removes it.
+	let art = match params.art {
+		Some(art) if art.as_os_str().is_empty() => Some(None),
+		Some(art) if art.is_file()              => Some(Some(art)),
+		Some(_)                                 => return Ok(resp::error(ERR_FS.0, ERR_FS.1, id)),
+		None                                    => None,
+	};
+
+	let mut playlists = PLAYLISTS.write();
+	let Some(metadata) = playlists.metadata_mut(&params.playlist) else {
+		return Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id));
+	};
+
+	if let Some(description) = params.description {
+		metadata.description = description.into_owned();
+	}
+	if let Some(folder) = params.folder {
+		metadata.folder = PlaylistMetadata::folder_normalize(&folder);
+	}
+	if let Some(art) = art {
+		metadata.art = art;
+	}
+	if let Some(pinned) = params.pinned {
+		metadata.pinned = pinned;
+	}
+	metadata.modified = benri::unix!();
+
+	Ok(resp::result(rpc::resp::PlaylistMetadataSet { metadata: metadata.clone() }, id))
+}
+
+async fn smart_playlist_set<'a>(
+	params:      rpc::param::SmartPlaylistSet<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let smart = shukusai::state::SmartPlaylist {
+		rules: params.rules,
+		limit: params.limit,
+		sort:  params.sort.unwrap_or_default(),
+	};
+
+	let name: Arc<str> = params.playlist.into();
+
+	let mut playlists = PLAYLISTS.write();
+	let mut smart_playlists = SMART_PLAYLISTS.write();
+	let existed = smart_playlists.insert(Arc::clone(&name), smart).is_some();
+	let len = smart_playlists.refresh(&name, &mut playlists, &collection).unwrap_or(0);
+
+	Ok(resp::result(rpc::resp::SmartPlaylistSet { existed, len }, id))
+}
+
+async fn smart_playlist_remove<'a>(
+	params: rpc::param::SmartPlaylistRemove<'a>,
+	id:     Option<Id<'a>>,
+) -> Result<Response<Body>, anyhow::Error> {
+	match SMART_PLAYLISTS.write().remove(&*params.playlist) {
+		Some(smart_playlist) => Ok(resp::result(rpc::resp::SmartPlaylistRemove { smart_playlist }, id)),
+		None                 => Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id)),
+	}
+}
+
+async fn smart_playlist_refresh<'a>(
+	params:      rpc::param::SmartPlaylistRefresh<'a>,
+	id:          Option<Id<'a>>,
+	collection:  Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let mut playlists = PLAYLISTS.write();
+	match SMART_PLAYLISTS.read().refresh(&params.playlist, &mut playlists, &collection) {
+		Some(len) => Ok(resp::result(rpc::resp::SmartPlaylistRefresh { len }, id)),
+		None      => Ok(resp::error(ERR_PLAYLIST.0, ERR_PLAYLIST.1, id)),
+	}
+}
+
+async fn smart_playlist_full<'a>(id: Option<Id<'a>>) -> Result<Response<Body>, anyhow::Error> {
+	let smart_playlists = SMART_PLAYLISTS.read();
+
+	let resp = serde_json::json!({
+		"len":             smart_playlists.len(),
+		"smart_playlists": &*smart_playlists,
+	});
+
+	Ok(resp::result(resp, id))
+}
+
+//---------------------------------------------------------------------------------------------------- History
+async fn history<'a>(
+	params: rpc::param::History,
+	id:     Option<Id<'a>>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let history = HISTORY.read();
+	let entries = history.page(params.offset, params.len);
+
+	let resp = serde_json::json!({
+		"len":       history.len(),
+		"offset":    params.offset,
+		"entry_len": entries.len(),
+		"entries":   entries,
+	});
+
+	Ok(resp::result(resp, id))
+}
+
+//---------------------------------------------------------------------------------------------------- Ratings
+async fn rate_song<'a>(
+	params:     rpc::param::RateSong,
+	id:         Option<Id<'a>>,
+	collection: Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let key = SongKey::from(params.key);
+	if collection.songs.get(key).is_none() {
+		return Ok(resp::error(ERR_KEY_SONG.0, ERR_KEY_SONG.1, id));
+	}
+
+	let previous = RATINGS.rate_song(key, params.rating, &collection);
+	let rating   = RATINGS.read().song(key, &collection);
+
+	Ok(resp::result(rpc::resp::RateSong { previous, rating }, id))
+}
+
+async fn rate_album<'a>(
+	params:     rpc::param::RateAlbum,
+	id:         Option<Id<'a>>,
+	collection: Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let key = AlbumKey::from(params.key);
+	if collection.albums.get(key).is_none() {
+		return Ok(resp::error(ERR_KEY_ALBUM.0, ERR_KEY_ALBUM.1, id));
+	}
+
+	let previous = RATINGS.rate_album(key, params.rating, &collection);
+	let rating   = RATINGS.read().album(key, &collection);
+
+	Ok(resp::result(rpc::resp::RateAlbum { previous, rating }, id))
+}
+
+async fn favorites<'a>(
+	id:         Option<Id<'a>>,
+	collection: Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let ratings = RATINGS.read();
+
+	let albums: Vec<serde_json::Value> = ratings
+		.favorite_albums(&collection)
+		.into_iter()
+		.map(|(k, rating)| serde_json::json!({ "rating": rating, "album": &collection.albums[k] }))
+		.collect();
+
+	let songs: Vec<serde_json::Value> = ratings
+		.favorite_songs(&collection)
+		.into_iter()
+		.map(|(k, rating)| serde_json::json!({ "rating": rating, "song": &collection.songs[k] }))
+		.collect();
+
+	let resp = serde_json::json!({
+		"album_len": albums.len(),
+		"albums":    albums,
+		"song_len":  songs.len(),
+		"songs":     songs,
+	});
+
+	Ok(resp::result(resp, id))
+}
+
+//---------------------------------------------------------------------------------------------------- Resume
+async fn resume_policy<'a>(
+	params:     rpc::param::ResumePolicy,
+	id:         Option<Id<'a>>,
+	collection: Arc<Collection>,
+	TO_KERNEL:  &Sender<FrontendToKernel>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let key = SongKey::from(params.key);
+	let Some(song) = collection.songs.get(key) else {
+		return Ok(resp::error(ERR_KEY_SONG.0, ERR_KEY_SONG.1, id));
+	};
+
+	let previous = RESUME.read().policy(&song.path);
+	let current  = params.policy;
+
+	if previous != current {
+		send!(TO_KERNEL, FrontendToKernel::ResumePolicy((key, current)));
+	}
+
+	Ok(resp::result(rpc::resp::ResumePolicy { previous, current }, id))
+}
+
+//---------------------------------------------------------------------------------------------------- Audio errors
+async fn audio_errors<'a>(
+	id:         Option<Id<'a>>,
+	collection: Arc<Collection>,
+) -> Result<Response<Body>, anyhow::Error> {
+	let audio_errors = AUDIO_ERRORS.read();
+	let songs = audio_errors.json(&collection);
+
+	let resp = serde_json::json!({
+		"len":       songs.len(),
+		"error_len": songs.iter().map(|s| s.errors.len()).sum::<usize>(),
+		"songs":     songs,
 	});
 
 	Ok(resp::result(resp, id))
@@ -5,8 +5,9 @@ use disk::Empty;
 use disk::{Bincode2, Json, Plain};
 use shukusai::constants::COPYRIGHT;
 use shukusai::signal::{
-    Back, Clear, Index, Next, Pause, Play, Previous, RepeatOff, RepeatQueue, RepeatSong, Seek,
-    SeekBackward, SeekForward, Shuffle, Skip, Stop, Toggle, Volume,
+    Back, Clear, Index, Next, Pause, Play, Previous, RepeatAb, RepeatAlbum, RepeatArtist,
+    RepeatCount, RepeatOff, RepeatQueue, RepeatSong, Seek, SeekBackward, SeekForward, Shuffle,
+    Skip, SleepAlbum, SleepMinutes, SleepOff, SleepQueue, SleepSong, Stop, Toggle, Volume,
 };
 use std::num::NonZeroUsize;
 
@@ -66,6 +67,42 @@ pub struct Cli {
     /// Turn off repeating
     repeat_off: bool,
 
+    #[arg(long)]
+    /// Turn on repeat of the current album's songs in the queue
+    repeat_album: bool,
+
+    #[arg(long)]
+    /// Turn on repeat of the current artist's songs in the queue
+    repeat_artist: bool,
+
+    #[arg(long)]
+    /// Repeat the current song `REPEAT_COUNT` more times, then turn off repeating
+    repeat_count: Option<u8>,
+
+    #[arg(long)]
+    /// Loop between two seconds of the current song, e.g. `30-45`
+    repeat_ab: Option<String>,
+
+    #[arg(long)]
+    /// Pause playback after the current song finishes
+    sleep_song: bool,
+
+    #[arg(long)]
+    /// Pause playback after the current album's songs in the queue finish
+    sleep_album: bool,
+
+    #[arg(long)]
+    /// Pause playback when the queue finishes
+    sleep_queue: bool,
+
+    #[arg(long)]
+    /// Pause playback after `SLEEP_MINUTES` minutes
+    sleep_minutes: Option<u32>,
+
+    #[arg(long)]
+    /// Turn off the sleep timer
+    sleep_off: bool,
+
     #[arg(long)]
     #[arg(value_parser = clap::value_parser!(u8).range(0..=100))]
     /// Set the volume to `VOLUME` (0-100)
@@ -264,6 +301,24 @@ impl Cli {
         if self.repeat_off {
             handle(RepeatOff::touch())
         }
+        if self.repeat_album {
+            handle(RepeatAlbum::touch())
+        }
+        if self.repeat_artist {
+            handle(RepeatArtist::touch())
+        }
+        if self.sleep_song {
+            handle(SleepSong::touch())
+        }
+        if self.sleep_album {
+            handle(SleepAlbum::touch())
+        }
+        if self.sleep_queue {
+            handle(SleepQueue::touch())
+        }
+        if self.sleep_off {
+            handle(SleepOff::touch())
+        }
 
         // Content signals.
         if let Some(volume) = self.volume {
@@ -287,6 +342,15 @@ impl Cli {
         if let Some(back) = self.back {
             handle(Back(back).save())
         }
+        if let Some(count) = self.repeat_count {
+            handle(RepeatCount(count).save())
+        }
+        if let Some(ab) = &self.repeat_ab {
+            handle(RepeatAb(ab.clone()).save())
+        }
+        if let Some(minutes) = self.sleep_minutes {
+            handle(SleepMinutes(minutes).save())
+        }
 
         // Docs
         if self.docs {
@@ -155,7 +155,7 @@ pub const GUI: &str = "gui";
 pub const STATE_VERSION: u8 = 1;
 
 /// Current major version of `GUI`'s `Settings`
-pub const SETTINGS_VERSION: u8 = 3;
+pub const SETTINGS_VERSION: u8 = 7;
 
 //---------------------------------------------------------------------------------------------------- Resolution
 // 700.0 works on some `Album`'s in view tabs
@@ -203,10 +203,23 @@ pub const RUNTIME_WIDTH: f32 = 165.0;
 /// variable width left panel on a 16:9 display.
 pub const UI_CONTROL_WIDTH: f32 = 77.0;
 
+//---------------------------------------------------------------------------------------------------- Albums tab
+/// How recently (in seconds) an `Album` must have been
+/// first seen to show up in the "Recently added" view.
+///
+/// This is 30 days.
+pub const RECENTLY_ADDED_SECONDS: u64 = 60 * 60 * 24 * 30;
+
 //---------------------------------------------------------------------------------------------------- Queue tab
 /// Fixed size of the `Album` art in the `Queue` tab.
 pub const QUEUE_ALBUM_ART_SIZE: f32 = 80.0;
 
+/// How many minutes the sleep timer button sets/adds.
+pub const SLEEP_TIMER_MINUTES: u32 = 15;
+
+/// How many seconds the sleep timer fades out the volume for.
+pub const SLEEP_TIMER_FADE: u32 = 30;
+
 //---------------------------------------------------------------------------------------------------- Settings
 pub const PREVIOUS_THRESHOLD_MIN: u32 = 0;
 pub const PREVIOUS_THRESHOLD_MAX: u32 = 20;
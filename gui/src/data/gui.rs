@@ -2,7 +2,7 @@
 use crate::data::{DebugInfo, Settings, State, StateRestore};
 use crossbeam::channel::{Receiver, Sender};
 use shukusai::{
-    collection::{Collection, KeyEnum, SongKey},
+    collection::{Collection, CollectionDiff, KeyEnum, SongKey},
     kernel::{FrontendToKernel, KernelToFrontend},
     state::{AudioState, ResetState},
 };
@@ -89,6 +89,12 @@ pub struct Gui {
     pub rfd_open: Arc<AtomicBool>,
     /// If a file was selected with RFD.
     pub rfd_new: Arc<Mutex<Option<PathBuf>>>,
+    /// If a playlist file to import was selected with RFD.
+    pub rfd_playlist_import: Arc<Mutex<Option<PathBuf>>>,
+    /// If a PATH to export a playlist to was selected with RFD.
+    pub rfd_playlist_export: Arc<Mutex<Option<(Arc<str>, PathBuf)>>>,
+    /// If an image to use as a playlist's art was selected with RFD.
+    pub rfd_playlist_art: Arc<Mutex<Option<(Arc<str>, PathBuf)>>>,
     /// A buffer of the indices of the PATHs the user wants deleted.
     pub deleted_paths: Vec<usize>,
 
@@ -116,14 +122,29 @@ pub struct Gui {
     pub playlist_swap_entry: Option<(Arc<str>, usize, usize)>,
     /// Remove the entry at this index from this playlist.
     pub playlist_remove_entry: Option<(Arc<str>, usize)>,
+    /// The playlist index of the entry currently
+    /// being dragged in the `Playlists` tab, if any.
+    pub playlist_drag: Option<usize>,
+    /// The playlist whose metadata (folder, description, art)
+    /// is being edited in the `Playlists` tab, if any,
+    /// and the folder text not yet applied to it.
+    pub playlist_details: Option<(Arc<str>, String)>,
+    /// The playlist folders that are closed in the `Playlists` tab.
+    pub playlist_folders_closed: std::collections::HashSet<String>,
     /// A playlist name edit had a `\n` enter, so we should save.
     pub playlist_name_edit_enter: bool,
+    /// The smart playlist (and its rules) currently being edited.
+    pub smart_playlist_edit: Option<(Arc<str>, shukusai::state::SmartPlaylist)>,
     /// Are we adding a `Artist/Album/Song` to a playlist?
     /// (fullscreen menu)
     /// This holds the keys that we should add.
     pub playlist_add_screen: Option<KeyEnum>,
     /// Add these keys to this playlist ASAP
     pub playlist_add_screen_result: Option<(Arc<str>, KeyEnum)>,
+    /// Are we reviewing playlist repairs?
+    /// (fullscreen menu)
+    /// This holds the repairs that were not applied.
+    pub playlist_repair: Option<Vec<shukusai::state::Repair>>,
 
     // Local cached variables.
     /// A cached, formatted version of [`Collection::count_artist`]
@@ -153,6 +174,29 @@ pub struct Gui {
     /// will always be `true`. This is used for things like
     /// the initial album art spinner screen.
     pub kernel_returned: bool,
+    /// The last [`CollectionDiff`] `Kernel` sent us after a
+    /// reset, and its summary text for the "what's new" banner.
+    ///
+    /// This is `None` when there is no diff, or the user dismissed it.
+    pub collection_diff: Option<(Arc<CollectionDiff>, String)>,
+
+    // Recently added.
+    /// Are we showing only the recently added
+    /// `Album`'s in the `Albums` tab?
+    pub recently_added: bool,
+    /// How many `Album`'s were first seen within [`crate::constants::RECENTLY_ADDED_SECONDS`].
+    ///
+    /// This is re-calculated on every new `Collection`.
+    pub count_recently_added: usize,
+
+    // Queue tab.
+    /// The queue index of the `Song` currently
+    /// being dragged in the `Queue` tab, if any.
+    pub queue_drag: Option<usize>,
+    /// Is the queue snapshot menu open in the `Queue` tab?
+    pub queue_snapshots_open: bool,
+    /// The snapshot name being typed in the queue snapshot menu.
+    pub queue_snapshot_name: String,
 
     // Debug screen.
     /// Are we showing the debug screen?
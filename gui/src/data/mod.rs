@@ -14,6 +14,14 @@ mod settings1;
 pub use settings1::*;
 mod settings2;
 pub use settings2::*;
+mod settings3;
+pub use settings3::*;
+mod settings4;
+pub use settings4::*;
+mod settings5;
+pub use settings5::*;
+mod settings6;
+pub use settings6::*;
 
 mod gui;
 pub use gui::*;
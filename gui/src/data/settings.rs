@@ -11,7 +11,7 @@ use crate::constants::{
 use crate::data::{AlbumSizing, SearchSort, WindowTitle};
 use const_format::formatcp;
 use shukusai::{
-    audio::PREVIOUS_THRESHOLD_DEFAULT,
+    audio::{ResamplerQuality, ShuffleMode, PREVIOUS_THRESHOLD_DEFAULT},
     constants::{FESTIVAL, HEADER, STATE_SUB_DIR},
     search::SearchKind,
     sort::{AlbumSort, ArtistSort, SongSort},
@@ -75,6 +75,23 @@ pub struct Settings {
     /// Start playback if we added stuff to an empty queue.
     pub empty_autoplay: bool,
 
+    /// Should the `Previous` button follow the
+    /// play history instead of the queue?
+    pub previous_history: bool,
+
+    /// Which [`ShuffleMode`] the shuffle and random buttons use.
+    pub shuffle: ShuffleMode,
+
+    /// Which [`ResamplerQuality`] to use when the
+    /// audio device doesn't support a `Song`'s sample rate.
+    pub resampler_quality: ResamplerQuality,
+
+    /// Send samples to the audio device untouched?
+    pub bit_perfect: bool,
+
+    /// Map the volume onto a decibel curve instead of a linear one?
+    pub volume_db_curve: bool,
+
     #[bincode(with_serde)]
     /// Our accent color.
     pub accent_color: egui::Color32,
@@ -104,6 +121,11 @@ impl Settings {
             auto_save: AUTO_SAVE_INTERVAL_SECONDS,
             restore_state: true,
             empty_autoplay: true,
+            previous_history: false,
+            shuffle: ShuffleMode::new(),
+            resampler_quality: ResamplerQuality::new(),
+            bit_perfect: false,
+            volume_db_curve: false,
             accent_color: ACCENT_COLOR,
             collection_paths: vec![],
             pixels_per_point: PIXELS_PER_POINT_DEFAULT,
@@ -127,11 +149,11 @@ mod test {
 
     // Empty.
     const S1: Lazy<Settings> = Lazy::new(|| {
-        Settings::from_path("../assets/festival/gui/state/settings3_new.bin").unwrap()
+        Settings::from_path("../assets/festival/gui/state/settings7_new.bin").unwrap()
     });
     // Filled.
     const S2: Lazy<Settings> = Lazy::new(|| {
-        Settings::from_path("../assets/festival/gui/state/settings3_real.bin").unwrap()
+        Settings::from_path("../assets/festival/gui/state/settings7_real.bin").unwrap()
     });
 
     #[test]
@@ -150,6 +172,11 @@ mod test {
         assert_eq!(S2.auto_save, 30);
         assert!(!S2.restore_state);
         assert!(!S2.empty_autoplay);
+        assert!(S2.previous_history);
+        assert_eq!(S2.shuffle, ShuffleMode::Weighted);
+        assert_eq!(S2.resampler_quality, ResamplerQuality::Best);
+        assert!(S2.bit_perfect);
+        assert!(S2.volume_db_curve);
         assert_eq!(S2.accent_color, egui::Color32::from_rgb(97, 101, 119));
         assert_eq!(S2.collection_paths, [PathBuf::from("/home/main/Music")]);
         assert_eq!(S2.pixels_per_point.round(), 2.0);
@@ -12,7 +12,7 @@ use crate::data::{AlbumSizing, ArtistSubTab, SearchSort, Settings, WindowTitle};
 use const_format::formatcp;
 use disk::Bincode2;
 use shukusai::{
-    audio::PREVIOUS_THRESHOLD_DEFAULT,
+    audio::{ResamplerQuality, ShuffleMode, PREVIOUS_THRESHOLD_DEFAULT},
     constants::{FESTIVAL, HEADER, STATE_SUB_DIR},
     search::SearchKind,
     sort::{AlbumSort, ArtistSort, SongSort},
@@ -172,6 +172,11 @@ impl From<Settings0> for Settings {
             // New fields.
             pixels_per_point: PIXELS_PER_POINT_DEFAULT,
             auto_save: AUTO_SAVE_INTERVAL_SECONDS,
+            previous_history: false,
+            shuffle: ShuffleMode::new(),
+            resampler_quality: ResamplerQuality::new(),
+            bit_perfect: false,
+            volume_db_curve: false,
         }
     }
 }
@@ -11,7 +11,7 @@ use const_format::formatcp;
 use disk::Bincode2;
 use serde::{Deserialize, Serialize};
 use shukusai::{
-    audio::PREVIOUS_THRESHOLD_DEFAULT,
+    audio::{ResamplerQuality, ShuffleMode, PREVIOUS_THRESHOLD_DEFAULT},
     constants::{FESTIVAL, HEADER, STATE_SUB_DIR},
     search::SearchKind,
     sort::{AlbumSort, ArtistSort, SongSort},
@@ -148,6 +148,11 @@ impl From<Settings2> for Settings {
 
             // New fields.
             auto_save: AUTO_SAVE_INTERVAL_SECONDS,
+            previous_history: false,
+            shuffle: ShuffleMode::new(),
+            resampler_quality: ResamplerQuality::new(),
+            bit_perfect: false,
+            volume_db_curve: false,
         }
     }
 }
@@ -11,6 +11,7 @@ pub const ARTISTS: &str = "Artists";
 pub const SONGS: &str = "Songs";
 pub const QUEUE: &str = "Queue";
 pub const PLAYLISTS: &str = "Playlists";
+pub const HISTORY: &str = "History";
 pub const SEARCH: &str = "Search";
 pub const SETTINGS: &str = "Settings";
 
@@ -58,9 +59,25 @@ pub enum Tab {
     Playlists,
     Search,
     Settings,
+    // This is last to keep older `State`'s `Tab`
+    // decoding the same, it is shown after `Playlists`.
+    History,
 }
 
 impl Tab {
+    /// All [`Tab`]'s, in the order they are shown in the `GUI`.
+    pub const ALL: [Self; 9] = [
+        Self::View,
+        Self::Albums,
+        Self::Artists,
+        Self::Songs,
+        Self::Queue,
+        Self::Playlists,
+        Self::History,
+        Self::Search,
+        Self::Settings,
+    ];
+
     /// No [`String`] allocation.
     pub fn human(&self) -> &'static str {
         match self {
@@ -70,6 +87,7 @@ impl Tab {
             Self::Songs => SONGS,
             Self::Queue => QUEUE,
             Self::Playlists => PLAYLISTS,
+            Self::History => HISTORY,
             Self::Search => SEARCH,
             Self::Settings => SETTINGS,
         }
@@ -86,7 +104,8 @@ impl Tab {
             Self::Artists => Self::Songs,
             Self::Songs => Self::Queue,
             Self::Queue => Self::Playlists,
-            Self::Playlists => Self::Search,
+            Self::Playlists => Self::History,
+            Self::History => Self::Search,
             Self::Search => Self::Settings,
             Self::Settings => Self::View,
         }
@@ -104,7 +123,8 @@ impl Tab {
             Self::Songs => Self::Artists,
             Self::Queue => Self::Songs,
             Self::Playlists => Self::Queue,
-            Self::Search => Self::Playlists,
+            Self::History => Self::Playlists,
+            Self::Search => Self::History,
             Self::Settings => Self::Search,
         }
     }
@@ -132,4 +152,13 @@ mod tests {
             assert!(set3.insert(i.previous()));
         }
     }
+
+    #[test]
+    // `ALL` has every variant once, in `.next()` order.
+    fn all() {
+        assert_eq!(Tab::ALL.len(), Tab::COUNT);
+        for (i, tab) in Tab::ALL.iter().enumerate() {
+            assert_eq!(tab.next(), Tab::ALL[(i + 1) % Tab::COUNT]);
+        }
+    }
 }
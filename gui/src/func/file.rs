@@ -24,6 +24,86 @@ pub fn spawn_rfd_thread(rfd_open: Arc<AtomicBool>, rfd_new: Arc<Mutex<Option<Pat
     });
 }
 
+// The playlist file extensions `Playlists::import_path()` understands.
+const PLAYLIST_EXTENSIONS: [&str; 4] = ["m3u8", "m3u", "pls", "xspf"];
+
+pub fn spawn_rfd_playlist_import(
+    rfd_open: Arc<AtomicBool>,
+    rfd_playlist_import: Arc<Mutex<Option<PathBuf>>>,
+) {
+    std::thread::spawn(move || {
+        atomic_store!(rfd_open, true);
+
+        match rfd::FileDialog::new()
+            .set_title("Import playlist")
+            .add_filter("Playlist", &PLAYLIST_EXTENSIONS)
+            .pick_file()
+        {
+            Some(path) => {
+                info!("RFD - Selected PATH: {}", path.display());
+                lock!(rfd_playlist_import).replace(path);
+            }
+            None => info!("RFD - No PATH selected"),
+        }
+
+        atomic_store!(rfd_open, false);
+    });
+}
+
+pub fn spawn_rfd_playlist_export(
+    rfd_open: Arc<AtomicBool>,
+    rfd_playlist_export: Arc<Mutex<Option<(Arc<str>, PathBuf)>>>,
+    playlist: Arc<str>,
+) {
+    std::thread::spawn(move || {
+        atomic_store!(rfd_open, true);
+
+        match rfd::FileDialog::new()
+            .set_title("Export playlist")
+            .set_file_name(&format!("{playlist}.m3u8"))
+            .add_filter("M3U8", &["m3u8"])
+            .add_filter("PLS", &["pls"])
+            .add_filter("XSPF", &["xspf"])
+            .save_file()
+        {
+            Some(path) => {
+                info!("RFD - Selected PATH: {}", path.display());
+                lock!(rfd_playlist_export).replace((playlist, path));
+            }
+            None => info!("RFD - No PATH selected"),
+        }
+
+        atomic_store!(rfd_open, false);
+    });
+}
+
+// The image file extensions a playlist's art can be.
+const ART_EXTENSIONS: [&str; 7] = ["jpg", "jpeg", "png", "bmp", "ico", "tiff", "webp"];
+
+pub fn spawn_rfd_playlist_art(
+    rfd_open: Arc<AtomicBool>,
+    rfd_playlist_art: Arc<Mutex<Option<(Arc<str>, PathBuf)>>>,
+    playlist: Arc<str>,
+) {
+    std::thread::spawn(move || {
+        atomic_store!(rfd_open, true);
+
+        match rfd::FileDialog::new()
+            .set_title(&format!("Pick art for [{playlist}]"))
+            .add_filter("Image", &ART_EXTENSIONS)
+            .pick_file()
+        {
+            Some(path) => {
+                info!("RFD - Selected PATH: {}", path.display());
+                lock!(rfd_playlist_art).replace((playlist, path));
+            }
+            None => info!("RFD - No PATH selected"),
+        }
+
+        atomic_store!(rfd_open, false);
+    });
+}
+
 //---------------------------------------------------------------------------------------------------- TESTS
 //#[cfg(test)]
 //mod tests {
@@ -1,8 +1,8 @@
 //---------------------------------------------------------------------------------------------------- Use
 use crate::constants::{
     ALBUMS_PER_ROW_MAX, ALBUMS_PER_ROW_MIN, ALBUM_ART_SIZE_MAX, ALBUM_ART_SIZE_MIN,
-    PIXELS_PER_POINT_MAX, PIXELS_PER_POINT_MIN, PIXELS_PER_POINT_UNIT, SETTINGS_VERSION,
-    STATE_VERSION,
+    PIXELS_PER_POINT_MAX, PIXELS_PER_POINT_MIN, PIXELS_PER_POINT_UNIT, PLAYLIST_NAME_MAX_LEN,
+    RECENTLY_ADDED_SECONDS, SETTINGS_VERSION, STATE_VERSION,
 };
 use crate::data::Gui;
 use crate::data::{AlbumSizing, StateRestore};
@@ -10,11 +10,14 @@ use benri::{log::*, sync::*};
 use disk::{Bincode2, Json};
 use log::{error, info, warn};
 use shukusai::{
-    collection::Collection,
+    collection::{Collection, CollectionDiff, FIRST_SEEN},
     constants::PLAYLIST_VERSION,
     kernel::FrontendToKernel,
-    state::{AUDIO_STATE, PLAYLISTS},
+    sort::AlbumSort,
+    state::{PlaylistFormat, Playlists, AUDIO_STATE, PLAYLISTS},
 };
+use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 
 //---------------------------------------------------------------------------------------------------- GUI `Drop` impl
@@ -212,6 +215,110 @@ impl Gui {
         }
     }
 
+    /// Spawn a RFD thread to pick a playlist file to import.
+    pub fn import_playlist(&self) {
+        if atomic_load!(self.rfd_open) {
+            warn!("GUI - Import playlist requested, but RFD is already open");
+        } else {
+            crate::func::spawn_rfd_playlist_import(
+                Arc::clone(&self.rfd_open),
+                Arc::clone(&self.rfd_playlist_import),
+            );
+        }
+    }
+
+    /// Spawn a RFD thread to pick where to export a playlist to.
+    pub fn export_playlist(&self, playlist: Arc<str>) {
+        if atomic_load!(self.rfd_open) {
+            warn!("GUI - Export playlist requested, but RFD is already open");
+        } else {
+            crate::func::spawn_rfd_playlist_export(
+                Arc::clone(&self.rfd_open),
+                Arc::clone(&self.rfd_playlist_export),
+                playlist,
+            );
+        }
+    }
+
+    /// Spawn a RFD thread to pick an image to use as the art of a playlist.
+    pub fn playlist_art(&self, playlist: Arc<str>) {
+        if atomic_load!(self.rfd_open) {
+            warn!("GUI - Playlist art requested, but RFD is already open");
+        } else {
+            crate::func::spawn_rfd_playlist_art(
+                Arc::clone(&self.rfd_open),
+                Arc::clone(&self.rfd_playlist_art),
+                playlist,
+            );
+        }
+    }
+
+    /// Import the playlist file at `path` as a new playlist named after the file.
+    ///
+    /// Existing playlists are not overwritten, ` (Copy)` is appended instead.
+    pub fn import_playlist_file(&mut self, path: &Path) {
+        let playlist = match Playlists::import_path(path, &self.collection) {
+            Ok(p) => p,
+            Err(e) => {
+                warn!("GUI - Playlist import error: {e}");
+                crate::toast_err!(self, format!("Import error [{}]", path.display()));
+                return;
+            }
+        };
+
+        let stem = path
+            .file_stem()
+            .map_or_else(|| "Playlist".into(), |s| s.to_string_lossy());
+        let mut stem = stem.chars().take(PLAYLIST_NAME_MAX_LEN).collect::<String>();
+
+        let mut playlists = PLAYLISTS.write();
+        while playlists.contains_key(stem.as_str()) {
+            stem += " (Copy)";
+        }
+
+        let invalid = Playlists::invalid_len(&playlist);
+        let msg = match invalid {
+            0 => format!("Imported playlist [{stem}]"),
+            i => format!("Imported playlist [{stem}], {i} songs were not found"),
+        };
+        playlists.playlist_set(stem.into(), playlist);
+        drop(playlists);
+
+        crate::toast_ok!(self, msg);
+    }
+
+    /// Export the playlist `name` to `path`.
+    ///
+    /// The format is picked from the extension (`M3U8` if unknown),
+    /// `Song` PATHs are written relative to the file's directory.
+    pub fn export_playlist_file(&mut self, name: &str, path: &Path) {
+        let format = PlaylistFormat::from_path(path).unwrap_or_default();
+
+        let result = match PLAYLISTS.read().get(name) {
+            Some(playlist) => std::fs::File::create(path).and_then(|file| {
+                let mut writer = std::io::BufWriter::new(file);
+                Playlists::export(
+                    name,
+                    playlist,
+                    format,
+                    path.parent(),
+                    &self.collection,
+                    &mut writer,
+                )?;
+                writer.flush()
+            }),
+            None => return,
+        };
+
+        match result {
+            Ok(()) => crate::toast_ok!(self, format!("Exported playlist [{}]", path.display())),
+            Err(e) => {
+                warn!("GUI - Playlist export error: {e}");
+                crate::toast_err!(self, format!("Export error [{}]", path.display()));
+            }
+        }
+    }
+
     /// Perform all the necessary steps to reset
     /// the [`Collection`] and enter the proper state.
     pub fn reset_collection(&mut self) {
@@ -252,12 +359,73 @@ impl Gui {
         self.resetting_collection = true;
     }
 
+    /// Sets the [`CollectionDiff`] of the last reset,
+    /// and formats the text for the "what's new" banner.
+    ///
+    /// Empty diffs are ignored, there's nothing to show.
+    pub fn new_collection_diff(&mut self, diff: Arc<CollectionDiff>) {
+        if diff.is_empty() {
+            info!("GUI ... Collection diff received, nothing changed");
+            self.collection_diff = None;
+            return;
+        }
+
+        info!("GUI ... Collection diff received");
+
+        // e.g: `Albums: 2 added, 1 removed`
+        fn part(name: &str, added: usize, removed: usize, modified: usize) -> Option<String> {
+            let counts: Vec<String> = [
+                (added, "added"),
+                (removed, "removed"),
+                (modified, "modified"),
+            ]
+            .into_iter()
+            .filter(|(n, _)| *n != 0)
+            .map(|(n, s)| format!("{n} {s}"))
+            .collect();
+
+            if counts.is_empty() {
+                None
+            } else {
+                Some(format!("{name}: {}", counts.join(", ")))
+            }
+        }
+
+        let text = [
+            part(
+                "Artists",
+                diff.artists_added.len(),
+                diff.artists_removed.len(),
+                diff.artists_modified.len(),
+            ),
+            part(
+                "Albums",
+                diff.albums_added.len(),
+                diff.albums_removed.len(),
+                diff.albums_modified.len(),
+            ),
+            part(
+                "Songs",
+                diff.songs_added.len(),
+                diff.songs_removed.len(),
+                diff.songs_modified.len(),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<String>>()
+        .join(" | ");
+
+        self.collection_diff = Some((diff, format!("🆕 {text}")));
+    }
+
     /// Caches some segments of [`Collection`] for local use
     /// so don't have to access it all the time.
     ///
     /// This should be called after we received a new [`Collection`].
     pub fn cache_collection(&mut self) {
         self.format_count_assign();
+        self.count_recently_added_assign();
     }
 
     /// Increments the [`Album`] art size.
@@ -339,6 +507,20 @@ impl Gui {
         self.count_art = format!("Art: {}", self.collection.count_art);
     }
 
+    /// Counts how many `Album`'s were first seen within [`RECENTLY_ADDED_SECONDS`].
+    ///
+    /// These are the first `n` `Album`'s of [`AlbumSort::DateAddedRev`].
+    pub fn count_recently_added_assign(&mut self) {
+        let since = benri::unix!().saturating_sub(RECENTLY_ADDED_SECONDS);
+        let first_seen = FIRST_SEEN.read();
+
+        self.count_recently_added = self
+            .collection
+            .album_iter(AlbumSort::DateAddedRev)
+            .take_while(|key| first_seen.album(**key, &self.collection) >= since)
+            .count();
+    }
+
     /// Add and set `Volume`.
     pub fn add_volume(&mut self, v: u8) {
         let v = self.state.volume + v;
@@ -3,7 +3,10 @@ use crate::constants::{
     APP_HEIGHT_DEFAULT, APP_HEIGHT_MIN, APP_WIDTH_DEFAULT, APP_WIDTH_MIN, FONT_ARRAY,
     RUNTIME_WIDTH, SETTINGS_VERSION, SPACING, STATE_VERSION, VISUALS,
 };
-use crate::data::{DebugInfo, Settings, Settings0, Settings1, Settings2, State, State0};
+use crate::data::{
+    DebugInfo, Settings, Settings0, Settings1, Settings2, Settings3, Settings4, Settings5,
+    Settings6, State, State0,
+};
 use benri::{atomic_store, now, send};
 use crossbeam::channel::{Receiver, Sender};
 use disk::{Bincode2, Json};
@@ -160,6 +163,10 @@ impl crate::data::Gui {
         // Read `Settings` from disk.
         let settings = Settings::from_versions(&[
             (SETTINGS_VERSION, Settings::from_file),
+            (6, Settings6::disk_into),
+            (5, Settings5::disk_into),
+            (4, Settings4::disk_into),
+            (3, Settings3::disk_into),
             (2, Settings2::disk_into),
             (1, Settings1::disk_into),
             (0, Settings0::disk_into),
@@ -191,6 +198,13 @@ impl crate::data::Gui {
             shukusai::audio::PREVIOUS_THRESHOLD,
             settings.previous_threshold
         );
+        atomic_store!(shukusai::audio::PREVIOUS_HISTORY, settings.previous_history);
+        atomic_store!(
+            shukusai::audio::RESAMPLER_QUALITY,
+            settings.resampler_quality as u8
+        );
+        atomic_store!(shukusai::audio::BIT_PERFECT, settings.bit_perfect);
+        atomic_store!(shukusai::audio::VOLUME_DB_CURVE, settings.volume_db_curve);
 
         // Send `CachePath` signal to `Kernel`.
         if settings.collection_paths.is_empty() {
@@ -291,6 +305,9 @@ impl crate::data::Gui {
             // `rfd`.
             rfd_open: Arc::new(AtomicBool::new(false)),
             rfd_new: Arc::new(Mutex::new(None)),
+            rfd_playlist_import: Arc::new(Mutex::new(None)),
+            rfd_playlist_export: Arc::new(Mutex::new(None)),
+            rfd_playlist_art: Arc::new(Mutex::new(None)),
             deleted_paths: vec![],
 
             // Search state.
@@ -304,9 +321,14 @@ impl crate::data::Gui {
             playlist_to: None,
             playlist_swap_entry: None,
             playlist_remove_entry: None,
+            playlist_drag: None,
+            playlist_details: None,
+            playlist_folders_closed: std::collections::HashSet::new(),
             playlist_name_edit_enter: false,
+            smart_playlist_edit: None,
             playlist_add_screen: None,
             playlist_add_screen_result: None,
+            playlist_repair: None,
 
             // Local cache.
             count_artist: "Artists: 0".to_string(),
@@ -319,6 +341,13 @@ impl crate::data::Gui {
 
             resetting_collection: false,
             kernel_returned: false,
+            collection_diff: None,
+            recently_added: false,
+            count_recently_added: 0,
+
+            queue_drag: None,
+            queue_snapshots_open: false,
+            queue_snapshot_name: String::new(),
 
             debug_screen: false,
             debug_info: DebugInfo::new(),
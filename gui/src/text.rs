@@ -11,9 +11,7 @@ use crate::constants::{
     PIXELS_PER_POINT_UNIT_STR, SEARCH_MAX_LEN, SETTINGS_VERSION, STATE_VERSION,
 };
 use const_format::formatcp;
-use shukusai::constants::{
-    AUDIO_VERSION, COLLECTION_VERSION, OS_ARCH, PLAYLIST_VERSION,
-};
+use shukusai::constants::{AUDIO_VERSION, COLLECTION_VERSION, OS_ARCH, PLAYLIST_VERSION};
 
 //---------------------------------------------------------------------------------------------------- Platform
 #[cfg(not(target_os = "macos"))]
@@ -24,6 +22,8 @@ pub const MOD: &str = "⌘";
 //---------------------------------------------------------------------------------------------------- Collection State
 pub const COLLECTION_LOADING: &str = "Loading Collection";
 pub const COLLECTION_RESETTING: &str = "Resetting Collection";
+pub const COLLECTION_DIFF: &str = "What changed since the last Collection reset";
+pub const COLLECTION_DIFF_DISMISS: &str = "Dismiss";
 
 //---------------------------------------------------------------------------------------------------- UI
 pub const UI_PLAY: &str = "⏵";
@@ -45,6 +45,9 @@ pub const UI_MINUS: &str = "➖";
 pub const UI_X: &str = "❌";
 pub const UI_UP: &str = "⬆";
 pub const UI_DOWN: &str = "⬇";
+pub const UI_RATING_STAR: &str = "★";
+pub const UI_RATING_STAR_EMPTY: &str = "☆";
+pub const RATING: &str = "Click a star to rate, click the current rating again to clear it";
 
 //---------------------------------------------------------------------------------------------------- Left Tab
 pub const INCREMENT_ALBUM_SIZE: &str = "Increase the album art size";
@@ -56,6 +59,10 @@ pub const REPEAT_QUEUE: &str = "The current queue will be repeated forever";
 pub const REPEAT_QUEUE_PAUSE: &str =
     "The current queue will be repeated forever, but will start paused";
 pub const REPEAT_OFF: &str = "Repeat is turned off";
+pub const REPEAT_ALBUM: &str = "The current album's songs in the queue will be repeated forever";
+pub const REPEAT_ARTIST: &str = "The current artist's songs in the queue will be repeated forever";
+pub const REPEAT_COUNT: &str = "The current song will be repeated, times left";
+pub const REPEAT_AB: &str = "The current song is looping between seconds";
 
 //---------------------------------------------------------------------------------------------------- Bottom Bar
 pub const SAVING: &str = "Festival is still saving a recently created Collection";
@@ -66,12 +73,16 @@ pub const ARTIST_TOTAL_SONG: &str = "Total song count";
 pub const ARTIST_TOTAL_RUNTIME: &str = "Total runtime";
 
 //---------------------------------------------------------------------------------------------------- Albums tab
+pub const RECENTLY_ADDED: &str = "🆕 Recently added";
+pub const RECENTLY_ADDED_TOOLTIP: &str = "Only show albums added in the last 30 days, newest first";
+pub const RECENTLY_ADDED_EMPTY: &str = "No albums were added in the last 30 days";
 pub const EMPTY_COLLECTION: &str = r#"This scans the system's Music directory by default.
 
 Configure which directories to scan in the [Settings] tab."#;
 
 //---------------------------------------------------------------------------------------------------- Songs tab
 pub const OPEN_PARENT_FOLDER: &str = "Open the directory containing this song";
+pub const SONG_SKIPS: &str = "Times skipped before being counted as played";
 
 //---------------------------------------------------------------------------------------------------- Queue tab
 pub const UI_QUEUE_CLEAR: &str = "⏹";
@@ -104,6 +115,31 @@ Secondary Mouse Click: Add a random Song to the back of the queue
 );
 pub const QUEUE_LENGTH: &str = "Current song index and total queue length";
 pub const QUEUE_RUNTIME: &str = "The total runtime of the queue";
+pub const UI_QUEUE_DRAG: &str = "☰";
+pub const QUEUE_DRAG: &str = "Drag onto another song to move this song there";
+pub const UI_QUEUE_SNAPSHOTS: &str = "💾";
+pub const QUEUE_SNAPSHOT_MENU: &str =
+    "Save the queue to come back to it later, or restore a saved one";
+pub const QUEUE_SNAPSHOT_NAME: &str = "The name to save the current queue as";
+pub const QUEUE_SNAPSHOT_SAVE: &str = "Save the current queue, song and time as this name";
+pub const QUEUE_SNAPSHOT_RESTORE: &str = "Replace the queue with this snapshot";
+pub const QUEUE_SNAPSHOT_DELETE: &str = "Delete this snapshot";
+pub const UI_AUTO_DJ: &str = "🎧";
+pub const AUTO_DJ_ON: &str = "Auto-DJ is on, related songs are added when the queue ends";
+pub const AUTO_DJ_OFF: &str = "Auto-DJ is off, playback stops when the queue ends";
+pub const QUEUE_AUTO_DJ: &str = "This song was added by Auto-DJ";
+pub const UI_SLEEP: &str = "🌙";
+pub const SLEEP_TIMER: &str = r#"Primary Mouse Click: Cycle the sleep timer (off, song, album, queue, minutes)
+
+Secondary Mouse Click: Add minutes to the sleep timer"#;
+pub const SLEEP_TIMER_LEFT: &str = "Time left";
+
+//---------------------------------------------------------------------------------------------------- History Tab
+pub const SELECT_HISTORY: &str = "🗋 Songs will show up here once they start playing";
+pub const HISTORY_ADD: &str = "Add this song to the back of the queue";
+pub const HISTORY_INVALID: &str = "This song no longer exists in the Collection";
+pub const HISTORY_AGO: &str = "ago";
+pub const HISTORY_NOW: &str = "Just now";
 
 //---------------------------------------------------------------------------------------------------- Playlists Tab
 pub const PLAYLIST_TEXT_EMPTY: &str = "Playlist name is empty";
@@ -115,12 +151,50 @@ pub const PLAYLIST_DELETE: &str = "Delete this playlist";
 pub const PLAYLIST_EDIT: &str = "Edit this playlist's name";
 pub const PLAYLIST_EDIT_SAVE: &str = "Save this playlist with the new name";
 pub const PLAYLIST_COPY: &str = "Create a copy of this playlist";
+pub const PLAYLIST_IMPORT: &str = "Import a M3U/M3U8, PLS or XSPF playlist file";
+pub const PLAYLIST_EXPORT: &str = "Export this playlist as a M3U8, PLS or XSPF playlist file";
 pub const PLAYLIST_COUNT: &str = "Total amount of playlists";
 pub const PLAYLIST_TOTAL_SONG: &str = "Total playlist song count";
 pub const PLAYLIST_TOTAL_RUNTIME: &str = "Total playlist runtime";
+pub const UI_PLAYLIST_PIN: &str = "📌";
+pub const PLAYLIST_PIN: &str = "Pin this playlist above all others";
+pub const PLAYLIST_PINNED: &str = "Pinned";
+pub const UI_PLAYLIST_DETAILS: &str = "📝";
+pub const PLAYLIST_DETAILS: &str = "Edit this playlist's folder, description and art";
+pub const PLAYLIST_FOLDER: &str =
+    "The folder this playlist is in, nested folders are separated with '/', e.g: 'Rock/80s'";
+pub const PLAYLIST_FOLDER_TOGGLE: &str = "Open/close this folder";
+pub const PLAYLIST_DESCRIPTION: &str = "A description of this playlist";
+pub const PLAYLIST_ART: &str =
+    "Pick an image to use as this playlist's art\nRight-click to remove it";
 pub const PLAYLIST_ENTRY_DELETE: &str = "Delete this entry";
 pub const PLAYLIST_ENTRY_UP: &str = "Move this entry up";
 pub const PLAYLIST_ENTRY_DOWN: &str = "Move this entry down";
+pub const UI_PLAYLIST_DRAG: &str = "☰";
+pub const PLAYLIST_ENTRY_DRAG: &str = "Drag onto another entry to move this entry there";
+pub const UI_PLAYLIST_DEDUP: &str = "♊";
+pub const PLAYLIST_DEDUP: &str = "Remove duplicate entries, the first of each is kept";
+pub const PLAYLIST_SORT: &str = "Sort the entries of this playlist";
+pub const UI_PLAYLIST_REPAIR: &str = "🔧";
+pub const PLAYLIST_REPAIR: &str =
+    "Find the most similar song for the invalid entries of this playlist";
+pub const PLAYLIST_REPAIR_APPLY: &str = "Replace the invalid entry with this song";
+pub const PLAYLIST_REPAIR_SKIP: &str = "Keep the invalid entry";
+pub const UI_SMART_PLAYLIST: &str = "⚙";
+pub const SMART_PLAYLIST: &str = "This is a smart playlist, its songs are picked by rules";
+pub const SMART_PLAYLIST_NEW: &str =
+    "Turn this into a smart playlist, its songs will be picked by rules";
+pub const SMART_PLAYLIST_EDIT: &str = "Edit the rules of this smart playlist";
+pub const SMART_PLAYLIST_REFRESH: &str = "Re-create this smart playlist's songs from its rules";
+pub const SMART_PLAYLIST_RULES: &str = "Every rule must match for a song to be included";
+pub const SMART_PLAYLIST_ADD_RULE: &str = "Add a rule";
+pub const SMART_PLAYLIST_LIMIT: &str = "The maximum amount of songs, picked after sorting";
+pub const SMART_PLAYLIST_SORT: &str = "The order of the songs";
+pub const SMART_PLAYLIST_SAVE: &str =
+    "Save these rules, this playlist's songs will be replaced by the songs that match";
+pub const SMART_PLAYLIST_CANCEL: &str = "Discard these changes";
+pub const SMART_PLAYLIST_REMOVE: &str =
+    "Remove the rules, the current songs are kept as a normal playlist";
 pub const PLAYLIST_INVALID: &str = r#"This song is invalid (it does not exist within the current Collection).
 
 This song & its runtime do not count towards the playlist's total stats.
@@ -134,7 +208,8 @@ after a Collection reset."#;
 //---------------------------------------------------------------------------------------------------- Settings Tab
 pub const RESET: &str = formatcp!("Reset changes ({MOD}+Z)");
 pub const SAVE: &str = formatcp!("Save changes to disk ({MOD}+S)");
-pub const SHUFFLE_MODE: &str = "Which method to shuffle songs by";
+pub const SHUFFLE_MODE: &str =
+    "Which method to shuffle songs by in the queue [🔀] and random [Artist/Album/Song] buttons";
 pub const ARTIST_SORT_ORDER: &str =
     formatcp!("Which method to sort the artists by in the [Artists] tab ({MOD}+W)");
 pub const ALBUM_SORT_ORDER: &str =
@@ -153,6 +228,27 @@ pub const ALBUM_PER_ROW:     &str = formatcp!("Show [x] amount of albums per row
 pub const PREVIOUS_THRESHOLD: &str = r#"If the current song runtime has passed this number, the [Previous] button will reset the current song instead of skipping backwards.
 
 Setting this to [0] will make the [Previous] button always go to the previous song."#;
+pub const PREVIOUS_HISTORY: &str = r#"Make the [Previous] button go back to the songs that actually played, instead of the previous song in the queue.
+
+This still works after the queue was shuffled or cleared, the song is re-added to the queue."#;
+pub const CHANNELS: &str = r#"How the left and right channels are mixed before playback.
+
+[Mono] plays the same audio out of both channels.
+[Swap L/R] swaps the left and right channels.
+The slider sets the left/right balance, [0] leaves both channels as-is."#;
+pub const CHANNELS_MONO: &str = "Down-mix the left and right channels into mono";
+pub const CHANNELS_SWAP: &str = "Swap the left and right channels";
+pub const CHANNELS_BIT_PERFECT: &str = "[Bit-Perfect] is enabled, audio is played untouched";
+pub const RESAMPLER_QUALITY: &str = r#"The quality of the resampler used when the audio device doesn't support the sample rate of a song."#;
+pub const BIT_PERFECT: &str = r#"Send audio to the audio device untouched, at each song's native sample rate and format.
+
+Formats the audio device can't play as-is are still converted.
+
+Volume and the sleep timer fade-out do nothing while this is enabled, and the [Channels] settings are disabled."#;
+pub const VOLUME_DB_CURVE: &str = r#"How the volume [0-100] maps onto loudness.
+
+[Decibel] spreads the 100 steps evenly from -60dB to 0dB, so each step sounds the same size.
+[Linear] scales the amplitude directly, most of the change happens near [0]."#;
 pub const AUTO_SAVE: &str = r#"Auto-save all state, settings, and playlist changes every [x] seconds.
 
 This setting is disabled if set to [0]."#;
@@ -4,8 +4,10 @@
 //use serde::{Serialize,Deserialize};
 use crate::constants::LESS_WHITE;
 use crate::data::AlbumSizing;
-use egui::{Label, RichText, ScrollArea, Sense, TextStyle};
+use crate::text::{RECENTLY_ADDED, RECENTLY_ADDED_EMPTY, RECENTLY_ADDED_TOOLTIP};
+use egui::{Label, RichText, ScrollArea, SelectableLabel, Sense, TextStyle};
 use readable::HeadTail;
+use shukusai::sort::AlbumSort;
 
 //---------------------------------------------------------------------------------------------------- Constants
 // How many `char`'s before we cut it off with `...`?
@@ -46,6 +48,41 @@ impl crate::data::Gui {
         // Small font.
         ui.style_mut().override_text_style = Some(TextStyle::Name("15".into()));
 
+        // "Recently added" toggle.
+        //
+        // This shows the `Album`'s first seen within `RECENTLY_ADDED_SECONDS`,
+        // newest first, ignoring the user's `AlbumSort`.
+        ui.horizontal(|ui| {
+            let label = SelectableLabel::new(self.recently_added, RECENTLY_ADDED);
+            if ui
+                .add(label)
+                .on_hover_text(RECENTLY_ADDED_TOOLTIP)
+                .clicked()
+            {
+                self.recently_added = !self.recently_added;
+            }
+            if self.recently_added {
+                ui.label(self.count_recently_added.to_string());
+            }
+        });
+
+        let (sort, count) = if self.recently_added {
+            (AlbumSort::DateAddedRev, self.count_recently_added)
+        } else {
+            (
+                self.settings.album_sort,
+                self.collection.count_album.inner() as usize,
+            )
+        };
+
+        if count == 0 && self.recently_added {
+            ui.add_sized(
+                [ui.available_width(), ui.available_height()],
+                Label::new(RichText::new(RECENTLY_ADDED_EMPTY).color(LESS_WHITE)),
+            );
+            return;
+        }
+
         let width = ui.available_width();
 
         match self.settings.album_sizing {
@@ -62,12 +99,20 @@ impl crate::data::Gui {
                 // Account for separation space and padding.
                 let pixel = pixel - 16.0;
                 // How many rows?
-                let rows =
-                    (self.collection.count_album.inner() as f32 / album_width).ceil() as usize;
+                let rows = (count as f32 / album_width).ceil() as usize;
 
                 let album_width = album_width as usize;
 
-                self.paint_albums(ui, ctx, pixel, rows, album_width, first_album_padding);
+                self.paint_albums(
+                    ui,
+                    ctx,
+                    sort,
+                    count,
+                    pixel,
+                    rows,
+                    album_width,
+                    first_album_padding,
+                );
             }
             AlbumSizing::Row => {
                 // Get per row count.
@@ -78,9 +123,9 @@ impl crate::data::Gui {
                 // Account for separation space and padding.
                 let pixel = pixel - 16.0;
                 // How many rows?
-                let rows = (self.collection.count_album.inner() as f32 / per_f32).ceil() as usize;
+                let rows = (count as f32 / per_f32).ceil() as usize;
 
-                self.paint_albums(ui, ctx, pixel, rows, album_width, 0.0);
+                self.paint_albums(ui, ctx, sort, count, pixel, rows, album_width, 0.0);
             }
         };
     }
@@ -91,16 +136,15 @@ impl crate::data::Gui {
         &mut self,
         ui: &mut egui::Ui,
         ctx: &egui::Context,
+        sort: AlbumSort,
+        count: usize,
         pixel: f32,
         rows: usize,
         album_width: usize,
         first_album_padding: f32,
     ) {
         // The iterator over sorted `Album`'s.
-        let mut iter = self
-            .collection
-            .album_iter(self.settings.album_sort)
-            .peekable();
+        let mut iter = self.collection.album_iter(sort).take(count).peekable();
 
         ScrollArea::vertical()
             .id_source("Albums")
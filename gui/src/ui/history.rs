@@ -0,0 +1,155 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::constants::{GRAY, MEDIUM_GRAY, WHITE};
+use crate::text::{
+    HISTORY_ADD, HISTORY_AGO, HISTORY_INVALID, HISTORY_NOW, SELECT_HISTORY, UI_PLUS,
+};
+use egui::{Button, Label, RichText, Sense};
+use egui_extras::{Column, TableBuilder};
+use readable::Time;
+use shukusai::state::{Entry, HISTORY};
+
+//---------------------------------------------------------------------------------------------------- History
+impl crate::data::Gui {
+    #[inline(always)]
+    pub fn show_tab_history(
+        &mut self,
+        ui: &mut egui::Ui,
+        _ctx: &egui::Context,
+        _width: f32,
+        _height: f32,
+    ) {
+        self.set_visuals(ui);
+
+        // Sizing.
+        let width = ui.available_width();
+        let height = ui.available_height();
+        const HEADER_HEIGHT: f32 = 40.0;
+        const ROW_HEIGHT: f32 = 35.0;
+        // c == Column sizing
+        let c_width = (width / 10.0) - 10.0;
+        let c_add = ROW_HEIGHT;
+        let c_played = c_width * 2.0;
+        let c_title = c_width * 3.0;
+        let c_album = c_width * 2.0;
+
+        let history = HISTORY.read();
+
+        // INVARIANT:
+        // We're returning early if the history is empty.
+        if history.is_empty() {
+            let label = Label::new(RichText::new(SELECT_HISTORY).color(GRAY));
+            ui.add_sized([width, height / 1.35], label);
+            return;
+        }
+
+        // Only the minutes are shown, so
+        // the labels don't change every second.
+        let now = benri::unix!();
+        let len = history.len();
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(c_add))
+            .column(Column::initial(c_played).resizable(true).clip(true))
+            .column(Column::initial(c_title).resizable(true).clip(true))
+            .column(Column::initial(c_album).resizable(true).clip(true))
+            .column(Column::remainder().clip(true))
+            .auto_shrink([false; 2])
+            .max_scroll_height(height)
+            .header(HEADER_HEIGHT, |mut header| {
+                header.col(|_| {});
+                for name in ["Played", "Title", "Album", "Artist"] {
+                    header.col(|ui| {
+                        ui.strong(RichText::new(name).color(WHITE));
+                    });
+                }
+            })
+            .body(|body| {
+                // Newest first.
+                body.rows(ROW_HEIGHT, len, |mut row| {
+                    let h = &history[len - 1 - row.index()];
+
+                    let ago = (now.saturating_sub(h.timestamp) / 60) * 60;
+                    let ago = if ago == 0 {
+                        HISTORY_NOW.to_string()
+                    } else {
+                        format!("{} {HISTORY_AGO}", Time::from(ago))
+                    };
+
+                    match &h.entry {
+                        Entry::Valid { key_song, .. } => {
+                            let key = *key_song;
+                            let (artist, album, song) = self.collection.walk(key);
+
+                            row.col(|ui| {
+                                let button = Button::new(UI_PLUS);
+                                if ui
+                                    .add_sized([c_add, c_add], button)
+                                    .on_hover_text(HISTORY_ADD)
+                                    .clicked()
+                                {
+                                    crate::add_song!(self, song.title, key);
+                                }
+                            });
+                            row.col(|ui| {
+                                ui.label(ago);
+                            });
+                            row.col(|ui| {
+                                crate::song_label!(
+                                    self,
+                                    song,
+                                    album,
+                                    key,
+                                    ui,
+                                    Label::new(&*song.title)
+                                );
+                            });
+                            row.col(|ui| {
+                                crate::album_label!(
+                                    self,
+                                    album,
+                                    song.album,
+                                    ui,
+                                    Label::new(&*album.title)
+                                );
+                            });
+                            row.col(|ui| {
+                                crate::artist_label!(
+                                    self,
+                                    artist,
+                                    album.artist,
+                                    ui,
+                                    Label::new(&*artist.name)
+                                );
+                            });
+                        }
+                        Entry::Invalid {
+                            artist,
+                            album,
+                            song,
+                        } => {
+                            row.col(|_| {});
+                            row.col(|ui| {
+                                ui.label(ago);
+                            });
+                            for text in [song, album, artist] {
+                                row.col(|ui| {
+                                    ui.add(Label::new(RichText::new(&**text).color(MEDIUM_GRAY)))
+                                        .on_hover_text(HISTORY_INVALID);
+                                });
+                            }
+                        }
+                    }
+                });
+            });
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+//#[cfg(test)]
+//mod tests {
+//  #[test]
+//  fn __TEST__() {
+//  }
+//}
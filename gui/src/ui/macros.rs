@@ -714,7 +714,11 @@ macro_rules! song_rand {
         if primary {
             // SAFETY: ui should be greyed out if `Collection`
             // is empty so that this never panics.
-            let key = $self.collection.rand_song(None).unwrap();
+            let key = $self
+                .settings
+                .shuffle
+                .rand_song(&$self.collection, $self.audio_state.song)
+                .unwrap();
             let song = &$self.collection.songs[key];
             if $self.modifiers.command {
                 $self.playlist_add_screen = Some(shukusai::collection::KeyEnum::Song(key));
@@ -724,7 +728,11 @@ macro_rules! song_rand {
             }
         } else if secondary || middle {
             // SAFETY: same as above.
-            let key = $self.collection.rand_song(None).unwrap();
+            let key = $self
+                .settings
+                .shuffle
+                .rand_song(&$self.collection, $self.audio_state.song)
+                .unwrap();
             let song = &$self.collection.songs[key];
             if secondary {
                 $crate::add_song!($self, &song.title, key);
@@ -749,7 +757,11 @@ macro_rules! album_rand {
         if primary {
             // SAFETY: ui should be greyed out if `Collection`
             // is empty so that this never panics.
-            let key = $self.collection.rand_album(None).unwrap();
+            let key = $self
+                .settings
+                .shuffle
+                .rand_album(&$self.collection, $self.audio_state.song)
+                .unwrap();
             let album = &$self.collection.albums[key];
             if $self.modifiers.command {
                 $self.playlist_add_screen = Some(shukusai::collection::KeyEnum::Album(key));
@@ -759,7 +771,11 @@ macro_rules! album_rand {
             }
         } else if secondary || middle {
             // SAFETY: same as above.
-            let key = $self.collection.rand_album(None).unwrap();
+            let key = $self
+                .settings
+                .shuffle
+                .rand_album(&$self.collection, $self.audio_state.song)
+                .unwrap();
             let album = &$self.collection.albums[key];
             if secondary {
                 $crate::add_album!($self, &album.title, key);
@@ -785,7 +801,11 @@ macro_rules! artist_rand {
         if primary {
             // SAFETY: ui should be greyed out if `Collection`
             // is empty so that this never panics.
-            let key = $self.collection.rand_artist(None).unwrap();
+            let key = $self
+                .settings
+                .shuffle
+                .rand_artist(&$self.collection, $self.audio_state.song)
+                .unwrap();
             let artist = &$self.collection.artists[key];
             if $self.modifiers.command {
                 $self.playlist_add_screen = Some(shukusai::collection::KeyEnum::Artist(key));
@@ -795,7 +815,11 @@ macro_rules! artist_rand {
             }
         } else if secondary || middle {
             // SAFETY: same as above.
-            let key = $self.collection.rand_artist(None).unwrap();
+            let key = $self
+                .settings
+                .shuffle
+                .rand_artist(&$self.collection, $self.audio_state.song)
+                .unwrap();
             let artist = &$self.collection.artists[key];
             if secondary {
                 $crate::add_artist!($self, artist, key);
@@ -827,6 +851,42 @@ macro_rules! playlist_label {
     };
 }
 
+#[macro_export]
+/// Clickable rating stars for a `Song` or `Album`.
+///
+/// `$msg` is the `FrontendToKernel` variant
+/// to send with the `$key` and the new rating.
+///
+/// Clicking the current rating clears it.
+///
+/// This expects `$ui` to have a horizontal layout.
+macro_rules! rating {
+    ($self:ident, $ui:ident, $rating:expr, $msg:ident, $key:expr) => {{
+        let rating: u8 = $rating;
+        $ui.spacing_mut().item_spacing.x = 0.0;
+
+        for i in 1..=shukusai::collection::RATING_MAX {
+            let star = if i <= rating {
+                $crate::text::UI_RATING_STAR
+            } else {
+                $crate::text::UI_RATING_STAR_EMPTY
+            };
+
+            if $ui
+                .add(egui::Label::new(star).sense(egui::Sense::click()))
+                .on_hover_text($crate::text::RATING)
+                .clicked()
+            {
+                let new = if i == rating { 0 } else { i };
+                ::benri::send!(
+                    $self.to_kernel,
+                    shukusai::kernel::FrontendToKernel::$msg(($key, new))
+                );
+            }
+        }
+    }};
+}
+
 #[macro_export]
 /// Reduces the default rounding settings for the scope's `ui`.
 macro_rules! no_rounding {
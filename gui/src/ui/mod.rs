@@ -1,6 +1,7 @@
 mod albums;
 mod artist;
 mod exit;
+mod history;
 mod macros;
 mod playlists;
 mod queue;
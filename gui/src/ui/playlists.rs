@@ -3,19 +3,33 @@ use crate::{
     constants::{BONE, GRAY, MEDIUM_GRAY, PLAYLIST_NAME_MAX_LEN, YELLOW},
     data::PlaylistSubTab,
     text::{
-        PLAYLIST_COPY, PLAYLIST_COUNT, PLAYLIST_CREATE, PLAYLIST_DELETE, PLAYLIST_EDIT,
-        PLAYLIST_EDIT_SAVE, PLAYLIST_EMPTY, PLAYLIST_ENTRY_DELETE, PLAYLIST_ENTRY_DOWN,
-        PLAYLIST_ENTRY_UP, PLAYLIST_EXISTS, PLAYLIST_INVALID, PLAYLIST_TEXT, PLAYLIST_TEXT_EMPTY,
-        PLAYLIST_TOTAL_RUNTIME, PLAYLIST_TOTAL_SONG, SELECT_PLAYLIST, UI_DOWN, UI_MINUS, UI_PLUS,
-        UI_UP,
+        PLAYLIST_ART, PLAYLIST_COPY, PLAYLIST_COUNT, PLAYLIST_CREATE, PLAYLIST_DEDUP,
+        PLAYLIST_DELETE, PLAYLIST_DESCRIPTION, PLAYLIST_DETAILS, PLAYLIST_EDIT, PLAYLIST_EDIT_SAVE,
+        PLAYLIST_EMPTY, PLAYLIST_ENTRY_DELETE, PLAYLIST_ENTRY_DOWN, PLAYLIST_ENTRY_DRAG,
+        PLAYLIST_ENTRY_UP, PLAYLIST_EXISTS, PLAYLIST_EXPORT, PLAYLIST_FOLDER,
+        PLAYLIST_FOLDER_TOGGLE, PLAYLIST_IMPORT, PLAYLIST_INVALID, PLAYLIST_PIN, PLAYLIST_PINNED,
+        PLAYLIST_REPAIR, PLAYLIST_SORT, PLAYLIST_TEXT, PLAYLIST_TEXT_EMPTY, PLAYLIST_TOTAL_RUNTIME,
+        PLAYLIST_TOTAL_SONG, SELECT_PLAYLIST, SMART_PLAYLIST, SMART_PLAYLIST_ADD_RULE,
+        SMART_PLAYLIST_CANCEL, SMART_PLAYLIST_EDIT, SMART_PLAYLIST_LIMIT, SMART_PLAYLIST_NEW,
+        SMART_PLAYLIST_REFRESH, SMART_PLAYLIST_REMOVE, SMART_PLAYLIST_RULES, SMART_PLAYLIST_SAVE,
+        SMART_PLAYLIST_SORT, UI_DOWN, UI_MINUS, UI_PLAYLIST_DEDUP, UI_PLAYLIST_DETAILS,
+        UI_PLAYLIST_DRAG, UI_PLAYLIST_PIN, UI_PLAYLIST_REPAIR, UI_PLUS, UI_SMART_PLAYLIST, UI_UP,
     },
 };
-use egui::{Button, Label, RichText, ScrollArea, SelectableLabel, Sense, TextEdit, TextStyle};
+use egui::{
+    Button, ComboBox, DragValue, Label, RichText, ScrollArea, SelectableLabel, Sense, TextEdit,
+    TextStyle,
+};
 use egui_extras::{Column, TableBuilder};
 use readable::HeadTail;
 use readable::{Runtime, Unsigned};
-use shukusai::state::Entry;
+use shukusai::{
+    collection::RATING_MAX,
+    sort::{PlaylistSort, SongSort},
+    state::{Entry, PlaylistMetadata, Playlists, Rule, REPAIR_THRESHOLD, SMART_PLAYLISTS},
+};
 use std::sync::Arc;
+use strum::IntoEnumIterator;
 
 //---------------------------------------------------------------------------------------------------- Artists
 impl crate::data::Gui {
@@ -88,6 +102,7 @@ impl crate::data::Gui {
                     .show_viewport(ui, |ui, _| {
                         const SIZE: f32 = 35.0;
                         const SIZE2: f32 = 50.0;
+                        const INDENT: f32 = 30.0;
 
                         //-------------------------------------------------- Playlist add/remove text edit.
                         ui.horizontal(|ui| {
@@ -121,6 +136,16 @@ impl crate::data::Gui {
                                     });
                                 }
 
+                                // Import button.
+                                let button = Button::new(RichText::new("📂").size(SIZE - 5.0));
+                                if ui
+                                    .add_sized([SIZE2, SIZE2], button)
+                                    .on_hover_text(PLAYLIST_IMPORT)
+                                    .clicked()
+                                {
+                                    self.import_playlist();
+                                }
+
                                 // Playlist count
                                 let text = Label::new(
                                     RichText::new(format!("[{}]", playlists.len()))
@@ -143,9 +168,11 @@ impl crate::data::Gui {
 
                                 // Check `[enter]` and add.
                                 if resp.lost_focus()
-                                    && ui.input(|i| i.key_pressed(egui::Key::Enter)) && !self.state.playlist_string.is_empty() && !playlists.contains_key(self.state.playlist_string.as_str()) {
-                                    let string =
-                                        std::mem::take(&mut self.state.playlist_string);
+                                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                                    && !self.state.playlist_string.is_empty()
+                                    && !playlists.contains_key(self.state.playlist_string.as_str())
+                                {
+                                    let string = std::mem::take(&mut self.state.playlist_string);
                                     playlists.playlist_new(&string);
                                 }
                             })
@@ -153,8 +180,109 @@ impl crate::data::Gui {
 
                         ui.add_space(10.0);
 
+                        // Pinned playlists first, then grouped by folder.
+                        let order: Vec<(bool, String, Arc<str>)> = playlists
+                            .name_arcs_by_folder()
+                            .into_iter()
+                            .map(|(pinned, folder, name)| (pinned, folder.to_string(), name))
+                            .collect();
+
+                        // If the previous playlist was pinned, and its folder components.
+                        let mut prev: Option<(bool, Vec<&str>)> = None;
+                        // Toggle the pin/details/folder of this playlist after the loop.
+                        let mut pin = None;
+                        let mut details = None;
+                        let mut folder_toggle = None;
+
                         // For each `Playlist`...
-                        for (playlist_name, playlist) in playlists.iter() {
+                        for (pinned, folder, playlist_name) in order.iter() {
+                            // Pinned playlists are shown outside of their folder.
+                            let components: Vec<&str> = if *pinned {
+                                vec![]
+                            } else {
+                                folder.split('/').filter(|s| !s.is_empty()).collect()
+                            };
+
+                            // `Pinned` header.
+                            if *pinned && prev.is_none() {
+                                let label = Label::new(
+                                    RichText::new(format!("{UI_PLAYLIST_PIN} {PLAYLIST_PINNED}"))
+                                        .color(BONE)
+                                        .text_style(TextStyle::Name("25".into())),
+                                );
+                                ui.add(label);
+                                ui.add_space(10.0);
+                            } else if !*pinned && matches!(prev, Some((true, _))) {
+                                ui.separator();
+                                ui.add_space(10.0);
+                            }
+
+                            // Folder headers the previous playlist did not already show.
+                            let common = match &prev {
+                                Some((false, p)) => p
+                                    .iter()
+                                    .zip(components.iter())
+                                    .take_while(|(a, b)| a == b)
+                                    .count(),
+                                _ => 0,
+                            };
+                            prev = Some((*pinned, components.clone()));
+
+                            let closed = |depth: usize| {
+                                (1..=depth).any(|i| {
+                                    self.playlist_folders_closed
+                                        .contains(&components[..i].join("/"))
+                                })
+                            };
+
+                            for depth in common..components.len() {
+                                // Hidden if a parent folder is closed.
+                                if closed(depth) {
+                                    break;
+                                }
+
+                                let path = components[..=depth].join("/");
+                                let icon = if self.playlist_folders_closed.contains(&path) {
+                                    "📁"
+                                } else {
+                                    "📂"
+                                };
+                                let label = SelectableLabel::new(
+                                    false,
+                                    RichText::new(format!("{icon} {}", components[depth]))
+                                        .color(BONE)
+                                        .text_style(TextStyle::Name("25".into())),
+                                );
+
+                                let mut toggle = false;
+                                ui.horizontal(|ui| {
+                                    ui.add_space(depth as f32 * INDENT);
+                                    toggle = ui
+                                        .add(label)
+                                        .on_hover_text(PLAYLIST_FOLDER_TOGGLE)
+                                        .clicked();
+                                });
+                                if toggle {
+                                    folder_toggle = Some(path);
+                                }
+                                ui.add_space(10.0);
+                            }
+
+                            // Skip playlists within closed folders.
+                            if closed(components.len()) {
+                                continue;
+                            }
+
+                            let indent = components.len() as f32 * INDENT;
+                            let details_open = self
+                                .playlist_details
+                                .as_ref()
+                                .is_some_and(|(x, _)| x == playlist_name);
+
+                            let Some(playlist) = playlists.get(playlist_name) else {
+                                continue;
+                            };
+
                             // `Playlist` name.
                             let label_name = Label::new(
                                 RichText::new(&**playlist_name)
@@ -164,10 +292,8 @@ impl crate::data::Gui {
                             // `Playlist` entry count.
                             let label_count = Label::new(
                                 RichText::new(
-                                    Unsigned::from(shukusai::state::Playlists::valid_len(
-                                        playlist,
-                                    ))
-                                    .as_str(),
+                                    Unsigned::from(shukusai::state::Playlists::valid_len(playlist))
+                                        .as_str(),
                                 )
                                 .color(MEDIUM_GRAY)
                                 .text_style(TextStyle::Name("25".into())),
@@ -196,6 +322,7 @@ impl crate::data::Gui {
                                 .is_some_and(|x| x == playlist_name);
 
                             ui.horizontal(|ui| {
+                                ui.add_space(indent);
                                 ui.group(|ui| {
                                     let button = Button::new(RichText::new(UI_MINUS).size(SIZE));
                                     if ui
@@ -270,6 +397,39 @@ impl crate::data::Gui {
                                         self.playlist_clone = Some(Arc::clone(playlist_name));
                                     }
 
+                                    let button = Button::new(RichText::new("💾").size(SIZE - 5.0));
+                                    if ui
+                                        .add_sized([SIZE2, SIZE2], button)
+                                        .on_hover_text(PLAYLIST_EXPORT)
+                                        .clicked()
+                                    {
+                                        self.export_playlist(Arc::clone(playlist_name));
+                                    }
+
+                                    let button = Button::new(
+                                        RichText::new(UI_PLAYLIST_PIN).size(SIZE - 5.0),
+                                    )
+                                    .selected(*pinned);
+                                    if ui
+                                        .add_sized([SIZE2, SIZE2], button)
+                                        .on_hover_text(PLAYLIST_PIN)
+                                        .clicked()
+                                    {
+                                        pin = Some(Arc::clone(playlist_name));
+                                    }
+
+                                    let button = Button::new(
+                                        RichText::new(UI_PLAYLIST_DETAILS).size(SIZE - 5.0),
+                                    )
+                                    .selected(details_open);
+                                    if ui
+                                        .add_sized([SIZE2, SIZE2], button)
+                                        .on_hover_text(PLAYLIST_DETAILS)
+                                        .clicked()
+                                    {
+                                        details = Some(Arc::clone(playlist_name));
+                                    }
+
                                     ui.add_space(15.0);
 
                                     if playlist_name_is_being_edited {
@@ -291,14 +451,124 @@ impl crate::data::Gui {
                                         ui.add(label_count).on_hover_text(PLAYLIST_TOTAL_SONG);
                                         ui.add_space(20.0);
                                         ui.add(label_runtime).on_hover_text(PLAYLIST_TOTAL_RUNTIME);
+
+                                        if let Some(metadata) = playlists.metadata(playlist_name) {
+                                            if !metadata.description.is_empty() {
+                                                ui.add_space(20.0);
+                                                let text = metadata.description.head_dot(64);
+                                                ui.add(Label::new(RichText::new(text).color(GRAY)))
+                                                    .on_hover_text(&metadata.description);
+                                            }
+                                        }
                                     }
 
                                     ui.add_space(ui.available_width());
                                 })
                             });
+
+                            // Playlist metadata editor.
+                            let mut art = false;
+                            if let (true, Some(metadata), Some((_, folder))) = (
+                                details_open,
+                                playlists.metadata_mut(playlist_name),
+                                self.playlist_details.as_mut(),
+                            ) {
+                                ui.add_space(5.0);
+                                ui.horizontal(|ui| {
+                                    ui.add_space(indent);
+                                    ui.group(|ui| {
+                                        let mut changed = false;
+
+                                        let hover = match &metadata.art {
+                                            Some(path) => {
+                                                format!("{PLAYLIST_ART}\n\n{}", path.display())
+                                            }
+                                            None => PLAYLIST_ART.to_string(),
+                                        };
+                                        let button =
+                                            Button::new(RichText::new("🖼").size(SIZE - 5.0))
+                                                .selected(metadata.art.is_some());
+                                        let resp = ui
+                                            .add_sized([SIZE2, SIZE2], button)
+                                            .on_hover_text(hover);
+                                        if resp.clicked() {
+                                            art = true;
+                                        } else if resp.secondary_clicked() && metadata.art.is_some()
+                                        {
+                                            metadata.art = None;
+                                            changed = true;
+                                        }
+
+                                        ui.add_space(15.0);
+
+                                        // The folder is only applied once done typing,
+                                        // else the playlist would move around while typing.
+                                        ui.label(RichText::new("Folder").color(BONE));
+                                        let text_edit =
+                                            TextEdit::singleline(folder).hint_text("Rock/80s");
+                                        let resp = ui
+                                            .add_sized([200.0, SIZE], text_edit)
+                                            .on_hover_text(PLAYLIST_FOLDER);
+                                        if resp.lost_focus() {
+                                            *folder = PlaylistMetadata::folder_normalize(folder);
+                                            if *folder != metadata.folder {
+                                                metadata.folder = folder.clone();
+                                                changed = true;
+                                            }
+                                        }
+
+                                        ui.add_space(15.0);
+
+                                        ui.label(RichText::new("Description").color(BONE));
+                                        let width = ui.available_width() - 10.0;
+                                        let text_edit =
+                                            TextEdit::singleline(&mut metadata.description);
+                                        ui.spacing_mut().text_edit_width = width;
+                                        changed |= ui
+                                            .add_sized([width, SIZE], text_edit)
+                                            .on_hover_text(PLAYLIST_DESCRIPTION)
+                                            .changed();
+
+                                        if changed {
+                                            metadata.modified = benri::unix!();
+                                        }
+                                    })
+                                });
+                            }
+                            if art {
+                                self.playlist_art(Arc::clone(playlist_name));
+                            }
+
                             ui.add_space(10.0);
                         }
 
+                        // Open/close folder if set.
+                        if let Some(path) = folder_toggle {
+                            if !self.playlist_folders_closed.remove(&path) {
+                                self.playlist_folders_closed.insert(path);
+                            }
+                        }
+
+                        // Toggle playlist pin if set.
+                        if let Some(metadata) = pin.and_then(|p| playlists.metadata_mut(&p)) {
+                            metadata.pinned = !metadata.pinned;
+                            metadata.modified = benri::unix!();
+                        }
+
+                        // Open/close playlist metadata editor if set.
+                        if let Some(name) = details {
+                            self.playlist_details = match self.playlist_details.take() {
+                                Some((open, _)) if open == name => None,
+                                _ => {
+                                    let folder = playlists
+                                        .metadata(&name)
+                                        .map(|m| m.folder.clone())
+                                        .unwrap_or_default();
+                                    Some((name, folder))
+                                }
+                            };
+                        }
+
                         // Clone playlist if set.
                         match self.playlist_clone.take() {
                             None => (),
@@ -318,7 +588,7 @@ impl crate::data::Gui {
                                         copy += " (Copy)";
                                     };
 
-                                    playlists.insert(new_name.into(), vec);
+                                    let _ = playlists.playlist_clone(name, &new_name);
                                 }
                             }
                         }
@@ -327,14 +597,18 @@ impl crate::data::Gui {
                         match self.playlist_remove.take() {
                             None => (),
                             Some(p) => {
-                                playlists.remove(&p);
+                                SMART_PLAYLISTS.write().remove(&p);
+                                playlists.playlist_remove(p);
                             }
                         }
 
                         // Swap keys if we renamed them above.
-                        if let (Some(from), Some(to)) = (&mut self.playlist_from, &mut self.playlist_to) {
-                            if let Some(value) = playlists.remove(&**from) {
-                                playlists.insert(Arc::clone(to), value);
+                        if let (Some(from), Some(to)) =
+                            (&mut self.playlist_from, &mut self.playlist_to)
+                        {
+                            // Ignore if `from` no longer exists.
+                            if playlists.playlist_rename(Arc::clone(from), to).is_ok() {
+                                SMART_PLAYLISTS.write().rename(from, to);
                             }
 
                             self.playlist_from = None;
@@ -356,6 +630,23 @@ impl crate::data::Gui {
                 };
                 let arc_str = std::sync::Arc::clone(arc_str);
 
+                // Show the smart playlist editor instead if we're editing this one.
+                if self
+                    .smart_playlist_edit
+                    .as_ref()
+                    .is_some_and(|(name, _)| *name == arc_str)
+                {
+                    self.show_smart_playlist_editor(ui, &mut playlists);
+                    return;
+                }
+
+                let smart = SMART_PLAYLISTS.read().get(&arc_str).cloned();
+                let mut smart_edit = false;
+                let mut smart_refresh = false;
+                let mut dedup = false;
+                let mut sort = None;
+                let mut repair = false;
+
                 let Some(playlist) = playlists.get(&arc_str) else {
                     return_on_none();
                     return;
@@ -402,6 +693,10 @@ impl crate::data::Gui {
                 let max_rows = ((height - (HEADER_HEIGHT - 5.0)) / ROW_HEIGHT) as usize;
                 let row_range = 0..max_rows;
 
+                // The playlist index the dragged entry is hovering over.
+                let mut drop_target = None;
+                let pointer = ui.input(|i| i.pointer.hover_pos());
+
                 ScrollArea::horizontal()
                     .id_source("PlaylistView")
                     .max_width(f32::INFINITY)
@@ -414,6 +709,55 @@ impl crate::data::Gui {
                             ui.add(label_count).on_hover_text(PLAYLIST_TOTAL_SONG);
                             ui.add_space(20.0);
                             ui.add(label_runtime).on_hover_text(PLAYLIST_TOTAL_RUNTIME);
+                            ui.add_space(20.0);
+
+                            let hover = if smart.is_some() {
+                                SMART_PLAYLIST_EDIT
+                            } else {
+                                SMART_PLAYLIST_NEW
+                            };
+                            let button = Button::new(RichText::new(UI_SMART_PLAYLIST).size(25.0));
+                            if ui.add(button).on_hover_text(hover).clicked() {
+                                smart_edit = true;
+                            }
+
+                            if smart.is_some() {
+                                let button = Button::new(RichText::new("🔄").size(25.0));
+                                if ui
+                                    .add(button)
+                                    .on_hover_text(SMART_PLAYLIST_REFRESH)
+                                    .clicked()
+                                {
+                                    smart_refresh = true;
+                                }
+                            }
+
+                            ui.add_space(20.0);
+
+                            let button = Button::new(RichText::new(UI_PLAYLIST_DEDUP).size(25.0));
+                            if ui.add(button).on_hover_text(PLAYLIST_DEDUP).clicked() {
+                                dedup = true;
+                            }
+
+                            ComboBox::from_id_source("playlist_sort")
+                                .selected_text(RichText::new("Sort").color(BONE))
+                                .show_ui(ui, |ui| {
+                                    for i in PlaylistSort::iter() {
+                                        if ui.selectable_label(false, i.human()).clicked() {
+                                            sort = Some(i);
+                                        }
+                                    }
+                                })
+                                .response
+                                .on_hover_text(PLAYLIST_SORT);
+
+                            if shukusai::state::Playlists::invalid_len(playlist) != 0 {
+                                let button =
+                                    Button::new(RichText::new(UI_PLAYLIST_REPAIR).size(25.0));
+                                if ui.add(button).on_hover_text(PLAYLIST_REPAIR).clicked() {
+                                    repair = true;
+                                }
+                            }
                         });
 
                         ui.add_space(10.0);
@@ -426,7 +770,8 @@ impl crate::data::Gui {
                             const SIZE: f32 = 35.0;
                             // c == Column sizing
                             let c_width = width / 10.0;
-                            let c_buttons = (SIZE * 3.0) + 20.0;
+                            let c_buttons = (SIZE * 4.0) + 20.0;
+                            let x_range = ui.max_rect().x_range();
                             let c_runtime = c_width;
                             let c_title = c_width * 4.0;
                             let c_album = c_width * 2.0;
@@ -470,6 +815,15 @@ impl crate::data::Gui {
                                                         self.collection.walk(key);
 
                                                     row.col(|ui| {
+                                                        Self::playlist_drag_handle(
+                                                            &mut self.playlist_drag,
+                                                            ui,
+                                                            offset,
+                                                            pointer,
+                                                            x_range,
+                                                            &mut drop_target,
+                                                        );
+
                                                         // Buttons.
                                                         if ui
                                                             .add_sized(
@@ -567,6 +921,15 @@ impl crate::data::Gui {
                                             } => {
                                                 body.row(ROW_HEIGHT, |mut row| {
                                                     row.col(|ui| {
+                                                        Self::playlist_drag_handle(
+                                                            &mut self.playlist_drag,
+                                                            ui,
+                                                            offset,
+                                                            pointer,
+                                                            x_range,
+                                                            &mut drop_target,
+                                                        );
+
                                                         // Buttons.
                                                         if ui
                                                             .add_sized(
@@ -647,22 +1010,279 @@ impl crate::data::Gui {
                         });
                     });
 
+                // Drop the dragged playlist entry.
+                if self.playlist_drag.is_some() && ui.input(|i| i.pointer.any_released()) {
+                    if let (Some(from), Some(to)) = (self.playlist_drag.take(), drop_target) {
+                        let _ = playlists.playlist_move_index(from, to, Arc::clone(&arc_str));
+                    }
+                }
+
                 // Remove playlist entry set above.
                 if let Some((playlist_name, index)) = self.playlist_remove_entry.take() {
-                    if let Some(playlist) = playlists.get_mut(&playlist_name) {
-                        playlist.remove(index);
-                    }
+                    let _ = playlists.playlist_remove_index(index, playlist_name);
                 }
 
                 // Swap playlist entry positions set above.
                 if let Some((playlist_name, from, to)) = self.playlist_swap_entry.take() {
                     if let Some(playlist) = playlists.get_mut(&playlist_name) {
                         playlist.swap(from, to);
+                        playlists.touch(&playlist_name);
                     }
                 }
+
+                // Dedup/sort set above.
+                if dedup {
+                    if let Some(removed) = playlists.playlist_dedup(Arc::clone(&arc_str)) {
+                        crate::toast!(self, format!("Removed {removed} duplicate entries"));
+                    }
+                } else if let Some(sort) = sort {
+                    playlists.playlist_sort(sort, Arc::clone(&arc_str), &self.collection);
+                } else if repair {
+                    let repairs = playlists.repair_playlist(
+                        REPAIR_THRESHOLD,
+                        &std::collections::HashMap::new(),
+                        Arc::clone(&arc_str),
+                        &self.collection,
+                    );
+                    let applied = repairs.iter().filter(|r| r.applied).count();
+                    let review: Vec<_> = repairs.into_iter().filter(|r| !r.applied).collect();
+                    crate::toast!(
+                        self,
+                        format!(
+                            "Repaired {applied} entries, {} left for review",
+                            review.len()
+                        )
+                    );
+                    if !review.is_empty() {
+                        self.playlist_repair = Some(review);
+                    }
+                }
+
+                // Start editing the smart playlist rules.
+                if smart_edit {
+                    self.smart_playlist_edit = Some((arc_str, smart.unwrap_or_default()));
+                } else if smart_refresh {
+                    SMART_PLAYLISTS
+                        .read()
+                        .refresh(&arc_str, &mut playlists, &self.collection);
+                }
             }
         } // end of match.
     }
+
+    // The drag handle for the playlist entry at `offset`.
+    //
+    // This takes `playlist_drag` instead of `&mut self` since
+    // the table rows are still borrowing `self.collection`.
+    //
+    // If an entry is being dragged and the pointer is over
+    // this row, `drop_target` is set and the landing spot is painted.
+    fn playlist_drag_handle(
+        playlist_drag: &mut Option<usize>,
+        ui: &mut egui::Ui,
+        offset: usize,
+        pointer: Option<egui::Pos2>,
+        x_range: egui::Rangef,
+        drop_target: &mut Option<usize>,
+    ) {
+        const SIZE: f32 = 35.0;
+
+        let handle = Label::new(UI_PLAYLIST_DRAG).sense(Sense::drag());
+        let handle = ui
+            .add_sized([SIZE, SIZE], handle)
+            .on_hover_text(PLAYLIST_ENTRY_DRAG);
+        if handle.drag_started() {
+            *playlist_drag = Some(offset);
+        }
+
+        // Show where the dragged entry would land.
+        if let (Some(from), Some(pos)) = (*playlist_drag, pointer) {
+            let rect = ui.max_rect();
+            if from != offset && rect.y_range().contains(pos.y) {
+                let y = if from < offset {
+                    rect.bottom()
+                } else {
+                    rect.top()
+                };
+                ui.ctx().layer_painter(ui.layer_id()).hline(
+                    x_range,
+                    y,
+                    ui.visuals().selection.stroke,
+                );
+                *drop_target = Some(offset);
+            }
+        }
+    }
+
+    // The rule editor for `self.smart_playlist_edit`.
+    //
+    // `playlists` is the already locked `PLAYLISTS`.
+    fn show_smart_playlist_editor(&mut self, ui: &mut egui::Ui, playlists: &mut Playlists) {
+        let Some((name, smart)) = &mut self.smart_playlist_edit else {
+            return;
+        };
+        let exists = SMART_PLAYLISTS.read().contains_key(name);
+
+        let mut save = false;
+        let mut cancel = false;
+        let mut remove = false;
+
+        ScrollArea::vertical()
+            .id_source("SmartPlaylistEditor")
+            .max_width(f32::INFINITY)
+            .max_height(f32::INFINITY)
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let label = Label::new(
+                        RichText::new(format!("{UI_SMART_PLAYLIST} {name}"))
+                            .text_style(TextStyle::Name("30".into())),
+                    );
+                    ui.add(label).on_hover_text(SMART_PLAYLIST);
+                });
+
+                ui.add_space(10.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                //-------------------------------------------------- Rules.
+                ui.label(RichText::new("Rules").color(BONE))
+                    .on_hover_text(SMART_PLAYLIST_RULES);
+
+                let mut remove_rule = None;
+                for (i, rule) in smart.rules.iter_mut().enumerate() {
+                    ui.push_id(i, |ui| {
+                        ui.horizontal(|ui| {
+                            if ui.button(UI_MINUS).clicked() {
+                                remove_rule = Some(i);
+                            }
+                            ui.label(rule.human());
+
+                            match rule {
+                                Rule::Genre(genre) => {
+                                    ui.add(TextEdit::singleline(genre));
+                                }
+                                Rule::Year { from, to } => {
+                                    ui.add(DragValue::new(from));
+                                    ui.label("-");
+                                    ui.add(DragValue::new(to));
+                                }
+                                Rule::RuntimeUnder(seconds) => {
+                                    ui.add(DragValue::new(seconds));
+                                }
+                                Rule::Artists(artists) => {
+                                    let mut remove_artist = None;
+                                    for (j, artist) in artists.iter_mut().enumerate() {
+                                        ui.add(TextEdit::singleline(artist).desired_width(150.0));
+                                        if ui.button(UI_MINUS).clicked() {
+                                            remove_artist = Some(j);
+                                        }
+                                    }
+                                    if let Some(j) = remove_artist {
+                                        artists.remove(j);
+                                    }
+                                    if ui.button(UI_PLUS).clicked() {
+                                        artists.push(String::new());
+                                    }
+                                }
+                                Rule::RatingAtLeast(rating) => {
+                                    ui.add(DragValue::new(rating).range(1..=RATING_MAX));
+                                }
+                                Rule::NotPlayedDays(days) | Rule::AddedDays(days) => {
+                                    ui.add(DragValue::new(days));
+                                }
+                            }
+                        });
+                    });
+                }
+                if let Some(i) = remove_rule {
+                    smart.rules.remove(i);
+                }
+
+                ComboBox::from_id_source("smart_playlist_add_rule")
+                    .selected_text(RichText::new(SMART_PLAYLIST_ADD_RULE).color(BONE))
+                    .show_ui(ui, |ui| {
+                        for rule in Rule::DEFAULTS {
+                            if ui.selectable_label(false, rule.human()).clicked() {
+                                smart.rules.push(rule);
+                            }
+                        }
+                    });
+
+                ui.add_space(10.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                //-------------------------------------------------- Limit & Sort.
+                ui.horizontal(|ui| {
+                    let mut limited = smart.limit.is_some();
+                    ui.checkbox(&mut limited, "Limit")
+                        .on_hover_text(SMART_PLAYLIST_LIMIT);
+                    match (limited, &mut smart.limit) {
+                        (true, Some(limit)) => {
+                            ui.add(DragValue::new(limit).range(1..=usize::MAX));
+                        }
+                        (true, None) => smart.limit = Some(50),
+                        (false, _) => smart.limit = None,
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Sort").on_hover_text(SMART_PLAYLIST_SORT);
+                    ComboBox::from_id_source("smart_playlist_sort")
+                        .selected_text(RichText::new(smart.sort.human()).color(BONE))
+                        .show_ui(ui, |ui| {
+                            for i in SongSort::iter() {
+                                ui.selectable_value(&mut smart.sort, i, i.human());
+                            }
+                        });
+                });
+
+                ui.add_space(10.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                //-------------------------------------------------- Save/Cancel/Remove.
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Save")
+                        .on_hover_text(SMART_PLAYLIST_SAVE)
+                        .clicked()
+                    {
+                        save = true;
+                    }
+                    if ui
+                        .button("Cancel")
+                        .on_hover_text(SMART_PLAYLIST_CANCEL)
+                        .clicked()
+                    {
+                        cancel = true;
+                    }
+                    if exists
+                        && ui
+                            .button("Remove")
+                            .on_hover_text(SMART_PLAYLIST_REMOVE)
+                            .clicked()
+                    {
+                        remove = true;
+                    }
+                });
+            });
+
+        if save {
+            if let Some((name, smart)) = self.smart_playlist_edit.take() {
+                let mut smart_playlists = SMART_PLAYLISTS.write();
+                smart_playlists.insert(Arc::clone(&name), smart);
+                smart_playlists.refresh(&name, playlists, &self.collection);
+            }
+        } else if remove {
+            if let Some((name, _)) = self.smart_playlist_edit.take() {
+                SMART_PLAYLISTS.write().remove(&name);
+            }
+        } else if cancel {
+            self.smart_playlist_edit = None;
+        }
+    }
 }
 
 //---------------------------------------------------------------------------------------------------- TESTS
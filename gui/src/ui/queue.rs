@@ -1,14 +1,22 @@
 //---------------------------------------------------------------------------------------------------- Use
-use crate::constants::{BONE, GRAY, GREEN, MEDIUM_GRAY, QUEUE_ALBUM_ART_SIZE, WHITE, YELLOW};
+use crate::constants::{
+    BONE, BRIGHT_YELLOW, GRAY, GREEN, MEDIUM_GRAY, QUEUE_ALBUM_ART_SIZE, SLEEP_TIMER_FADE,
+    SLEEP_TIMER_MINUTES, WHITE, YELLOW,
+};
 use crate::text::{
-    QUEUE_CLEAR, QUEUE_LENGTH, QUEUE_RUNTIME, QUEUE_SHUFFLE, QUEUE_SHUFFLE_ALBUM,
-    QUEUE_SHUFFLE_ARTIST, QUEUE_SHUFFLE_SONG, REPEAT_OFF, REPEAT_QUEUE, REPEAT_QUEUE_PAUSE,
-    REPEAT_SONG, SELECT_QUEUE, UI_MINUS, UI_QUEUE_CLEAR, UI_QUEUE_SHUFFLE, UI_QUEUE_SHUFFLE_ALBUM,
-    UI_QUEUE_SHUFFLE_ARTIST, UI_QUEUE_SHUFFLE_SONG, UI_REPEAT, UI_REPEAT_SONG,
+    AUTO_DJ_OFF, AUTO_DJ_ON, QUEUE_AUTO_DJ, QUEUE_CLEAR, QUEUE_DRAG, QUEUE_LENGTH, QUEUE_RUNTIME,
+    QUEUE_SHUFFLE, QUEUE_SHUFFLE_ALBUM, QUEUE_SHUFFLE_ARTIST, QUEUE_SHUFFLE_SONG,
+    QUEUE_SNAPSHOT_DELETE, QUEUE_SNAPSHOT_MENU, QUEUE_SNAPSHOT_NAME, QUEUE_SNAPSHOT_RESTORE,
+    QUEUE_SNAPSHOT_SAVE, REPEAT_AB, REPEAT_ALBUM, REPEAT_ARTIST, REPEAT_COUNT, REPEAT_OFF,
+    REPEAT_QUEUE, REPEAT_QUEUE_PAUSE, REPEAT_SONG, SELECT_QUEUE, SLEEP_TIMER, SLEEP_TIMER_LEFT,
+    UI_AUTO_DJ, UI_MINUS, UI_QUEUE_CLEAR, UI_QUEUE_DRAG, UI_QUEUE_SHUFFLE, UI_QUEUE_SHUFFLE_ALBUM,
+    UI_QUEUE_SHUFFLE_ARTIST, UI_QUEUE_SHUFFLE_SONG, UI_QUEUE_SNAPSHOTS, UI_REPEAT, UI_REPEAT_SONG,
+    UI_SLEEP,
 };
-use benri::{now, send};
-use egui::{Button, Label, RichText, ScrollArea, Sense, TextStyle};
-use shukusai::kernel::FrontendToKernel;
+use benri::{flip, now, send};
+use egui::{Button, Label, RichText, ScrollArea, SelectableLabel, Sense, TextEdit, TextStyle};
+use shukusai::{kernel::FrontendToKernel, state::QUEUE_SNAPSHOTS};
+use std::sync::Arc;
 
 //---------------------------------------------------------------------------------------------------- Queue
 impl crate::data::Gui {
@@ -64,7 +72,7 @@ impl crate::data::Gui {
                 });
 
                 ui.horizontal(|ui| {
-                    let width = (width / 6.0) - 7.5;
+                    let width = (width / 9.0) - 7.5;
 
                     // Stop.
                     let button = Button::new(RichText::new(UI_QUEUE_CLEAR).size(SIZE));
@@ -80,20 +88,36 @@ impl crate::data::Gui {
                     let button = Button::new(RichText::new(UI_QUEUE_SHUFFLE).size(SIZE));
                     if ui
                         .add_sized([width, SIZE2], button)
-                        .on_hover_text(QUEUE_SHUFFLE)
+                        .on_hover_text(format!(
+                            "{QUEUE_SHUFFLE}\n\n{}",
+                            self.settings.shuffle.human()
+                        ))
                         .clicked()
                     {
-                        send!(self.to_kernel, FrontendToKernel::Shuffle);
+                        send!(
+                            self.to_kernel,
+                            FrontendToKernel::Shuffle(self.settings.shuffle)
+                        );
                     }
 
                     // Repeat.
                     {
                         use shukusai::audio::Repeat;
-                        let (icon, text, color) = match self.state.repeat {
-                            Repeat::Song => (UI_REPEAT_SONG, REPEAT_SONG, YELLOW),
-                            Repeat::Queue => (UI_REPEAT, REPEAT_QUEUE, GREEN),
-                            Repeat::QueuePause => (UI_REPEAT, REPEAT_QUEUE_PAUSE, WHITE),
-                            Repeat::Off => (UI_REPEAT, REPEAT_OFF, MEDIUM_GRAY),
+                        let (icon, text, color): (&str, String, _) = match self.state.repeat {
+                            Repeat::Song => (UI_REPEAT_SONG, REPEAT_SONG.into(), YELLOW),
+                            Repeat::Queue => (UI_REPEAT, REPEAT_QUEUE.into(), GREEN),
+                            Repeat::QueuePause => (UI_REPEAT, REPEAT_QUEUE_PAUSE.into(), WHITE),
+                            Repeat::Off => (UI_REPEAT, REPEAT_OFF.into(), MEDIUM_GRAY),
+                            Repeat::Album => (UI_REPEAT, REPEAT_ALBUM.into(), YELLOW),
+                            Repeat::Artist => (UI_REPEAT, REPEAT_ARTIST.into(), BRIGHT_YELLOW),
+                            Repeat::Count(c) => {
+                                (UI_REPEAT_SONG, format!("{REPEAT_COUNT}: {c}"), GREEN)
+                            }
+                            Repeat::Ab { a, b } => (
+                                UI_REPEAT_SONG,
+                                format!("{REPEAT_AB}: {a}-{b}"),
+                                BRIGHT_YELLOW,
+                            ),
                         };
                         let button = Button::new(RichText::new(icon).size(30.0).color(color));
                         if ui
@@ -108,6 +132,63 @@ impl crate::data::Gui {
                         }
                     }
 
+                    // Auto-DJ.
+                    let auto_dj = self.audio_state.auto_dj;
+                    let label = SelectableLabel::new(auto_dj, RichText::new(UI_AUTO_DJ).size(SIZE));
+                    if ui
+                        .add_sized([width, SIZE2], label)
+                        .on_hover_text(if auto_dj { AUTO_DJ_ON } else { AUTO_DJ_OFF })
+                        .clicked()
+                    {
+                        send!(self.to_kernel, FrontendToKernel::AutoDj(!auto_dj));
+                    }
+
+                    // Sleep timer.
+                    {
+                        use shukusai::audio::SleepTimer;
+                        let sleep = self.audio_state.sleep;
+                        let now = benri::unix!();
+                        let text = match sleep.remaining(now) {
+                            Some(left) => format!(
+                                "{SLEEP_TIMER}\n\n{}\n{SLEEP_TIMER_LEFT}: {}",
+                                sleep.human(),
+                                readable::Runtime::from(left),
+                            ),
+                            None => format!("{SLEEP_TIMER}\n\n{}", sleep.human()),
+                        };
+                        let label = SelectableLabel::new(
+                            sleep != SleepTimer::Off,
+                            RichText::new(UI_SLEEP).size(SIZE),
+                        );
+                        let resp = ui.add_sized([width, SIZE2], label).on_hover_text(text);
+
+                        let next = if resp.clicked() {
+                            Some(match sleep {
+                                SleepTimer::Off => SleepTimer::Song,
+                                SleepTimer::Song => SleepTimer::Album,
+                                SleepTimer::Album => SleepTimer::Queue,
+                                SleepTimer::Queue => {
+                                    SleepTimer::minutes(SLEEP_TIMER_MINUTES, SLEEP_TIMER_FADE, now)
+                                }
+                                SleepTimer::Time { .. } => SleepTimer::Off,
+                            })
+                        } else if resp.secondary_clicked() {
+                            // Add onto the current time left, if any.
+                            let left = sleep.remaining(now).unwrap_or(0);
+                            Some(SleepTimer::Time {
+                                at: now + left + SLEEP_TIMER_MINUTES as u64 * 60,
+                                fade: SLEEP_TIMER_FADE,
+                            })
+                        } else {
+                            None
+                        };
+
+                        if let Some(next) = next {
+                            send!(self.to_kernel, FrontendToKernel::Sleep(next));
+                            self.audio_state.sleep = next;
+                        }
+                    }
+
                     // INVARIANT:
                     // Below `*_rand` macros unwrap on the rand functions which
                     // return `Option` since the `Collection` might be empty.
@@ -133,8 +214,92 @@ impl crate::data::Gui {
                             .on_hover_text(QUEUE_SHUFFLE_SONG);
                         crate::song_rand!(self, ui, resp);
                     });
+
+                    // Snapshots.
+                    let label = SelectableLabel::new(
+                        self.queue_snapshots_open,
+                        RichText::new(UI_QUEUE_SNAPSHOTS).size(SIZE),
+                    );
+                    if ui
+                        .add_sized([width, SIZE2], label)
+                        .on_hover_text(QUEUE_SNAPSHOT_MENU)
+                        .clicked()
+                    {
+                        flip!(self.queue_snapshots_open);
+                    }
                 });
 
+                //-------------------------------------------------- Snapshots.
+                if self.queue_snapshots_open {
+                    ui.add_space(5.0);
+                    ui.separator();
+
+                    ui.horizontal(|ui| {
+                        // Save button.
+                        let name_ok = !self.queue_snapshot_name.trim().is_empty();
+                        let button = Button::new(RichText::new("Save").color(BONE));
+                        let save = ui
+                            .add_enabled(name_ok, button)
+                            .on_hover_text(QUEUE_SNAPSHOT_SAVE)
+                            .clicked();
+
+                        // Text edit.
+                        let width = ui.available_width() - 10.0;
+                        let text_edit = TextEdit::singleline(&mut self.queue_snapshot_name)
+                            .hint_text("Enter snapshot name...");
+                        ui.spacing_mut().text_edit_width = width;
+                        let resp = ui
+                            .add_sized([width, SIZE], text_edit)
+                            .on_hover_text(QUEUE_SNAPSHOT_NAME);
+                        let enter =
+                            resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                        if name_ok && (save || enter) {
+                            let name: Arc<str> = self.queue_snapshot_name.trim().into();
+                            send!(self.to_kernel, FrontendToKernel::QueueSave(name));
+                            self.queue_snapshot_name.clear();
+                        }
+                    });
+
+                    for (name, snapshot) in QUEUE_SNAPSHOTS.read().iter() {
+                        ui.horizontal(|ui| {
+                            // Delete button.
+                            if ui
+                                .add_sized([SIZE, SIZE], Button::new(UI_MINUS))
+                                .on_hover_text(QUEUE_SNAPSHOT_DELETE)
+                                .clicked()
+                            {
+                                send!(
+                                    self.to_kernel,
+                                    FrontendToKernel::QueueSnapshotDelete(Arc::clone(name))
+                                );
+                            }
+
+                            // Restore button.
+                            let len = snapshot.queue.len();
+                            let index = snapshot.queue_idx.map_or(0, |i| i + 1);
+                            let button = Button::new(format!("{name} [{index}/{len}]"));
+                            if ui
+                                .add(button)
+                                .on_hover_text(QUEUE_SNAPSHOT_RESTORE)
+                                .clicked()
+                            {
+                                send!(
+                                    self.to_kernel,
+                                    FrontendToKernel::QueueRestore(Arc::clone(name))
+                                );
+                            }
+
+                            let invalid = len - snapshot.valid_len();
+                            if invalid != 0 {
+                                ui.label(
+                                    RichText::new(format!("{invalid} missing")).color(MEDIUM_GRAY),
+                                );
+                            }
+                        });
+                    }
+                }
+
                 ui.add_space(5.0);
                 ui.separator();
 
@@ -152,6 +317,10 @@ impl crate::data::Gui {
                 let mut current_artist = None;
                 let mut current_album = None;
 
+                // The queue index the dragged `Song` is hovering over.
+                let mut drop_target = None;
+                let pointer = ui.input(|i| i.pointer.hover_pos());
+
                 for (index, key) in self.audio_state.queue.iter().enumerate() {
                     let (artist, album, song) = self.collection.walk(key);
 
@@ -231,12 +400,26 @@ impl crate::data::Gui {
                     }
 
                     //-------------------------------------------------- Song.
-                    ui.horizontal(|ui| {
+                    let row = ui.horizontal(|ui| {
+                        // Drag handle.
+                        let handle = Label::new(UI_QUEUE_DRAG).sense(Sense::drag());
+                        let handle = ui.add_sized([SIZE, SIZE], handle).on_hover_text(QUEUE_DRAG);
+                        if handle.drag_started() {
+                            self.queue_drag = Some(index);
+                        }
+
                         // Remove button.
                         if ui.add_sized([SIZE, SIZE], Button::new(UI_MINUS)).clicked() {
                             crate::remove_queue_range!(self, index..index + 1);
                         }
 
+                        // Auto-DJ marker.
+                        if self.audio_state.is_auto_dj(index) {
+                            let label = Label::new(RichText::new(UI_AUTO_DJ).color(MEDIUM_GRAY));
+                            ui.add_sized([SIZE, SIZE], label)
+                                .on_hover_text(QUEUE_AUTO_DJ);
+                        }
+
                         // HACK:
                         // If we remove an index but are still playing the `Song`,
                         // the colored label indicating which one we're on will be wrong,
@@ -258,6 +441,28 @@ impl crate::data::Gui {
                             ui.available_width()
                         );
                     });
+
+                    // Show where the dragged `Song` would land.
+                    if let (Some(from), Some(pos)) = (self.queue_drag, pointer) {
+                        let rect = row.response.rect;
+                        if from != index && rect.contains(pos) {
+                            let y = if from < index {
+                                rect.bottom()
+                            } else {
+                                rect.top()
+                            };
+                            ui.painter()
+                                .hline(rect.x_range(), y, ui.visuals().selection.stroke);
+                            drop_target = Some(index);
+                        }
+                    }
+                }
+
+                // Drop.
+                if self.queue_drag.is_some() && ui.input(|i| i.pointer.any_released()) {
+                    if let (Some(from), Some(to)) = (self.queue_drag.take(), drop_target) {
+                        send!(self.to_kernel, FrontendToKernel::QueueMove((from, to)));
+                    }
                 }
             });
     }
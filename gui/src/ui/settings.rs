@@ -13,13 +13,15 @@ use crate::constants::{
 };
 use crate::data::{AlbumSizing, WindowTitle};
 use crate::text::*;
-use benri::{atomic_store, flip};
+use benri::{atomic_store, flip, send};
 use egui::containers::scroll_area::ScrollBarVisibility;
 use egui::{
     Button, ComboBox, Label, RichText, ScrollArea, SelectableLabel, Sense, Slider, TextStyle,
 };
 use shukusai::{
+    audio::{ResamplerQuality, ShuffleMode, BALANCE_MAX},
     constants::COPYRIGHT,
+    kernel::FrontendToKernel,
     search::SearchKind,
     sort::{AlbumSort, ArtistSort, SongSort},
 };
@@ -440,6 +442,283 @@ impl crate::data::Gui {
                 ui.separator();
                 ui.add_space(40.0);
 
+                //-------------------------------------------------- Previous History.
+                // Heading.
+                let label = Label::new(
+                    RichText::new("Previous Follows History")
+                        .color(BONE)
+                        .text_style(TextStyle::Heading),
+                );
+                ui.add_sized([width, text], label)
+                    .on_hover_text(PREVIOUS_HISTORY);
+
+                // SelectableLabel.
+                ui.add_space(10.0);
+                let old_history = self.settings.previous_history;
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        let width = (width / 2.0) - 25.0;
+                        if ui
+                            .add_sized(
+                                [width, text],
+                                SelectableLabel::new(self.settings.previous_history, "Yes"),
+                            )
+                            .clicked()
+                        {
+                            flip!(self.settings.previous_history);
+                        }
+                        ui.separator();
+                        if ui
+                            .add_sized(
+                                [width, text],
+                                SelectableLabel::new(!self.settings.previous_history, "No"),
+                            )
+                            .clicked()
+                        {
+                            flip!(self.settings.previous_history);
+                        }
+                    })
+                });
+                if old_history != self.settings.previous_history {
+                    atomic_store!(
+                        shukusai::audio::PREVIOUS_HISTORY,
+                        self.settings.previous_history
+                    );
+                }
+
+                ui.add_space(40.0);
+                ui.separator();
+                ui.add_space(40.0);
+
+                //-------------------------------------------------- Channels.
+                let old_mix = self.audio_state.channel_mix;
+                let mut mix = old_mix;
+
+                // Heading.
+                let label = Label::new(
+                    RichText::new(format!("Channels (Balance: {})", mix.balance))
+                        .color(BONE)
+                        .text_style(TextStyle::Heading),
+                );
+                ui.add_sized([width, text], label).on_hover_text(CHANNELS);
+
+                // These do nothing with bit-perfect output.
+                let enabled = !self.settings.bit_perfect;
+
+                // SelectableLabel.
+                ui.add_space(10.0);
+                ui.group(|ui| {
+                    ui.set_enabled(enabled);
+                    ui.horizontal(|ui| {
+                        let width = (width / 2.0) - 25.0;
+                        if ui
+                            .add_sized([width, text], SelectableLabel::new(mix.mono, "Mono"))
+                            .on_hover_text(CHANNELS_MONO)
+                            .on_disabled_hover_text(CHANNELS_BIT_PERFECT)
+                            .clicked()
+                        {
+                            flip!(mix.mono);
+                        }
+                        ui.separator();
+                        if ui
+                            .add_sized([width, text], SelectableLabel::new(mix.swap, "Swap L/R"))
+                            .on_hover_text(CHANNELS_SWAP)
+                            .on_disabled_hover_text(CHANNELS_BIT_PERFECT)
+                            .clicked()
+                        {
+                            flip!(mix.swap);
+                        }
+                    })
+                });
+
+                // Slider.
+                ui.add_space(10.0);
+                ui.scope(|ui| {
+                    ui.set_enabled(enabled);
+                    {
+                        let v = &mut ui.visuals_mut().widgets;
+                        v.inactive.fg_stroke = SLIDER_CIRCLE_INACTIVE;
+                        v.hovered.fg_stroke = SLIDER_CIRCLE_HOVERED;
+                        v.active.fg_stroke = SLIDER_CIRCLE_ACTIVE;
+                    }
+                    let slider = Slider::new(&mut mix.balance, -BALANCE_MAX..=BALANCE_MAX);
+                    let slider = slider.step_by(1.0).show_value(false).trailing_fill(false);
+                    ui.add_sized([width, text], slider)
+                        .on_disabled_hover_text(CHANNELS_BIT_PERFECT);
+                });
+                if old_mix != mix {
+                    send!(self.to_kernel, FrontendToKernel::ChannelMix(mix));
+                    // Update our local copy as well, so the
+                    // slider doesn't jump back until `Kernel` responds.
+                    self.audio_state.channel_mix = mix;
+                }
+
+                ui.add_space(40.0);
+                ui.separator();
+                ui.add_space(40.0);
+
+                //-------------------------------------------------- Shuffle Mode.
+                // Heading.
+                let label = Label::new(
+                    RichText::new("Shuffle Mode")
+                        .color(BONE)
+                        .text_style(TextStyle::Heading),
+                );
+                ui.add_sized([width, text], label)
+                    .on_hover_text(SHUFFLE_MODE);
+
+                // ComboBox.
+                ui.add_space(10.0);
+                ComboBox::from_id_source("settings_shuffle_mode")
+                    .selected_text(RichText::new(self.settings.shuffle.human()).color(BONE))
+                    .show_ui(ui, |ui| {
+                        for i in ShuffleMode::iter() {
+                            ui.selectable_value(&mut self.settings.shuffle, i, i.human());
+                        }
+                    });
+
+                ui.add_space(40.0);
+                ui.separator();
+                ui.add_space(40.0);
+
+                //-------------------------------------------------- Resampler Quality.
+                // Only Windows resamples, Linux/macOS always
+                // play the native sample rate, so hide it there.
+                if cfg!(windows) {
+                    // Heading.
+                    let label = Label::new(
+                        RichText::new("Resampler Quality")
+                            .color(BONE)
+                            .text_style(TextStyle::Heading),
+                    );
+                    ui.add_sized([width, text], label)
+                        .on_hover_text(RESAMPLER_QUALITY);
+
+                    // ComboBox.
+                    ui.add_space(10.0);
+                    let old_quality = self.settings.resampler_quality;
+                    ComboBox::from_id_source("settings_resampler_quality")
+                        .selected_text(
+                            RichText::new(self.settings.resampler_quality.human()).color(BONE),
+                        )
+                        .show_ui(ui, |ui| {
+                            for i in ResamplerQuality::iter() {
+                                ui.selectable_value(
+                                    &mut self.settings.resampler_quality,
+                                    i,
+                                    i.human(),
+                                );
+                            }
+                        });
+                    if old_quality != self.settings.resampler_quality {
+                        atomic_store!(
+                            shukusai::audio::RESAMPLER_QUALITY,
+                            self.settings.resampler_quality as u8
+                        );
+                    }
+
+                    ui.add_space(40.0);
+                    ui.separator();
+                    ui.add_space(40.0);
+                }
+
+                //-------------------------------------------------- Bit-Perfect.
+                // Heading.
+                let label = Label::new(
+                    RichText::new("Bit-Perfect")
+                        .color(BONE)
+                        .text_style(TextStyle::Heading),
+                );
+                ui.add_sized([width, text], label)
+                    .on_hover_text(BIT_PERFECT);
+
+                // SelectableLabel.
+                ui.add_space(10.0);
+                let old_bit_perfect = self.settings.bit_perfect;
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        let width = (width / 2.0) - 25.0;
+                        if ui
+                            .add_sized(
+                                [width, text],
+                                SelectableLabel::new(self.settings.bit_perfect, "Yes"),
+                            )
+                            .clicked()
+                        {
+                            flip!(self.settings.bit_perfect);
+                        }
+                        ui.separator();
+                        if ui
+                            .add_sized(
+                                [width, text],
+                                SelectableLabel::new(!self.settings.bit_perfect, "No"),
+                            )
+                            .clicked()
+                        {
+                            flip!(self.settings.bit_perfect);
+                        }
+                    })
+                });
+                if old_bit_perfect != self.settings.bit_perfect {
+                    atomic_store!(shukusai::audio::BIT_PERFECT, self.settings.bit_perfect);
+                }
+
+                ui.add_space(40.0);
+                ui.separator();
+                ui.add_space(40.0);
+
+                //-------------------------------------------------- Volume Curve.
+                // Heading.
+                let label = Label::new(
+                    RichText::new("Volume Curve")
+                        .color(BONE)
+                        .text_style(TextStyle::Heading),
+                );
+                ui.add_sized([width, text], label)
+                    .on_hover_text(VOLUME_DB_CURVE);
+
+                // SelectableLabel.
+                ui.add_space(10.0);
+                let old_volume_db_curve = self.settings.volume_db_curve;
+                ui.group(|ui| {
+                    // Volume does nothing with bit-perfect output.
+                    ui.set_enabled(!self.settings.bit_perfect);
+                    ui.horizontal(|ui| {
+                        let width = (width / 2.0) - 25.0;
+                        if ui
+                            .add_sized(
+                                [width, text],
+                                SelectableLabel::new(self.settings.volume_db_curve, "Decibel"),
+                            )
+                            .on_disabled_hover_text(CHANNELS_BIT_PERFECT)
+                            .clicked()
+                        {
+                            flip!(self.settings.volume_db_curve);
+                        }
+                        ui.separator();
+                        if ui
+                            .add_sized(
+                                [width, text],
+                                SelectableLabel::new(!self.settings.volume_db_curve, "Linear"),
+                            )
+                            .on_disabled_hover_text(CHANNELS_BIT_PERFECT)
+                            .clicked()
+                        {
+                            flip!(self.settings.volume_db_curve);
+                        }
+                    })
+                });
+                if old_volume_db_curve != self.settings.volume_db_curve {
+                    atomic_store!(
+                        shukusai::audio::VOLUME_DB_CURVE,
+                        self.settings.volume_db_curve
+                    );
+                }
+
+                ui.add_space(40.0);
+                ui.separator();
+                ui.add_space(40.0);
+
                 //-------------------------------------------------- Auto-save
                 // Heading.
                 let label = Label::new(
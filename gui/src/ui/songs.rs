@@ -1,9 +1,11 @@
 //---------------------------------------------------------------------------------------------------- Use
 use crate::constants::WHITE;
+use crate::text::SONG_SKIPS;
 use egui::{ComboBox, Label, RichText, ScrollArea, Sense};
 use egui_extras::{Column, TableBuilder};
 use log::warn;
 use readable::Unsigned;
+use shukusai::collection::{PLAY_STATS, RATINGS};
 use shukusai::sort::SongSort;
 
 //---------------------------------------------------------------------------------------------------- Songs
@@ -22,12 +24,14 @@ impl crate::data::Gui {
         let width = ui.available_width();
         let height = ui.available_height();
         // c == Column sizing
-        let c_width = (width / 10.0) - 10.0; // Account for separators, let `Path` peek a little.
+        let c_width = (width / 12.0) - 10.0; // Account for separators, let `Path` peek a little.
         let c_title = c_width * 2.5;
         let c_album = c_width * 2.5;
         let c_artist = c_width;
         let c_release = c_width;
         let c_runtime = c_width;
+        let c_plays = c_width;
+        let c_rating = c_width;
         let c_track = c_width;
         let c_disc = c_width;
 
@@ -76,6 +80,8 @@ impl crate::data::Gui {
                     .column(Column::initial(c_artist).resizable(true).clip(true))
                     .column(Column::initial(c_release).resizable(true).clip(true))
                     .column(Column::initial(c_runtime).resizable(true).clip(true))
+                    .column(Column::initial(c_plays).resizable(true).clip(true))
+                    .column(Column::initial(c_rating).resizable(true).clip(true))
                     .column(Column::initial(c_track).resizable(true).clip(true))
                     .column(Column::initial(c_disc).resizable(true).clip(true))
                     .column(Column::remainder().clip(true))
@@ -212,6 +218,51 @@ impl crate::data::Gui {
                                 });
                         });
 
+                        // Plays.
+                        header.col(|ui| {
+                            const SORT: [SongSort; 4] =
+                                [PlayCount, PlayCountRev, LastPlayed, LastPlayedRev];
+
+                            ComboBox::from_id_source("__song_sort_plays")
+                                .selected_text(RichText::new("Plays").color(WHITE))
+                                .width(ui.available_width() - 5.0)
+                                .show_ui(ui, |ui| {
+                                    for i in SORT {
+                                        if ui
+                                            .selectable_label(
+                                                self.settings.song_sort == i,
+                                                i.human(),
+                                            )
+                                            .clicked()
+                                        {
+                                            self.settings.song_sort = i;
+                                        }
+                                    }
+                                });
+                        });
+
+                        // Rating.
+                        header.col(|ui| {
+                            const SORT: [SongSort; 2] = [Rating, RatingRev];
+
+                            ComboBox::from_id_source("__song_sort_rating")
+                                .selected_text(RichText::new("Rating").color(WHITE))
+                                .width(ui.available_width() - 5.0)
+                                .show_ui(ui, |ui| {
+                                    for i in SORT {
+                                        if ui
+                                            .selectable_label(
+                                                self.settings.song_sort == i,
+                                                i.human(),
+                                            )
+                                            .clicked()
+                                        {
+                                            self.settings.song_sort = i;
+                                        }
+                                    }
+                                });
+                        });
+
                         header.col(|ui| {
                             ui.strong("Track");
                         });
@@ -224,7 +275,10 @@ impl crate::data::Gui {
                     })
                     .body(|mut body| {
                         // Song iterator.
-                        for key in self.collection.song_iter(self.settings.song_sort) {
+                        let song_iter = self.collection.song_iter(self.settings.song_sort);
+                        let play_stats = PLAY_STATS.read();
+                        let ratings = RATINGS.read();
+                        for key in song_iter {
                             body.row(ROW_HEIGHT, |mut row| {
                                 let (artist, album, song) = self.collection.walk(key);
 
@@ -233,7 +287,7 @@ impl crate::data::Gui {
                                         self,
                                         song,
                                         album,
-                                        *key,
+                                        key,
                                         ui,
                                         Label::new(&*song.title)
                                     );
@@ -265,6 +319,27 @@ impl crate::data::Gui {
                                 row.col(|ui| {
                                     ui.label(song.runtime.as_str());
                                 });
+                                match play_stats.get(&song.path) {
+                                    Some(s) => row.col(|ui| {
+                                        ui.label(Unsigned::from(s.play_count).as_str())
+                                            .on_hover_text(format!(
+                                                "{SONG_SKIPS}: {}",
+                                                s.skip_count
+                                            ));
+                                    }),
+                                    None => row.col(|ui| {
+                                        ui.label("0");
+                                    }),
+                                };
+                                row.col(|ui| {
+                                    crate::rating!(
+                                        self,
+                                        ui,
+                                        ratings.get(&song.path),
+                                        RateSong,
+                                        key
+                                    );
+                                });
 
                                 match song.track {
                                     Some(t) => row.col(|ui| {
@@ -1,13 +1,14 @@
 //---------------------------------------------------------------------------------------------------- Use
 use crate::constants::{
     BLACK, BONE, RUNTIME_WIDTH, SLIDER_CIRCLE_ACTIVE, SLIDER_CIRCLE_HOVERED,
-    SLIDER_CIRCLE_INACTIVE, UI_CONTROL_WIDTH,
+    SLIDER_CIRCLE_INACTIVE, UI_CONTROL_WIDTH, YELLOW,
 };
 use crate::data::Gui;
 use crate::data::{KeyPress, Tab, ALPHANUMERIC_KEY, EXIT_COUNTDOWN, SHOULD_EXIT};
 use crate::text::{
-    COLLECTION_LOADING, COLLECTION_RESETTING, DRAG_AND_DROP, EMPTY_COLLECTION, MOD, UI_FORWARDS,
-    UI_PAUSE, UI_PLAY, UI_PREVIOUS,
+    COLLECTION_DIFF, COLLECTION_DIFF_DISMISS, COLLECTION_LOADING, COLLECTION_RESETTING,
+    DRAG_AND_DROP, EMPTY_COLLECTION, MOD, PLAYLIST_REPAIR_APPLY, PLAYLIST_REPAIR_SKIP, UI_FORWARDS,
+    UI_PAUSE, UI_PLAY, UI_PREVIOUS, UI_X,
 };
 use benri::{debug_panic, flip, log::*, sync::*, time::*};
 use disk::Plain;
@@ -20,7 +21,7 @@ use egui::{
 use log::{debug, error, info, warn};
 use readable::HeadTail;
 use shukusai::{
-    collection::KeyEnum,
+    collection::{KeyEnum, CHAPTERS},
     constants::FESTIVAL,
     kernel::{FrontendToKernel, KernelToFrontend},
     state::{Entry, AUDIO_STATE, PLAYLISTS, RESET_STATE},
@@ -179,6 +180,7 @@ impl Gui {
                 use KernelToFrontend::*;
                 match msg {
                     NewCollection(collection) => self.new_collection(collection),
+                    CollectionDiff(diff) => self.new_collection_diff(diff),
                     Failed((old_collection, err)) => {
                         warn!("GUI - New Collection error: {err}");
                         crate::toast_err!(self, format!("New Collection error: {err}"));
@@ -208,6 +210,14 @@ impl Gui {
                             format!("Audio file error: {err} | Song: {}", song.title)
                         );
                     }
+                    DecodeError((key, err)) => {
+                        let song = &self.collection.songs[key];
+                        warn!("GUI - Audio decode error: {err} | Song: {song:#?}");
+                        crate::toast_err!(
+                            self,
+                            format!("Audio decode error: {err} | Song: {}", song.title)
+                        );
+                    }
 
                     // These should never be received here.
                     DropCollection => debug_panic!("incorrect gui recv(): `DropCollection`"),
@@ -434,6 +444,7 @@ impl Gui {
                 } else if input.consume_key(Modifiers::NONE, Key::Escape) {
                     self.debug_screen = false;
                     self.playlist_add_screen = None;
+                    self.playlist_repair = None;
                 // Check for [A-Za-z0-9] (Search)
                 } else if input.consume_key(Modifiers::NONE, Key::Space) {
                     send!(self.to_kernel, FrontendToKernel::Toggle);
@@ -515,6 +526,23 @@ impl Gui {
             }
         }
 
+        // Check if `RFD` thread picked a playlist to import/export.
+        let import = lock!(self.rfd_playlist_import).take();
+        if let Some(p) = import {
+            self.import_playlist_file(&p);
+        }
+        let export = lock!(self.rfd_playlist_export).take();
+        if let Some((name, p)) = export {
+            self.export_playlist_file(&name, &p);
+        }
+        let art = lock!(self.rfd_playlist_art).take();
+        if let Some((name, p)) = art {
+            if let Some(metadata) = PLAYLISTS.write().metadata_mut(&name) {
+                metadata.art = Some(p);
+                metadata.modified = benri::unix!();
+            }
+        }
+
         // Show full-screen debug screen if `true`.
         if self.debug_screen {
             self.show_debug_screen(ctx, width, height);
@@ -527,6 +555,12 @@ impl Gui {
             return;
         }
 
+        // Show full-screen playlist repair screen if `true`.
+        if self.playlist_repair.is_some() {
+            self.show_playlist_repair_screen(ctx, width, height);
+            return;
+        }
+
         // Size definitions of the major UI panels.
         let bottom_panel_height = (height / 15.0).clamp(50.0, 60.0);
         let side_panel_width = (width / 8.0).clamp(125.0, 250.0);
@@ -645,6 +679,33 @@ impl Gui {
                         .show_value(false),
                     );
 
+                    // Chapter ticks, hovering shows the current chapter.
+                    let resp = match self.audio_state.song {
+                        Some(key) => {
+                            let chapters = CHAPTERS.read();
+                            let chapters = chapters.song(key, &self.collection);
+                            let runtime = self.audio_state.runtime.inner();
+                            if runtime != 0 {
+                                let rect = resp.rect;
+                                // The slider's rail is inset by its handle's radius.
+                                let radius = rect.height() / 2.5;
+                                let (left, right) = (rect.left() + radius, rect.right() - radius);
+                                let y = rect.center().y;
+                                let stroke = ui.visuals().widgets.inactive.fg_stroke;
+                                for c in chapters.iter().filter(|c| c.start != 0) {
+                                    let x =
+                                        egui::lerp(left..=right, c.start as f32 / runtime as f32);
+                                    ui.painter().vline(x, (y - h)..=(y + h), stroke);
+                                }
+                            }
+                            match self.audio_state.chapter.and_then(|i| chapters.get(i)) {
+                                Some(c) if !c.title.is_empty() => resp.on_hover_text(&c.title),
+                                _ => resp,
+                            }
+                        }
+                        None => resp,
+                    };
+
                     // Only send signal if the slider was dragged + released.
                     if resp.drag_released() {
                         // If we dragged to the very last second, just skip.
@@ -685,14 +746,14 @@ impl Gui {
 
             // Size definitions of the elements within the left panel.
             let half_height = height / 2.0;
-            let tab_height = half_height / 9.0;
+            let tab_height = half_height / 10.0;
             let tab_width = width / 1.2;
 
             // Main UI
             ui.vertical_centered_justified(|ui| {
                 // Display `SelectableLabel` for each `Tab`.
                 ui.add_space(2.5);
-                for tab in Tab::iter() {
+                for tab in Tab::ALL {
                     if ui
                         .add_sized(
                             [tab_width, tab_height],
@@ -801,6 +862,11 @@ impl Gui {
                 return;
             }
 
+            // Show the "what's new" banner after a reset.
+            if self.collection_diff.is_some() && self.state.tab != Tab::Settings {
+                self.show_collection_diff(ui, width);
+            }
+
             match self.state.tab {
                 Tab::View => self.show_tab_view(ui, ctx, width, height),
                 Tab::Albums => self.show_tab_albums(ui, ctx, width, height),
@@ -808,6 +874,7 @@ impl Gui {
                 Tab::Songs => self.show_tab_songs(ui, ctx, width, height),
                 Tab::Queue => self.show_tab_queue(ui, ctx, width, height),
                 Tab::Playlists => self.show_tab_playlists(ui, ctx, width, height),
+                Tab::History => self.show_tab_history(ui, ctx, width, height),
                 Tab::Search => self.show_tab_search(ui, ctx, width, height),
                 Tab::Settings => self.show_tab_settings(ui, ctx, width, height),
             }
@@ -815,6 +882,42 @@ impl Gui {
     }
 }
 
+//---------------------------------------------------------------------------------------------------- Collection Diff
+// Shows a dismissible "what's new" banner
+// with the `CollectionDiff` of the last reset.
+impl Gui {
+    #[inline(always)]
+    fn show_collection_diff(&mut self, ui: &mut egui::Ui, width: f32) {
+        let text = match &self.collection_diff {
+            Some((_, text)) => text,
+            None => return,
+        };
+
+        let mut dismiss = false;
+        ui.horizontal(|ui| {
+            let button_width = 30.0;
+            if ui
+                .add_sized([button_width, 25.0], Button::new(UI_X))
+                .on_hover_text(COLLECTION_DIFF_DISMISS)
+                .clicked()
+            {
+                dismiss = true;
+            }
+
+            ui.add_sized(
+                [width - button_width, 25.0],
+                Label::new(RichText::new(text).color(BONE)),
+            )
+            .on_hover_text(COLLECTION_DIFF);
+        });
+        ui.separator();
+
+        if dismiss {
+            self.collection_diff = None;
+        }
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- Empty Collection
 // Shows a button that resets the `Collection` and some help text.
 // Used for tabs when the `Collection` is empty.
@@ -1013,7 +1116,8 @@ impl Gui {
                     });
 
                 if let Some((playlist_name, key)) = self.playlist_add_screen_result.take() {
-                    if let Some(playlist) = PLAYLISTS.write().get_mut(&playlist_name) {
+                    let mut playlists = PLAYLISTS.write();
+                    if let Some(playlist) = playlists.get_mut(&playlist_name) {
                         match key {
                             KeyEnum::Artist(k) => Entry::valid_from_artist(k, &self.collection)
                                 .into_iter()
@@ -1025,6 +1129,7 @@ impl Gui {
                                 playlist.push_back(Entry::valid_from_song(k, &self.collection))
                             }
                         }
+                        playlists.touch(&playlist_name);
 
                         crate::toast!(
                             self,
@@ -1039,6 +1144,122 @@ impl Gui {
     }
 }
 
+//---------------------------------------------------------------------------------------------------- Playlist repair
+// This is a fullscreen screen for reviewing the
+// playlist repairs that were not applied automatically.
+impl Gui {
+    #[inline(always)]
+    fn show_playlist_repair_screen(&mut self, ctx: &egui::Context, width: f32, height: f32) {
+        CentralPanel::default().show(ctx, |ui| {
+            self.set_visuals(ui);
+            ui.vertical_centered(|ui| {
+                let header = height / 25.0;
+
+                // Exit button.
+                ui.add_space(header);
+                if ui
+                    .add_sized([width / 1.5, header], Button::new("Exit (or press ESC)"))
+                    .clicked()
+                {
+                    self.playlist_repair = None;
+                    return;
+                }
+                ui.add_space(header);
+
+                ui.separator();
+                ui.add_space(header);
+
+                let Some(repairs) = &mut self.playlist_repair else {
+                    return;
+                };
+
+                // Header.
+                let text = RichText::new("Repair playlist entries")
+                    .size(header)
+                    .color(BONE);
+                ui.add_sized([width, header], Label::new(text));
+                ui.label("These matches were not confident enough to be applied automatically");
+
+                ui.add_space(header);
+
+                let names = |entry: &Entry| match entry {
+                    Entry::Valid {
+                        artist,
+                        album,
+                        song,
+                        ..
+                    }
+                    | Entry::Invalid {
+                        artist,
+                        album,
+                        song,
+                    } => format!("{artist} - {album} - {song}"),
+                };
+
+                // The index of the repair that was answered, and if it should be applied.
+                let mut done = None;
+
+                ScrollArea::both()
+                    .id_source("PlaylistRepairScreen")
+                    .max_width(width)
+                    .max_height(height)
+                    .auto_shrink([false; 2])
+                    .show_viewport(ui, |ui, _| {
+                        let width = ui.available_width() - 10.0;
+
+                        for (i, repair) in repairs.iter().enumerate() {
+                            ui.group(|ui| {
+                                ui.set_width(width);
+                                ui.label(format!(
+                                    "[{}] #{} ({:.0}%)",
+                                    repair.playlist,
+                                    repair.index + 1,
+                                    repair.score * 100.0,
+                                ));
+                                ui.label(RichText::new(names(&repair.from)).color(YELLOW));
+                                ui.label(RichText::new(names(&repair.to)).color(BONE));
+                                ui.horizontal(|ui| {
+                                    if ui
+                                        .button("Apply")
+                                        .on_hover_text(PLAYLIST_REPAIR_APPLY)
+                                        .clicked()
+                                    {
+                                        done = Some((i, true));
+                                    }
+                                    if ui
+                                        .button("Skip")
+                                        .on_hover_text(PLAYLIST_REPAIR_SKIP)
+                                        .clicked()
+                                    {
+                                        done = Some((i, false));
+                                    }
+                                });
+                            });
+
+                            ui.add_space(10.0);
+                        }
+                    });
+
+                if let Some((i, apply)) = done {
+                    let repair = repairs.remove(i);
+                    if let (true, Entry::Valid { key_song, .. }) = (apply, &repair.to) {
+                        // The playlist could have been removed, ignore if so.
+                        let _ = PLAYLISTS.write().repair_apply(
+                            repair.index,
+                            *key_song,
+                            repair.playlist,
+                            &self.collection,
+                        );
+                    }
+                    if repairs.is_empty() {
+                        self.playlist_repair = None;
+                    }
+                }
+            });
+        });
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- Debug screen
 // This is a fullscreen debug screen, showing
 // a bunch of useful runtime information.
@@ -9,7 +9,7 @@ use crate::{
 use egui::{
     Color32, Frame, Label, RichText, Rounding, ScrollArea, Sense, SidePanel, TextStyle, Vec2,
 };
-use shukusai::collection::AlbumKey;
+use shukusai::collection::{AlbumKey, RATINGS};
 
 //---------------------------------------------------------------------------------------------------- Main central panel.
 impl crate::data::Gui {
@@ -80,6 +80,15 @@ impl crate::data::Gui {
             // `Album` runtime.
             ui.label(album.runtime.as_str());
 
+            // `Album` rating.
+            let rating = RATINGS.read().album(album_key, &self.collection);
+            let width = ui.available_width();
+            ui.horizontal(|ui| {
+                // Roughly center the stars.
+                ui.add_space((width - 60.0) / 2.0);
+                crate::rating!(self, ui, rating, RateAlbum, album_key);
+            });
+
             ui.add_space(8.0);
         });
 
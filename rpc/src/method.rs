@@ -51,6 +51,11 @@ pub enum Method {
 	CollectionPerf,
 	CollectionHealth,
 	CollectionResourceSize,
+	CollectionDuplicates,
+	CollectionMistags,
+	CollectionExport,
+	CollectionDiffLast,
+	CollectionRecent,
 
 	DaemonConfig,
 	DaemonMethods,
@@ -71,6 +76,7 @@ pub enum Method {
 	StateRepeat,
 	StateRuntime,
 	StateVolume,
+	StateSleepTimer,
 
 	// Key (exact key)
 	KeyArtist,
@@ -126,15 +132,21 @@ pub enum Method {
 	Next,
 	Stop,
 	Previous,
+	NextChapter,
+	PreviousChapter,
 	Clear,
 	Seek,
 	Skip,
 	Back,
 	Shuffle,
 	Repeat,
+	AutoDj,
+	SleepTimer,
 	Volume,
 	VolumeUp,
 	VolumeDown,
+	VolumeDb,
+	ChannelMix,
 
 	QueueAddKeyArtist,
 	QueueAddKeyAlbum,
@@ -149,6 +161,12 @@ pub enum Method {
 	QueueAddPlaylist,
 	QueueSetIndex,
 	QueueRemoveRange,
+	QueueMove,
+	QueueInsert,
+	QueueSave,
+	QueueRestore,
+	QueueSnapshots,
+	QueueSnapshotDelete,
 
 	// Playlists.
 	PlaylistNew,
@@ -165,6 +183,36 @@ pub enum Method {
 	PlaylistSingle,
 	PlaylistBrief,
 	PlaylistFull,
+	PlaylistImport,
+	PlaylistExport,
+	PlaylistRename,
+	PlaylistMoveIndex,
+	PlaylistInsertKeys,
+	PlaylistDedup,
+	PlaylistSort,
+	PlaylistRepair,
+	PlaylistRepairApply,
+	PlaylistMetadataSet,
+
+	// Smart Playlists.
+	SmartPlaylistSet,
+	SmartPlaylistRemove,
+	SmartPlaylistRefresh,
+	SmartPlaylistFull,
+
+	// History.
+	History,
+
+	// Ratings.
+	RateSong,
+	RateAlbum,
+	Favorites,
+
+	// Resume.
+	ResumePolicy,
+
+	// Audio errors.
+	AudioErrors,
  }
 
 impl Method {
@@ -196,6 +244,11 @@ pub enum Rpc {
 	CollectionPerf(crate::param::CollectionPerf),
 	CollectionHealth(crate::param::CollectionHealth),
 	CollectionResourceSize(crate::param::CollectionResourceSize),
+	CollectionDuplicates(crate::param::CollectionDuplicates),
+	CollectionMistags(crate::param::CollectionMistags),
+	CollectionExport(crate::param::CollectionExport),
+	CollectionDiffLast(crate::param::CollectionDiffLast),
+	CollectionRecent(crate::param::CollectionRecent),
 
 	DaemonConfig(crate::param::DaemonConfig),
 	DaemonMethods(crate::param::DaemonMethods),
@@ -215,6 +268,7 @@ pub enum Rpc {
 	StateRepeat(crate::param::StateRepeat),
 	StateRuntime(crate::param::StateRuntime),
 	StateVolume(crate::param::StateVolume),
+	StateSleepTimer(crate::param::StateSleepTimer),
 
 	KeyArtist(crate::param::KeyArtist),
 	KeyAlbum(crate::param::KeyAlbum),
@@ -264,15 +318,21 @@ pub enum Rpc {
 	Next(crate::param::Next),
 	Stop(crate::param::Stop),
 	Previous(crate::param::Previous),
+	NextChapter(crate::param::NextChapter),
+	PreviousChapter(crate::param::PreviousChapter),
 	Clear(crate::param::Clear),
 	Seek(crate::param::Seek),
 	Skip(crate::param::Skip),
 	Back(crate::param::Back),
 	Shuffle(crate::param::Shuffle),
 	Repeat(crate::param::Repeat),
+	AutoDj(crate::param::AutoDj),
+	SleepTimer(crate::param::SleepTimer),
 	Volume(crate::param::Volume),
 	VolumeUp(crate::param::VolumeUp),
 	VolumeDown(crate::param::VolumeDown),
+	VolumeDb(crate::param::VolumeDb),
+	ChannelMix(crate::param::ChannelMix),
 
 	QueueAddKeyArtist(crate::param::QueueAddKeyArtist),
 	QueueAddKeyAlbum(crate::param::QueueAddKeyAlbum),
@@ -287,6 +347,12 @@ pub enum Rpc {
 	QueueAddPlaylist(crate::param::QueueAddPlaylistOwned),
 	QueueSetIndex(crate::param::QueueSetIndex),
 	QueueRemoveRange(crate::param::QueueRemoveRange),
+	QueueMove(crate::param::QueueMove),
+	QueueInsert(crate::param::QueueInsert),
+	QueueSave(crate::param::QueueSaveOwned),
+	QueueRestore(crate::param::QueueRestoreOwned),
+	QueueSnapshots(crate::param::QueueSnapshots),
+	QueueSnapshotDelete(crate::param::QueueSnapshotDeleteOwned),
 
 	PlaylistNew(crate::param::PlaylistNewOwned),
 	PlaylistRemove(crate::param::PlaylistRemoveOwned),
@@ -302,6 +368,31 @@ pub enum Rpc {
 	PlaylistSingle(crate::param::PlaylistSingleOwned),
 	PlaylistBrief(crate::param::PlaylistBrief),
 	PlaylistFull(crate::param::PlaylistFull),
+	PlaylistImport(crate::param::PlaylistImportOwned),
+	PlaylistExport(crate::param::PlaylistExportOwned),
+	PlaylistRename(crate::param::PlaylistRenameOwned),
+	PlaylistMoveIndex(crate::param::PlaylistMoveIndexOwned),
+	PlaylistInsertKeys(crate::param::PlaylistInsertKeysOwned),
+	PlaylistDedup(crate::param::PlaylistDedupOwned),
+	PlaylistSort(crate::param::PlaylistSortOwned),
+	PlaylistRepair(crate::param::PlaylistRepairOwned),
+	PlaylistRepairApply(crate::param::PlaylistRepairApplyOwned),
+	PlaylistMetadataSet(crate::param::PlaylistMetadataSetOwned),
+
+	SmartPlaylistSet(crate::param::SmartPlaylistSetOwned),
+	SmartPlaylistRemove(crate::param::SmartPlaylistRemoveOwned),
+	SmartPlaylistRefresh(crate::param::SmartPlaylistRefreshOwned),
+	SmartPlaylistFull(crate::param::SmartPlaylistFull),
+
+	History(crate::param::History),
+
+	RateSong(crate::param::RateSong),
+	RateAlbum(crate::param::RateAlbum),
+	Favorites(crate::param::Favorites),
+
+	ResumePolicy(crate::param::ResumePolicy),
+
+	AudioErrors(crate::param::AudioErrors),
 }
 
 //---------------------------------------------------------------------------------------------------- TESTS
@@ -10,6 +10,9 @@ use shukusai::{
 	collection::{SongKey,AlbumKey,ArtistKey},
 	audio::Append2,
 	search::SearchKind,
+	sort::{SongSort,PlaylistSort},
+	export::ExportFormat,
+	state::{Rule,PlaylistFormat},
 };
 
 use crate::{
@@ -88,6 +91,39 @@ impl_rpc! {
 	"collection/collection_resource_size",
 	CollectionResourceSize => Method::CollectionResourceSize
 }
+impl_rpc! {
+	"View groups of acoustically identical Songs (requires fingerprinting)",
+	"collection/collection_duplicates",
+	CollectionDuplicates => Method::CollectionDuplicates
+}
+impl_rpc! {
+	"View groups of acoustically identical Songs with differing tags (requires fingerprinting)",
+	"collection/collection_mistags",
+	CollectionMistags => Method::CollectionMistags
+}
+impl_rpc_param! {
+	"Export every Song in the Collection as CSV, M3U8 or JSON Lines",
+	"collection/collection_export",
+	CollectionExport => Method::CollectionExport,
+	"The format to export as",
+	#[arg(value_name = "csv|m3u8|jsonl")]
+	format: ExportFormat,
+	"The order to export the Songs in, default is `lexi`",
+	#[arg(value_name = "SONG_SORT")]
+	sort: Option<SongSort>
+}
+impl_rpc! {
+	"View what was added, removed and modified in the last Collection reset",
+	"collection/collection_diff_last",
+	CollectionDiffLast => Method::CollectionDiffLast
+}
+impl_rpc_param! {
+	"Retrieve the Albums and Songs first seen since a UNIX timestamp, newest first",
+	"collection/collection_recent",
+	CollectionRecent => Method::CollectionRecent,
+	"UNIX timestamp (unsigned integer), only return items first seen at or after this",
+	since: u64
+}
 
 //---------------------------------------------------------------------------------------------------- Daemon
 impl_rpc! {
@@ -177,6 +213,11 @@ impl_rpc! {
 	"state/state_volume",
 	StateVolume => Method::StateVolume
 }
+impl_rpc! {
+	"Retrieve the currently set sleep timer",
+	"state/state_sleep_timer",
+	StateSleepTimer => Method::StateSleepTimer
+}
 
 //---------------------------------------------------------------------------------------------------- Key
 impl_rpc_param! {
@@ -502,18 +543,46 @@ impl_rpc! {
 	"playback/stop",
 	Stop => Method::Stop
 }
-impl_rpc! {
+impl_rpc_param! {
 	"Shuffle the current queue, then start playing from the 1st Song in the queue",
 	"playback/shuffle",
-	Shuffle => Method::Shuffle
+	Shuffle => Method::Shuffle,
+	"The shuffle mode to use.",
+	#[arg(value_name = "songs|albums|spread|weighted")]
+	mode: Option<shukusai::audio::ShuffleMode>
 }
 impl_rpc_param! {
 	"Set a repeat mode",
 	"playback/repeat",
 	Repeat => Method::Repeat,
 	"The repeat mode to set.",
-	#[arg(value_name = "off|song|queue")]
-	mode: shukusai::audio::Repeat
+	#[arg(value_name = "off|song|queue|queue_pause|album|artist|count|ab")]
+	mode: shukusai::audio::Repeat,
+	"If `count` was picked, repeat the current song this many more times.",
+	count: Option<u8>,
+	"If `ab` was picked, the second the loop starts at.",
+	a: Option<u32>,
+	"If `ab` was picked, the second the loop jumps back to `a` at.",
+	b: Option<u32>
+}
+impl_rpc_param! {
+	"Turn Auto-DJ on/off",
+	"playback/auto_dj",
+	AutoDj => Method::AutoDj,
+	"Should related Songs be appended when the queue ends?",
+	on: bool
+}
+impl_rpc_param! {
+	"Set a sleep timer that pauses playback",
+	"playback/sleep_timer",
+	SleepTimer => Method::SleepTimer,
+	"The sleep timer to set.",
+	#[arg(value_name = "off|time|song|album|queue")]
+	mode: shukusai::audio::SleepTimer,
+	"If `time` was picked, pause after this many minutes from now.",
+	minutes: Option<u32>,
+	"If `time` was picked, fade out the volume over this many seconds before pausing.",
+	fade: Option<u32>
 }
 impl_rpc_param! {
 	"Set the current Song to the previous in the queue",
@@ -523,6 +592,16 @@ impl_rpc_param! {
 	#[arg(value_name = "SECONDS")]
 	threshold: Option<u32>
 }
+impl_rpc! {
+	"Skip to the next chapter of the current Song",
+	"playback/next_chapter",
+	NextChapter => Method::NextChapter
+}
+impl_rpc! {
+	"Go back to the previous chapter of the current Song",
+	"playback/previous_chapter",
+	PreviousChapter => Method::PreviousChapter
+}
 impl_rpc_param! {
 	"Set the playback volume",
 	"playback/volume",
@@ -535,17 +614,37 @@ impl_rpc_param! {
 	"Raise the playback volume",
 	"playback/volume_up",
 	VolumeUp => Method::VolumeUp,
-	"The number to raise the volume by. Must be in-between 0..100.",
+	"The number to raise the volume by. Must be in-between 0..100. If `null`, the `volume_step` config option is used.",
 	#[arg(value_parser = clap::value_parser!(u8).range(0..=100))]
-	up: u8
+	up: Option<u8>
 }
 impl_rpc_param! {
 	"Lower the playback volume",
 	"playback/volume_down",
 	VolumeDown => Method::VolumeDown,
-	"The number to lower the volume by. Must be in-between 0..100.",
+	"The number to lower the volume by. Must be in-between 0..100. If `null`, the `volume_step` config option is used.",
 	#[arg(value_parser = clap::value_parser!(u8).range(0..=100))]
-	down: u8
+	down: Option<u8>
+}
+impl_rpc_param! {
+	"Set the playback volume in decibels",
+	"playback/volume_db",
+	VolumeDb => Method::VolumeDb,
+	"The dB to play at, following the volume curve. 0.0 is full volume.",
+	#[arg(allow_negative_numbers = true)]
+	db: f32
+}
+impl_rpc_param! {
+	"Set mono downmix, left/right balance and channel swap",
+	"playback/channel_mix",
+	ChannelMix => Method::ChannelMix,
+	"Play the same (down-mixed) audio out of both channels. `null` leaves this as-is.",
+	mono: Option<bool>,
+	"The left/right balance. Must be in-between -100..100. `null` leaves this as-is.",
+	#[arg(allow_negative_numbers = true, value_parser = clap::value_parser!(i8).range(-100..=100))]
+	balance: Option<i8>,
+	"Swap the left and right channels. `null` leaves this as-is.",
+	swap: Option<bool>
 }
 impl_rpc_param! {
 	"Clear the queue",
@@ -559,9 +658,9 @@ impl_rpc_param! {
 	"playback/seek",
 	Seek => Method::Seek,
 	r#"The "type" of seeking we should do"#,
-	#[arg(value_name = "forward|backward|absolute")]
+	#[arg(value_name = "forward|backward|absolute|next_chapter|previous_chapter")]
 	kind: shukusai::audio::Seek,
-	"The second to seek forward/backwards/to",
+	"The second to seek forward/backwards/to (ignored for chapters)",
 	second: u64
 }
 impl_rpc_param! {
@@ -707,7 +806,10 @@ impl_rpc_param! {
 	"If the `index` append option was picked, this will be index used",
 	index: Option<usize>,
 	"Should we start at an offset within the Artist?",
-	offset: Option<usize>
+	offset: Option<usize>,
+	"How should the random pick be made?",
+	#[arg(value_name = "songs|albums|spread|weighted")]
+	mode: Option<shukusai::audio::ShuffleMode>
 }
 impl_rpc_param! {
 	"Add a random Album to the queue",
@@ -723,7 +825,10 @@ impl_rpc_param! {
 	"If the `index` append option was picked, this will be index used",
 	index: Option<usize>,
 	"Should we start at an offset within the Album?",
-	offset: Option<usize>
+	offset: Option<usize>,
+	"How should the random pick be made?",
+	#[arg(value_name = "songs|albums|spread|weighted")]
+	mode: Option<shukusai::audio::ShuffleMode>
 }
 impl_rpc_param! {
 	"Add a random Song to the queue",
@@ -737,7 +842,10 @@ impl_rpc_param! {
 	"Should we start playing?",
 	play: bool,
 	"If the `index` append option was picked, this will be index used",
-	index: Option<usize>
+	index: Option<usize>,
+	"How should the random pick be made?",
+	#[arg(value_name = "songs|albums|spread|weighted")]
+	mode: Option<shukusai::audio::ShuffleMode>
 }
 impl_rpc_param! {
 	"Add a random Song to the queue, receive it back in Entry form",
@@ -751,7 +859,10 @@ impl_rpc_param! {
 	"Should we start playing?",
 	play: bool,
 	"If the `index` append option was picked, this will be index used",
-	index: Option<usize>
+	index: Option<usize>,
+	"How should the random pick be made?",
+	#[arg(value_name = "songs|albums|spread|weighted")]
+	mode: Option<shukusai::audio::ShuffleMode>
 }
 impl_struct_lt!(QueueAddPlaylist, #[serde(borrow)] playlist: Cow<'a, str>, append: Append2, clear: bool, play: bool, index: Option<usize>, offset: Option<usize>);
 impl_rpc_param! {
@@ -790,6 +901,53 @@ impl_rpc_param! {
 	"This flag will skip to the next song if the range includes the current one",
 	skip: bool
 }
+impl_rpc_param! {
+	"Move a Song in the queue to another index",
+	"queue/queue_move",
+	QueueMove => Method::QueueMove,
+	"The index of the Song to move",
+	from: usize,
+	"The index to move it to",
+	to: usize
+}
+impl_rpc_param! {
+	"Insert Song(s) into the queue at an index",
+	"queue/queue_insert",
+	QueueInsert => Method::QueueInsert,
+	"The index to insert at, the queue length will append",
+	index: usize,
+	"Song key(s) to insert, to insert multiple, use this flag per key",
+	keys: Vec<usize>
+}
+impl_struct_lt!(QueueSave, #[serde(borrow)] name: Cow<'a, str>);
+impl_rpc_param! {
+	"Save the current queue as a named snapshot",
+	"queue/queue_save",
+	QueueSaveOwned => Method::QueueSave,
+	"The name of the snapshot, an existing one will be overwritten",
+	name: String
+}
+impl_struct_lt!(QueueRestore, #[serde(borrow)] name: Cow<'a, str>);
+impl_rpc_param! {
+	"Replace the queue with a named snapshot",
+	"queue/queue_restore",
+	QueueRestoreOwned => Method::QueueRestore,
+	"The name of the snapshot",
+	name: String
+}
+impl_rpc! {
+	"Retrieve all queue snapshots",
+	"queue/queue_snapshots",
+	QueueSnapshots => Method::QueueSnapshots
+}
+impl_struct_lt!(QueueSnapshotDelete, #[serde(borrow)] name: Cow<'a, str>);
+impl_rpc_param! {
+	"Delete a named queue snapshot",
+	"queue/queue_snapshot_delete",
+	QueueSnapshotDeleteOwned => Method::QueueSnapshotDelete,
+	"The name of the snapshot",
+	name: String
+}
 
 //---------------------------------------------------------------------------------------------------- Playlists
 impl_struct_lt!(PlaylistNew, #[serde(borrow)] playlist: Cow<'a, str>);
@@ -952,6 +1110,222 @@ impl_rpc! {
 	"playlist/playlist_full",
 	PlaylistFull => Method::PlaylistFull
 }
+impl_struct_lt!(PlaylistImport, #[serde(borrow)] playlist: Cow<'a, str>, path: PathBuf, format: Option<PlaylistFormat>);
+impl_rpc_param! {
+	"Import a M3U/M3U8, PLS or XSPF playlist file as a playlist",
+	"playlist/playlist_import",
+	PlaylistImportOwned => Method::PlaylistImport,
+	"The name of the playlist to create (or overwrite)",
+	playlist: String,
+	"The filesystem PATH of the playlist file, on the computer `festivald` is on",
+	path: PathBuf,
+	"The playlist file format, this is guessed from the PATH's extension if not provided",
+	#[arg(value_name = "m3u8|pls|xspf")]
+	format: Option<PlaylistFormat>
+}
+impl_struct_lt!(PlaylistExport, #[serde(borrow)] playlist: Cow<'a, str>, format: PlaylistFormat, relative_to: Option<PathBuf>);
+impl_rpc_param! {
+	"Export a playlist as a M3U8, PLS or XSPF playlist file",
+	"playlist/playlist_export",
+	PlaylistExportOwned => Method::PlaylistExport,
+	"The name of the playlist",
+	playlist: String,
+	"The playlist file format",
+	#[arg(value_name = "m3u8|pls|xspf")]
+	format: PlaylistFormat,
+	"Write Song PATHs relative to this directory instead of absolute",
+	relative_to: Option<PathBuf>
+}
+impl_struct_lt!(PlaylistRename, #[serde(borrow)] from: Cow<'a, str>, to: Cow<'a, str>);
+impl_rpc_param! {
+	"Rename a playlist",
+	"playlist/playlist_rename",
+	PlaylistRenameOwned => Method::PlaylistRename,
+	"The name of the playlist to rename",
+	from: String,
+	"The new name of the playlist",
+	to: String
+}
+impl_struct_lt!(PlaylistMoveIndex, #[serde(borrow)] playlist: Cow<'a, str>, from: usize, to: usize);
+impl_rpc_param! {
+	"Move a Playlist Entry in a Playlist from one index to another",
+	"playlist/playlist_move_index",
+	PlaylistMoveIndexOwned => Method::PlaylistMoveIndex,
+	"The name of the playlist",
+	playlist: String,
+	"The index of the entry to move",
+	from: usize,
+	"The index to move it to",
+	to: usize
+}
+impl_struct_lt!(PlaylistInsertKeys, #[serde(borrow)] playlist: Cow<'a, str>, index: usize, keys: Vec<usize>);
+impl_rpc_param! {
+	"Insert Song(s) into a playlist at an index",
+	"playlist/playlist_insert_keys",
+	PlaylistInsertKeysOwned => Method::PlaylistInsertKeys,
+	"The name of the playlist",
+	playlist: String,
+	"The index to insert at, the playlist length will append",
+	index: usize,
+	"Song key(s) to insert, to insert multiple, use this flag per key",
+	keys: Vec<usize>
+}
+impl_struct_lt!(PlaylistDedup, #[serde(borrow)] playlist: Cow<'a, str>);
+impl_rpc_param! {
+	"Remove duplicate Playlist Entries in a Playlist",
+	"playlist/playlist_dedup",
+	PlaylistDedupOwned => Method::PlaylistDedup,
+	"The name of the playlist",
+	playlist: String
+}
+impl_struct_lt!(PlaylistSort, #[serde(borrow)] playlist: Cow<'a, str>, sort: PlaylistSort);
+impl_rpc_param! {
+	"Sort the Playlist Entries in a Playlist",
+	"playlist/playlist_sort",
+	PlaylistSortOwned => Method::PlaylistSort,
+	"The name of the playlist",
+	playlist: String,
+	"The order to sort the entries in",
+	#[arg(value_name = "PLAYLIST_SORT")]
+	sort: PlaylistSort
+}
+impl_struct_lt!(PlaylistRepair, #[serde(borrow)] playlist: Option<Cow<'a, str>>, threshold: Option<u8>);
+impl_rpc_param! {
+	"Find the most similar Song for invalid Playlist Entries, and replace the confident matches",
+	"playlist/playlist_repair",
+	PlaylistRepairOwned => Method::PlaylistRepair,
+	"The name of the playlist to repair, all playlists are repaired if not provided",
+	playlist: Option<String>,
+	"Matches at least this confident (0-100) are applied, the default is 90",
+	threshold: Option<u8>
+}
+impl_struct_lt!(PlaylistRepairApply, #[serde(borrow)] playlist: Cow<'a, str>, index: usize, key: usize);
+impl_rpc_param! {
+	"Replace an invalid Playlist Entry with a Song, e.g. a match from `playlist_repair`",
+	"playlist/playlist_repair_apply",
+	PlaylistRepairApplyOwned => Method::PlaylistRepairApply,
+	"The name of the playlist",
+	playlist: String,
+	"The index of the invalid entry in the playlist",
+	index: usize,
+	"Song key to replace it with",
+	key: usize
+}
+impl_struct_lt!(PlaylistMetadataSet, #[serde(borrow)] playlist: Cow<'a, str>, #[serde(borrow)] description: Option<Cow<'a, str>>, #[serde(borrow)] folder: Option<Cow<'a, str>>, art: Option<PathBuf>, pinned: Option<bool>);
+impl_rpc_param! {
+	"Set the description, folder, art and pin of a Playlist, fields not provided are left as-is",
+	"playlist/playlist_metadata_set",
+	PlaylistMetadataSetOwned => Method::PlaylistMetadataSet,
+	"The name of the playlist",
+	playlist: String,
+	"The description of the playlist, an empty string removes it",
+	description: Option<String>,
+	"The `/` separated folder the playlist is in, e.g `Rock/80s`, an empty string moves it to the top-level",
+	folder: Option<String>,
+	"PATH to an image file to use as the playlist's art, an empty PATH removes it",
+	art: Option<PathBuf>,
+	"Should the playlist be shown before all others?",
+	pinned: Option<bool>
+}
+
+//---------------------------------------------------------------------------------------------------- Smart Playlists
+// Parse a single smart playlist `Rule` from JSON, for `clap`.
+fn rule(s: &str) -> Result<Rule, serde_json::Error> {
+	serde_json::from_str(s)
+}
+
+impl_struct_lt!(SmartPlaylistSet, #[serde(borrow)] playlist: Cow<'a, str>, rules: Vec<Rule>, limit: Option<usize>, sort: Option<SongSort>);
+impl_rpc_param! {
+	"Create (or overwrite) a smart playlist from rules",
+	"smart_playlist/smart_playlist_set",
+	SmartPlaylistSetOwned => Method::SmartPlaylistSet,
+	"The name of the smart playlist",
+	playlist: String,
+	"A rule as JSON, e.g: `{\"genre\":\"Rock\"}`, to use multiple rules, use this flag per rule",
+	#[arg(value_name = "JSON", value_parser = rule)]
+	rules: Vec<Rule>,
+	"The maximum amount of Songs",
+	limit: Option<usize>,
+	"The order of the Songs, default is `lexi`",
+	#[arg(value_name = "SONG_SORT")]
+	sort: Option<SongSort>
+}
+impl_struct_lt!(SmartPlaylistRemove, #[serde(borrow)] playlist: Cow<'a, str>);
+impl_rpc_param! {
+	"Remove a smart playlist's rules, its Songs are kept as a normal playlist",
+	"smart_playlist/smart_playlist_remove",
+	SmartPlaylistRemoveOwned => Method::SmartPlaylistRemove,
+	"The name of the smart playlist",
+	playlist: String
+}
+impl_struct_lt!(SmartPlaylistRefresh, #[serde(borrow)] playlist: Cow<'a, str>);
+impl_rpc_param! {
+	"Re-create a smart playlist's Songs from its rules",
+	"smart_playlist/smart_playlist_refresh",
+	SmartPlaylistRefreshOwned => Method::SmartPlaylistRefresh,
+	"The name of the smart playlist",
+	playlist: String
+}
+impl_rpc! {
+	"Retrieve the rules of all smart playlists",
+	"smart_playlist/smart_playlist_full",
+	SmartPlaylistFull => Method::SmartPlaylistFull
+}
+
+//---------------------------------------------------------------------------------------------------- History
+impl_rpc_param! {
+	"Retrieve a page of the play history, newest first",
+	"history/history",
+	History => Method::History,
+	"How many of the newest entries to skip",
+	offset: usize,
+	"The maximum amount of entries to return",
+	len: usize
+}
+
+//---------------------------------------------------------------------------------------------------- Ratings
+impl_rpc_param! {
+	"Rate a Song with a Song key",
+	"ratings/rate_song",
+	RateSong => Method::RateSong,
+	"Song key (unsigned integer)",
+	key: usize,
+	"The rating, 1-5 (higher is clamped, 0 clears the rating)",
+	rating: u8
+}
+impl_rpc_param! {
+	"Rate an Album with an Album key",
+	"ratings/rate_album",
+	RateAlbum => Method::RateAlbum,
+	"Album key (unsigned integer)",
+	key: usize,
+	"The rating, 1-5 (higher is clamped, 0 clears the rating)",
+	rating: u8
+}
+impl_rpc! {
+	"Retrieve all rated Song's and Album's, highest rated first",
+	"ratings/favorites",
+	Favorites => Method::Favorites
+}
+
+//---------------------------------------------------------------------------------------------------- Resume
+impl_rpc_param! {
+	"Set whether a Song's playback position is remembered",
+	"resume/resume_policy",
+	ResumePolicy => Method::ResumePolicy,
+	"Song key (unsigned integer)",
+	key: usize,
+	"The policy to set.",
+	#[arg(value_name = "auto|always|never")]
+	policy: shukusai::collection::ResumePolicy
+}
+
+//---------------------------------------------------------------------------------------------------- Audio errors
+impl_rpc! {
+	"Retrieve every decode/demux error recorded while playing, per Song",
+	"audio_errors/audio_errors",
+	AudioErrors => Method::AudioErrors
+}
 
 //---------------------------------------------------------------------------------------------------- TESTS
 #[cfg(test)]
@@ -1151,9 +1525,27 @@ mod tests {
 	//---------------------------------------------------------------------------------------------------- Playback
 	#[test]
 	fn repeat() {
-		t(Repeat { mode: shukusai::audio::Repeat::Off }, r#"{"mode":"off"}"#);
-		t(Repeat { mode: shukusai::audio::Repeat::Song }, r#"{"mode":"song"}"#);
-		t(Repeat { mode: shukusai::audio::Repeat::Queue }, r#"{"mode":"queue"}"#);
+		t(Repeat { mode: shukusai::audio::Repeat::Off, count: None, a: None, b: None }, r#"{"mode":"off","count":null,"a":null,"b":null}"#);
+		t(Repeat { mode: shukusai::audio::Repeat::Song, count: None, a: None, b: None }, r#"{"mode":"song","count":null,"a":null,"b":null}"#);
+		t(Repeat { mode: shukusai::audio::Repeat::Queue, count: None, a: None, b: None }, r#"{"mode":"queue","count":null,"a":null,"b":null}"#);
+		t(Repeat { mode: shukusai::audio::Repeat::Album, count: None, a: None, b: None }, r#"{"mode":"album","count":null,"a":null,"b":null}"#);
+		t(Repeat { mode: shukusai::audio::Repeat::Count(3), count: None, a: None, b: None }, r#"{"mode":{"count":3},"count":null,"a":null,"b":null}"#);
+		t(Repeat { mode: shukusai::audio::Repeat::Count(0), count: Some(3), a: None, b: None }, r#"{"mode":{"count":0},"count":3,"a":null,"b":null}"#);
+		t(Repeat { mode: shukusai::audio::Repeat::Ab { a: 30, b: 45 }, count: None, a: None, b: None }, r#"{"mode":{"ab":{"a":30,"b":45}},"count":null,"a":null,"b":null}"#);
+	}
+
+	#[test]
+	fn auto_dj() {
+		t(AutoDj { on: true }, r#"{"on":true}"#);
+		t(AutoDj { on: false }, r#"{"on":false}"#);
+	}
+
+	#[test]
+	fn sleep_timer() {
+		t(SleepTimer { mode: shukusai::audio::SleepTimer::Off, minutes: None, fade: None }, r#"{"mode":"off","minutes":null,"fade":null}"#);
+		t(SleepTimer { mode: shukusai::audio::SleepTimer::Song, minutes: None, fade: None }, r#"{"mode":"song","minutes":null,"fade":null}"#);
+		t(SleepTimer { mode: shukusai::audio::SleepTimer::Queue, minutes: None, fade: None }, r#"{"mode":"queue","minutes":null,"fade":null}"#);
+		t(SleepTimer { mode: shukusai::audio::SleepTimer::Time { at: 0, fade: 0 }, minutes: Some(30), fade: Some(10) }, r#"{"mode":{"time":{"at":0,"fade":0}},"minutes":30,"fade":10}"#);
 	}
 
 	#[test]
@@ -1163,6 +1555,13 @@ mod tests {
 		t(Previous { threshold: None },           r#"{"threshold":null}"#);
 	}
 
+	#[test]
+	fn shuffle() {
+		t(Shuffle { mode: None },                                           r#"{"mode":null}"#);
+		t(Shuffle { mode: Some(shukusai::audio::ShuffleMode::Albums) },     r#"{"mode":"albums"}"#);
+		t(Shuffle { mode: Some(shukusai::audio::ShuffleMode::Weighted) },   r#"{"mode":"weighted"}"#);
+	}
+
 	#[test]
 	fn volume() {
 		t(Volume { volume: u8::MAX }, r#"{"volume":255}"#);
@@ -1171,14 +1570,28 @@ mod tests {
 
 	#[test]
 	fn volume_up() {
-		t(VolumeUp { up: u8::MAX }, r#"{"up":255}"#);
-		t(VolumeUp { up: 0 },       r#"{"up":0}"#);
+		t(VolumeUp { up: Some(u8::MAX) }, r#"{"up":255}"#);
+		t(VolumeUp { up: Some(0) },       r#"{"up":0}"#);
+		t(VolumeUp { up: None },          r#"{"up":null}"#);
 	}
 
 	#[test]
 	fn volume_down() {
-		t(VolumeDown { down: u8::MAX }, r#"{"down":255}"#);
-		t(VolumeDown { down: 0 },       r#"{"down":0}"#);
+		t(VolumeDown { down: Some(u8::MAX) }, r#"{"down":255}"#);
+		t(VolumeDown { down: Some(0) },       r#"{"down":0}"#);
+		t(VolumeDown { down: None },          r#"{"down":null}"#);
+	}
+
+	#[test]
+	fn volume_db() {
+		t(VolumeDb { db: -6.5 }, r#"{"db":-6.5}"#);
+		t(VolumeDb { db: 0.0 },  r#"{"db":0.0}"#);
+	}
+
+	#[test]
+	fn channel_mix() {
+		t(ChannelMix { mono: Some(true), balance: Some(-100), swap: Some(false) }, r#"{"mono":true,"balance":-100,"swap":false}"#);
+		t(ChannelMix { mono: None, balance: None, swap: None },                    r#"{"mono":null,"balance":null,"swap":null}"#);
 	}
 
 	#[test]
@@ -1192,6 +1605,8 @@ mod tests {
 		t(Seek { kind: shukusai::audio::Seek::Forward, second: 0 }, r#"{"kind":"forward","second":0}"#);
 		t(Seek { kind: shukusai::audio::Seek::Backward, second: 1 }, r#"{"kind":"backward","second":1}"#);
 		t(Seek { kind: shukusai::audio::Seek::Absolute, second: u64::MAX }, r#"{"kind":"absolute","second":18446744073709551615}"#);
+		t(Seek { kind: shukusai::audio::Seek::NextChapter, second: 0 }, r#"{"kind":"next_chapter","second":0}"#);
+		t(Seek { kind: shukusai::audio::Seek::PreviousChapter, second: 0 }, r#"{"kind":"previous_chapter","second":0}"#);
 	}
 
 	#[test]
@@ -1335,14 +1750,14 @@ mod tests {
 
 	#[test]
 	fn queue_add_rand_artist() {
-		t(QueueAddRandArtist { append: shukusai::audio::Append2::Back, clear: false, play: false, index: None, offset: None },
-			r#"{"append":"back","clear":false,"play":false,"index":null,"offset":null}"#
+		t(QueueAddRandArtist { append: shukusai::audio::Append2::Back, clear: false, play: false, index: None, offset: None, mode: None },
+			r#"{"append":"back","clear":false,"play":false,"index":null,"offset":null,"mode":null}"#
 		);
-		t(QueueAddRandArtist { append: shukusai::audio::Append2::Back, clear: false, play: false, index: None, offset: Some(0) },
-			r#"{"append":"back","clear":false,"play":false,"index":null,"offset":0}"#
+		t(QueueAddRandArtist { append: shukusai::audio::Append2::Back, clear: false, play: false, index: None, offset: Some(0), mode: Some(shukusai::audio::ShuffleMode::Spread) },
+			r#"{"append":"back","clear":false,"play":false,"index":null,"offset":0,"mode":"spread"}"#
 		);
-		t(QueueAddRandArtist { append: shukusai::audio::Append2::Back, clear: true, play: true, index: None, offset: Some(1) },
-			r#"{"append":"back","clear":true,"play":true,"index":null,"offset":1}"#
+		t(QueueAddRandArtist { append: shukusai::audio::Append2::Back, clear: true, play: true, index: None, offset: Some(1), mode: Some(shukusai::audio::ShuffleMode::Weighted) },
+			r#"{"append":"back","clear":true,"play":true,"index":null,"offset":1,"mode":"weighted"}"#
 		);
 	}
 
@@ -1353,27 +1768,30 @@ mod tests {
 			clear: false,
 			play: false,
 			index: None,
-			offset: None
+			offset: None,
+			mode: None
 			},
-			r#"{"append":"back","clear":false,"play":false,"index":null,"offset":null}"#
+			r#"{"append":"back","clear":false,"play":false,"index":null,"offset":null,"mode":null}"#
 		);
 		t(QueueAddRandAlbum {
 			append: shukusai::audio::Append2::Back,
 			clear: false,
 			play: false,
 			index: None,
-			offset: Some(0)
+			offset: Some(0),
+			mode: Some(shukusai::audio::ShuffleMode::Spread)
 			},
-			r#"{"append":"back","clear":false,"play":false,"index":null,"offset":0}"#
+			r#"{"append":"back","clear":false,"play":false,"index":null,"offset":0,"mode":"spread"}"#
 		);
 		t(QueueAddRandAlbum {
 			append: shukusai::audio::Append2::Back,
 			clear: true,
 			play: true,
 			index: None,
-			offset: Some(1)
+			offset: Some(1),
+			mode: Some(shukusai::audio::ShuffleMode::Weighted)
 			},
-			r#"{"append":"back","clear":true,"play":true,"index":null,"offset":1}"#
+			r#"{"append":"back","clear":true,"play":true,"index":null,"offset":1,"mode":"weighted"}"#
 		);
 	}
 
@@ -1384,24 +1802,27 @@ mod tests {
 			clear: false,
 			play: false,
 			index: None,
+			mode: None,
 			},
-			r#"{"append":"back","clear":false,"play":false,"index":null}"#
+			r#"{"append":"back","clear":false,"play":false,"index":null,"mode":null}"#
 		);
 		t(QueueAddRandSong {
 			append: shukusai::audio::Append2::Back,
 			clear: false,
 			play: false,
 			index: None,
+			mode: Some(shukusai::audio::ShuffleMode::Spread),
 			},
-			r#"{"append":"back","clear":false,"play":false,"index":null}"#
+			r#"{"append":"back","clear":false,"play":false,"index":null,"mode":"spread"}"#
 		);
 		t(QueueAddRandSong {
 			append: shukusai::audio::Append2::Back,
 			clear: true,
 			play: true,
 			index: None,
+			mode: Some(shukusai::audio::ShuffleMode::Weighted),
 			},
-			r#"{"append":"back","clear":true,"play":true,"index":null}"#
+			r#"{"append":"back","clear":true,"play":true,"index":null,"mode":"weighted"}"#
 		);
 	}
 
@@ -1412,24 +1833,27 @@ mod tests {
 			clear: false,
 			play: false,
 			index: None,
+			mode: None,
 			},
-			r#"{"append":"back","clear":false,"play":false,"index":null}"#
+			r#"{"append":"back","clear":false,"play":false,"index":null,"mode":null}"#
 		);
 		t(QueueAddRandEntry {
 			append: shukusai::audio::Append2::Back,
 			clear: false,
 			play: false,
 			index: None,
+			mode: Some(shukusai::audio::ShuffleMode::Spread),
 			},
-			r#"{"append":"back","clear":false,"play":false,"index":null}"#
+			r#"{"append":"back","clear":false,"play":false,"index":null,"mode":"spread"}"#
 		);
 		t(QueueAddRandEntry {
 			append: shukusai::audio::Append2::Back,
 			clear: true,
 			play: true,
 			index: None,
+			mode: Some(shukusai::audio::ShuffleMode::Weighted),
 			},
-			r#"{"append":"back","clear":true,"play":true,"index":null}"#
+			r#"{"append":"back","clear":true,"play":true,"index":null,"mode":"weighted"}"#
 		);
 	}
 
@@ -1458,6 +1882,33 @@ mod tests {
 		t(QueueRemoveRange { start: 0, end: 0, skip: false },                  r#"{"start":0,"end":0,"skip":false}"#);
 	}
 
+	#[test]
+	fn queue_move() {
+		t(QueueMove { from: usize::MAX, to: usize::MAX }, r#"{"from":18446744073709551615,"to":18446744073709551615}"#);
+		t(QueueMove { from: 0, to: 0 },                   r#"{"from":0,"to":0}"#);
+	}
+
+	#[test]
+	fn queue_insert() {
+		t(QueueInsert { index: usize::MAX, keys: vec![0, 1] }, r#"{"index":18446744073709551615,"keys":[0,1]}"#);
+		t(QueueInsert { index: 0, keys: vec![] },              r#"{"index":0,"keys":[]}"#);
+	}
+
+	#[test]
+	fn queue_save() {
+		t(QueueSaveOwned { name: "hello".into() }, r#"{"name":"hello"}"#);
+	}
+
+	#[test]
+	fn queue_restore() {
+		t(QueueRestoreOwned { name: "hello".into() }, r#"{"name":"hello"}"#);
+	}
+
+	#[test]
+	fn queue_snapshot_delete() {
+		t(QueueSnapshotDeleteOwned { name: "hello".into() }, r#"{"name":"hello"}"#);
+	}
+
 	//---------------------------------------------------------------------------------------------------- Queue
 	#[test]
 	fn playlist_new() {
@@ -1566,4 +2017,137 @@ mod tests {
 	fn playlist_single() {
 		t(PlaylistSingleOwned { playlist: "hello".into() }, r#"{"playlist":"hello"}"#);
 	}
+
+	#[test]
+	fn playlist_import() {
+		t(PlaylistImportOwned { playlist: "hello".into(), path: PathBuf::from("/path.m3u"), format: None },
+			r#"{"playlist":"hello","path":"/path.m3u","format":null}"#
+		);
+		t(PlaylistImportOwned { playlist: "hello".into(), path: PathBuf::from("/path"), format: Some(PlaylistFormat::Xspf) },
+			r#"{"playlist":"hello","path":"/path","format":"xspf"}"#
+		);
+	}
+
+	#[test]
+	fn playlist_export() {
+		t(PlaylistExportOwned { playlist: "hello".into(), format: PlaylistFormat::M3u8, relative_to: None },
+			r#"{"playlist":"hello","format":"m3u8","relative_to":null}"#
+		);
+		t(PlaylistExportOwned { playlist: "hello".into(), format: PlaylistFormat::Pls, relative_to: Some(PathBuf::from("/path")) },
+			r#"{"playlist":"hello","format":"pls","relative_to":"/path"}"#
+		);
+	}
+
+	#[test]
+	fn playlist_rename() {
+		t(PlaylistRenameOwned { from: "hello".into(), to: "hello2".into() }, r#"{"from":"hello","to":"hello2"}"#);
+	}
+
+	#[test]
+	fn playlist_move_index() {
+		t(PlaylistMoveIndexOwned { playlist: "hello".into(), from: 0, to: 1 }, r#"{"playlist":"hello","from":0,"to":1}"#);
+	}
+
+	#[test]
+	fn playlist_insert_keys() {
+		t(PlaylistInsertKeysOwned { playlist: "hello".into(), index: 0, keys: vec![0, 1] }, r#"{"playlist":"hello","index":0,"keys":[0,1]}"#);
+		t(PlaylistInsertKeysOwned { playlist: "hello".into(), index: 0, keys: vec![] },     r#"{"playlist":"hello","index":0,"keys":[]}"#);
+	}
+
+	#[test]
+	fn playlist_dedup() {
+		t(PlaylistDedupOwned { playlist: "hello".into() }, r#"{"playlist":"hello"}"#);
+	}
+
+	#[test]
+	fn playlist_sort() {
+		t(PlaylistSortOwned { playlist: "hello".into(), sort: PlaylistSort::Artist },     r#"{"playlist":"hello","sort":"artist"}"#);
+		t(PlaylistSortOwned { playlist: "hello".into(), sort: PlaylistSort::RuntimeRev }, r#"{"playlist":"hello","sort":"runtime_rev"}"#);
+	}
+
+	#[test]
+	fn playlist_repair() {
+		t(PlaylistRepairOwned { playlist: Some("hello".into()), threshold: Some(80) }, r#"{"playlist":"hello","threshold":80}"#);
+		t(PlaylistRepairOwned { playlist: None, threshold: None },                     r#"{"playlist":null,"threshold":null}"#);
+	}
+
+	#[test]
+	fn playlist_repair_apply() {
+		t(PlaylistRepairApplyOwned { playlist: "hello".into(), index: 0, key: 123 }, r#"{"playlist":"hello","index":0,"key":123}"#);
+	}
+
+	#[test]
+	fn playlist_metadata_set() {
+		t(
+			PlaylistMetadataSetOwned {
+				playlist: "hello".into(),
+				description: Some("desc".into()),
+				folder: Some("Rock/80s".into()),
+				art: Some(PathBuf::from("/art.jpg")),
+				pinned: Some(true),
+			},
+			r#"{"playlist":"hello","description":"desc","folder":"Rock/80s","art":"/art.jpg","pinned":true}"#,
+		);
+		t(
+			PlaylistMetadataSetOwned { playlist: "hello".into(), description: None, folder: None, art: None, pinned: None },
+			r#"{"playlist":"hello","description":null,"folder":null,"art":null,"pinned":null}"#,
+		);
+	}
+
+	//---------------------------------------------------------------------------------------------------- Smart Playlists
+	#[test]
+	fn smart_playlist_set() {
+		t(
+			SmartPlaylistSetOwned {
+				playlist: "hello".into(),
+				rules: vec![Rule::Genre("Rock".into()), Rule::NotPlayedDays(30)],
+				limit: Some(50),
+				sort: Some(SongSort::RatingRev),
+			},
+			r#"{"playlist":"hello","rules":[{"genre":"Rock"},{"not_played_days":30}],"limit":50,"sort":"rating_rev"}"#,
+		);
+		t(
+			SmartPlaylistSetOwned { playlist: "hello".into(), rules: vec![], limit: None, sort: None },
+			r#"{"playlist":"hello","rules":[],"limit":null,"sort":null}"#,
+		);
+	}
+
+	#[test]
+	fn smart_playlist_remove() {
+		t(SmartPlaylistRemoveOwned { playlist: "hello".into() }, r#"{"playlist":"hello"}"#);
+	}
+
+	#[test]
+	fn smart_playlist_refresh() {
+		t(SmartPlaylistRefreshOwned { playlist: "hello".into() }, r#"{"playlist":"hello"}"#);
+	}
+
+	//---------------------------------------------------------------------------------------------------- History
+	#[test]
+	fn history() {
+		t(History { offset: usize::MAX, len: usize::MAX }, r#"{"offset":18446744073709551615,"len":18446744073709551615}"#);
+		t(History { offset: 0, len: 10 },                  r#"{"offset":0,"len":10}"#);
+	}
+
+	//---------------------------------------------------------------------------------------------------- Ratings
+	#[test]
+	fn rate_song() {
+		t(RateSong { key: 123, rating: 5 }, r#"{"key":123,"rating":5}"#);
+		t(RateSong { key: 0, rating: 0 },   r#"{"key":0,"rating":0}"#);
+	}
+
+	#[test]
+	fn rate_album() {
+		t(RateAlbum { key: 123, rating: 5 }, r#"{"key":123,"rating":5}"#);
+		t(RateAlbum { key: 0, rating: 0 },   r#"{"key":0,"rating":0}"#);
+	}
+
+	//---------------------------------------------------------------------------------------------------- Resume
+	#[test]
+	fn resume_policy() {
+		use shukusai::collection::ResumePolicy as R;
+		t(ResumePolicy { key: 123, policy: R::Always }, r#"{"key":123,"policy":"always"}"#);
+		t(ResumePolicy { key: 0, policy: R::Never },    r#"{"key":0,"policy":"never"}"#);
+		t(ResumePolicy { key: 0, policy: R::Auto },     r#"{"key":0,"policy":"auto"}"#);
+	}
 }
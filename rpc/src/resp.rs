@@ -15,11 +15,19 @@ use shukusai::{
 		SongKey,
 		AlbumKey,
 		ArtistKey,
+		SongRatingJson,
+		AlbumRatingJson,
 	},
 	search::SearchKind,
 	state::{
 		PlaylistsJson,
+		PlaylistMetadata,
 		EntryJson,
+		HistoryEntryJson,
+		AudioErrorsJson,
+		QueueSnapshotsJson,
+		QueueSnapshotJson,
+		SmartPlaylist,
 	},
 };
 use crate::{
@@ -36,6 +44,7 @@ use std::{
 		VecDeque,
 		HashSet,
 		BTreeSet,
+		BTreeMap,
 	},
 };
 
@@ -121,13 +130,63 @@ impl_struct_lt! {
 	song_len: usize,
 	missing_len: usize,
 	#[serde(borrow)]
-	missing: Cow<'a, [shukusai::collection::EntryJson<'a>]>
+	missing: Cow<'a, [shukusai::collection::EntryJson<'a>]>,
+	audio_error_len: usize,
+	#[serde(borrow)]
+	audio_error: Cow<'a, [shukusai::collection::EntryJson<'a>]>
 }
 impl_struct! {
 	CollectionResourceSize,
 	audio: u64,
 	art: usize
 }
+impl_struct_lt! {
+	CollectionDuplicates,
+	len: usize,
+	#[serde(borrow)]
+	groups: Cow<'a, [Vec<shukusai::collection::EntryJson<'a>>]>
+}
+impl_struct_lt! {
+	CollectionMistags,
+	len: usize,
+	#[serde(borrow)]
+	groups: Cow<'a, [Vec<shukusai::collection::EntryJson<'a>>]>
+}
+impl_struct_lt! {
+	CollectionExport,
+	format: shukusai::export::ExportFormat,
+	len: usize,
+	#[serde(borrow)]
+	data: Cow<'a, str>
+}
+impl_struct_lt! {
+	CollectionDiffLastInner,
+	timestamp_old:    u64,
+	timestamp_new:    u64,
+	artists_added:    Cow<'a, [ArtistKey]>,
+	#[serde(borrow)]
+	artists_removed:  Cow<'a, [Cow<'a, str>]>,
+	artists_modified: Cow<'a, [ArtistKey]>,
+	albums_added:     Cow<'a, [AlbumKey]>,
+	albums_removed:   Cow<'a, [shukusai::collection::RemovedAlbum]>,
+	albums_modified:  Cow<'a, [AlbumKey]>,
+	#[serde(borrow)]
+	songs_added:      Cow<'a, [shukusai::collection::EntryJson<'a>]>,
+	songs_removed:    Cow<'a, [shukusai::collection::RemovedSong]>,
+	#[serde(borrow)]
+	songs_modified:   Cow<'a, [shukusai::collection::EntryJson<'a>]>
+}
+impl_struct_anon_lt!(CollectionDiffLast, Option<CollectionDiffLastInner<'a>>);
+impl_struct_lt! {
+	CollectionRecent,
+	since:     u64,
+	album_len: usize,
+	#[serde(borrow)]
+	albums:    Cow<'a, [AlbumJson<'a>]>,
+	song_len:  usize,
+	#[serde(borrow)]
+	songs:     Cow<'a, [shukusai::collection::EntryJson<'a>]>
+}
 
 //---------------------------------------------------------------------------------------------------- Daemon
 impl_struct_lt! {
@@ -140,6 +199,9 @@ impl_struct_lt! {
 	sleep_on_fail:       Option<u64>,
 	#[serde(borrow)]
 	collection_paths:    Cow<'a, [PathBuf]>,
+	fingerprint_seconds: u32,
+	#[serde(borrow)]
+	sort_articles:       Cow<'a, [Cow<'a, str>]>,
 	tls:                 bool,
 	#[serde(borrow)]
 	certificate:         Option<Cow<'a, Path>>,
@@ -155,6 +217,15 @@ impl_struct_lt! {
 	cache_time:          u64,
 	restore_audio_state: bool,
 	previous_threshold:  u32,
+	previous_history:    bool,
+	auto_dj:             bool,
+	resume_threshold:    u32,
+	volume_db_curve:     bool,
+	volume_step:         u8,
+	limiter:             bool,
+	max_decode_errors:   u32,
+	resampler_quality:   shukusai::audio::ResamplerQuality,
+	bit_perfect:         bool,
 	media_controls:      bool,
 	authorization:       bool,
 	confirm_no_tls_auth: bool,
@@ -237,6 +308,14 @@ impl_struct_lt! {
 	runtime:   u32,
 	repeat:    shukusai::audio::Repeat,
 	volume:    u8,
+	auto_dj:   bool,
+	auto_dj_span: Option<(usize, usize)>,
+	sleep:     shukusai::audio::SleepTimer,
+	chapter:   Option<usize>,
+	chapters:  Cow<'a, [shukusai::collection::Chapter]>,
+	channel_mix: shukusai::audio::ChannelMix,
+	output_path: shukusai::audio::OutputPath,
+	output_rate: u32,
 	#[serde(borrow)]
 	song:      Option<SongJson<'a>>
 }
@@ -278,6 +357,11 @@ impl_struct! {
 	StateVolume,
 	volume: shukusai::audio::Volume
 }
+impl_struct! {
+	StateSleepTimer,
+	timer: shukusai::audio::SleepTimer,
+	remaining: Option<u64>
+}
 
 //---------------------------------------------------------------------------------------------------- Key
 impl_struct_lt! {
@@ -511,6 +595,16 @@ impl_struct! {
 	previous: shukusai::audio::Repeat,
 	current: shukusai::audio::Repeat
 }
+impl_struct! {
+	AutoDj,
+	previous: bool,
+	current: bool
+}
+impl_struct! {
+	SleepTimer,
+	previous: shukusai::audio::SleepTimer,
+	current: shukusai::audio::SleepTimer
+}
 //impl_struct_anon!(Previous, ());
 impl_struct! {
 	Volume,
@@ -527,6 +621,16 @@ impl_struct! {
 	previous: shukusai::audio::Volume,
 	current: shukusai::audio::Volume
 }
+impl_struct! {
+	VolumeDb,
+	previous: Option<f32>,
+	current: Option<f32>
+}
+impl_struct! {
+	ChannelMix,
+	previous: shukusai::audio::ChannelMix,
+	current: shukusai::audio::ChannelMix
+}
 //impl_struct_anon!(Clear, ());
 //impl_struct_anon!(Seek, ());
 //impl_struct_anon!(Skip, ());
@@ -545,6 +649,17 @@ impl_struct_lt!(QueueAddRandSong, #[serde(borrow)] song: Cow<'a, SongJson<'a>>);
 impl_struct_lt!(QueueAddRandEntry, #[serde(borrow)] entry: Cow<'a, shukusai::collection::EntryJson<'a>>);
 impl_struct!(QueueSetIndex, out_of_bounds: bool, index: usize, queue_len: usize);
 impl_struct!(QueueRemoveRange, out_of_bounds: bool, start: usize, end: usize, queue_len: usize);
+impl_struct!(QueueMove, out_of_bounds: bool, from: usize, to: usize, queue_len: usize);
+impl_struct!(QueueInsert, out_of_bounds: bool, index: usize, len: usize, queue_len: usize);
+impl_struct!(QueueSave, existed: bool, len: usize);
+impl_struct!(QueueRestore, len: usize, valid_len: usize, index: Option<usize>, elapsed: u32);
+impl_struct_lt! {
+	QueueSnapshots,
+	len: usize,
+	#[serde(borrow)]
+	snapshots: Cow<'a, QueueSnapshotsJson<'a>>
+}
+impl_struct_lt!(QueueSnapshotDelete, #[serde(borrow)] snapshot: Option<Cow<'a, QueueSnapshotJson<'a>>>);
 
 //---------------------------------------------------------------------------------------------------- Playlist
 impl_struct_lt!(PlaylistNew, len: Option<usize>, #[serde(borrow)] entries: Option<Cow<'a, [EntryJson<'a>]>>);
@@ -558,6 +673,22 @@ impl_struct!(PlaylistAddKeySong, existed: bool, old_len: usize, new_len: usize);
 impl_struct!(PlaylistAddMapArtist, existed: bool, old_len: usize, new_len: usize);
 impl_struct!(PlaylistAddMapAlbum, existed: bool, old_len: usize, new_len: usize);
 impl_struct!(PlaylistAddMapSong, existed: bool, old_len: usize, new_len: usize);
+impl_struct!(PlaylistImport, existed: bool, len: usize, valid_len: usize, invalid_len: usize);
+impl_struct_lt! {
+	PlaylistExport,
+	format: shukusai::state::PlaylistFormat,
+	len: usize,
+	#[serde(borrow)]
+	data: Cow<'a, str>
+}
+impl_struct_lt!(PlaylistRename, len: Option<usize>, #[serde(borrow)] entries: Option<Cow<'a, [EntryJson<'a>]>>);
+impl_struct!(PlaylistMoveIndex, out_of_bounds: bool, from: usize, to: usize, playlist_len: usize);
+impl_struct!(PlaylistInsertKeys, out_of_bounds: bool, index: usize, len: usize, playlist_len: usize);
+impl_struct!(PlaylistDedup, removed: usize, len: usize);
+impl_struct!(PlaylistSort, len: usize);
+impl_struct!(PlaylistRepair, applied: usize, review: usize, repairs: Vec<shukusai::state::Repair>);
+impl_struct!(PlaylistRepairApply, applied: bool);
+impl_struct!(PlaylistMetadataSet, metadata: PlaylistMetadata);
 impl_struct_lt! {
 	PlaylistSingle,
 	#[serde(borrow)]
@@ -572,7 +703,8 @@ impl_struct_lt! {
 impl_struct_lt! {
 	PlaylistBrief,
 	len: usize,
-	playlists: Cow<'a, [Cow<'a, str>]>
+	playlists: Cow<'a, [Cow<'a, str>]>,
+	metadata: BTreeMap<String, PlaylistMetadata>
 }
 impl_struct_lt! {
 	PlaylistFull,
@@ -582,7 +714,57 @@ impl_struct_lt! {
 	valid_len: usize,
 	invalid_len: usize,
 	#[serde(borrow)]
-	playlists: Cow<'a, PlaylistsJson<'a>>
+	playlists: Cow<'a, PlaylistsJson<'a>>,
+	metadata: BTreeMap<String, PlaylistMetadata>
+}
+
+//---------------------------------------------------------------------------------------------------- Smart Playlists
+impl_struct!(SmartPlaylistSet, existed: bool, len: usize);
+impl_struct!(SmartPlaylistRemove, smart_playlist: SmartPlaylist);
+impl_struct!(SmartPlaylistRefresh, len: usize);
+impl_struct! {
+	SmartPlaylistFull,
+	len: usize,
+	smart_playlists: BTreeMap<String, SmartPlaylist>
+}
+
+//---------------------------------------------------------------------------------------------------- History
+impl_struct_lt! {
+	History,
+	len: usize,
+	offset: usize,
+	entry_len: usize,
+	#[serde(borrow)]
+	entries: Cow<'a, [HistoryEntryJson<'a>]>
+}
+
+//---------------------------------------------------------------------------------------------------- Ratings
+impl_struct!(RateSong, previous: u8, rating: u8);
+impl_struct!(RateAlbum, previous: u8, rating: u8);
+impl_struct_lt! {
+	Favorites,
+	album_len: usize,
+	#[serde(borrow)]
+	albums: Cow<'a, [AlbumRatingJson<'a>]>,
+	song_len: usize,
+	#[serde(borrow)]
+	songs: Cow<'a, [SongRatingJson<'a>]>
+}
+
+//---------------------------------------------------------------------------------------------------- Resume
+impl_struct! {
+	ResumePolicy,
+	previous: shukusai::collection::ResumePolicy,
+	current: shukusai::collection::ResumePolicy
+}
+
+//---------------------------------------------------------------------------------------------------- Audio errors
+impl_struct_lt! {
+	AudioErrors,
+	len: usize,
+	error_len: usize,
+	#[serde(borrow)]
+	songs: Cow<'a, [AudioErrorsJson<'a>]>
 }
 
 //---------------------------------------------------------------------------------------------------- TESTS
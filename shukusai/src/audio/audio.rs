@@ -1,22 +1,31 @@
 //---------------------------------------------------------------------------------------------------- Use
 use crate::{
     audio::{
-        output::{AudioOutput, Output},
-        Append, AudioToKernel, KernelToAudio, Repeat, Seek, Volume,
+        auto_dj, auto_dj_recent,
+        output::{sample_format, AudioOutput, Output},
+        repeat_span, Append, AudioToKernel, ChannelMix, KernelToAudio, OutputPath, Repeat,
+        ResamplerQuality, Seek, ShuffleMode, SleepTimer, Volume, AUTO_DJ_COUNT,
+    },
+    collection::{
+        chapter_index, next_chapter, previous_chapter, AlbumKey, ArtistKey, Chapter, Collection,
+        PlayStats, SongKey, CHAPTERS, PLAY_STATS, RESUME,
+    },
+    state::{
+        AudioError, AudioErrorKind, AudioState, AUDIO_ERRORS, AUDIO_STATE, HISTORY,
+        MEDIA_CONTROLS_RAISE, MEDIA_CONTROLS_SHOULD_EXIT, PLAYLISTS, SLEEP_FADE, VOLUME,
     },
-    collection::{AlbumKey, ArtistKey, Collection, SongKey},
-    state::{AudioState, AUDIO_STATE, MEDIA_CONTROLS_RAISE, MEDIA_CONTROLS_SHOULD_EXIT, VOLUME},
 };
 use anyhow::anyhow;
 use benri::{debug_panic, flip, log::*, sleep, sync::*};
 use crossbeam::channel::{Receiver, Sender};
 use log::{debug, error, trace, warn};
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use readable::Runtime;
 use std::fs::File;
-use std::sync::atomic::AtomicU32;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8};
+use std::time::{Duration, Instant};
 use symphonia::core::{
     audio::Signal,
     codecs::{Decoder, DecoderOptions},
@@ -55,6 +64,13 @@ const RECV_TIMEOUT: Duration = Duration::from_millis(4);
 // they either are there or we break and continue with audio.
 const MSG_PROCESS_LIMIT: u8 = 6;
 
+// While playing, how often should the current
+// `Song`'s position be remembered in `RESUME`?
+//
+// It is also remembered when pausing or changing `Song`,
+// and by `Kernel` on exit, so this only matters on a crash.
+const RESUME_INTERVAL: Duration = Duration::from_secs(30);
+
 /// When receiving a `Previous` signal, there is runtime
 /// threshold for the song to reach until we reset the
 /// current instead of actually going to the previous song.
@@ -69,6 +85,77 @@ pub static PREVIOUS_THRESHOLD: AtomicU32 = AtomicU32::new(PREVIOUS_THRESHOLD_DEF
 /// The default used in [`PREVIOUS_THRESHOLD`].
 pub const PREVIOUS_THRESHOLD_DEFAULT: u32 = 3;
 
+/// Should a `Previous` signal follow the [`History`](crate::state::History)
+/// instead of the queue?
+///
+/// If `true`, `Previous` will insert the last `Song` that actually played
+/// (e.g, before a shuffle or queue clear) at the current queue index and play it.
+/// Repeated `Previous`'s keep walking back through the history.
+///
+/// [`PREVIOUS_THRESHOLD`] still applies, and if there is nothing
+/// left in the history, `Previous` falls back to the queue.
+///
+/// A `Frontend` can mutate this data, it is `false` by default.
+pub static PREVIOUS_HISTORY: AtomicBool = AtomicBool::new(false);
+
+/// Should [`crate::state::VOLUME`] follow a decibel curve instead of a linear one?
+///
+/// If `true`, [`Volume::f32_db`] is used instead of [`Volume::f32`],
+/// which spreads the same `100` steps evenly in `dB`, see [`Volume::gain`].
+///
+/// A `Frontend` can mutate this data, it is `false` by default.
+pub static VOLUME_DB_CURVE: AtomicBool = AtomicBool::new(false);
+
+/// Should a peak limiter be applied after all gain stages?
+///
+/// This prevents loud masters from clipping. It is transparent,
+/// samples that don't peak above `~-0.1dBFS` are not touched.
+///
+/// A `Frontend` can mutate this data, it is `true` by default.
+pub static LIMITER: AtomicBool = AtomicBool::new(true);
+
+/// How many corrupt packets a single `Song` can have
+/// before playback of it is given up on?
+///
+/// Packets that fail to decode are skipped, and if the demuxer
+/// fails to read the next packet, it is re-synced by seeking
+/// a second past the corrupt data. Each of these incidents is
+/// recorded in [`crate::state::AUDIO_ERRORS`].
+///
+/// Once a `Song` has more errors than this, it is skipped
+/// (or playback is paused, if it would be repeated).
+///
+/// `0` gives up on the first error.
+///
+/// A `Frontend` can mutate this data.
+pub static DECODE_ERROR_THRESHOLD: AtomicU32 = AtomicU32::new(DECODE_ERROR_THRESHOLD_DEFAULT);
+
+/// The default used in [`DECODE_ERROR_THRESHOLD`].
+pub const DECODE_ERROR_THRESHOLD_DEFAULT: u32 = 100;
+
+/// The [`ResamplerQuality`] used when the audio
+/// device doesn't support the `Song`'s sample rate.
+///
+/// This holds `ResamplerQuality as u8`, use [`ResamplerQuality::from_u8`] to read it.
+///
+/// Changes take effect the next time the audio device is re-opened.
+///
+/// A `Frontend` can mutate this data, it is [`ResamplerQuality::Balanced`] by default.
+pub static RESAMPLER_QUALITY: AtomicU8 = AtomicU8::new(ResamplerQuality::new() as u8);
+
+/// Should samples be sent to the audio device untouched?
+///
+/// If `true`, the audio device is re-opened at each `Song`'s native
+/// sample rate and sample format (if the device supports it) so that
+/// no resampling or conversion happens, see [`OutputPath::BitPerfect`].
+///
+/// Volume, [`ChannelMix`] and the [`LIMITER`] are bypassed while bit-perfect.
+/// This includes the fade-out of [`SleepTimer::Time`], playback still
+/// pauses when the timer goes off, but the volume doesn't fade beforehand.
+///
+/// A `Frontend` can mutate this data, it is `false` by default.
+pub static BIT_PERFECT: AtomicBool = AtomicBool::new(false);
+
 //---------------------------------------------------------------------------------------------------- Audio Init
 pub(crate) struct Audio {
     // A handle to the audio output device.
@@ -100,6 +187,34 @@ pub(crate) struct Audio {
     // a message, e.g, to change the volume.
     state: AudioState,
 
+    // How far back into `HISTORY` the current
+    // `Song` is, `0` is the newest entry.
+    //
+    // This is only non-zero after `Previous`
+    // followed the history, see `back_history()`.
+    history_cursor: usize,
+    // Is the current `set()` walking (or restoring) the history?
+    //
+    // If so, it should not be pushed to `HISTORY`.
+    from_history: bool,
+    // Was the current `Song`'s play (or skip)
+    // already counted in `PLAY_STATS`?
+    play_counted: bool,
+    // The chapters of the current `Song`, sorted.
+    chapters: Vec<Chapter>,
+    // How many corrupt packets the current `Song` had so far.
+    decode_errors: u32,
+    // The `Repeat::Count` that was set, every
+    // new `Song` starts counting down from this.
+    repeat_count: u8,
+    // When the current `Song`'s position was last remembered in `RESUME`.
+    resume_saved: Instant,
+    // The Auto-DJ `Song`'s picked to append when the
+    // queue runs out, and the `Song` they were picked for.
+    //
+    // See `auto_dj_prepare()`.
+    auto_dj_pick: Option<(SongKey, Vec<SongKey>)>,
+
     // OS media controls.
     //
     // This is optional for the user, but also
@@ -192,12 +307,33 @@ impl Audio {
             None => Collection::dummy(),
         };
 
+        // The output path and sleep timer saved to disk are from the last session.
+        let state = AudioState {
+            sleep: SleepTimer::Off,
+            output_path: OutputPath::None,
+            output_rate: 0,
+            ..state
+        };
+
+        let repeat_count = match state.repeat {
+            Repeat::Count(c) => c,
+            _ => 0,
+        };
+
         // Init data.
         let audio = Self {
             output,
             current: None,
             seek: None,
             state,
+            history_cursor: 0,
+            from_history: false,
+            play_counted: false,
+            chapters: vec![],
+            decode_errors: 0,
+            repeat_count,
+            resume_saved: Instant::now(),
+            auto_dj_pick: None,
             media_controls,
             from_mc,
             collection,
@@ -316,7 +452,10 @@ impl Audio {
                     ) {
                         send!(self.to_kernel, AudioToKernel::SeekError(anyhow!(e)));
                     } else {
-                        AUDIO_STATE.write().elapsed = Runtime::from(seek.seconds);
+                        let mut state = AUDIO_STATE.write();
+                        state.elapsed = Runtime::from(seek.seconds);
+                        state.chapter = chapter_index(&self.chapters, seek.seconds as u32);
+                        drop(state);
                         #[cfg(feature = "gui")]
                         gui_request_update();
                     }
@@ -336,13 +475,43 @@ impl Audio {
                     // This "end of stream" error is currently the only way
                     // a FormatReader can indicate the media is complete.
                     Err(symphonia::core::errors::Error::IoError(_err)) => {
-                        self.skip(1, &mut AUDIO_STATE.write());
+                        self.song_end();
                         #[cfg(feature = "gui")]
                         gui_request_update();
                         continue;
                     }
+                    // The tracks changed (e.g, chained streams).
+                    Err(symphonia::core::errors::Error::ResetRequired) => {
+                        decoder.reset();
+                        continue;
+                    }
+                    // The packet couldn't be read, re-sync
+                    // the demuxer by seeking past the corrupt data.
                     Err(err) => {
-                        error!("Audio - {err}");
+                        let elapsed = time.seconds;
+                        let resync = Time {
+                            seconds: elapsed + 1,
+                            frac: 0.0,
+                        };
+                        let resynced = reader
+                            .seek(
+                                symphonia::core::formats::SeekMode::Coarse,
+                                symphonia::core::formats::SeekTo::Time {
+                                    time: resync,
+                                    track_id: None,
+                                },
+                            )
+                            .is_ok();
+
+                        if resynced {
+                            decoder.reset();
+                            *time = resync;
+                        }
+
+                        error!("Audio - demux error at {elapsed}s, re-synced: {resynced}: {err}");
+                        if self.audio_error(AudioErrorKind::Demux, elapsed, &err) || !resynced {
+                            self.decode_abort(elapsed, &err);
+                        }
                         continue;
                     }
                 };
@@ -359,10 +528,18 @@ impl Audio {
                         // decoder, but the length is not.
                         let duration = decoded.capacity() as u64;
 
-                        if spec != self.output.spec || duration != self.output.duration {
+                        // The native sample format, used for bit-perfect output.
+                        let format = sample_format(&decoded);
+
+                        if spec != self.output.spec
+                            || duration != self.output.duration
+                            || self.output.outdated(format)
+                        {
                             // If the spec/duration is different, we must re-open a
                             // matching audio output device or audio will get weird.
-                            match AudioOutput::try_open(spec, duration) {
+                            //
+                            // The same goes for when the output settings changed.
+                            match AudioOutput::try_open(spec, duration, format) {
                                 Ok(o) => {
                                     drop(o);
                                     self.output.flush();
@@ -371,7 +548,7 @@ impl Audio {
                                     let old = std::mem::replace(&mut self.output, nulled);
                                     drop(old);
 
-                                    let o = AudioOutput::try_open(spec, duration)
+                                    let o = AudioOutput::try_open(spec, duration, format)
                                         .expect("we could open audio just a moment ago");
                                     let tmp = std::mem::replace(&mut self.output, o);
                                     let no_drop_nulled = std::mem::ManuallyDrop::new(tmp);
@@ -401,7 +578,7 @@ impl Audio {
                         // we'll have to wait - around 0.05-0.08~ seconds per buffer.
                         //
                         // Resampling + Volume is applied within `write()`.
-                        if let Err(e) = self.output.write(decoded) {
+                        if let Err(e) = self.output.write(decoded, self.state.channel_mix) {
                             // Pause playback on write error.
                             self.state.playing = false;
                             AUDIO_STATE.write().playing = false;
@@ -409,13 +586,91 @@ impl Audio {
                             continue;
                         }
 
+                        // Report the output path if it changed.
+                        if self.state.output_path != self.output.path
+                            || self.state.output_rate != self.output.rate
+                        {
+                            let (path, rate) = (self.output.path, self.output.rate);
+                            debug!("Audio - output path: {path} @ {rate}Hz");
+
+                            self.state.output_path = path;
+                            self.state.output_rate = rate;
+                            {
+                                let mut state = AUDIO_STATE.write();
+                                state.output_path = path;
+                                state.output_rate = rate;
+                            }
+
+                            #[cfg(feature = "gui")]
+                            gui_request_update();
+                        }
+
                         // Set runtime timestamp.
                         let new_time = timebase.calc_time(packet.ts);
+
+                        // Jump back to `A` once we reach `B`.
+                        //
+                        // This is checked every packet (not every second)
+                        // with the local `repeat`, so playback never runs past `B`.
+                        if let Repeat::Ab { a, b } = self.state.repeat {
+                            if new_time.seconds >= b as u64 && self.seek.is_none() {
+                                trace!("Audio - A-B loop: {b} -> {a}");
+                                self.seek = Some(symphonia::core::units::Time {
+                                    seconds: a as u64,
+                                    frac: 0.0,
+                                });
+                            }
+                        }
+
                         if time.seconds != new_time.seconds {
                             *time = new_time;
 
                             // Set state.
-                            AUDIO_STATE.write().elapsed = Runtime::from(time.seconds);
+                            let (song, runtime, sleep) = {
+                                let mut state = AUDIO_STATE.write();
+                                state.elapsed = Runtime::from(time.seconds);
+                                state.chapter = chapter_index(&self.chapters, time.seconds as u32);
+                                (state.song, state.runtime.inner(), state.sleep)
+                            };
+
+                            // Fade out, then pause once the sleep timer goes off.
+                            if let SleepTimer::Time { .. } = sleep {
+                                let now = benri::unix!();
+                                if sleep.remaining(now) == Some(0) {
+                                    debug!("Audio - sleep timer went off, pausing");
+                                    let mut state = AUDIO_STATE.write();
+                                    state.sleep = SleepTimer::Off;
+                                    self.sleep_pause(&mut state);
+                                    #[cfg(feature = "gui")]
+                                    gui_request_update();
+                                    continue;
+                                }
+                                atomic_store!(SLEEP_FADE, sleep.fade(now));
+                            }
+
+                            // Count the play once enough of the `Song` was heard,
+                            // and remember the position of `Song`'s that resume.
+                            if let Some(key) = song {
+                                if self.resume_saved.elapsed() >= RESUME_INTERVAL {
+                                    RESUME.write().remember(
+                                        key,
+                                        time.seconds as u32,
+                                        &self.collection,
+                                    );
+                                    self.resume_saved = Instant::now();
+                                }
+
+                                if !self.play_counted
+                                    && PlayStats::counts(time.seconds as u32, runtime)
+                                {
+                                    self.play_counted = true;
+                                    PLAY_STATS.write().played(
+                                        key,
+                                        benri::unix!(),
+                                        &self.collection,
+                                    );
+                                }
+                            }
 
                             // Wake up the GUI thread.
                             #[cfg(feature = "gui")]
@@ -436,12 +691,20 @@ impl Audio {
                     }
                     // We're done playing audio.
                     Err(symphonia::core::errors::Error::IoError(_err)) => {
-                        self.skip(1, &mut AUDIO_STATE.write());
+                        self.song_end();
                         #[cfg(feature = "gui")]
                         gui_request_update();
                         continue;
                     }
-                    Err(err) => warn!("audio error: {err}"),
+                    Err(symphonia::core::errors::Error::ResetRequired) => decoder.reset(),
+                    // The packet is corrupt, skip it.
+                    Err(err) => {
+                        let elapsed = time.seconds;
+                        warn!("Audio - decode error at {elapsed}s, skipping packet: {err}");
+                        if self.audio_error(AudioErrorKind::Decode, elapsed, &err) {
+                            self.decode_abort(elapsed, &err);
+                        }
+                    }
                 }
             }
 
@@ -453,17 +716,38 @@ impl Audio {
     #[inline(always)]
     fn kernel_msg(&mut self, msg: KernelToAudio) {
         use KernelToAudio::*;
+
+        // Editing the queue in any way other than
+        // appending to the back forgets the Auto-DJ span.
+        let forget_auto_dj = match &msg {
+            QueueAddSong((_, append, clear, _)) => *clear || *append != Append::Back,
+            QueueAddAlbum((_, append, clear, ..))
+            | QueueAddArtist((_, append, clear, ..))
+            | QueueAddPlaylist((_, append, clear, ..)) => *clear || *append != Append::Back,
+            Shuffle(_) | Clear(_) | QueueRemoveRange(_) | QueueMove(_) | QueueInsert(_)
+            | QueueSwap(_) | QueueRestore(_) => true,
+            _ => false,
+        };
+        if forget_auto_dj {
+            AUDIO_STATE.write().auto_dj_span = None;
+            self.auto_dj_pick = None;
+        }
+        self.auto_dj_prepare();
+
         match msg {
             // Audio playback.
             Toggle => self.toggle(),
             Play => self.play(),
             Pause => self.pause(),
-            Next => self.skip(1, &mut AUDIO_STATE.write()),
+            Next => self.user_skip(1),
             Previous(threshold) => self.back(1, threshold, &mut AUDIO_STATE.write()),
 
             // Audio settings.
             Repeat(r) => self.repeat(r),
             Volume(v) => self.volume(v),
+            AutoDj(b) => self.auto_dj(b),
+            Sleep(s) => self.sleep(s),
+            ChannelMix(mix) => self.channel_mix(mix),
 
             // Queue.
             QueueAddSong((s_key, append, clear, play)) => {
@@ -478,19 +762,25 @@ impl Audio {
             QueueAddPlaylist((p, append, clear, play, offset)) => {
                 self.queue_add_playlist(p, append, clear, play, offset)
             }
-            Shuffle => self.shuffle(),
+            Shuffle(mode) => self.shuffle(mode),
             Clear(play) => {
                 self.clear(play, &mut AUDIO_STATE.write());
                 #[cfg(feature = "gui")]
                 gui_request_update();
             }
             Seek((seek, time)) => self.seek(seek, time, &mut AUDIO_STATE.write()),
-            Skip(skip) => self.skip(skip, &mut AUDIO_STATE.write()),
+            Skip(skip) => self.user_skip(skip),
             Back(back) => self.back(back, Some(0), &mut AUDIO_STATE.write()),
 
             // Queue Index.
             QueueSetIndex(idx) => self.queue_set_index(idx),
             QueueRemoveRange((range, next)) => self.queue_remove_range(range, next),
+            QueueMove((from, to)) => self.queue_move(from, to),
+            QueueInsert((index, keys)) => self.queue_insert(index, keys),
+            QueueSwap((a, b)) => self.queue_swap(a, b),
+
+            // Queue Snapshots.
+            QueueRestore(name) => self.queue_restore(name),
 
             // Audio State.
             RestoreAudioState => self.restore_audio_state(),
@@ -505,11 +795,12 @@ impl Audio {
     fn mc_msg(&mut self, event: souvlaki::MediaControlEvent) {
         use crate::audio::Seek;
         use souvlaki::{MediaControlEvent::*, SeekDirection};
+        self.auto_dj_prepare();
         match event {
             Toggle => self.toggle(),
             Play => self.play(),
             Pause => self.pause(),
-            Next => self.skip(1, &mut AUDIO_STATE.write()),
+            Next => self.user_skip(1),
             Previous => self.back(1, None, &mut AUDIO_STATE.write()),
             Stop => {
                 self.clear(false, &mut AUDIO_STATE.write());
@@ -705,10 +996,47 @@ impl Audio {
                 )
                 .is_ok()
             {
+                // Remember where we left the previous `Song`.
+                if let Some(old) = state.song {
+                    self.resume_save(old, state.elapsed.inner());
+                }
+
+                // An A-B loop only applies to the `Song` it was set on,
+                // and a new `Song` starts its repeat count over.
+                if state.song != Some(key) {
+                    match state.repeat {
+                        Repeat::Ab { .. } => state.repeat = Repeat::Off,
+                        Repeat::Count(_) => state.repeat = Repeat::Count(self.repeat_count),
+                        _ => (),
+                    }
+                    self.state.repeat = state.repeat;
+                }
+
                 // Set song state.
                 state.song = Some(key);
                 state.elapsed = Runtime::zero();
                 state.runtime = self.collection.songs[key].runtime;
+                self.play_counted = false;
+                self.decode_errors = 0;
+                self.chapters = CHAPTERS.read().song(key, &self.collection).to_vec();
+
+                // Continue where we left off.
+                if let Some(position) = RESUME.read().position(key, &self.collection) {
+                    debug!("Audio - resuming {key:?} at {position}");
+                    state.elapsed = Runtime::from(position);
+                    self.seek = Some(symphonia::core::units::Time {
+                        seconds: position as u64,
+                        frac: 0.0,
+                    });
+                }
+                state.chapter = chapter_index(&self.chapters, state.elapsed.inner());
+
+                // Record it in the history, unless we're walking it.
+                if !self.from_history {
+                    self.history_cursor = 0;
+                    HISTORY.write().push(key, benri::unix!(), &self.collection);
+                }
+
                 #[cfg(feature = "gui")]
                 gui_request_update();
                 self.set_media_controls_metadata(key);
@@ -741,6 +1069,9 @@ impl Audio {
         self.state.playing = keep_playing;
 
         if !keep_playing {
+            if let Some(key) = state.song {
+                self.resume_save(key, state.elapsed.inner());
+            }
             state.finish();
             self.seek = None;
             self.current = None;
@@ -753,6 +1084,7 @@ impl Audio {
     }
 
     fn inner_play(&mut self, state: &mut std::sync::RwLockWriteGuard<'_, AudioState>) {
+        Self::expire_sleep(state);
         let _ = self.output.play();
         self.state.playing = true;
         state.playing = true;
@@ -767,6 +1099,11 @@ impl Audio {
 
             let mut state = AUDIO_STATE.write();
             flip!(state.playing);
+            if state.playing {
+                Self::expire_sleep(&mut state);
+            } else if let Some(key) = state.song {
+                self.resume_save(key, state.elapsed.inner());
+            }
 
             self.set_media_controls_progress(&mut state);
 
@@ -794,6 +1131,9 @@ impl Audio {
 
             let mut state = AUDIO_STATE.write();
             state.playing = false;
+            if let Some(key) = state.song {
+                self.resume_save(key, state.elapsed.inner());
+            }
             self.set_media_controls_progress(&mut state);
 
             #[cfg(feature = "gui")]
@@ -801,6 +1141,167 @@ impl Audio {
         }
     }
 
+    // A `SleepTimer::Time` is only checked while decoding, so if it
+    // passed while we were paused, turn it `Off` before playing again
+    // instead of pausing right away.
+    fn expire_sleep(state: &mut std::sync::RwLockWriteGuard<'_, AudioState>) {
+        if state.sleep.remaining(benri::unix!()) == Some(0) {
+            debug!("Audio - sleep timer passed while paused, turning off");
+            state.sleep = SleepTimer::Off;
+            atomic_store!(SLEEP_FADE, 100);
+        }
+    }
+
+    // Pause because a sleep timer went off.
+    //
+    // The fade is reset, the caller should set the timer `Off`.
+    fn sleep_pause(&mut self, state: &mut std::sync::RwLockWriteGuard<'_, AudioState>) {
+        if let Some(key) = state.song {
+            self.resume_save(key, state.elapsed.inner());
+        }
+        let _ = self.output.pause();
+        self.state.playing = false;
+        state.playing = false;
+        atomic_store!(SLEEP_FADE, 100);
+        self.set_media_controls_progress(state);
+    }
+
+    // Remember `elapsed` as the position of `key` in `RESUME`, if it resumes.
+    //
+    // This happens every `RESUME_INTERVAL` while playing, and
+    // on pause and `Song` changes, not every second.
+    fn resume_save(&mut self, key: SongKey, elapsed: u32) {
+        RESUME.write().remember(key, elapsed, &self.collection);
+        self.resume_saved = Instant::now();
+    }
+
+    // The current `Song` finished playing by itself.
+    //
+    // This is a `skip(1)` that also handles the
+    // sleep timers waiting on the `Song`/`Album`/queue to end.
+    fn song_end(&mut self) {
+        self.auto_dj_prepare();
+        let mut state = AUDIO_STATE.write();
+
+        let pause = match (state.sleep, state.queue_idx) {
+            (SleepTimer::Song, _) => true,
+            (SleepTimer::Album, Some(i)) => match state.queue.get(i + 1) {
+                Some(next) => {
+                    self.collection.songs[*next].album
+                        != self.collection.songs[state.queue[i]].album
+                }
+                None => true,
+            },
+            (SleepTimer::Queue, Some(i)) => i + 1 >= state.queue.len(),
+            (SleepTimer::Album | SleepTimer::Queue, None) => true,
+            _ => false,
+        };
+
+        // It finished, the next play starts from the beginning.
+        if let Some(key) = state.song {
+            RESUME.write().finish(key, &self.collection);
+        }
+
+        self.skip(1, &mut state);
+
+        if pause {
+            debug!("Audio - sleep timer {:?} went off, pausing", state.sleep);
+            state.sleep = SleepTimer::Off;
+            self.sleep_pause(&mut state);
+        }
+    }
+
+    // Record a corrupt packet of the current `Song` in `AUDIO_ERRORS`.
+    //
+    // Returns `true` if the `Song` now has more errors
+    // than `DECODE_ERROR_THRESHOLD` and should be given up on.
+    fn audio_error(
+        &mut self,
+        kind: AudioErrorKind,
+        elapsed: u64,
+        error: &symphonia::core::errors::Error,
+    ) -> bool {
+        self.decode_errors = self.decode_errors.saturating_add(1);
+
+        let song = AUDIO_STATE.read().song;
+        if let Some(key) = song {
+            AUDIO_ERRORS.write().push(
+                &self.collection.songs[key].path,
+                AudioError {
+                    timestamp: benri::unix!(),
+                    elapsed: elapsed as u32,
+                    kind,
+                    error: error.to_string(),
+                },
+            );
+        }
+
+        self.decode_errors > atomic_load!(DECODE_ERROR_THRESHOLD)
+    }
+
+    // Give up on the current `Song`, it had too many corrupt packets.
+    //
+    // Repeating it would hit the same errors
+    // again, so playback is paused in that case,
+    // else, this is the same as the `Song` ending.
+    fn decode_abort(&mut self, elapsed: u64, error: &symphonia::core::errors::Error) {
+        let (song, repeat) = {
+            let state = AUDIO_STATE.read();
+            (state.song, state.repeat)
+        };
+
+        let key = match song {
+            Some(k) => k,
+            None => return,
+        };
+
+        let error = format!("gave up after {} errors: {error}", self.decode_errors);
+        warn!("Audio - {key:?} {error}");
+
+        AUDIO_ERRORS.write().push(
+            &self.collection.songs[key].path,
+            AudioError {
+                timestamp: benri::unix!(),
+                elapsed: elapsed as u32,
+                kind: AudioErrorKind::Abort,
+                error: error.clone(),
+            },
+        );
+        send!(
+            self.to_kernel,
+            AudioToKernel::DecodeError((key, anyhow!(error)))
+        );
+
+        if matches!(
+            repeat,
+            Repeat::Song | Repeat::Count(1..) | Repeat::Ab { .. }
+        ) {
+            self.pause();
+        } else {
+            self.song_end();
+        }
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
+    // A `skip()` the user asked for (`Next`, `Skip`).
+    //
+    // This counts as a skip of the current `Song`
+    // in `PLAY_STATS` if its play wasn't counted yet.
+    fn user_skip(&mut self, skip: usize) {
+        let mut state = AUDIO_STATE.write();
+
+        if let Some(key) = state.song {
+            if skip != 0 && !self.play_counted && self.current.is_some() {
+                self.play_counted = true;
+                PLAY_STATS.write().skipped(key, &self.collection);
+            }
+        }
+
+        self.skip(skip, &mut state);
+    }
+
     fn skip(&mut self, skip: usize, state: &mut std::sync::RwLockWriteGuard<'_, AudioState>) {
         trace!("Audio - skip({skip})");
 
@@ -808,12 +1309,67 @@ impl Audio {
             return;
         }
 
-        if state.repeat == Repeat::Song {
-            if let Some(key) = state.song {
-                trace!("Audio - repeating song: {key:?}");
-                self.set(key, state);
+        match state.repeat {
+            Repeat::Song => {
+                if let Some(key) = state.song {
+                    trace!("Audio - repeating song: {key:?}");
+                    self.set(key, state);
+                }
+                return;
+            }
+            Repeat::Count(count) if count != 0 => {
+                if let Some(key) = state.song {
+                    trace!("Audio - repeating song: {key:?}, {count} left");
+                    self.set(key, state);
+                }
+                state.repeat = match count - 1 {
+                    0 => Repeat::Off,
+                    c => Repeat::Count(c),
+                };
+                self.state.repeat = state.repeat;
+                return;
+            }
+            Repeat::Ab { a, .. } => {
+                if let Some(key) = state.song {
+                    trace!("Audio - repeating A-B: {key:?}");
+                    self.set(key, state);
+                    self.seek = Some(symphonia::core::units::Time {
+                        seconds: a as u64,
+                        frac: 0.0,
+                    });
+                }
+                return;
+            }
+            _ => (),
+        }
+
+        // Go back to the start of the current
+        // `Album`/`Artist` span if we're at its end.
+        if skip == 1 && matches!(state.repeat, Repeat::Album | Repeat::Artist) {
+            if let Some(index) = state.queue_idx {
+                let (start, end) = match state.repeat {
+                    Repeat::Album => {
+                        repeat_span(&state.queue, index, |k| self.collection.songs[*k].album)
+                    }
+                    _ => repeat_span(&state.queue, index, |k| {
+                        self.collection.albums[self.collection.songs[*k].album].artist
+                    }),
+                };
+
+                if index == end {
+                    let key = state.queue[start];
+                    trace!(
+                        "Audio - repeating {:?} span, setting: {key:?}",
+                        state.repeat
+                    );
+                    self.set(key, state);
+                    state.song = Some(key);
+                    state.queue_idx = Some(start);
+                    #[cfg(feature = "gui")]
+                    gui_request_update();
+                    return;
+                }
             }
-            return;
         }
 
         // For 1 skips.
@@ -836,6 +1392,9 @@ impl Audio {
                             state.playing = false;
                             self.state.playing = false;
                         }
+                    } else if let Some(next) = self.auto_dj_next(state) {
+                        trace!("Audio - Auto-DJ appended, setting: {next:?}");
+                        self.set(next, state);
                     } else {
                         trace!("Audio - no songs left, calling state.finish()");
                         state.finish();
@@ -881,6 +1440,66 @@ impl Audio {
         gui_request_update();
     }
 
+    // If Auto-DJ is on, append the `Song`'s `auto_dj_prepare()`
+    // picked for the current one and go to the first of them.
+    //
+    // Returns `None` if Auto-DJ is off or nothing was picked.
+    fn auto_dj_next(
+        &mut self,
+        state: &mut std::sync::RwLockWriteGuard<'_, AudioState>,
+    ) -> Option<SongKey> {
+        if !state.auto_dj {
+            return None;
+        }
+
+        let keys = match self.auto_dj_pick.take() {
+            Some((seed, keys)) if state.song == Some(seed) && !keys.is_empty() => keys,
+            _ => return None,
+        };
+
+        debug!("Audio - Auto-DJ appending {} songs", keys.len());
+        state.auto_dj_append(keys);
+        state.next()
+    }
+
+    // Pick the Auto-DJ `Song`'s for when the queue runs out after the current `Song`.
+    //
+    // This scans the whole `Collection` and `PLAYLISTS`, so it's done
+    // before taking the `AUDIO_STATE` write lock that `skip()` runs
+    // under, which then only appends them, see `auto_dj_next()`.
+    //
+    // Nothing is picked unless Auto-DJ is on and the current `Song` is
+    // the last in the queue, and only once per `Song`.
+    fn auto_dj_prepare(&mut self) {
+        let (seed, mut avoid) = {
+            let state = AUDIO_STATE.read();
+            let last = state.queue_idx.is_some_and(|i| i + 1 >= state.queue.len());
+            match state.song {
+                Some(seed) if state.auto_dj && last => {
+                    if matches!(&self.auto_dj_pick, Some((s, _)) if *s == seed) {
+                        return;
+                    }
+                    (seed, state.queue.iter().copied().collect::<HashSet<_>>())
+                }
+                _ => {
+                    self.auto_dj_pick = None;
+                    return;
+                }
+            }
+        };
+        avoid.extend(auto_dj_recent(&HISTORY.read()));
+
+        let keys = auto_dj(
+            &self.collection,
+            seed,
+            &avoid,
+            &PLAYLISTS.read(),
+            AUTO_DJ_COUNT,
+            &mut rand::thread_rng(),
+        );
+        self.auto_dj_pick = Some((seed, keys));
+    }
+
     fn back(
         &mut self,
         back: usize,
@@ -890,6 +1509,17 @@ impl Audio {
         let atomic_threshold = atomic_load!(PREVIOUS_THRESHOLD);
         trace!("Audio - back(back: {back}, threshold: {threshold:?}), atomic_threshold: {atomic_threshold}");
 
+        if atomic_load!(PREVIOUS_HISTORY) {
+            let threshold = threshold.unwrap_or(atomic_threshold);
+            if (threshold == 0 || state.elapsed.inner() <= threshold)
+                && self.back_history(back, state)
+            {
+                #[cfg(feature = "gui")]
+                gui_request_update();
+                return;
+            }
+        }
+
         if !state.queue.is_empty() {
             // FIXME:
             // Same as `skip()`.
@@ -928,6 +1558,38 @@ impl Audio {
         }
     }
 
+    // Play the `Song` `back` valid entries before the current one in `HISTORY`.
+    //
+    // See `AudioState::queue_back_to()` for where it goes in the queue.
+    //
+    // Returns `false` and does nothing if there is no such `Song`.
+    fn back_history(
+        &mut self,
+        back: usize,
+        state: &mut std::sync::RwLockWriteGuard<'_, AudioState>,
+    ) -> bool {
+        let Some((cursor, key)) = HISTORY.read().valid_from(self.history_cursor + back) else {
+            return false;
+        };
+
+        // `HISTORY` may have been validated against
+        // a newer `Collection` than the one we have.
+        if self.collection.songs.get(key).is_none() {
+            return false;
+        }
+
+        trace!("Audio - back_history({back}), cursor: {cursor}, setting: {key:?}");
+
+        state.queue_back_to(key);
+
+        self.history_cursor = cursor;
+        self.from_history = true;
+        self.set(key, state);
+        self.from_history = false;
+
+        true
+    }
+
     fn seek(
         &mut self,
         seek: Seek,
@@ -972,6 +1634,25 @@ impl Audio {
                         });
                     }
                 }
+                Seek::NextChapter => match next_chapter(&self.chapters, elapsed as u32) {
+                    Some(start) => {
+                        self.seek = Some(symphonia::core::units::Time {
+                            seconds: start as u64,
+                            frac: 0.0,
+                        });
+                    }
+                    None => {
+                        debug!("Audio - seek next chapter: last chapter, calling .skip(1)");
+                        self.skip(1, state);
+                    }
+                },
+                Seek::PreviousChapter => {
+                    let start = previous_chapter(&self.chapters, elapsed as u32);
+                    self.seek = Some(symphonia::core::units::Time {
+                        seconds: start as u64,
+                        frac: 0.0,
+                    });
+                }
             }
         }
 
@@ -980,16 +1661,16 @@ impl Audio {
     }
 
     //-------------------------------------------------- Audio settings.
-    fn shuffle(&mut self) {
-        trace!("Audio - Shuffle");
+    fn shuffle(&mut self, mode: ShuffleMode) {
+        trace!("Audio - Shuffle::{mode:?}");
 
         let mut state = AUDIO_STATE.write();
 
         if !state.queue.is_empty() {
-            use rand::{prelude::SliceRandom, SeedableRng};
+            use rand::SeedableRng;
             let mut rng = rand::rngs::SmallRng::from_entropy();
 
-            state.queue.make_contiguous().shuffle(&mut rng);
+            mode.shuffle(state.queue.make_contiguous(), &self.collection, &mut rng);
             state.queue_idx = Some(0);
             self.set(state.queue[0], &mut state);
         }
@@ -997,9 +1678,46 @@ impl Audio {
 
     fn repeat(&mut self, repeat: Repeat) {
         trace!("Audio - Repeat::{repeat:?}");
+
+        if !repeat.is_valid() {
+            warn!("Audio - invalid Repeat::{repeat:?}, ignoring");
+            return;
+        }
+
+        if let Repeat::Count(c) = repeat {
+            self.repeat_count = c;
+        }
+
+        self.state.repeat = repeat;
         AUDIO_STATE.write().repeat = repeat;
     }
 
+    fn auto_dj(&mut self, auto_dj: bool) {
+        trace!("Audio - auto_dj({auto_dj})");
+        AUDIO_STATE.write().auto_dj = auto_dj;
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
+    fn sleep(&mut self, sleep: SleepTimer) {
+        trace!("Audio - {sleep:?}");
+        AUDIO_STATE.write().sleep = sleep;
+        atomic_store!(SLEEP_FADE, 100);
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
+    fn channel_mix(&mut self, mix: ChannelMix) {
+        trace!("Audio - {mix:?}");
+        self.state.channel_mix = mix;
+        AUDIO_STATE.write().channel_mix = mix;
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
     fn volume(&mut self, volume: Volume) {
         trace!("Audio - {volume:?}");
         atomic_store!(VOLUME, volume.inner());
@@ -1329,6 +2047,81 @@ impl Audio {
         gui_request_update();
     }
 
+    fn queue_move(&mut self, from: usize, to: usize) {
+        if AUDIO_STATE.write().queue_move(from, to) {
+            trace!("Audio - queue_move({from}, {to})");
+        } else {
+            warn!("Audio - index is invalid, skipping queue_move({from}, {to})");
+            return;
+        }
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
+    fn queue_insert(&mut self, index: usize, keys: Box<[SongKey]>) {
+        if AUDIO_STATE.write().queue_insert(index, &keys) {
+            trace!("Audio - queue_insert({index}, {} keys)", keys.len());
+        } else {
+            warn!("Audio - index is invalid, skipping queue_insert({index})");
+            return;
+        }
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
+    fn queue_swap(&mut self, a: usize, b: usize) {
+        if AUDIO_STATE.write().queue_swap(a, b) {
+            trace!("Audio - queue_swap({a}, {b})");
+        } else {
+            warn!("Audio - index is invalid, skipping queue_swap({a}, {b})");
+            return;
+        }
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
+    //-------------------------------------------------- Queue Snapshots.
+    fn queue_restore(&mut self, name: Arc<str>) {
+        let Some((queue, index, elapsed)) = crate::state::QUEUE_SNAPSHOTS
+            .read()
+            .get(&name)
+            .map(|s| s.restore())
+        else {
+            warn!("Audio - {name} doesn't exist, skipping queue_restore()");
+            return;
+        };
+
+        trace!("Audio - queue_restore({name}), index: {index:?}, elapsed: {elapsed}");
+
+        let mut state = AUDIO_STATE.write();
+
+        // Nothing valid left to restore.
+        let Some(index) = index else {
+            self.clear(false, &mut state);
+            #[cfg(feature = "gui")]
+            gui_request_update();
+            return;
+        };
+
+        let playing = state.playing;
+        self.clear(playing, &mut state);
+
+        let key = queue[index];
+        state.queue = queue;
+        state.queue_idx = Some(index);
+        self.set(key, &mut state);
+
+        if elapsed > 0 {
+            self.seek(Seek::Absolute, elapsed as u64, &mut state);
+        }
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
     //-------------------------------------------------- Restore Audio State.
     // Sets the global `AUDIO_STATE` to our local `self.state`.
     fn restore_audio_state(&mut self) {
@@ -1350,7 +2143,14 @@ impl Audio {
             // Start playback.
             let elapsed = state.elapsed.inner() as u64;
             debug!("Audio - Restore ... setting {key:?}");
+            // This `Song` is already in the
+            // history from last time, don't re-add it.
+            self.from_history = true;
             self.set(key, &mut state);
+            self.from_history = false;
+            // If we're already past the play threshold,
+            // the play was counted last time.
+            self.play_counted = PlayStats::counts(elapsed as u32, state.runtime.inner());
 
             // HACK:
             // The above `set()` resets some of the state, so re-copy.
@@ -1408,6 +2208,12 @@ impl Audio {
                         self.current = None;
                     }
 
+                    // The chapters may have changed with the `Collection`.
+                    self.chapters = match self.state.song {
+                        Some(key) => CHAPTERS.read().song(key, &self.collection).to_vec(),
+                        None => vec![],
+                    };
+
                     return;
                 }
                 _ => {
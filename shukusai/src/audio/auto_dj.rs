@@ -0,0 +1,191 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::audio::shuffle::weighted_order;
+use crate::collection::{Collection, SongKey};
+use crate::state::{Entry, History, Playlists};
+use rand::{seq::SliceRandom, Rng};
+use std::collections::{HashMap, HashSet};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// How many `Song`'s Auto-DJ appends to the queue each time it runs out.
+pub const AUTO_DJ_COUNT: usize = 10;
+
+/// How many of the newest [`History`] entries Auto-DJ avoids picking again.
+pub const AUTO_DJ_RECENT: usize = 100;
+
+// Affinity points for a `Song` that shares...
+const SAME_ARTIST: f64 = 4.0; // ...the same `Artist`.
+const SAME_GENRE: f64 = 3.0; // ...the same genre.
+const YEAR_CLOSE: f64 = 2.0; // ...a release year within `YEAR_CLOSE_RANGE`.
+const YEAR_NEAR: f64 = 1.0; // ...a release year within `YEAR_NEAR_RANGE`.
+const PLAYLIST: f64 = 1.0; // ...a playlist (per playlist, up to `PLAYLIST_MAX`).
+
+const YEAR_CLOSE_RANGE: u16 = 2;
+const YEAR_NEAR_RANGE: u16 = 5;
+const PLAYLIST_MAX: usize = 3;
+
+//---------------------------------------------------------------------------------------------------- Auto-DJ
+/// INVARIANT: assumes `seed` and the keys in `avoid` are valid.
+///
+/// Pick up to `count` `Song`'s related to `seed` for Auto-DJ.
+///
+/// Each `Song` in the [`Collection`] is scored by its affinity to `seed`:
+/// - Same `Artist`
+/// - Same genre
+/// - Release year proximity
+/// - How many [`Playlists`] it shares with `seed`
+///
+/// `Song`'s with a higher affinity are more likely to be picked (and come first).
+///
+/// `seed` and `Song`'s in `avoid` are never picked, unless that
+/// leaves nothing to pick, in which case `avoid` is ignored.
+///
+/// If no `Song` has any affinity to `seed`, random ones are picked.
+pub fn auto_dj<R: Rng + ?Sized>(
+    collection: &Collection,
+    seed: SongKey,
+    avoid: &HashSet<SongKey>,
+    playlists: &Playlists,
+    count: usize,
+    rng: &mut R,
+) -> Vec<SongKey> {
+    let (_, seed_album, _) = collection.walk(seed);
+    let seed_year = seed_album.release.year();
+
+    // How many playlists each `Song` shares with `seed`.
+    let mut shared: HashMap<SongKey, usize> = HashMap::new();
+    for playlist in playlists.values() {
+        let keys: HashSet<SongKey> = playlist
+            .iter()
+            .filter_map(|e| match e {
+                Entry::Valid { key_song, .. } => Some(*key_song),
+                Entry::Invalid { .. } => None,
+            })
+            .collect();
+
+        if keys.contains(&seed) {
+            for key in keys {
+                *shared.entry(key).or_default() += 1;
+            }
+        }
+    }
+
+    let candidates = |avoid: &HashSet<SongKey>| -> Vec<SongKey> {
+        collection
+            .songs
+            .iter()
+            .map(|s| s.key)
+            .filter(|k| *k != seed && !avoid.contains(k))
+            .collect()
+    };
+
+    let mut keys = candidates(avoid);
+    if keys.is_empty() {
+        keys = candidates(&HashSet::new());
+    }
+
+    let weights: HashMap<SongKey, f64> = keys
+        .iter()
+        .map(|k| {
+            let album = &collection.albums[collection.songs[*k].album];
+            let same_genre = match (&album.genre, &seed_album.genre) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                _ => false,
+            };
+            let a = affinity(
+                album.artist == seed_album.artist,
+                same_genre,
+                year_affinity(album.release.year(), seed_year),
+                shared.get(k).copied().unwrap_or(0),
+            );
+            (*k, a)
+        })
+        .collect();
+
+    let mut related: Vec<SongKey> = keys.iter().copied().filter(|k| weights[k] > 0.0).collect();
+
+    if related.is_empty() {
+        keys.shuffle(rng);
+        keys.truncate(count);
+        return keys;
+    }
+
+    weighted_order(&mut related, |k| weights[k], rng);
+    related.truncate(count);
+    related
+}
+
+/// The `Song`'s in the newest [`AUTO_DJ_RECENT`] entries of `history`,
+/// for use as [`auto_dj()`]'s `avoid`.
+pub fn auto_dj_recent(history: &History) -> HashSet<SongKey> {
+    history
+        .iter_newest()
+        .take(AUTO_DJ_RECENT)
+        .filter_map(|h| match h.entry {
+            Entry::Valid { key_song, .. } => Some(key_song),
+            Entry::Invalid { .. } => None,
+        })
+        .collect()
+}
+
+//---------------------------------------------------------------------------------------------------- Private functions
+// The affinity of a `Song` to the `seed`, `0.0` means unrelated.
+fn affinity(same_artist: bool, same_genre: bool, year: f64, shared_playlists: usize) -> f64 {
+    let mut a = year + PLAYLIST * shared_playlists.min(PLAYLIST_MAX) as f64;
+
+    if same_artist {
+        a += SAME_ARTIST;
+    }
+    if same_genre {
+        a += SAME_GENRE;
+    }
+
+    a
+}
+
+// The release year affinity, `0` years are unknown and unrelated.
+fn year_affinity(a: u16, b: u16) -> f64 {
+    if a == 0 || b == 0 {
+        return 0.0;
+    }
+
+    match a.abs_diff(b) {
+        d if d <= YEAR_CLOSE_RANGE => YEAR_CLOSE,
+        d if d <= YEAR_NEAR_RANGE => YEAR_NEAR,
+        _ => 0.0,
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Unknown years never match, closer years score higher.
+    fn _year_affinity() {
+        assert_eq!(year_affinity(0, 0), 0.0);
+        assert_eq!(year_affinity(0, 2000), 0.0);
+        assert_eq!(year_affinity(2000, 2000), YEAR_CLOSE);
+        assert_eq!(year_affinity(2002, 2000), YEAR_CLOSE);
+        assert_eq!(year_affinity(2000, 2005), YEAR_NEAR);
+        assert_eq!(year_affinity(2000, 2006), 0.0);
+    }
+
+    #[test]
+    // Every shared trait adds up, playlists are capped.
+    fn _affinity() {
+        assert_eq!(affinity(false, false, 0.0, 0), 0.0);
+        assert_eq!(affinity(true, false, 0.0, 0), SAME_ARTIST);
+        assert_eq!(affinity(false, true, 0.0, 0), SAME_GENRE);
+        assert_eq!(affinity(false, false, YEAR_NEAR, 0), YEAR_NEAR);
+        assert_eq!(affinity(false, false, 0.0, 1), PLAYLIST);
+        assert_eq!(
+            affinity(false, false, 0.0, usize::MAX),
+            PLAYLIST * PLAYLIST_MAX as f64
+        );
+        assert_eq!(
+            affinity(true, true, YEAR_CLOSE, 1),
+            SAME_ARTIST + SAME_GENRE + YEAR_CLOSE + PLAYLIST
+        );
+    }
+}
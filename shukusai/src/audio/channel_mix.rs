@@ -0,0 +1,202 @@
+//---------------------------------------------------------------------------------------------------- Use
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The maximum (absolute) [`ChannelMix::balance`].
+///
+/// `-BALANCE_MAX` is fully left, `BALANCE_MAX` is fully right.
+pub const BALANCE_MAX: i8 = 100;
+
+//---------------------------------------------------------------------------------------------------- ChannelMix
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+)]
+/// How the left/right channels are mixed before being sent to the audio device.
+///
+/// This is applied after the volume, in this order:
+/// 1. [`Self::mono`]
+/// 2. [`Self::swap`]
+/// 3. [`Self::balance`]
+///
+/// Only the first 2 (front left/right) channels are touched,
+/// mono files and any extra channels are passed through as-is.
+pub struct ChannelMix {
+    /// Down-mix the left and right channels, playing the same audio out of both.
+    pub mono: bool,
+    /// The left/right balance, in-between `-100..=100`.
+    ///
+    /// Negative values turn the right channel down,
+    /// positive values turn the left channel down.
+    ///
+    /// `0` leaves both channels as-is.
+    pub balance: i8,
+    /// Swap the left and right channels.
+    pub swap: bool,
+}
+
+impl ChannelMix {
+    #[inline]
+    /// Returns the default, which leaves the channels as-is.
+    pub const fn new() -> Self {
+        Self {
+            mono: false,
+            balance: 0,
+            swap: false,
+        }
+    }
+
+    #[inline]
+    /// Checks the [`ChannelMix`] for correctness.
+    ///
+    /// [`Self::balance`] is clamped to `-100..=100`.
+    pub const fn check(self) -> Self {
+        let balance = if self.balance > BALANCE_MAX {
+            BALANCE_MAX
+        } else if self.balance < -BALANCE_MAX {
+            -BALANCE_MAX
+        } else {
+            self.balance
+        };
+
+        Self { balance, ..self }
+    }
+
+    #[inline]
+    /// Does this leave the channels as-is?
+    pub const fn is_passthrough(&self) -> bool {
+        !self.mono && !self.swap && self.balance == 0
+    }
+
+    /// The `(left, right)` `0.0..1.0` multipliers [`Self::balance`] applies.
+    pub fn gains(&self) -> (f32, f32) {
+        let balance = self.check().balance as f32 / BALANCE_MAX as f32;
+        if balance < 0.0 {
+            (1.0, 1.0 + balance)
+        } else {
+            (1.0 - balance, 1.0)
+        }
+    }
+
+    #[inline]
+    // Mix a single left/right sample pair.
+    fn frame(&self, (gain_l, gain_r): (f32, f32), left: &mut f32, right: &mut f32) {
+        if self.mono {
+            let mid = (*left + *right) / 2.0;
+            *left = mid;
+            *right = mid;
+        } else if self.swap {
+            std::mem::swap(left, right);
+        }
+
+        *left *= gain_l;
+        *right *= gain_r;
+    }
+
+    // Mix interleaved samples with this many channels.
+    pub(crate) fn apply_interleaved(&self, samples: &mut [f32], channels: usize) {
+        if channels < 2 || self.is_passthrough() {
+            return;
+        }
+
+        let gains = self.gains();
+        for frame in samples.chunks_exact_mut(channels) {
+            if let [left, right, ..] = frame {
+                self.frame(gains, left, right);
+            }
+        }
+    }
+
+    // Mix planar samples, 1 plane per channel.
+    pub(crate) fn apply_planar(&self, planes: &mut [&mut [f32]]) {
+        if self.is_passthrough() {
+            return;
+        }
+
+        let gains = self.gains();
+        if let [left, right, ..] = planes {
+            for (left, right) in left.iter_mut().zip(right.iter_mut()) {
+                self.frame(gains, left, right);
+            }
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn mix(mono: bool, balance: i8, swap: bool) -> ChannelMix {
+        ChannelMix {
+            mono,
+            balance,
+            swap,
+        }
+    }
+
+    #[test]
+    // Balance turns the opposite channel down, and is clamped.
+    fn gains() {
+        assert_eq!(mix(false, 0, false).gains(), (1.0, 1.0));
+        assert_eq!(mix(false, -50, false).gains(), (1.0, 0.5));
+        assert_eq!(mix(false, 100, false).gains(), (0.0, 1.0));
+        assert_eq!(mix(false, i8::MIN, false).gains(), (1.0, 0.0));
+        assert_eq!(mix(false, i8::MAX, false).check().balance, BALANCE_MAX);
+    }
+
+    #[test]
+    fn interleaved() {
+        // 2 stereo frames.
+        let samples = [1.0, 0.0, 0.5, 0.5];
+
+        let mut s = samples;
+        mix(false, 0, false).apply_interleaved(&mut s, 2);
+        assert_eq!(s, samples);
+
+        let mut s = samples;
+        mix(true, 0, false).apply_interleaved(&mut s, 2);
+        assert_eq!(s, [0.5, 0.5, 0.5, 0.5]);
+
+        let mut s = samples;
+        mix(false, 0, true).apply_interleaved(&mut s, 2);
+        assert_eq!(s, [0.0, 1.0, 0.5, 0.5]);
+
+        let mut s = samples;
+        mix(false, 50, true).apply_interleaved(&mut s, 2);
+        assert_eq!(s, [0.0, 1.0, 0.25, 0.5]);
+
+        // Mono files are left alone.
+        let mut s = samples;
+        mix(true, 100, true).apply_interleaved(&mut s, 1);
+        assert_eq!(s, samples);
+
+        // Extra channels are left alone.
+        let mut s = [1.0, 0.0, 0.7];
+        mix(false, 0, true).apply_interleaved(&mut s, 3);
+        assert_eq!(s, [0.0, 1.0, 0.7]);
+    }
+
+    #[test]
+    fn planar() {
+        let (mut l, mut r) = ([1.0, 0.5], [0.0, 0.5]);
+        mix(true, -100, false).apply_planar(&mut [&mut l, &mut r]);
+        assert_eq!((l, r), ([0.5, 0.5], [0.0, 0.0]));
+
+        let mut l = [1.0];
+        mix(true, 0, true).apply_planar(&mut [&mut l]);
+        assert_eq!(l, [1.0]);
+    }
+}
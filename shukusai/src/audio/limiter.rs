@@ -0,0 +1,156 @@
+//---------------------------------------------------------------------------------------------------- Constants
+// The peak (`~-0.1dBFS`) samples are limited to.
+const THRESHOLD: f32 = 0.989;
+
+// How many seconds it takes the gain
+// to (mostly) recover after limiting.
+const RELEASE_SECONDS: f32 = 0.2;
+
+// Once the gain reduction released below this, it snaps to `0.0`.
+//
+// This is `~-0.0001dB`, inaudible, and well above the `f32`
+// precision issues of releasing the gain itself near `1.0`.
+const RELEASE_FLOOR: f32 = 0.00001;
+
+//---------------------------------------------------------------------------------------------------- Limiter
+// A peak limiter, the last gain stage before samples hit the device.
+//
+// Loud masters (and lossy decoders) can produce samples
+// above `1.0`, which clip. This lowers the gain instantly
+// (no look-ahead) when a frame would go above [`THRESHOLD`],
+// then slowly releases it back to `1.0`.
+//
+// This is transparent: if nothing peaks above [`THRESHOLD`],
+// the samples are not touched at all.
+//
+// The gain is linked across all channels,
+// so the stereo image doesn't shift while limiting.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct Limiter {
+    // The current gain reduction (`1.0 - gain`), `0.0..=1.0`.
+    //
+    // This is stored instead of the gain, as decaying it towards
+    // `0.0` doesn't get stuck on `f32` precision like `1.0` does.
+    reduction: f32,
+    // The per-frame multiplier used to release `reduction`.
+    release: f32,
+}
+
+impl Limiter {
+    // Create a `Limiter` for audio played at this sample rate.
+    pub(crate) fn new(sample_rate: u32) -> Self {
+        let frames = (sample_rate.max(1) as f32) * RELEASE_SECONDS;
+
+        Self {
+            reduction: 0.0,
+            release: (-1.0 / frames).exp(),
+        }
+    }
+
+    #[inline]
+    // Returns the gain to apply to a frame with this (absolute) peak.
+    fn gain(&mut self, peak: f32) -> f32 {
+        let ceiling = if peak > THRESHOLD {
+            THRESHOLD / peak
+        } else {
+            1.0
+        };
+        let needed = 1.0 - ceiling;
+
+        if needed > self.reduction {
+            // Attack instantly.
+            self.reduction = needed;
+        } else if self.reduction > 0.0 {
+            // Release slowly.
+            self.reduction = (self.reduction * self.release).max(needed);
+            if self.reduction < RELEASE_FLOOR {
+                self.reduction = 0.0;
+            }
+        }
+
+        // `1.0 - (1.0 - x)` isn't always `x` in `f32`,
+        // so never let that rounding push a peak over.
+        (1.0 - self.reduction).min(ceiling)
+    }
+
+    // Limit interleaved samples with this many channels.
+    pub(crate) fn apply_interleaved(&mut self, samples: &mut [f32], channels: usize) {
+        for frame in samples.chunks_exact_mut(channels.max(1)) {
+            let peak = frame.iter().fold(0.0_f32, |p, s| p.max(s.abs()));
+            let gain = self.gain(peak);
+            if gain < 1.0 {
+                frame.iter_mut().for_each(|s| *s *= gain);
+            }
+        }
+    }
+
+    // Limit planar samples, 1 plane per channel.
+    pub(crate) fn apply_planar(&mut self, planes: &mut [&mut [f32]]) {
+        let frames = planes.iter().map(|p| p.len()).min().unwrap_or(0);
+
+        for i in 0..frames {
+            let peak = planes
+                .iter()
+                .fold(0.0_f32, |p, plane| p.max(plane[i].abs()));
+            let gain = self.gain(peak);
+            if gain < 1.0 {
+                planes.iter_mut().for_each(|plane| plane[i] *= gain);
+            }
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Samples under the threshold are left bit-for-bit as-is.
+    fn transparent() {
+        let mut l = Limiter::new(44_100);
+        let samples = [0.5, -0.5, THRESHOLD, -THRESHOLD, 0.0, 0.1];
+
+        let mut s = samples;
+        l.apply_interleaved(&mut s, 2);
+        assert_eq!(s, samples);
+        assert_eq!(l.reduction, 0.0);
+    }
+
+    #[test]
+    // Peaks are limited instantly, the gain is
+    // linked across channels and slowly released.
+    fn limit() {
+        let mut l = Limiter::new(44_100);
+
+        let mut s = [2.0, 1.0, -4.0, 0.5];
+        l.apply_interleaved(&mut s, 2);
+        assert!(s.iter().all(|s| s.abs() <= THRESHOLD));
+        assert_eq!(s[0], 2.0 * THRESHOLD / 2.0);
+        assert_eq!(s[1], 1.0 * THRESHOLD / 2.0);
+
+        // The gain recovers, but not instantly.
+        let reduction = l.reduction;
+        let mut s = [0.1, 0.1];
+        l.apply_interleaved(&mut s, 2);
+        assert!(l.reduction < reduction);
+        assert!(l.reduction > 0.0);
+
+        // And eventually goes back to `1.0`, leaving samples untouched.
+        let mut s = vec![0.1; 44_100 * 3 * 2];
+        l.apply_interleaved(&mut s, 2);
+        assert_eq!(l.reduction, 0.0);
+        let mut s = [0.1, -0.3];
+        l.apply_interleaved(&mut s, 2);
+        assert_eq!(s, [0.1, -0.3]);
+    }
+
+    #[test]
+    fn planar() {
+        let mut l = Limiter::new(48_000);
+        let (mut left, mut right) = ([1.5, 0.0], [-0.5, 0.0]);
+        l.apply_planar(&mut [&mut left, &mut right]);
+        assert!(left[0] <= THRESHOLD);
+        assert!((left[0] / right[0] + 3.0).abs() < 0.0001);
+    }
+}
@@ -3,19 +3,35 @@ pub(crate) use msg::*;
 
 mod audio;
 pub(crate) use audio::*;
-pub use audio::{PREVIOUS_THRESHOLD, PREVIOUS_THRESHOLD_DEFAULT};
+pub use audio::{
+    BIT_PERFECT, DECODE_ERROR_THRESHOLD, DECODE_ERROR_THRESHOLD_DEFAULT, LIMITER, PREVIOUS_HISTORY,
+    PREVIOUS_THRESHOLD, PREVIOUS_THRESHOLD_DEFAULT, RESAMPLER_QUALITY, VOLUME_DB_CURVE,
+};
 
 // Public
 mod volume;
-pub use volume::Volume;
+pub use volume::{Volume, VOLUME_DB_MIN};
 mod append;
 pub use append::*;
 mod repeat;
 pub use repeat::*;
 mod seek;
 pub use seek::*;
+mod shuffle;
+pub use shuffle::*;
+mod auto_dj;
+pub use auto_dj::*;
+mod sleep;
+pub use sleep::*;
+mod channel_mix;
+pub use channel_mix::*;
+mod resampler_quality;
+pub use resampler_quality::*;
+mod output_path;
+pub use output_path::*;
 
 // Symphonia-related.
+pub(super) mod limiter;
 pub(super) mod output;
 #[cfg(not(target_os = "linux"))]
 pub(super) mod resampler;
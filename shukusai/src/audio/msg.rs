@@ -1,6 +1,6 @@
 //---------------------------------------------------------------------------------------------------- Use
 use crate::{
-    audio::{Append, Repeat, Seek, Volume},
+    audio::{Append, ChannelMix, Repeat, Seek, ShuffleMode, SleepTimer, Volume},
     collection::{AlbumKey, ArtistKey, Collection, SongKey},
 };
 use std::sync::Arc;
@@ -11,6 +11,7 @@ pub(crate) enum AudioToKernel {
     PlayError(anyhow::Error),   // There was an error while attempting to play a sound.
     SeekError(anyhow::Error),   // There was an error while attempting to seek audio.
     PathError((SongKey, anyhow::Error)), // `Path` error occurred when trying to play a song (probably doesn't exist).
+    DecodeError((SongKey, anyhow::Error)), // Too many packets of this song were corrupt, playback of it was given up on.
 }
 
 // These mostly map to `FrontendToKernel` messages.
@@ -25,13 +26,16 @@ pub(crate) enum KernelToAudio {
     // Audio settings.
     Repeat(Repeat),
     Volume(Volume),
+    AutoDj(bool),
+    Sleep(SleepTimer),
+    ChannelMix(ChannelMix),
 
     // Queue.
     QueueAddSong((SongKey, Append, bool, bool)),
     QueueAddAlbum((AlbumKey, Append, bool, bool, usize)),
     QueueAddArtist((ArtistKey, Append, bool, bool, usize)),
     QueueAddPlaylist((Arc<str>, Append, bool, bool, usize)),
-    Shuffle,
+    Shuffle(ShuffleMode),
     Clear(bool),
     Seek((Seek, u64)),
     Skip(usize),
@@ -40,6 +44,12 @@ pub(crate) enum KernelToAudio {
     // Queue Index.
     QueueSetIndex(usize),
     QueueRemoveRange((std::ops::Range<usize>, bool)),
+    QueueMove((usize, usize)),
+    QueueInsert((usize, Box<[SongKey]>)),
+    QueueSwap((usize, usize)),
+
+    // Queue Snapshots.
+    QueueRestore(Arc<str>),
 
     // Audio State.
     RestoreAudioState,
@@ -9,14 +9,42 @@
 // `https://github.com/pdeljanov/Symphonia/blob/master/symphonia-play/src/output.rs`
 
 //---------------------------------------------------------------------------------------------------- Use
-use crate::audio::Volume;
+use crate::audio::{
+    limiter::Limiter, ChannelMix, OutputPath, ResamplerQuality, Volume, BIT_PERFECT, LIMITER,
+    RESAMPLER_QUALITY,
+};
 use crate::constants::FESTIVAL;
-use crate::state::VOLUME;
+use crate::state::{SLEEP_FADE, VOLUME};
 use anyhow::anyhow;
 use benri::atomic_load;
 use symphonia::core::audio::*;
+use symphonia::core::sample::SampleFormat;
 use symphonia::core::units::Duration;
 
+//---------------------------------------------------------------------------------------------------- Volume
+// The `0.0..1.0` multiplier applied to samples, which
+// is the `VOLUME` with a sleep timer's fade applied.
+fn volume() -> f32 {
+    Volume::new(atomic_load!(VOLUME)).gain() * (atomic_load!(SLEEP_FADE) as f32 / 100.0)
+}
+
+//---------------------------------------------------------------------------------------------------- Sample Format
+// The native `SampleFormat` of a decoded buffer.
+pub(crate) fn sample_format(decoded: &AudioBufferRef<'_>) -> SampleFormat {
+    match decoded {
+        AudioBufferRef::U8(_) => SampleFormat::U8,
+        AudioBufferRef::U16(_) => SampleFormat::U16,
+        AudioBufferRef::U24(_) => SampleFormat::U24,
+        AudioBufferRef::U32(_) => SampleFormat::U32,
+        AudioBufferRef::S8(_) => SampleFormat::S8,
+        AudioBufferRef::S16(_) => SampleFormat::S16,
+        AudioBufferRef::S24(_) => SampleFormat::S24,
+        AudioBufferRef::S32(_) => SampleFormat::S32,
+        AudioBufferRef::F32(_) => SampleFormat::F32,
+        AudioBufferRef::F64(_) => SampleFormat::F64,
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- Audio Output
 // This `Output` trait describes the functions
 // needed to output audio to the device.
@@ -24,13 +52,23 @@ use symphonia::core::units::Duration;
 // It's needed because Linux uses `PulseAudio`
 // while Windows/macOS will use the `cpal` backend.
 pub(crate) trait Output: Sized {
-    fn write(&mut self, decoded: AudioBufferRef<'_>) -> std::result::Result<(), AudioOutputError>;
+    fn write(
+        &mut self,
+        decoded: AudioBufferRef<'_>,
+        mix: ChannelMix,
+    ) -> std::result::Result<(), AudioOutputError>;
     // Discard current audio samples.
     fn flush(&mut self);
+    // `format` is the native `SampleFormat` of the `Song`,
+    // which is only used for bit-perfect output.
     fn try_open(
         spec: SignalSpec,
         duration: Duration,
+        format: SampleFormat,
     ) -> std::result::Result<Self, AudioOutputError>;
+    // Should the audio device be re-opened for a `Song` with this
+    // `SampleFormat`, e.g, because the output settings were changed?
+    fn outdated(&self, format: SampleFormat) -> bool;
     fn play(&mut self) -> std::result::Result<(), AudioOutputError>;
     fn pause(&mut self) -> std::result::Result<(), AudioOutputError>;
 
@@ -44,7 +82,7 @@ pub(crate) trait Output: Sized {
             channels: Channels::FRONT_LEFT,
         };
 
-        Self::try_open(spec, 4096)
+        Self::try_open(spec, 4096, SampleFormat::F32)
     }
 }
 
@@ -84,14 +122,104 @@ mod output {
     use libpulse_binding as pulse;
     use libpulse_simple_binding as psimple;
 
-    use log::{error, warn};
+    use log::{debug, error, warn};
+    use symphonia::core::sample::i24;
 
     pub(crate) struct AudioOutput {
         pa: psimple::Simple,
         sample_buf: RawSampleBuffer<f32>,
         audio_buf: AudioBuffer<f32>,
+        // `Some` if the stream was opened bit-perfect.
+        raw_buf: Option<RawBuffer>,
+        limiter: Limiter,
+        // The `BIT_PERFECT` setting and `SampleFormat` this was opened with.
+        bit_perfect: bool,
+        format: SampleFormat,
         pub(crate) spec: SignalSpec,
         pub(crate) duration: Duration,
+        pub(crate) path: OutputPath,
+        pub(crate) rate: u32,
+    }
+
+    // An interleaved buffer in one of the native
+    // sample formats PulseAudio can play as-is.
+    enum RawBuffer {
+        U8(RawSampleBuffer<u8>),
+        S16(RawSampleBuffer<i16>),
+        S24(RawSampleBuffer<i24>),
+        S32(RawSampleBuffer<i32>),
+        F32(RawSampleBuffer<f32>),
+    }
+
+    impl RawBuffer {
+        // Returns the buffer and matching PulseAudio format for `format`.
+        //
+        // `None` if PulseAudio can't play it as-is.
+        fn new(
+            format: SampleFormat,
+            duration: Duration,
+            spec: SignalSpec,
+        ) -> Option<(Self, pulse::sample::Format)> {
+            use pulse::sample::Format;
+            let raw = match format {
+                SampleFormat::U8 => (Self::U8(RawSampleBuffer::new(duration, spec)), Format::U8),
+                SampleFormat::S16 => (
+                    Self::S16(RawSampleBuffer::new(duration, spec)),
+                    Format::S16NE,
+                ),
+                SampleFormat::S24 => (
+                    Self::S24(RawSampleBuffer::new(duration, spec)),
+                    Format::S24NE,
+                ),
+                SampleFormat::S32 => (
+                    Self::S32(RawSampleBuffer::new(duration, spec)),
+                    Format::S32NE,
+                ),
+                SampleFormat::F32 => (
+                    Self::F32(RawSampleBuffer::new(duration, spec)),
+                    Format::FLOAT32NE,
+                ),
+                _ => return None,
+            };
+            Some(raw)
+        }
+
+        // Interleave the samples as-is and return the bytes.
+        fn copy(&mut self, decoded: AudioBufferRef<'_>) -> &[u8] {
+            match self {
+                Self::U8(b) => {
+                    b.copy_interleaved_ref(decoded);
+                    b.as_bytes()
+                }
+                Self::S16(b) => {
+                    b.copy_interleaved_ref(decoded);
+                    b.as_bytes()
+                }
+                Self::S24(b) => {
+                    b.copy_interleaved_ref(decoded);
+                    b.as_bytes()
+                }
+                Self::S32(b) => {
+                    b.copy_interleaved_ref(decoded);
+                    b.as_bytes()
+                }
+                Self::F32(b) => {
+                    b.copy_interleaved_ref(decoded);
+                    b.as_bytes()
+                }
+            }
+        }
+    }
+
+    // Write interleaved bytes to PulseAudio.
+    fn pa_write(pa: &psimple::Simple, bytes: &[u8]) -> std::result::Result<(), AudioOutputError> {
+        match pa.write(bytes) {
+            Err(err) => {
+                error!("Audio - AudioOutput stream write error: {err}");
+                Err(AudioOutputError::StreamClosed(anyhow!(err)))
+            }
+            _ => Ok(()),
+        }
     }
 
     impl Output for AudioOutput {
@@ -106,15 +234,32 @@ mod output {
         fn try_open(
             spec: SignalSpec,
             duration: Duration,
+            format: SampleFormat,
         ) -> std::result::Result<Self, AudioOutputError> {
             // An interleaved buffer is required to send data to PulseAudio. Use a SampleBuffer to
             // move data between Symphonia AudioBuffers and the byte buffers required by PulseAudio.
             let sample_buf = RawSampleBuffer::<f32>::new(duration, spec);
             let audio_buf = AudioBuffer::<f32>::new(duration, spec);
 
+            // PulseAudio is always opened at the native sample rate,
+            // so bit-perfect output only has to match the format.
+            //
+            // Formats PulseAudio can't play as-is fallback to `f32`.
+            let bit_perfect = atomic_load!(BIT_PERFECT);
+            let (raw_buf, pa_format) = match bit_perfect {
+                true => match RawBuffer::new(format, duration, spec) {
+                    Some((raw, pa_format)) => (Some(raw), pa_format),
+                    None => {
+                        debug!("Audio - no bit-perfect output for {format:?}, using f32");
+                        (None, pulse::sample::Format::FLOAT32NE)
+                    }
+                },
+                false => (None, pulse::sample::Format::FLOAT32NE),
+            };
+
             // Create a PulseAudio stream specification.
             let pa_spec = pulse::sample::Spec {
-                format: pulse::sample::Format::FLOAT32NE,
+                format: pa_format,
                 channels: spec.channels.count() as u8,
                 rate: spec.rate,
             };
@@ -170,8 +315,17 @@ mod output {
                     pa,
                     sample_buf,
                     audio_buf,
+                    path: match raw_buf {
+                        Some(_) => OutputPath::BitPerfect,
+                        None => OutputPath::Native,
+                    },
+                    raw_buf,
+                    limiter: Limiter::new(spec.rate),
+                    bit_perfect,
+                    format,
                     spec,
                     duration,
+                    rate: spec.rate,
                 }),
                 Err(err) => Err(AudioOutputError::OpenStream(anyhow!(
                     "stream open error: {err}"
@@ -182,35 +336,52 @@ mod output {
         fn write(
             &mut self,
             decoded: AudioBufferRef<'_>,
+            mix: ChannelMix,
         ) -> std::result::Result<(), AudioOutputError> {
             // Do nothing if there are no audio frames.
             if decoded.frames() == 0 {
                 return Ok(());
             }
 
+            // Bit-perfect, write the samples untouched.
+            if let Some(raw_buf) = self.raw_buf.as_mut() {
+                return pa_write(&self.pa, raw_buf.copy(decoded));
+            }
+
             // Convert the buffer to `f32` and multiply
             // it by `0.0..1.0` to set volume levels.
-            let volume = Volume::new(atomic_load!(VOLUME)).f32();
+            let volume = volume();
             decoded.convert(&mut self.audio_buf);
             self.audio_buf.transform(|f| f * volume);
 
+            // Mix the left/right channels.
+            mix.apply_planar(self.audio_buf.planes_mut().planes());
+
+            // Limit peaks after all the gain stages.
+            if atomic_load!(LIMITER) {
+                self.limiter
+                    .apply_planar(self.audio_buf.planes_mut().planes());
+            }
+
             // Interleave samples from the audio buffer into the sample buffer.
             self.sample_buf
                 .copy_interleaved_ref(self.audio_buf.as_audio_buffer_ref());
 
             // Write interleaved samples to PulseAudio.
-            match self.pa.write(self.sample_buf.as_bytes()) {
-                Err(err) => {
-                    error!("Audio - AudioOutput stream write error: {err}");
-                    Err(AudioOutputError::StreamClosed(anyhow!(err)))
-                }
-                _ => Ok(()),
-            }
+            pa_write(&self.pa, self.sample_buf.as_bytes())
         }
 
         fn flush(&mut self) {
             _ = self.pa.flush();
         }
+
+        fn outdated(&self, format: SampleFormat) -> bool {
+            let bit_perfect = atomic_load!(BIT_PERFECT);
+            // `SampleFormat` isn't `PartialEq`.
+            let format_changed =
+                std::mem::discriminant(&format) != std::mem::discriminant(&self.format);
+            bit_perfect != self.bit_perfect || (bit_perfect && format_changed)
+        }
     }
 
     /// Maps a set of Symphonia `Channels` to a PulseAudio channel map.
@@ -279,8 +450,14 @@ mod output {
         stream: cpal::Stream,
         resampler: Option<Resampler<f32>>,
         samples: Vec<f32>,
+        limiter: Limiter,
+        // The `BIT_PERFECT` and `RESAMPLER_QUALITY` settings this was opened with.
+        bit_perfect: bool,
+        quality: ResamplerQuality,
         pub(crate) spec: SignalSpec,
         pub(crate) duration: Duration,
+        pub(crate) path: OutputPath,
+        pub(crate) rate: u32,
     }
 
     impl Output for AudioOutput {
@@ -300,7 +477,11 @@ mod output {
         fn try_open(
             spec: SignalSpec,
             duration: Duration,
+            _format: SampleFormat,
         ) -> std::result::Result<Self, AudioOutputError> {
+            let bit_perfect = atomic_load!(BIT_PERFECT);
+            let quality = ResamplerQuality::from_u8(atomic_load!(RESAMPLER_QUALITY));
+
             // Get default host.
             let host = cpal::default_host();
 
@@ -314,13 +495,13 @@ mod output {
                 }
             };
 
-            let config = match device.default_output_config() {
+            let default_config = match device.default_output_config() {
                 Ok(config) => config,
                 Err(err) => return Err(AudioOutputError::OpenStream(anyhow!(err))),
             };
 
             // SOMEDAY: support i16/u16.
-            if config.sample_format() != cpal::SampleFormat::F32 {
+            if default_config.sample_format() != cpal::SampleFormat::F32 {
                 return Err(AudioOutputError::NonF32(anyhow!(
                     "sample format is not f32"
                 )));
@@ -328,37 +509,62 @@ mod output {
 
             let num_channels = spec.channels.count();
 
-            // Output audio stream config.
-            #[cfg(windows)]
-            let config = config.config();
-            #[cfg(unix)]
-            let config = cpal::StreamConfig {
+            // The `Song`'s native stream config.
+            let native_config = cpal::StreamConfig {
                 channels: num_channels as cpal::ChannelCount,
                 sample_rate: cpal::SampleRate(spec.rate),
                 buffer_size: cpal::BufferSize::Default,
             };
 
+            // Output audio stream config.
+            //
+            // Bit-perfect output always asks for the native config.
+            #[cfg(windows)]
+            let config = match bit_perfect {
+                true => native_config,
+                false => default_config.config(),
+            };
+            #[cfg(unix)]
+            let config = native_config;
+
             // Create a ring buffer with a capacity for up-to 50ms of audio.
             let ring_len = ((50 * spec.rate as usize) / 1000) * num_channels;
 
             let ring_buf = SpscRb::new(ring_len);
-            let (ring_buf_producer, ring_buf_consumer) = (ring_buf.producer(), ring_buf.consumer());
-
-            let stream_result = device.build_output_stream(
-                &config,
-                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                    // Write out as many samples as possible from the ring buffer to the audio output.
-                    let written = ring_buf_consumer.read(data).unwrap_or(0);
-
-                    // Mute any remaining samples.
-                    data[written..].fill(0.0);
-                },
-                move |err| warn!("Audio - audio output error: {err}"),
-                None,
-            );
+            let ring_buf_producer = ring_buf.producer();
+
+            let build_stream = |config: &cpal::StreamConfig| {
+                let ring_buf_consumer = ring_buf.consumer();
+                device.build_output_stream(
+                    config,
+                    move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                        // Write out as many samples as possible from the ring buffer to the audio output.
+                        let written = ring_buf_consumer.read(data).unwrap_or(0);
+
+                        // Mute any remaining samples.
+                        data[written..].fill(0.0);
+                    },
+                    move |err| warn!("Audio - audio output error: {err}"),
+                    None,
+                )
+            };
 
-            let stream = match stream_result {
-                Ok(s) => s,
+            // If the device doesn't support the native
+            // config, fallback to the default + resampling.
+            let (stream, config) = match build_stream(&config) {
+                Ok(s) => (s, config),
+                #[cfg(windows)]
+                Err(err) if bit_perfect => {
+                    warn!(
+                        "Audio - bit-perfect output at {}Hz failed, resampling: {err}",
+                        spec.rate
+                    );
+                    let config = default_config.config();
+                    match build_stream(&config) {
+                        Ok(s) => (s, config),
+                        Err(err) => return Err(AudioOutputError::OpenStream(anyhow!(err))),
+                    }
+                }
                 Err(err) => return Err(AudioOutputError::OpenStream(anyhow!(err))),
             };
 
@@ -382,10 +588,10 @@ mod output {
 
             let resampler = if resampler_needed {
                 debug!(
-                    "Audio - resampling {}Hz to {}Hz",
+                    "Audio - resampling {}Hz to {}Hz ({quality})",
                     spec.rate, config.sample_rate.0
                 );
-                match Resampler::new(spec, config.sample_rate.0 as usize, duration) {
+                match Resampler::new(spec, config.sample_rate.0 as usize, duration, quality) {
                     Ok(r) => Some(r),
                     Err(e) => {
                         error!("Audio - failed to create resampler: {e}");
@@ -399,21 +605,33 @@ mod output {
 
             let samples = Vec::with_capacity(num_channels * duration as usize);
 
+            let path = match (&resampler, bit_perfect) {
+                (Some(_), _) => OutputPath::Resampled,
+                (None, true) => OutputPath::BitPerfect,
+                (None, false) => OutputPath::Native,
+            };
+
             Ok(Self {
                 ring_buf,
                 ring_buf_producer,
                 sample_buf,
                 samples,
+                limiter: Limiter::new(config.sample_rate.0),
                 stream,
                 resampler,
+                bit_perfect,
+                quality,
                 spec,
                 duration,
+                path,
+                rate: config.sample_rate.0,
             })
         }
 
         fn write(
             &mut self,
             decoded: AudioBufferRef<'_>,
+            mix: ChannelMix,
         ) -> std::result::Result<(), AudioOutputError> {
             // Do nothing if there are no audio frames.
             if decoded.frames() == 0 {
@@ -442,15 +660,27 @@ mod output {
             self.samples.clear();
             self.samples.extend_from_slice(samples);
 
-            // Apply volume transformation.
-            let volume = Volume::new(atomic_load!(VOLUME)).f32();
+            // Bit-perfect, leave the samples untouched.
+            if self.path != OutputPath::BitPerfect {
+                // Apply volume transformation.
+                let volume = volume();
 
-            // Taken from: https://docs.rs/symphonia-core/0.5.3/src/symphonia_core/audio.rs.html#680-692
-            //
-            // Changed to use iterators over indexing.
-            self.samples
-                .chunks_mut(capacity)
-                .for_each(|plane| plane.iter_mut().for_each(|sample| *sample *= volume));
+                // Taken from: https://docs.rs/symphonia-core/0.5.3/src/symphonia_core/audio.rs.html#680-692
+                //
+                // Changed to use iterators over indexing.
+                self.samples
+                    .chunks_mut(capacity)
+                    .for_each(|plane| plane.iter_mut().for_each(|sample| *sample *= volume));
+
+                // Mix the left/right channels.
+                mix.apply_interleaved(&mut self.samples, self.spec.channels.count());
+
+                // Limit peaks after all the gain stages.
+                if atomic_load!(LIMITER) {
+                    self.limiter
+                        .apply_interleaved(&mut self.samples, self.spec.channels.count());
+                }
+            }
 
             let mut samples = self.samples.as_slice();
 
@@ -472,5 +702,12 @@ mod output {
                 std::thread::sleep(std::time::Duration::from_millis(1));
             }
         }
+
+        fn outdated(&self, _format: SampleFormat) -> bool {
+            // The quality only matters if we're resampling.
+            atomic_load!(BIT_PERFECT) != self.bit_perfect
+                || (self.resampler.is_some()
+                    && ResamplerQuality::from_u8(atomic_load!(RESAMPLER_QUALITY)) != self.quality)
+        }
     }
 }
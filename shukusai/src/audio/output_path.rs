@@ -0,0 +1,49 @@
+//---------------------------------------------------------------------------------------------------- Use
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
+
+//---------------------------------------------------------------------------------------------------- OutputPath
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    AsRefStr,
+    Display,
+    EnumCount,
+    EnumIter,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+/// How `Audio` is currently getting samples to the audio device.
+///
+/// This is reported in [`crate::state::AudioState::output_path`].
+pub enum OutputPath {
+    #[default]
+    /// Nothing has been played yet.
+    None,
+    /// Samples are sent at the `Song`'s native sample rate,
+    /// converted to `f32` with volume and [`crate::audio::ChannelMix`] applied.
+    Native,
+    /// Samples are resampled to the audio device's sample rate
+    /// with the current [`crate::audio::ResamplerQuality`].
+    Resampled,
+    /// Samples are sent untouched at the `Song`'s native sample rate and format.
+    ///
+    /// Volume, [`crate::audio::ChannelMix`] and the
+    /// [`crate::audio::LIMITER`] are bypassed.
+    BitPerfect,
+}
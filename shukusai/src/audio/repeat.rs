@@ -13,6 +13,14 @@ const REPEAT_QUEUE_PAUSE: &str =
     "Repeat the entire queue after it finishes, but do not start immediately";
 /// [`Repeat::Off`]
 const REPEAT_OFF: &str = "Turn off all repeating";
+/// [`Repeat::Album`]
+const REPEAT_ALBUM: &str = "Repeat the current album's songs in the queue after they finish";
+/// [`Repeat::Artist`]
+const REPEAT_ARTIST: &str = "Repeat the current artist's songs in the queue after they finish";
+/// [`Repeat::Count`]
+const REPEAT_COUNT: &str = "Repeat a single song a set amount of times, then turn off repeating";
+/// [`Repeat::Ab`]
+const REPEAT_AB: &str = "Loop between two timestamps (A-B) within the current song";
 
 //---------------------------------------------------------------------------------------------------- Repeat
 #[derive(
@@ -49,6 +57,32 @@ pub enum Repeat {
     QueuePause,
     /// Turn off all repeating.
     Off,
+    /// When finishing the last [`Song`] of the current [`Album`]'s
+    /// span in the queue, go back to the first [`Song`] of that span.
+    ///
+    /// The span is all the [`Song`]'s next to the current
+    /// one in the queue that belong to the same [`Album`].
+    Album,
+    /// Same as [`Self::Album`], but for the current [`Artist`].
+    Artist,
+    /// When finishing a [`Song`] in the queue, repeat
+    /// it this many more times, then turn off repeating.
+    ///
+    /// The count starts over when the current [`Song`] changes.
+    ///
+    /// `0` acts the same as [`Self::Off`].
+    Count(u8),
+    /// Loop between second `a` and second `b` of the current [`Song`].
+    ///
+    /// `a` must be less than `b`.
+    ///
+    /// This is turned [`Self::Off`] when the current [`Song`] changes.
+    Ab {
+        /// The second the loop starts at.
+        a: u32,
+        /// The second the loop jumps back to `a` at.
+        b: u32,
+    },
 }
 
 impl Repeat {
@@ -66,32 +100,79 @@ impl Repeat {
             Queue => REPEAT_QUEUE,
             QueuePause => REPEAT_QUEUE_PAUSE,
             Off => REPEAT_OFF,
+            Album => REPEAT_ALBUM,
+            Artist => REPEAT_ARTIST,
+            Count(_) => REPEAT_COUNT,
+            Ab { .. } => REPEAT_AB,
         }
     }
 
     /// Returns the next sequential [`Self`] variant.
     ///
     /// This returns the _first_ if at the _last_.
+    ///
+    /// [`Self::Count`] and [`Self::Ab`] need values, so
+    /// they are not part of the cycle and return [`Self::Off`].
     pub const fn next(&self) -> Self {
         match self {
-            Self::Song => Self::Queue,
+            Self::Song => Self::Album,
+            Self::Album => Self::Artist,
+            Self::Artist => Self::Queue,
             Self::Queue => Self::QueuePause,
             Self::QueuePause => Self::Off,
             Self::Off => Self::Song,
+            Self::Count(_) | Self::Ab { .. } => Self::Off,
         }
     }
 
     /// Returns the previous sequential [`Self`] variant.
     ///
     /// This returns the _last_ if at the _first_.
+    ///
+    /// [`Self::Count`] and [`Self::Ab`] return [`Self::Off`].
     pub const fn previous(&self) -> Self {
         match self {
             Self::Song => Self::Off,
-            Self::Queue => Self::Song,
+            Self::Album => Self::Song,
+            Self::Artist => Self::Album,
+            Self::Queue => Self::Artist,
             Self::QueuePause => Self::Queue,
             Self::Off => Self::QueuePause,
+            Self::Count(_) | Self::Ab { .. } => Self::Off,
         }
     }
+
+    /// Returns `false` if this is an [`Self::Ab`] where `a` is not less than `b`.
+    pub const fn is_valid(&self) -> bool {
+        match self {
+            Self::Ab { a, b } => *a < *b,
+            _ => true,
+        }
+    }
+}
+
+/// Returns the inclusive `(start, end)` indices of the span around `index`
+/// where every item in `queue` has the same `group` as `queue[index]`.
+///
+/// This is the span [`Repeat::Album`] and [`Repeat::Artist`] loop over.
+pub(crate) fn repeat_span<T, G: PartialEq>(
+    queue: &std::collections::VecDeque<T>,
+    index: usize,
+    group: impl Fn(&T) -> G,
+) -> (usize, usize) {
+    let current = group(&queue[index]);
+
+    let mut start = index;
+    while start > 0 && group(&queue[start - 1]) == current {
+        start -= 1;
+    }
+
+    let mut end = index;
+    while end + 1 < queue.len() && group(&queue[end + 1]) == current {
+        end += 1;
+    }
+
+    (start, end)
 }
 
 impl Default for Repeat {
@@ -111,6 +192,8 @@ mod tests {
     // 1. Gives a different string
     // 2. `.next()` gives a different variant
     // 3. `.prev()` gives a different variant
+    //
+    // `Count` and `Ab` are not part of the cycle.
     fn diff() {
         let mut set1 = std::collections::HashSet::new();
         let mut set2 = std::collections::HashSet::new();
@@ -118,8 +201,42 @@ mod tests {
 
         for i in Repeat::iter() {
             assert!(set1.insert(i.human()));
+            if matches!(i, Repeat::Count(_) | Repeat::Ab { .. }) {
+                assert_eq!(i.next(), Repeat::Off);
+                assert_eq!(i.previous(), Repeat::Off);
+                continue;
+            }
             assert!(set2.insert(i.next()));
             assert!(set3.insert(i.previous()));
         }
     }
+
+    #[test]
+    // The span stops at the first different group on each side.
+    fn span() {
+        let queue: std::collections::VecDeque<u8> = [0, 1, 1, 1, 0, 1].into();
+        assert_eq!(repeat_span(&queue, 0, |t| *t), (0, 0));
+        assert_eq!(repeat_span(&queue, 1, |t| *t), (1, 3));
+        assert_eq!(repeat_span(&queue, 2, |t| *t), (1, 3));
+        assert_eq!(repeat_span(&queue, 3, |t| *t), (1, 3));
+        assert_eq!(repeat_span(&queue, 5, |t| *t), (5, 5));
+    }
+
+    #[test]
+    // Data variants are objects, the rest are strings.
+    fn serde() {
+        for (repeat, json) in [
+            (Repeat::Off, r#""off""#),
+            (Repeat::Album, r#""album""#),
+            (Repeat::Artist, r#""artist""#),
+            (Repeat::Count(3), r#"{"count":3}"#),
+            (Repeat::Ab { a: 30, b: 45 }, r#"{"ab":{"a":30,"b":45}}"#),
+        ] {
+            assert_eq!(serde_json::to_string(&repeat).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Repeat>(json).unwrap(), repeat);
+        }
+
+        assert!(Repeat::Ab { a: 1, b: 2 }.is_valid());
+        assert!(!Repeat::Ab { a: 2, b: 2 }.is_valid());
+    }
 }
@@ -18,13 +18,43 @@
 // I did not write this code so I have zero clue
 // on how to reimplement it for `0.12.0`+ versions.
 
+use crate::audio::ResamplerQuality;
 use anyhow::{bail, Error};
 use symphonia::core::audio::{AudioBuffer, AudioBufferRef, Signal, SignalSpec};
 use symphonia::core::conv::{FromSample, IntoSample};
 use symphonia::core::sample::Sample;
 
+// The `rubato` resampler picked by the `ResamplerQuality`.
+//
+// `rubato::Resampler` has generic methods,
+// so it can't be used as a trait object.
+enum Inner {
+    Fft(rubato::FftFixedIn<f32>),
+    Sinc(rubato::SincFixedIn<f32>),
+}
+
+// The sinc interpolation parameters for `ResamplerQuality::{Fast,Best}`.
+fn sinc_parameters(quality: ResamplerQuality) -> rubato::InterpolationParameters {
+    match quality {
+        ResamplerQuality::Best => rubato::InterpolationParameters {
+            sinc_len: 256,
+            f_cutoff: 0.95,
+            oversampling_factor: 256,
+            interpolation: rubato::InterpolationType::Cubic,
+            window: rubato::WindowFunction::BlackmanHarris2,
+        },
+        _ => rubato::InterpolationParameters {
+            sinc_len: 64,
+            f_cutoff: 0.915,
+            oversampling_factor: 128,
+            interpolation: rubato::InterpolationType::Linear,
+            window: rubato::WindowFunction::Hann2,
+        },
+    }
+}
+
 pub(super) struct Resampler<T> {
-    resampler: rubato::FftFixedIn<f32>,
+    resampler: Inner,
     input: Vec<Vec<f32>>,
     output: Vec<Vec<f32>>,
     interleaved: Vec<T>,
@@ -44,12 +74,14 @@ where
             }
 
             // Resample.
-            rubato::Resampler::process_into_buffer(
-                &mut self.resampler,
-                &input,
-                &mut self.output,
-                None,
-            )?;
+            match &mut self.resampler {
+                Inner::Fft(r) => {
+                    rubato::Resampler::process_into_buffer(r, &input, &mut self.output, None)?
+                }
+                Inner::Sinc(r) => {
+                    rubato::Resampler::process_into_buffer(r, &input, &mut self.output, None)?
+                }
+            }
         }
 
         // Remove consumed samples from the input buffer.
@@ -76,19 +108,34 @@ where
         spec: SignalSpec,
         to_sample_rate: usize,
         duration: u64,
+        quality: ResamplerQuality,
     ) -> Result<Self, Error> {
         let duration = TryInto::<usize>::try_into(duration)?;
         let num_channels = spec.channels.count();
 
-        let resampler = rubato::FftFixedIn::<f32>::new(
-            spec.rate as usize,
-            to_sample_rate,
-            duration,
-            2,
-            num_channels,
-        )?;
+        let resampler = match quality {
+            ResamplerQuality::Balanced => Inner::Fft(rubato::FftFixedIn::<f32>::new(
+                spec.rate as usize,
+                to_sample_rate,
+                duration,
+                2,
+                num_channels,
+            )?),
+            ResamplerQuality::Fast | ResamplerQuality::Best => {
+                Inner::Sinc(rubato::SincFixedIn::<f32>::new(
+                    to_sample_rate as f64 / spec.rate as f64,
+                    1.0,
+                    sinc_parameters(quality),
+                    duration,
+                    num_channels,
+                )?)
+            }
+        };
 
-        let output = rubato::Resampler::output_buffer_allocate(&resampler);
+        let output = match &resampler {
+            Inner::Fft(r) => rubato::Resampler::output_buffer_allocate(r),
+            Inner::Sinc(r) => rubato::Resampler::output_buffer_allocate(r),
+        };
 
         let input = vec![Vec::with_capacity(duration); num_channels];
 
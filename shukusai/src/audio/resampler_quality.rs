@@ -0,0 +1,111 @@
+//---------------------------------------------------------------------------------------------------- Use
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// [`ResamplerQuality::Fast`]
+const RESAMPLER_FAST: &str = "Fast, low CPU usage with some aliasing";
+/// [`ResamplerQuality::Balanced`]
+const RESAMPLER_BALANCED: &str = "Balanced, good quality with moderate CPU usage";
+/// [`ResamplerQuality::Best`]
+const RESAMPLER_BEST: &str = "Best, highest quality with the most CPU usage";
+
+//---------------------------------------------------------------------------------------------------- ResamplerQuality
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    AsRefStr,
+    Display,
+    EnumCount,
+    EnumIter,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+/// The quality of the resampler used when the audio device
+/// doesn't support the sample rate of the current `Song`.
+///
+/// This is only used on platforms (Windows/macOS) where `Audio` resamples,
+/// and it does nothing if [`crate::audio::BIT_PERFECT`] output is active.
+pub enum ResamplerQuality {
+    /// A short sinc filter with linear interpolation.
+    Fast,
+    /// A synchronous FFT resampler.
+    Balanced,
+    /// A long sinc filter with cubic interpolation.
+    Best,
+}
+
+impl ResamplerQuality {
+    /// Returns the default, [`Self::Balanced`].
+    pub const fn new() -> Self {
+        Self::Balanced
+    }
+
+    #[inline]
+    /// Returns formatted, human readable versions.
+    pub const fn human(&self) -> &'static str {
+        match self {
+            Self::Fast => RESAMPLER_FAST,
+            Self::Balanced => RESAMPLER_BALANCED,
+            Self::Best => RESAMPLER_BEST,
+        }
+    }
+
+    #[inline]
+    /// Create a [`ResamplerQuality`] from the [`u8`] stored in
+    /// [`crate::audio::RESAMPLER_QUALITY`], e.g `ResamplerQuality::Best as u8`.
+    ///
+    /// Invalid values return [`Self::new`].
+    pub const fn from_u8(u: u8) -> Self {
+        match u {
+            0 => Self::Fast,
+            1 => Self::Balanced,
+            2 => Self::Best,
+            _ => Self::new(),
+        }
+    }
+}
+
+impl Default for ResamplerQuality {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::*;
+
+    #[test]
+    // Each variant survives the `u8` round-trip and has a different string.
+    fn from_u8() {
+        let mut set = std::collections::HashSet::new();
+
+        for i in ResamplerQuality::iter() {
+            assert_eq!(ResamplerQuality::from_u8(i as u8), i);
+            assert!(set.insert(i.human()));
+        }
+
+        assert_eq!(
+            ResamplerQuality::from_u8(u8::MAX),
+            ResamplerQuality::Balanced
+        );
+    }
+}
@@ -35,4 +35,13 @@ pub enum Seek {
     Backward,
     /// Seek to an absolute second timestamp
     Absolute,
+    /// Seek to the start of the next chapter, the second amount is ignored
+    ///
+    /// This skips to the next song if there are no more chapters,
+    /// see [`crate::collection::Chapters`].
+    NextChapter,
+    /// Seek to the start of the current (or previous) chapter, the second amount is ignored
+    ///
+    /// See [`crate::collection::previous_chapter`].
+    PreviousChapter,
 }
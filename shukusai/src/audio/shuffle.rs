@@ -0,0 +1,486 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::collection::{
+    AlbumKey, ArtistKey, Collection, PlayStats, Ratings, SongKey, PLAY_STATS, RATINGS, RATING_MAX,
+};
+use bincode::{Decode, Encode};
+use rand::{distributions::WeightedIndex, prelude::Distribution, seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// [`ShuffleMode::Songs`]
+const SHUFFLE_SONGS: &str = "Shuffle every song";
+/// [`ShuffleMode::Albums`]
+const SHUFFLE_ALBUMS: &str = "Shuffle the album order, but keep each album's tracks in order";
+/// [`ShuffleMode::Spread`]
+const SHUFFLE_SPREAD: &str = "Shuffle, but avoid the same artist or album back to back";
+/// [`ShuffleMode::Weighted`]
+const SHUFFLE_WEIGHTED: &str = "Shuffle, but favor rarely played and highly rated songs";
+
+/// How many times [`ShuffleMode::Spread`] re-rolls a random
+/// pick that has the same `Artist` before settling for it.
+const SPREAD_ATTEMPTS: usize = 16;
+
+//---------------------------------------------------------------------------------------------------- ShuffleMode
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    AsRefStr,
+    Display,
+    EnumCount,
+    EnumIter,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+/// The different ways the queue (or a random pick) can be shuffled.
+pub enum ShuffleMode {
+    /// A plain random permutation of every [`Song`].
+    Songs,
+    /// Shuffle the order of [`Album`]'s, but keep the
+    /// [`Song`]'s within each [`Album`] in track order.
+    Albums,
+    /// Shuffle, but avoid the same [`Artist`]
+    /// (or at least the same [`Album`]) back to back.
+    Spread,
+    /// Shuffle, but favor [`Song`]'s that have been
+    /// played less often or are rated higher.
+    ///
+    /// Unrated [`Song`]'s are treated as a middle rating.
+    Weighted,
+}
+
+impl ShuffleMode {
+    /// Returns the default, [`Self::Songs`].
+    pub const fn new() -> Self {
+        Self::Songs
+    }
+
+    #[inline]
+    /// Returns formatted, human readable versions.
+    pub const fn human(&self) -> &'static str {
+        use ShuffleMode::*;
+        match self {
+            Songs => SHUFFLE_SONGS,
+            Albums => SHUFFLE_ALBUMS,
+            Spread => SHUFFLE_SPREAD,
+            Weighted => SHUFFLE_WEIGHTED,
+        }
+    }
+
+    /// Returns the next sequential [`Self`] variant.
+    ///
+    /// This returns the _first_ if at the _last_.
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Songs => Self::Albums,
+            Self::Albums => Self::Spread,
+            Self::Spread => Self::Weighted,
+            Self::Weighted => Self::Songs,
+        }
+    }
+
+    /// Returns the previous sequential [`Self`] variant.
+    ///
+    /// This returns the _last_ if at the _first_.
+    pub const fn previous(&self) -> Self {
+        match self {
+            Self::Songs => Self::Weighted,
+            Self::Albums => Self::Songs,
+            Self::Spread => Self::Albums,
+            Self::Weighted => Self::Spread,
+        }
+    }
+
+    //-------------------------------------------------- Shuffle.
+    /// INVARIANT: assumes the keys in `queue` are valid.
+    ///
+    /// Shuffle `queue` in place according to this mode.
+    pub fn shuffle<R: Rng + ?Sized>(
+        &self,
+        queue: &mut [SongKey],
+        collection: &Collection,
+        rng: &mut R,
+    ) {
+        match self {
+            Self::Songs => queue.shuffle(rng),
+            Self::Albums => {
+                // `Album::songs` is always in track order.
+                shuffle_groups(
+                    queue,
+                    |k| collection.songs[*k].album,
+                    |k| {
+                        let album = &collection.albums[collection.songs[*k].album];
+                        album.songs.iter().position(|s| s == k)
+                    },
+                    rng,
+                )
+            }
+            Self::Spread => {
+                queue.shuffle(rng);
+                spread(
+                    queue,
+                    |k| collection.albums[collection.songs[*k].album].artist,
+                    |k| collection.songs[*k].album,
+                );
+            }
+            Self::Weighted => {
+                let (play_stats, ratings) = (PLAY_STATS.read(), RATINGS.read());
+                weighted_order(
+                    queue,
+                    |k| song_weight(*k, collection, &play_stats, &ratings),
+                    rng,
+                );
+            }
+        }
+    }
+
+    //-------------------------------------------------- Random picks.
+    /// Get a random [`SongKey`] according to this mode.
+    ///
+    /// `current` is the `Song` currently set, if any, which
+    /// [`Self::Spread`] uses to pick a `Song` by a different `Artist`.
+    ///
+    /// [`Self::Songs`] and [`Self::Albums`] are the same as [`Collection::rand_song`].
+    ///
+    /// [`Option::None`] is returned if there are no `Song`'s.
+    pub fn rand_song(&self, collection: &Collection, current: Option<SongKey>) -> Option<SongKey> {
+        match (self, current) {
+            (Self::Spread, Some(current)) => {
+                let artist = collection.artist_from_song(current).1;
+                reroll(
+                    || collection.rand_song(Some(current)),
+                    |k| collection.artist_from_song(k).1 != artist,
+                )
+            }
+            (Self::Weighted, _) => {
+                let (play_stats, ratings) = (PLAY_STATS.read(), RATINGS.read());
+                let weights = collection
+                    .songs
+                    .iter()
+                    .map(|s| song_weight(s.key, collection, &play_stats, &ratings));
+                weighted_pick(weights).map(SongKey::from)
+            }
+            _ => collection.rand_song(None),
+        }
+    }
+
+    /// Get a random [`AlbumKey`] according to this mode.
+    ///
+    /// [`Self::Spread`] picks an `Album` by a different `Artist` than `current`.
+    ///
+    /// [`Self::Weighted`] weighs each `Album` by the average weight of its `Song`'s.
+    ///
+    /// [`Option::None`] is returned if there are no `Album`'s.
+    pub fn rand_album(
+        &self,
+        collection: &Collection,
+        current: Option<SongKey>,
+    ) -> Option<AlbumKey> {
+        match (self, current) {
+            (Self::Spread, Some(current)) => {
+                let artist = collection.artist_from_song(current).1;
+                reroll(
+                    || collection.rand_album(None),
+                    |k| collection.albums[k].artist != artist,
+                )
+            }
+            (Self::Weighted, _) => {
+                let (play_stats, ratings) = (PLAY_STATS.read(), RATINGS.read());
+                let weights = collection.albums.iter().map(|a| {
+                    average(
+                        a.songs
+                            .iter()
+                            .map(|k| song_weight(*k, collection, &play_stats, &ratings)),
+                    )
+                });
+                weighted_pick(weights).map(AlbumKey::from)
+            }
+            _ => collection.rand_album(None),
+        }
+    }
+
+    /// Get a random [`ArtistKey`] according to this mode.
+    ///
+    /// [`Self::Spread`] never picks the `Artist` of `current`.
+    ///
+    /// [`Self::Weighted`] weighs each `Artist` by the average weight of its `Song`'s.
+    ///
+    /// [`Option::None`] is returned if there are no `Artist`'s.
+    pub fn rand_artist(
+        &self,
+        collection: &Collection,
+        current: Option<SongKey>,
+    ) -> Option<ArtistKey> {
+        match (self, current) {
+            (Self::Spread, Some(current)) => {
+                collection.rand_artist(Some(collection.artist_from_song(current).1))
+            }
+            (Self::Weighted, _) => {
+                let (play_stats, ratings) = (PLAY_STATS.read(), RATINGS.read());
+                let weights = collection.artists.iter().map(|a| {
+                    average(
+                        a.songs
+                            .iter()
+                            .map(|k| song_weight(*k, collection, &play_stats, &ratings)),
+                    )
+                });
+                weighted_pick(weights).map(ArtistKey::from)
+            }
+            _ => collection.rand_artist(None),
+        }
+    }
+}
+
+impl Default for ShuffleMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Private functions
+// The weight of a `Song` in `ShuffleMode::Weighted`.
+fn song_weight(
+    key: SongKey,
+    collection: &Collection,
+    play_stats: &PlayStats,
+    ratings: &Ratings,
+) -> f64 {
+    weight(
+        ratings.song(key, collection),
+        play_stats.song(key, collection).play_count,
+    )
+}
+
+// Higher ratings weigh more, each play weighs less.
+//
+// Unrated (`0`) counts as the middle rating.
+fn weight(rating: u8, play_count: u32) -> f64 {
+    let rating = match rating {
+        0 => (RATING_MAX + 1) / 2,
+        r => r.min(RATING_MAX),
+    };
+
+    rating as f64 / (play_count as f64 + 1.0)
+}
+
+// The average of `weights`, `0.0` if empty.
+fn average(weights: impl Iterator<Item = f64>) -> f64 {
+    let (sum, len) = weights.fold((0.0, 0_usize), |(sum, len), w| (sum + w, len + 1));
+    match len {
+        0 => 0.0,
+        _ => sum / len as f64,
+    }
+}
+
+// Pick a random index, weighted by `weights`.
+//
+// `None` if empty or every weight is `0.0`.
+fn weighted_pick(weights: impl Iterator<Item = f64>) -> Option<usize> {
+    WeightedIndex::new(weights)
+        .ok()
+        .map(|w| w.sample(&mut rand::thread_rng()))
+}
+
+// Call `pick` until `ok`, settling for the last
+// pick after `SPREAD_ATTEMPTS` tries.
+fn reroll<T: Copy>(mut pick: impl FnMut() -> Option<T>, ok: impl Fn(T) -> bool) -> Option<T> {
+    let mut last = None;
+
+    for _ in 0..SPREAD_ATTEMPTS {
+        let t = pick()?;
+        if ok(t) {
+            return Some(t);
+        }
+        last = Some(t);
+    }
+
+    last
+}
+
+// Split `queue` into groups, sort each group by `order`, then shuffle the order of the groups.
+fn shuffle_groups<T, G, O, R>(
+    queue: &mut [T],
+    group: impl Fn(&T) -> G,
+    order: impl Fn(&T) -> O,
+    rng: &mut R,
+) where
+    T: Copy,
+    G: Ord,
+    O: Ord,
+    R: Rng + ?Sized,
+{
+    let mut groups: BTreeMap<G, Vec<T>> = BTreeMap::new();
+    for t in queue.iter() {
+        groups.entry(group(t)).or_default().push(*t);
+    }
+
+    let mut groups: Vec<Vec<T>> = groups.into_values().collect();
+    groups.iter_mut().for_each(|g| g.sort_by_key(&order));
+    groups.shuffle(rng);
+
+    for (slot, t) in queue.iter_mut().zip(groups.into_iter().flatten()) {
+        *slot = t;
+    }
+}
+
+// Swap later items forward so that neighbours share the same
+// `primary` as rarely as possible. If that can't be avoided,
+// at least avoid sharing the same `secondary`.
+fn spread<T, A, B>(queue: &mut [T], primary: impl Fn(&T) -> A, secondary: impl Fn(&T) -> B)
+where
+    A: PartialEq,
+    B: PartialEq,
+{
+    let len = queue.len();
+
+    for i in 1..len {
+        let a = primary(&queue[i - 1]);
+        if primary(&queue[i]) != a {
+            continue;
+        }
+
+        let b = secondary(&queue[i - 1]);
+        let swap = (i + 1..len).find(|j| primary(&queue[*j]) != a).or_else(|| {
+            match secondary(&queue[i]) == b {
+                true => (i + 1..len).find(|j| secondary(&queue[*j]) != b),
+                false => None,
+            }
+        });
+
+        if let Some(j) = swap {
+            queue.swap(i, j);
+        }
+    }
+}
+
+// Order `queue` by a weighted random sample without replacement,
+// so heavier items tend to come first (Efraimidis-Spirakis).
+pub(super) fn weighted_order<T, R>(queue: &mut [T], weight: impl Fn(&T) -> f64, rng: &mut R)
+where
+    T: Copy,
+    R: Rng + ?Sized,
+{
+    let mut keyed: Vec<(f64, T)> = queue
+        .iter()
+        .map(|t| {
+            let w = weight(t);
+            let u: f64 = rng.gen();
+            let key = if w > 0.0 { u.powf(1.0 / w) } else { 0.0 };
+            (key, *t)
+        })
+        .collect();
+
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    for (slot, (_, t)) in queue.iter_mut().zip(keyed) {
+        *slot = t;
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, SeedableRng};
+    use strum::*;
+
+    #[test]
+    // Asserts each variant:
+    // 1. Gives a different string
+    // 2. `.next()` gives a different variant
+    // 3. `.prev()` gives a different variant
+    fn diff() {
+        let mut set1 = std::collections::HashSet::new();
+        let mut set2 = std::collections::HashSet::new();
+        let mut set3 = std::collections::HashSet::new();
+
+        for i in ShuffleMode::iter() {
+            assert!(set1.insert(i.human()));
+            assert!(set2.insert(i.next()));
+            assert!(set3.insert(i.previous()));
+        }
+    }
+
+    #[test]
+    // Unrated is the middle, plays lower the weight.
+    fn _weight() {
+        assert_eq!(weight(0, 0), 3.0);
+        assert_eq!(weight(5, 0), 5.0);
+        assert_eq!(weight(5, 4), 1.0);
+        assert_eq!(weight(u8::MAX, 0), RATING_MAX as f64);
+        assert!(weight(1, 0) > weight(1, 1));
+    }
+
+    #[test]
+    // Groups stay together and in order.
+    fn _shuffle_groups() {
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        // (group, order)
+        let mut queue = [(0, 2), (1, 0), (0, 0), (2, 1), (1, 1), (0, 1), (2, 0)];
+        shuffle_groups(&mut queue, |t| t.0, |t| t.1, &mut rng);
+
+        let mut seen = vec![];
+        for (i, t) in queue.iter().enumerate() {
+            if i > 0 && queue[i - 1].0 == t.0 {
+                assert_eq!(queue[i - 1].1 + 1, t.1);
+            } else {
+                assert!(!seen.contains(&t.0));
+                seen.push(t.0);
+                assert_eq!(t.1, 0);
+            }
+        }
+        assert_eq!(seen.len(), 3);
+    }
+
+    #[test]
+    // Neighbours with the same `primary` are split up.
+    fn _spread() {
+        // (artist, album)
+        let mut queue = [(0, 0), (0, 0), (0, 1), (1, 2), (1, 2), (1, 2)];
+        spread(&mut queue, |t| t.0, |t| t.1);
+        assert_eq!(queue, [(0, 0), (1, 2), (0, 1), (1, 2), (0, 0), (1, 2)]);
+
+        // Only 1 artist, so fall back to albums.
+        let mut queue = [(0, 0), (0, 0), (0, 1)];
+        spread(&mut queue, |t| t.0, |t| t.1);
+        assert_eq!(queue, [(0, 0), (0, 1), (0, 0)]);
+    }
+
+    #[test]
+    // Heavy items come first more often.
+    fn _weighted_order() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let (mut heavy, mut light) = (0, 0);
+
+        for _ in 0..100 {
+            let mut queue = [0, 1, 2, 3];
+            weighted_order(&mut queue, |t| if *t == 0 { 50.0 } else { 1.0 }, &mut rng);
+
+            let mut sorted = queue;
+            sorted.sort();
+            assert_eq!(sorted, [0, 1, 2, 3]);
+
+            match queue[0] {
+                0 => heavy += 1,
+                _ => light += 1,
+            }
+        }
+
+        assert!(heavy > light);
+    }
+}
@@ -0,0 +1,195 @@
+//---------------------------------------------------------------------------------------------------- Use
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// [`SleepTimer::Off`]
+const SLEEP_OFF: &str = "No sleep timer is set";
+/// [`SleepTimer::Time`]
+const SLEEP_TIME: &str = "Pause playback at a set time, optionally fading out the volume";
+/// [`SleepTimer::Song`]
+const SLEEP_SONG: &str = "Pause playback after the current song finishes";
+/// [`SleepTimer::Album`]
+const SLEEP_ALBUM: &str = "Pause playback after the current album's songs in the queue finish";
+/// [`SleepTimer::Queue`]
+const SLEEP_QUEUE: &str = "Pause playback when the queue finishes";
+
+//---------------------------------------------------------------------------------------------------- SleepTimer
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    AsRefStr,
+    Display,
+    EnumCount,
+    EnumIter,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+/// The different sleep timers that can stop playback.
+///
+/// Once a timer goes off, playback is paused (the queue
+/// is kept) and the timer is set back to [`Self::Off`].
+///
+/// Sleep timers do not survive restarts or `Collection` resets,
+/// although they are saved to disk along with the rest of [`crate::state::AudioState`].
+pub enum SleepTimer {
+    /// No sleep timer is set.
+    Off,
+    /// Pause once the UNIX timestamp `at` is reached.
+    ///
+    /// The volume fades out over the last `fade` seconds, `0` means no fade.
+    Time {
+        /// The UNIX timestamp (seconds) to pause at.
+        at: u64,
+        /// How many seconds before `at` the volume starts fading out.
+        fade: u32,
+    },
+    /// Pause after the current [`Song`] finishes.
+    Song,
+    /// Pause after the last [`Song`] of the current [`Album`]'s span in the queue finishes.
+    ///
+    /// The span is all the [`Song`]'s next to the current
+    /// one in the queue that belong to the same [`Album`].
+    Album,
+    /// Pause when the queue finishes.
+    ///
+    /// This takes priority over [`Repeat`](crate::audio::Repeat)
+    /// and Auto-DJ, which will set up the next [`Song`], but paused.
+    Queue,
+}
+
+impl SleepTimer {
+    /// Returns the default, [`Self::Off`].
+    pub const fn new() -> Self {
+        Self::Off
+    }
+
+    /// A [`Self::Time`] that goes off `minutes` from `now`.
+    ///
+    /// `now` is a UNIX timestamp (seconds).
+    pub const fn minutes(minutes: u32, fade: u32, now: u64) -> Self {
+        Self::Time {
+            at: now.saturating_add(minutes as u64 * 60),
+            fade,
+        }
+    }
+
+    #[inline]
+    /// Returns formatted, human readable versions.
+    pub const fn human(&self) -> &'static str {
+        use SleepTimer::*;
+        match self {
+            Off => SLEEP_OFF,
+            Time { .. } => SLEEP_TIME,
+            Song => SLEEP_SONG,
+            Album => SLEEP_ALBUM,
+            Queue => SLEEP_QUEUE,
+        }
+    }
+
+    /// How many seconds are left until a [`Self::Time`] goes off.
+    ///
+    /// `None` if this isn't a [`Self::Time`].
+    pub const fn remaining(&self, now: u64) -> Option<u64> {
+        match self {
+            Self::Time { at, .. } => Some(at.saturating_sub(now)),
+            _ => None,
+        }
+    }
+
+    /// The volume `%` (`0..=100`) a [`Self::Time`] is fading to at `now`.
+    ///
+    /// This is `100` (no change) if this isn't a [`Self::Time`], or
+    /// if `now` is before the last `fade` seconds of the timer.
+    pub const fn fade(&self, now: u64) -> u8 {
+        match self {
+            Self::Time { at, fade } if *fade != 0 => {
+                let left = at.saturating_sub(now);
+                if left >= *fade as u64 {
+                    100
+                } else {
+                    (left * 100 / *fade as u64) as u8
+                }
+            }
+            _ => 100,
+        }
+    }
+}
+
+impl Default for SleepTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::*;
+
+    #[test]
+    // Asserts each variant gives a different string.
+    fn diff() {
+        let mut set = std::collections::HashSet::new();
+        for i in SleepTimer::iter() {
+            assert!(set.insert(i.human()));
+        }
+    }
+
+    #[test]
+    fn minutes() {
+        assert_eq!(
+            SleepTimer::minutes(30, 10, 1000),
+            SleepTimer::Time { at: 2800, fade: 10 }
+        );
+        assert_eq!(SleepTimer::minutes(30, 10, 1000).remaining(2000), Some(800));
+        assert_eq!(SleepTimer::minutes(30, 10, 1000).remaining(9999), Some(0));
+        assert_eq!(SleepTimer::Song.remaining(0), None);
+    }
+
+    #[test]
+    // The volume only fades in the last `fade` seconds.
+    fn fade() {
+        let t = SleepTimer::Time { at: 100, fade: 10 };
+        assert_eq!(t.fade(0), 100);
+        assert_eq!(t.fade(90), 100);
+        assert_eq!(t.fade(95), 50);
+        assert_eq!(t.fade(99), 10);
+        assert_eq!(t.fade(100), 0);
+        assert_eq!(t.fade(200), 0);
+
+        assert_eq!(SleepTimer::Time { at: 100, fade: 0 }.fade(100), 100);
+        assert_eq!(SleepTimer::Song.fade(0), 100);
+    }
+
+    #[test]
+    // Data variants are objects, the rest are strings.
+    fn serde() {
+        for (sleep, json) in [
+            (SleepTimer::Off, r#""off""#),
+            (SleepTimer::Album, r#""album""#),
+            (
+                SleepTimer::Time { at: 100, fade: 10 },
+                r#"{"time":{"at":100,"fade":10}}"#,
+            ),
+        ] {
+            assert_eq!(serde_json::to_string(&sleep).unwrap(), json);
+            assert_eq!(serde_json::from_str::<SleepTimer>(json).unwrap(), sleep);
+        }
+    }
+}
@@ -1,7 +1,15 @@
 //---------------------------------------------------------------------------------------------------- Use
+use crate::audio::VOLUME_DB_CURVE;
+use benri::atomic_load;
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
+//---------------------------------------------------------------------------------------------------- Constants
+/// The `dB` the lowest non-muted [`Volume`] (`1`) maps to when using [`Volume::f32_db`].
+///
+/// `Volume(100)` is `0.0dB` and `Volume(0)` is muted.
+pub const VOLUME_DB_MIN: f32 = -60.0;
+
 //---------------------------------------------------------------------------------------------------- Volume.
 /// Wrapper around [`u8`] that is between `0..100`
 ///
@@ -10,6 +18,9 @@ use serde::{Deserialize, Serialize};
 /// It guarantees the inner [`u8`] is between `0..100` so that
 /// frontends can't just send random numbers that make no sense in the
 /// context of changing the volume level, like `253`.
+///
+/// These `101` steps are the only resolution, both
+/// [`Self::from_db`] and [`Self::from_gain_db`] pick the closest one.
 #[derive(
     Copy, Clone, Debug, Hash, Eq, Ord, PartialEq, PartialOrd, Serialize, Deserialize, Encode, Decode,
 )]
@@ -70,6 +81,100 @@ impl Volume {
         self.0 as f32 / 100.0
     }
 
+    #[inline]
+    /// Returns the `dB` this [`Volume`] maps to on a decibel curve.
+    ///
+    /// `1..=100` is spread evenly over [`VOLUME_DB_MIN`]`..=0.0`,
+    /// so each step is `0.6dB`, and `0` is [`f32::NEG_INFINITY`] (muted).
+    ///
+    /// E.g:
+    /// - `Volume(100)` outputs `0.0`
+    /// - `Volume(1)` outputs `-60.0`
+    /// - `Volume(0)` outputs [`f32::NEG_INFINITY`]
+    pub fn db(&self) -> f32 {
+        if self.0 == 0 {
+            return f32::NEG_INFINITY;
+        }
+
+        VOLUME_DB_MIN * f32::from(100 - self.0) / 99.0
+    }
+
+    /// Create a new [`Volume`] from a `dB` value, the inverse of [`Self::db`].
+    ///
+    /// The closest [`Volume`] is picked, `dB` above `0.0` returns [`Self::new_100`]
+    /// and `dB` below [`VOLUME_DB_MIN`] (or `NaN`) returns [`Self::new_0`].
+    pub fn from_db(db: f32) -> Self {
+        if db.is_nan() || db < VOLUME_DB_MIN {
+            return Self::new_0();
+        } else if db >= 0.0 {
+            return Self::new_100();
+        }
+
+        let volume = 100.0 - (db / VOLUME_DB_MIN * 99.0);
+        Self((volume.round() as u8).clamp(1, 100))
+    }
+
+    #[inline]
+    /// Returns the inner [`u8`] as a [`f32`] that is `0.0-1.0`,
+    /// following a perceptual decibel curve (see [`Self::db`]) instead of linearly.
+    ///
+    /// There are still `100` steps, but each is the same `0.6dB` apart, where
+    /// [`Self::f32`] has large `dB` jumps at low volumes and tiny ones at high volumes.
+    ///
+    /// E.g:
+    /// - `Volume(100)` outputs `1.0`
+    /// - `Volume(50)` outputs `0.03~` (`-30.3dB`)
+    /// - `Volume(0)` outputs `0.0`
+    pub fn f32_db(&self) -> f32 {
+        if self.0 == 0 {
+            return 0.0;
+        }
+
+        10.0_f32.powf(self.db() / 20.0)
+    }
+
+    #[inline]
+    /// Returns the `0.0-1.0` multiplier this [`Volume`] is actually played at.
+    ///
+    /// This is [`Self::f32_db`] if [`VOLUME_DB_CURVE`] is `true`, else [`Self::f32`].
+    pub fn gain(&self) -> f32 {
+        if atomic_load!(VOLUME_DB_CURVE) {
+            self.f32_db()
+        } else {
+            self.f32()
+        }
+    }
+
+    /// Returns [`Self::gain`] in `dB`, [`f32::NEG_INFINITY`] if muted.
+    ///
+    /// E.g, `Volume(50)` outputs `-6.02~` with
+    /// a linear curve and `-30.3~` with a decibel curve.
+    pub fn gain_db(&self) -> f32 {
+        if atomic_load!(VOLUME_DB_CURVE) {
+            self.db()
+        } else if self.0 == 0 {
+            f32::NEG_INFINITY
+        } else {
+            20.0 * self.f32().log10()
+        }
+    }
+
+    /// Create a new [`Volume`] that is played at this `dB`, the inverse of [`Self::gain_db`].
+    ///
+    /// The closest [`Volume`] is picked, `dB` above `0.0` returns [`Self::new_100`].
+    pub fn from_gain_db(db: f32) -> Self {
+        if atomic_load!(VOLUME_DB_CURVE) {
+            return Self::from_db(db);
+        } else if db.is_nan() {
+            return Self::new_0();
+        } else if db >= 0.0 {
+            return Self::new_100();
+        }
+
+        let volume = 100.0 * 10.0_f32.powf(db / 20.0);
+        Self((volume.round() as u8).min(100))
+    }
+
     pub(crate) const fn const_default() -> Self {
         Self::new_100()
     }
@@ -163,6 +268,52 @@ mod tests {
         }
     }
 
+    #[test]
+    // Asserts the decibel curve and its inverse.
+    fn db() {
+        assert_eq!(Volume::new_100().db(), 0.0);
+        assert_eq!(Volume::new_1().db(), VOLUME_DB_MIN);
+        assert_eq!(Volume::new_0().db(), f32::NEG_INFINITY);
+
+        assert_eq!(Volume::new_100().f32_db(), 1.0);
+        assert_eq!(Volume::new_0().f32_db(), 0.0);
+
+        // The curve is monotonic and under the linear one.
+        for i in 1..100_u8 {
+            let (v1, v2) = (Volume::new(i), Volume::new(i + 1));
+            assert!(v1.f32_db() < v2.f32_db());
+            assert!(v1.f32_db() <= v1.f32());
+        }
+
+        for i in 0..=100_u8 {
+            let v = Volume::new(i);
+            assert_eq!(Volume::from_db(v.db()), v);
+        }
+
+        assert_eq!(Volume::from_db(6.0), Volume::new_100());
+        assert_eq!(Volume::from_db(-61.0), Volume::new_0());
+        assert_eq!(Volume::from_db(f32::NAN), Volume::new_0());
+    }
+
+    #[test]
+    // Asserts the linear (default) `gain()` and its inverse.
+    fn gain_linear() {
+        assert!(!atomic_load!(VOLUME_DB_CURVE));
+
+        assert_eq!(Volume::new_50().gain(), 0.5);
+        assert_eq!(Volume::new_100().gain_db(), 0.0);
+        assert_eq!(Volume::new_0().gain_db(), f32::NEG_INFINITY);
+        assert!((Volume::new_50().gain_db() + 6.0206).abs() < 0.001);
+
+        for i in 0..=100_u8 {
+            let v = Volume::new(i);
+            assert_eq!(Volume::from_gain_db(v.gain_db()), v);
+        }
+
+        assert_eq!(Volume::from_gain_db(-200.0), Volume::new_0());
+        assert_eq!(Volume::from_gain_db(6.0), Volume::new_100());
+    }
+
     #[test]
     // Tests math and under/overflows work correctly.
     fn math() {
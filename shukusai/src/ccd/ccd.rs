@@ -2,9 +2,14 @@
 use crate::ccd::msg::CcdToKernel;
 use crate::{
     collection::{
-        AlbumKey, Albums, ArtistKey, Artists, Collection, Image, Map, SongKey, Songs,
+        AlbumKey, Albums, ArtistKey, Artists, Chapters, Collection, FirstSeen, Image, Map, SongKey,
+        Songs, SortDateAdded, SortPlayStats, SortRatings, CHAPTERS, FINGERPRINTS,
+        FINGERPRINT_SECONDS, FIRST_SEEN, PLAY_STATS, RATINGS,
+    },
+    constants::{
+        CHAPTERS_VERSION, COLLECTION_VERSION, FINGERPRINT_VERSION, FIRST_SEEN_VERSION,
+        HISTORY_VERSION, PLAYLIST_VERSION, QUEUE_SNAPSHOT_VERSION,
     },
-    constants::{COLLECTION_VERSION, PLAYLIST_VERSION},
     state::Phase,
 };
 use benri::{debug_panic, log::*, sync::*, thread::*, time::*};
@@ -72,6 +77,7 @@ impl Ccd {
         // 1. Destruct the old `Collection` and dealloc textures.
         // 2. WalkDir given path(s), filtering for audio files.
         // 3. For each file, append metadata to appropriate `Vec`.
+        // 3.5 (Optionally) fingerprint each file's audio.
         // 4. Make sure `Vec<Album>` metadata matches the songs.
         // 5. Create sorted `Key`'s.
         // 6. Create the "Map"
@@ -155,12 +161,33 @@ impl Ccd {
         //-------------------------------------------------------------------------------- 3
         let now = now!();
         send!(to_kernel, CcdToKernel::UpdatePhase((5.00, Phase::Parse)));
-        let (mut vec_artist, mut vec_album, vec_song, count_art) =
+        let (mut vec_artist, mut vec_album, vec_song, count_art, sort_tags, chapters) =
             Self::the_loop(&to_kernel, paths);
+        *CHAPTERS.write() = Chapters(chapters);
         // Update should be < 50% at this point.
         let perf_metadata = secs_f32!(now);
         trace!("CCD [3/14] ... Metadata: {perf_metadata}");
 
+        //-------------------------------------------------------------------------------- 3.5
+        // Optional, only if the `Frontend` enabled it.
+        let fingerprint_seconds = atomic_load!(FINGERPRINT_SECONDS);
+        let perf_fingerprint = if fingerprint_seconds == 0 {
+            trace!("CCD [3.5/14] ... Fingerprint: SKIP");
+            0.0
+        } else {
+            let now = now!();
+            send!(
+                to_kernel,
+                CcdToKernel::UpdatePhase((50.00, Phase::Fingerprint))
+            );
+            let old = std::mem::take(&mut *FINGERPRINTS.write());
+            let fingerprints = Self::fingerprint(&to_kernel, &vec_song, old, fingerprint_seconds);
+            *FINGERPRINTS.write() = fingerprints;
+            let perf_fingerprint = secs_f32!(now);
+            trace!("CCD [3.5/14] ... Fingerprint: {perf_fingerprint}");
+            perf_fingerprint
+        };
+
         //-------------------------------------------------------------------------------- 4
         let now = now!();
         send!(to_kernel, CcdToKernel::UpdatePhase((50.00, Phase::Fix)));
@@ -172,7 +199,10 @@ impl Ccd {
         let now = now!();
         send!(to_kernel, CcdToKernel::UpdatePhase((52.50, Phase::Sort)));
 
-        let sort_artist_lexi = Self::sort_artist_lexi(&vec_artist);
+        let names = Self::sort_names(&vec_artist, &vec_album, &vec_song, &sort_tags);
+        drop(sort_tags);
+
+        let sort_artist_lexi = Self::sort_artist_lexi(&names.artists);
         let sort_artist_lexi_rev = sort_artist_lexi
             .iter()
             .rev()
@@ -214,15 +244,31 @@ impl Ccd {
             &vec_artist,
             &vec_album,
         );
-        let sort_album_lexi_artist_lexi =
-            Self::sort_album_lexi_artist_iter(&sort_artist_lexi, &vec_artist, &vec_album);
-        let sort_album_lexi_artist_lexi_rev =
-            Self::sort_album_lexi_artist_iter(&sort_artist_lexi_rev, &vec_artist, &vec_album);
-        let sort_album_lexi_rev_artist_lexi =
-            Self::sort_album_lexi_rev_artist_iter(&sort_artist_lexi, &vec_artist, &vec_album);
-        let sort_album_lexi_rev_artist_lexi_rev =
-            Self::sort_album_lexi_rev_artist_iter(&sort_artist_lexi_rev, &vec_artist, &vec_album);
-        let sort_album_lexi = Self::sort_album_lexi(&vec_album);
+        let sort_album_lexi_artist_lexi = Self::sort_album_lexi_artist_iter(
+            &sort_artist_lexi,
+            &vec_artist,
+            &vec_album,
+            &names.albums,
+        );
+        let sort_album_lexi_artist_lexi_rev = Self::sort_album_lexi_artist_iter(
+            &sort_artist_lexi_rev,
+            &vec_artist,
+            &vec_album,
+            &names.albums,
+        );
+        let sort_album_lexi_rev_artist_lexi = Self::sort_album_lexi_rev_artist_iter(
+            &sort_artist_lexi,
+            &vec_artist,
+            &vec_album,
+            &names.albums,
+        );
+        let sort_album_lexi_rev_artist_lexi_rev = Self::sort_album_lexi_rev_artist_iter(
+            &sort_artist_lexi_rev,
+            &vec_artist,
+            &vec_album,
+            &names.albums,
+        );
+        let sort_album_lexi = Self::sort_album_lexi(&names.albums);
         let sort_album_lexi_rev = sort_album_lexi
             .iter()
             .rev()
@@ -269,7 +315,7 @@ impl Ccd {
             .rev()
             .copied()
             .collect::<Box<[SongKey]>>();
-        let sort_song_lexi = Self::sort_song_lexi(&vec_song);
+        let sort_song_lexi = Self::sort_song_lexi(&names.songs);
         let sort_song_lexi_rev = sort_song_lexi
             .iter()
             .rev()
@@ -362,6 +408,9 @@ impl Ccd {
             sort_song_runtime_rev,
             sort_song_title,
             sort_song_title_rev,
+
+            // This needs the `timestamp`, set below.
+            sort_date_added: SortDateAdded::new(),
         };
         // Fix metadata.
         {
@@ -384,6 +433,20 @@ impl Ccd {
 
             // Set `timestamp`.
             collection.timestamp = benri::unix!();
+
+            // Set `FirstSeen` timestamps, new files were first seen now.
+            let old = std::mem::take(&mut *FIRST_SEEN.write());
+            let first_seen = FirstSeen::update(old, &collection.songs.0, collection.timestamp);
+            collection.sort_date_added = SortDateAdded::from_collection(&collection, &first_seen);
+            *FIRST_SEEN.write() = first_seen;
+
+            // Re-sort the play statistics and ratings for the new `SongKey`'s.
+            {
+                let mut play_stats = PLAY_STATS.write();
+                play_stats.sort = SortPlayStats::from_collection(&collection, &play_stats);
+            }
+            let mut ratings = RATINGS.write();
+            ratings.sort = SortRatings::from_collection(&collection, &ratings);
         }
         let perf_prepare = secs_f32!(now);
         trace!("CCD [7/14] ... Prepare: {perf_prepare}");
@@ -534,8 +597,18 @@ impl Ccd {
 
         //-------------------------------------------------------------------------------- 12
         let now = now!();
-        crate::state::PLAYLISTS.write().validate(&collection);
-        let playlists_for_disk = crate::state::PLAYLISTS.read().clone();
+        let playlists_for_disk = {
+            let mut playlists = crate::state::PLAYLISTS.write();
+            playlists.validate(&collection);
+            // Smart playlists are re-created from their rules.
+            crate::state::SMART_PLAYLISTS
+                .read()
+                .refresh_all(&mut playlists, &collection);
+            playlists.metadata_sync();
+            playlists.clone()
+        };
+        crate::state::HISTORY.write().validate(&collection);
+        crate::state::QUEUE_SNAPSHOTS.write().validate(&collection);
         let perf_playlists = secs_f32!(now);
         info!("CCD [12/14] ... Playlists: {perf_playlists}");
 
@@ -580,6 +653,48 @@ impl Ccd {
             }
         }
 
+        // SAFETY: `memmap` used.
+        //
+        // Save the `History`.
+        match unsafe { crate::state::HISTORY.read().save_atomic_memmap() } {
+            Ok(md) => debug!("CCD ... History{HISTORY_VERSION}: {md}"),
+            Err(e) => fail!("CCD ... History{HISTORY_VERSION}: {e}"),
+        }
+
+        // SAFETY: `memmap` used.
+        //
+        // Save the `QueueSnapshots`.
+        match unsafe { crate::state::QUEUE_SNAPSHOTS.read().save_atomic_memmap() } {
+            Ok(md) => debug!("CCD ... QueueSnapshots{QUEUE_SNAPSHOT_VERSION}: {md}"),
+            Err(e) => fail!("CCD ... QueueSnapshots{QUEUE_SNAPSHOT_VERSION}: {e}"),
+        }
+
+        // SAFETY: `memmap` used.
+        //
+        // Save the `Fingerprints` (if we made new ones).
+        if fingerprint_seconds != 0 {
+            match unsafe { FINGERPRINTS.read().save_atomic_memmap() } {
+                Ok(md) => debug!("CCD ... Fingerprints{FINGERPRINT_VERSION}: {md}"),
+                Err(e) => fail!("CCD ... Fingerprints{FINGERPRINT_VERSION}: {e}"),
+            }
+        }
+
+        // SAFETY: `memmap` used.
+        //
+        // Save the `Chapters`.
+        match unsafe { CHAPTERS.read().save_atomic_memmap() } {
+            Ok(md) => debug!("CCD ... Chapters{CHAPTERS_VERSION}: {md}"),
+            Err(e) => fail!("CCD ... Chapters{CHAPTERS_VERSION}: {e}"),
+        }
+
+        // SAFETY: `memmap` used.
+        //
+        // Save the `FirstSeen` timestamps.
+        match unsafe { FIRST_SEEN.read().save_atomic_memmap() } {
+            Ok(md) => debug!("CCD ... FirstSeen{FIRST_SEEN_VERSION}: {md}"),
+            Err(e) => fail!("CCD ... FirstSeen{FIRST_SEEN_VERSION}: {e}"),
+        }
+
         #[cfg(feature = "gui")]
         {
             // Delete old images.
@@ -612,6 +727,7 @@ impl Ccd {
             deconstruct: perf_deconstruct,
             walkdir: perf_walkdir,
             metadata: perf_metadata,
+            fingerprint: perf_fingerprint,
             fix: perf_fix,
             sort: perf_sort,
             map: perf_map,
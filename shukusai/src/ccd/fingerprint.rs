@@ -0,0 +1,416 @@
+//---------------------------------------------------------------------------------------------------- Use
+use super::CcdToKernel;
+use crate::collection::{Fingerprint, FingerprintEntry, Fingerprints, Song};
+use anyhow::anyhow;
+use benri::sync::*;
+use crossbeam::channel::Sender;
+use log::warn;
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use symphonia::core::{
+    audio::SampleBuffer,
+    codecs::{DecoderOptions, CODEC_TYPE_NULL},
+    errors::Error,
+    io::MediaSourceStream,
+    probe::Hint,
+};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Audio is down-mixed to mono and down-sampled to this before fingerprinting.
+const SAMPLE_RATE: u32 = 11025;
+
+// Samples per analysis frame, and how far each frame moves.
+const FRAME_SIZE: usize = 4096;
+const FRAME_HOP: usize = FRAME_SIZE / 3;
+
+// The range of MIDI notes (C2..=B6) folded into the 12 chroma bins.
+const NOTE_LOW: u8 = 36;
+const NOTE_HIGH: u8 = 95;
+
+// `(chroma_bin, goertzel_coefficient)` for every note we look at.
+static NOTES: Lazy<Box<[(usize, f32)]>> = Lazy::new(|| {
+    (NOTE_LOW..=NOTE_HIGH)
+        .map(|n| {
+            let freq = 440.0 * 2.0_f32.powf((n as f32 - 69.0) / 12.0);
+            let coefficient = 2.0 * (2.0 * std::f32::consts::PI * freq / SAMPLE_RATE as f32).cos();
+            ((n % 12) as usize, coefficient)
+        })
+        .collect()
+});
+
+// Hann window.
+static WINDOW: Lazy<Box<[f32]>> = Lazy::new(|| {
+    (0..FRAME_SIZE)
+        .map(|i| {
+            0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_SIZE - 1) as f32).cos()
+        })
+        .collect()
+});
+
+//---------------------------------------------------------------------------------------------------- Fingerprint functions.
+impl crate::ccd::Ccd {
+    // Fingerprint all `Song`'s.
+    //
+    // Entries in the old cache with matching `mtime` and `seconds` are
+    // reused, the rest are decoded. Entries for files no longer in the
+    // `Collection` are dropped.
+    pub(super) fn fingerprint(
+        to_kernel: &Sender<CcdToKernel>,
+        vec_song: &[Song],
+        old: Fingerprints,
+        seconds: u32,
+    ) -> Fingerprints {
+        let map: BTreeMap<_, _> = vec_song
+            .par_iter()
+            .filter_map(|song| {
+                // The percent doesn't move during this
+                // optional phase, only the title updates.
+                send!(
+                    to_kernel,
+                    CcdToKernel::UpdateIncrement((0.0, Arc::clone(&song.title)))
+                );
+
+                let mtime = Self::mtime(&song.path);
+
+                if let Some(entry) = old.0.get(&song.path) {
+                    if entry.mtime == mtime && entry.seconds == seconds {
+                        return Some((song.path.clone(), entry.clone()));
+                    }
+                }
+
+                match Self::fingerprint_path(&song.path, &song.extension, seconds) {
+                    Ok(fingerprint) => Some((
+                        song.path.clone(),
+                        FingerprintEntry {
+                            mtime,
+                            seconds,
+                            fingerprint,
+                        },
+                    )),
+                    Err(e) => {
+                        warn!("CCD - Fingerprint error: {e} ... {}", song.path.display());
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Fingerprints(map)
+    }
+
+    #[inline(always)]
+    // Modification time of a file in UNIX seconds, `0` on error.
+    fn mtime(path: &Path) -> u64 {
+        std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    // Decode the first `seconds` of an audio file into a `Fingerprint`.
+    fn fingerprint_path(
+        path: &Path,
+        extension: &str,
+        seconds: u32,
+    ) -> Result<Fingerprint, anyhow::Error> {
+        let file = std::fs::File::open(path)?;
+        let mss = MediaSourceStream::new(Box::new(file), Default::default());
+        let mut hint = Hint::new();
+        hint.with_extension(extension);
+
+        let probe = symphonia::default::get_probe().format(
+            &hint,
+            mss,
+            &Default::default(),
+            &Default::default(),
+        )?;
+        let mut reader = probe.format;
+
+        // Select the first track with a known codec.
+        let track = match reader
+            .tracks()
+            .iter()
+            .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        {
+            Some(t) => t,
+            None => return Err(anyhow!("Could not find track codec")),
+        };
+        let track_id = track.id;
+        let sample_rate = match track.codec_params.sample_rate {
+            Some(s) => s,
+            None => return Err(anyhow!("Unknown sample rate")),
+        };
+
+        let decoder_opts = DecoderOptions { verify: false };
+        let mut decoder =
+            symphonia::default::get_codecs().make(&track.codec_params, &decoder_opts)?;
+
+        // Down-mix to mono and re-sample to `SAMPLE_RATE`.
+        let max = SAMPLE_RATE as usize * seconds as usize;
+        let mut samples: Vec<f32> = Vec::with_capacity(max);
+        let mut sample_buf: Option<SampleBuffer<f32>> = None;
+        let mut resample = Resample::new(sample_rate);
+
+        while samples.len() < max {
+            let packet = match reader.next_packet() {
+                Ok(p) => p,
+                // End of stream.
+                Err(Error::IoError(_)) => break,
+                Err(e) => return Err(e.into()),
+            };
+
+            if packet.track_id() != track_id {
+                continue;
+            }
+
+            let decoded = match decoder.decode(&packet) {
+                Ok(d) => d,
+                // Skip corrupted packets.
+                Err(Error::DecodeError(_)) => continue,
+                Err(e) => return Err(e.into()),
+            };
+
+            let spec = *decoded.spec();
+            let channels = spec.channels.count().max(1);
+            let duration = decoded.capacity() as u64;
+            if sample_buf
+                .as_ref()
+                .map_or(true, |b| b.capacity() < duration as usize * channels)
+            {
+                sample_buf = Some(SampleBuffer::new(duration, spec));
+            }
+            let buf = sample_buf.as_mut().unwrap();
+            buf.copy_interleaved_ref(decoded);
+
+            for frame in buf.samples().chunks_exact(channels) {
+                resample.push(frame.iter().sum::<f32>() / channels as f32, &mut samples);
+                if samples.len() >= max {
+                    break;
+                }
+            }
+        }
+
+        samples.truncate(max);
+        Ok(Self::fingerprint_samples(&samples))
+    }
+
+    // Turn mono `SAMPLE_RATE` audio into a `Fingerprint`.
+    //
+    // Each frame's 12-bin chroma is compared against itself and
+    // the previous frame's, creating a 32-bit sub-fingerprint:
+    //
+    // - bits 0..12  = bin `n` louder than bin `n + 1`
+    // - bits 12..24 = bin `n` louder than in the previous frame
+    // - bits 24..32 = bin `n` louder than bin `n + 7` (a fifth above)
+    fn fingerprint_samples(samples: &[f32]) -> Fingerprint {
+        let mut vec = Vec::with_capacity(samples.len() / FRAME_HOP);
+        let mut frame = vec![0.0_f32; FRAME_SIZE];
+        let mut prev: Option<[f32; 12]> = None;
+
+        for start in (0..samples.len().saturating_sub(FRAME_SIZE - 1)).step_by(FRAME_HOP) {
+            for ((f, s), w) in frame
+                .iter_mut()
+                .zip(&samples[start..start + FRAME_SIZE])
+                .zip(WINDOW.iter())
+            {
+                *f = s * w;
+            }
+
+            let chroma = Self::chroma(&frame);
+
+            if let Some(prev) = prev {
+                let mut bits = 0_u32;
+                for n in 0..12 {
+                    if chroma[n] > chroma[(n + 1) % 12] {
+                        bits |= 1 << n;
+                    }
+                    if chroma[n] > prev[n] {
+                        bits |= 1 << (12 + n);
+                    }
+                }
+                for n in 0..8 {
+                    if chroma[n] > chroma[(n + 7) % 12] {
+                        bits |= 1 << (24 + n);
+                    }
+                }
+                vec.push(bits);
+            }
+
+            prev = Some(chroma);
+        }
+
+        Fingerprint(vec.into_boxed_slice())
+    }
+
+    #[inline]
+    // Normalized 12-bin chroma of a single (windowed) frame, via Goertzel.
+    fn chroma(frame: &[f32]) -> [f32; 12] {
+        let mut chroma = [0.0_f32; 12];
+
+        for (bin, coefficient) in NOTES.iter() {
+            let (mut s1, mut s2) = (0.0_f32, 0.0_f32);
+            for x in frame {
+                let s = x + coefficient * s1 - s2;
+                s2 = s1;
+                s1 = s;
+            }
+            chroma[*bin] += (s1 * s1 + s2 * s2 - coefficient * s1 * s2).max(0.0);
+        }
+
+        let norm = chroma.iter().map(|c| c * c).sum::<f32>().sqrt();
+        if norm > f32::EPSILON {
+            chroma.iter_mut().for_each(|c| *c /= norm);
+        }
+
+        chroma
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Resample
+// Re-samples mono audio at some sample rate to `SAMPLE_RATE`.
+//
+// Down-sampling averages all source samples in each output sample.
+// Up-sampling (sources below `SAMPLE_RATE`) linearly interpolates
+// between source samples, so the output is never wrongly timed.
+struct Resample {
+    // Source samples per output sample.
+    step: f64,
+    // Position of the next output sample, in source samples.
+    next: f64,
+    // How many source samples were pushed.
+    index: f64,
+    // Down-sampling: the source samples in the current output sample.
+    sum: f32,
+    count: u32,
+    // Up-sampling: the last source sample.
+    prev: f32,
+}
+
+impl Resample {
+    fn new(sample_rate: u32) -> Self {
+        let step = sample_rate as f64 / SAMPLE_RATE as f64;
+        Self {
+            step,
+            // Down-sampling outputs once `step` samples
+            // were averaged, up-sampling starts at `0`.
+            next: if step >= 1.0 { step } else { 0.0 },
+            index: 0.0,
+            sum: 0.0,
+            count: 0,
+            prev: 0.0,
+        }
+    }
+
+    #[inline]
+    // Push a source sample, writing any finished output samples into `out`.
+    fn push(&mut self, sample: f32, out: &mut Vec<f32>) {
+        if self.step >= 1.0 {
+            self.sum += sample;
+            self.count += 1;
+            self.index += 1.0;
+
+            if self.index >= self.next {
+                out.push(self.sum / self.count as f32);
+                self.sum = 0.0;
+                self.count = 0;
+                self.next += self.step;
+            }
+        } else {
+            // Output every position between the last source sample and this one.
+            while self.next <= self.index {
+                let t = (self.next - (self.index - 1.0)) as f32;
+                out.push(self.prev + (sample - self.prev) * t);
+                self.next += self.step;
+            }
+
+            self.prev = sample;
+            self.index += 1.0;
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ccd::Ccd;
+
+    // A pure tone at `freq` Hz.
+    fn tone(freq: f32, seconds: usize) -> Vec<f32> {
+        tone_at(freq, seconds, SAMPLE_RATE)
+    }
+
+    // A pure tone at `freq` Hz, sampled at `rate`.
+    fn tone_at(freq: f32, seconds: usize, rate: u32) -> Vec<f32> {
+        (0..rate as usize * seconds)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / rate as f32).sin())
+            .collect()
+    }
+
+    // A melody of notes and their fifths, half a second each, sampled at `rate`.
+    fn melody_at(rate: u32) -> Vec<f32> {
+        [
+            261.63, 329.63, 392.0, 440.0, 349.23, 293.66, 493.88, 261.63, 392.0, 329.63,
+        ]
+        .iter()
+        .flat_map(|freq| {
+            let root = tone_at(*freq, 1, rate);
+            let fifth = tone_at(freq * 1.5, 1, rate);
+            let len = rate as usize / 2;
+            (0..len).map(move |i| root[i] + fifth[i] * 0.5)
+        })
+        .collect()
+    }
+
+    // Re-sample all of `samples` to `SAMPLE_RATE`.
+    fn resample(samples: &[f32], rate: u32) -> Vec<f32> {
+        let mut resample = Resample::new(rate);
+        let mut out = vec![];
+        for s in samples {
+            resample.push(*s, &mut out);
+        }
+        out
+    }
+
+    #[test]
+    // Up and down-sampled audio keeps its timing,
+    // and fingerprints the same as the original.
+    fn resample_rates() {
+        let expected = Ccd::fingerprint_samples(&melody_at(SAMPLE_RATE));
+
+        for rate in [8000, 11025, 22050, 44100, 48000] {
+            let melody = melody_at(rate);
+            let out = resample(&melody, rate);
+
+            let len = melody.len() * SAMPLE_RATE as usize / rate as usize;
+            assert!(out.len().abs_diff(len) <= 1, "{rate}: {}", out.len());
+            assert!(Ccd::fingerprint_samples(&out).matches(&expected), "{rate}");
+        }
+    }
+
+    #[test]
+    // Same audio at different volumes creates the same fingerprint,
+    // different audio creates a different one.
+    fn fingerprint_samples() {
+        let a = tone(440.0, 5);
+        let quiet: Vec<f32> = a.iter().map(|s| s * 0.25).collect();
+        let b: Vec<f32> = a
+            .iter()
+            .zip(tone(311.13, 5))
+            .map(|(x, y)| x * 0.2 + y)
+            .collect();
+
+        let a = Ccd::fingerprint_samples(&a);
+        let quiet = Ccd::fingerprint_samples(&quiet);
+        let b = Ccd::fingerprint_samples(&b);
+
+        assert!(!a.is_empty());
+        assert!(a.matches(&quiet));
+        assert!(a.similarity(&b) < a.similarity(&quiet));
+    }
+}
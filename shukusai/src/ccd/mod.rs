@@ -13,6 +13,7 @@ mod ccd;
 pub(crate) use ccd::*;
 
 //----- CCD internal functions.
+mod fingerprint;
 mod sort;
 mod the_loop;
 mod walk;
@@ -32,6 +32,8 @@ pub(crate) struct Phases {
     pub(super) deconstruct: f32,
     pub(super) walkdir: f32,
     pub(super) metadata: f32,
+    #[serde(default)]
+    pub(super) fingerprint: f32,
     pub(super) fix: f32,
     pub(super) sort: f32,
     pub(super) map: f32,
@@ -1,5 +1,21 @@
 //---------------------------------------------------------------------------------------------------- Use
+use super::the_loop::SortTags;
 use crate::collection::{Album, AlbumKey, Artist, ArtistKey, Song, SongKey};
+use crate::sort::{cmp_natural, sort_name, SORT_ARTICLES};
+use benri::lockr;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+//---------------------------------------------------------------------------------------------------- SortNames
+// The strings the `Lexi` sorts compare, indexed by key.
+//
+// These are the `ARTISTSORT`, `ALBUMSORT` and `TITLESORT` tags if
+// they exist, else the name/title, both passed through `sort_name()`.
+pub(super) struct SortNames {
+    pub(super) artists: Box<[String]>,
+    pub(super) albums: Box<[String]>,
+    pub(super) songs: Box<[String]>,
+}
 
 //---------------------------------------------------------------------------------------------------- __NAME__
 // These functions create new sorted `Vec<_Key>`'s.
@@ -20,15 +36,63 @@ impl super::Ccd {
         (0..len).collect()
     }
 
+    //--------------------------------------------------------------- `SortNames`.
+    // An `Artist`/`Album` uses the sort tag of its first `Song` that has one.
+    pub(super) fn sort_names(
+        artists: &[Artist],
+        albums: &[Album],
+        songs: &[Song],
+        sort_tags: &HashMap<PathBuf, SortTags>,
+    ) -> SortNames {
+        let articles = lockr!(SORT_ARTICLES);
+
+        let mut artist_tags: Vec<Option<&str>> = vec![None; artists.len()];
+        let mut album_tags: Vec<Option<&str>> = vec![None; albums.len()];
+
+        let songs: Box<[String]> = songs
+            .iter()
+            .map(|song| {
+                let tags = match sort_tags.get(&song.path) {
+                    Some(t) => t,
+                    None => return sort_name(&song.title, &articles),
+                };
+
+                let album = song.album.inner();
+                let artist = albums[album].artist.inner();
+                if album_tags[album].is_none() {
+                    album_tags[album] = tags.album.as_deref();
+                }
+                if artist_tags[artist].is_none() {
+                    artist_tags[artist] = tags.artist.as_deref();
+                }
+
+                sort_name(tags.title.as_deref().unwrap_or(&song.title), &articles)
+            })
+            .collect();
+
+        let artists = artists
+            .iter()
+            .zip(artist_tags)
+            .map(|(a, tag)| sort_name(tag.unwrap_or(&a.name), &articles))
+            .collect();
+
+        let albums = albums
+            .iter()
+            .zip(album_tags)
+            .map(|(a, tag)| sort_name(tag.unwrap_or(&a.title), &articles))
+            .collect();
+
+        SortNames {
+            artists,
+            albums,
+            songs,
+        }
+    }
+
     //--------------------------------------------------------------- `ArtistKey` sorts.
-    pub(super) fn sort_artist_lexi(artists: &[Artist]) -> Box<[ArtistKey]> {
-        let mut vec_artist = Self::filled_vec_usize(artists.len());
-        vec_artist.sort_by(|a, b| {
-            artists[*a]
-                .name
-                .to_lowercase()
-                .cmp(&artists[*b].name.to_lowercase())
-        });
+    pub(super) fn sort_artist_lexi(names: &[String]) -> Box<[ArtistKey]> {
+        let mut vec_artist = Self::filled_vec_usize(names.len());
+        vec_artist.sort_by(|a, b| cmp_natural(&names[*a], &names[*b]));
         vec_artist.into_iter().map(ArtistKey::from).collect()
     }
 
@@ -112,17 +176,13 @@ impl super::Ccd {
         sorted_artists: &[ArtistKey],
         artists: &[Artist],
         albums: &[Album],
+        names: &[String],
     ) -> Box<[AlbumKey]> {
         let mut vec_album: Vec<Vec<AlbumKey>> = Vec::with_capacity(albums.len());
 
         for artist in sorted_artists {
             let mut tmp: Vec<AlbumKey> = artists[artist.inner()].albums.clone();
-            tmp.sort_by(|a, b| {
-                albums[a.inner()]
-                    .title
-                    .to_lowercase()
-                    .cmp(&albums[b.inner()].title.to_lowercase())
-            });
+            tmp.sort_by(|a, b| cmp_natural(&names[a.inner()], &names[b.inner()]));
             vec_album.push(tmp);
         }
 
@@ -133,17 +193,13 @@ impl super::Ccd {
         sorted_artists: &[ArtistKey],
         artists: &[Artist],
         albums: &[Album],
+        names: &[String],
     ) -> Box<[AlbumKey]> {
         let mut vec_album: Vec<Vec<AlbumKey>> = Vec::with_capacity(albums.len());
 
         for artist in sorted_artists {
             let mut tmp: Vec<AlbumKey> = artists[artist.inner()].albums.clone();
-            tmp.sort_by(|a, b| {
-                albums[a.inner()]
-                    .title
-                    .to_lowercase()
-                    .cmp(&albums[b.inner()].title.to_lowercase())
-            });
+            tmp.sort_by(|a, b| cmp_natural(&names[a.inner()], &names[b.inner()]));
             vec_album.push(tmp.into_iter().rev().collect());
         }
 
@@ -151,15 +207,10 @@ impl super::Ccd {
     }
 
     // Doesn't require `Vec<Artist>`.
-    pub(super) fn sort_album_lexi(albums: &[Album]) -> Box<[AlbumKey]> {
-        let mut vec_album = Self::filled_vec_usize(albums.len());
+    pub(super) fn sort_album_lexi(names: &[String]) -> Box<[AlbumKey]> {
+        let mut vec_album = Self::filled_vec_usize(names.len());
 
-        vec_album.sort_by(|a, b| {
-            albums[*a]
-                .title
-                .to_lowercase()
-                .cmp(&albums[*b].title.to_lowercase())
-        });
+        vec_album.sort_by(|a, b| cmp_natural(&names[*a], &names[*b]));
 
         vec_album.into_iter().map(AlbumKey::from).collect()
     }
@@ -212,15 +263,10 @@ impl super::Ccd {
         vec_song.into_iter().flatten().collect()
     }
 
-    pub(super) fn sort_song_lexi(songs: &[Song]) -> Box<[SongKey]> {
-        let mut vec_song = Self::filled_vec_usize(songs.len());
+    pub(super) fn sort_song_lexi(names: &[String]) -> Box<[SongKey]> {
+        let mut vec_song = Self::filled_vec_usize(names.len());
 
-        vec_song.sort_by(|a, b| {
-            songs[*a]
-                .title
-                .to_lowercase()
-                .cmp(&songs[*b].title.to_lowercase())
-        });
+        vec_song.sort_by(|a, b| cmp_natural(&names[*a], &names[*b]));
 
         vec_song.into_iter().map(SongKey::from).collect()
     }
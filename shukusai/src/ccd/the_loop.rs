@@ -1,12 +1,12 @@
 //---------------------------------------------------------------------------------------------------- Use
 use super::CcdToKernel;
-use crate::collection::{Album, AlbumKey, Art, Artist, ArtistKey, Song, SongKey};
+use crate::collection::{Album, AlbumKey, Art, Artist, ArtistKey, Chapter, Song, SongKey};
 use anyhow::{anyhow, bail};
 use benri::sync::*;
 use crossbeam::channel::Sender;
 use log::warn;
 use readable::{Date, Runtime, Unsigned};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use symphonia::core::{
@@ -33,6 +33,20 @@ struct TagMetadata {
     art: Option<Box<[u8]>>,
     release: Option<String>,
     genre: Option<String>,
+    sort: SortTags,
+    chapters: Vec<Chapter>,
+}
+
+#[derive(Debug, Default)]
+// The `ARTISTSORT`, `ALBUMSORT` and `TITLESORT`
+// tags of a single file, if they exist.
+//
+// These are not saved in the `Collection`, they
+// are only used by `CCD` to create the `Lexi` sorts.
+pub(super) struct SortTags {
+    pub(super) artist: Option<String>,
+    pub(super) album: Option<String>,
+    pub(super) title: Option<String>,
 }
 
 //---------------------------------------------------------------------------------------------------- Metadata functions.
@@ -45,6 +59,11 @@ impl crate::ccd::Ccd {
     // Outputs the three main `Vec`'s of the `Collection` with
     // mostly done but incomplete data (needs sorting, addition, etc).
     //
+    // The `SortTags` of any file that had them are also
+    // returned, keyed by `PATH`, for `Ccd::sort_names()`.
+    //
+    // Same with the chapters of any file that had them, for `Chapters`.
+    //
     // Unlike the `convert_art()` functions, this one is too long to
     // justify making 2 copies for single/multi-threaded purposes.
     //
@@ -61,7 +80,14 @@ impl crate::ccd::Ccd {
     pub(super) fn the_loop(
         to_kernel: &Sender<CcdToKernel>,
         vec_paths: Vec<(PathBuf, &'static str, &'static str)>,
-    ) -> (Vec<Artist>, Vec<Album>, Vec<Song>, usize) {
+    ) -> (
+        Vec<Artist>,
+        Vec<Album>,
+        Vec<Song>,
+        usize,
+        HashMap<PathBuf, SortTags>,
+        BTreeMap<PathBuf, Vec<Chapter>>,
+    ) {
         // ResetUpdate.
         //
         // These are sent to `Kernel` for progress updates.
@@ -107,6 +133,8 @@ impl crate::ccd::Ccd {
         let vec_album: Mutex<Vec<Album>> = Mutex::new(Vec::with_capacity(album_len_maybe));
         let vec_song: Mutex<Vec<Song>> = Mutex::new(Vec::with_capacity(song_len_maybe));
         let count_art: Mutex<usize> = Mutex::new(0);
+        let sort_tags: Mutex<HashMap<PathBuf, SortTags>> = Mutex::new(HashMap::new());
+        let chapters: Mutex<BTreeMap<PathBuf, Vec<Chapter>>> = Mutex::new(BTreeMap::new());
 
         // In this loop, each `PathBuf` represents a new `Song` with metadata.
         // There are 3 logical possibilities with 3 actions associated with them:
@@ -176,8 +204,18 @@ impl crate::ccd::Ccd {
                             art,
                             release,
                             genre,
+                            sort,
+                            chapters: song_chapters,
                         } = metadata;
 
+                        // Most files don't have these, only keep the ones that do.
+                        if sort.artist.is_some() || sort.album.is_some() || sort.title.is_some() {
+                            lock!(sort_tags).insert(path.clone(), sort);
+                        }
+                        if !song_chapters.is_empty() {
+                            lock!(chapters).insert(path.clone(), song_chapters);
+                        }
+
                         // Convert `String`'s to `Arc<str>`.
                         let artist_lowercase: Arc<str> = artist.to_lowercase().into();
                         let album_lowercase: Arc<str> = album.to_lowercase().into();
@@ -436,18 +474,22 @@ impl crate::ccd::Ccd {
         // INVARIANT:
         // As long as none of the above `scoped` threads
         // `panic()!`'ed, these `.into_inner()`'s are safe.
-        let (mut vec_artist, mut vec_album, mut vec_song, count_art) = (
+        let (mut vec_artist, mut vec_album, mut vec_song, count_art, sort_tags, chapters) = (
             vec_artist.into_inner().unwrap(),
             vec_album.into_inner().unwrap(),
             vec_song.into_inner().unwrap(),
             count_art.into_inner().unwrap(),
+            sort_tags.into_inner().unwrap(),
+            chapters.into_inner().unwrap(),
         );
 
         vec_artist.shrink_to_fit();
         vec_album.shrink_to_fit();
         vec_song.shrink_to_fit();
 
-        (vec_artist, vec_album, vec_song, count_art)
+        (
+            vec_artist, vec_album, vec_song, count_art, sort_tags, chapters,
+        )
     }
 
     #[inline(always)]
@@ -636,6 +678,30 @@ impl crate::ccd::Ccd {
         None
     }
 
+    #[inline(always)]
+    // Attempt to get the `ARTISTSORT`, `ALBUMSORT` and `TITLESORT` tags.
+    //
+    // Like `tag_artist()`, `AlbumArtist` is preferred.
+    fn tag_sort(tag: &mut [Tag]) -> SortTags {
+        let mut find = |keys: &[StandardTagKey]| {
+            for key in keys {
+                if let Some(t) = tag.iter_mut().find(|i| i.std_key == Some(*key)) {
+                    let o = Self::value(t);
+                    if o.is_some() {
+                        return o;
+                    }
+                }
+            }
+            None
+        };
+
+        SortTags {
+            artist: find(&[StandardTagKey::SortAlbumArtist, StandardTagKey::SortArtist]),
+            album: find(&[StandardTagKey::SortAlbum]),
+            title: find(&[StandardTagKey::SortTrackTitle]),
+        }
+    }
+
     #[inline(always)]
     // Attempt to get album title.
     fn tag_album(tag: &mut [Tag]) -> Option<String> {
@@ -745,6 +811,24 @@ impl crate::ccd::Ccd {
         }
     }
 
+    #[inline(always)]
+    // Get the chapters, from Vorbis `CHAPTERxx` comments, or
+    // if there are none, from the ID3 `CHAP` frames/MP4 `chpl` atom.
+    fn tag_chapters(tag: &[Tag], path: &Path) -> Vec<Chapter> {
+        use symphonia::core::meta::Value;
+        let chapters =
+            crate::collection::vorbis_chapters(tag.iter().filter_map(|t| match &t.value {
+                Value::String(s) => Some((t.key.as_str(), s.as_str())),
+                _ => None,
+            }));
+
+        if chapters.is_empty() {
+            crate::collection::chapters(path)
+        } else {
+            chapters
+        }
+    }
+
     #[inline(always)]
     fn art(mut visuals: Vec<Visual>) -> Option<Box<[u8]>> {
         if !visuals.is_empty() {
@@ -920,6 +1004,8 @@ impl crate::ccd::Ccd {
         let disc = Self::tag_disc(&mut tags);
         let release = Self::tag_release(&mut tags);
         let genre = Self::tag_genre(&mut tags);
+        let sort = Self::tag_sort(&mut tags);
+        let chapters = Self::tag_chapters(&tags, path);
 
         Ok(TagMetadata {
             artist,
@@ -933,6 +1019,8 @@ impl crate::ccd::Ccd {
             art,
             release,
             genre,
+            sort,
+            chapters,
         })
     }
 }
@@ -0,0 +1,1019 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{Collection, SongKey},
+    constants::{CHAPTERS_VERSION, FESTIVAL, FRONTEND_SUB_DIR, HEADER, STATE_SUB_DIR},
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Don't read tags/atoms bigger than this, something is wrong with the file.
+const MAX_READ: u64 = 64 * 1024 * 1024;
+
+// MP4 `chpl` timestamps are in 100 nanosecond units.
+const CHPL_TIMESCALE: u64 = 10_000_000;
+
+// Don't read more samples than this from a QuickTime chapter track.
+const MAX_QT_CHAPTERS: usize = 10_000;
+
+/// Going to the previous chapter within this many seconds
+/// of the current chapter's start goes to the chapter before it,
+/// instead of the start of the current one.
+pub const CHAPTER_PREVIOUS_THRESHOLD: u32 = 3;
+
+//---------------------------------------------------------------------------------------------------- Statics
+/// This is the single, global copy of `Chapters` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `CHAPTERS.read()`.
+pub static CHAPTERS: ChaptersLock = ChaptersLock(RwLock::new(Chapters::new()));
+
+//---------------------------------------------------------------------------------------------------- ChaptersLock
+/// There is only a single, global copy of `Chapters` that `Kernel` uses: [`CHAPTERS`].
+///
+/// To obtain a read-only lock, use `CHAPTERS.read()`.
+pub struct ChaptersLock(RwLock<Chapters>);
+
+impl ChaptersLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`Chapters`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, Chapters> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`Chapters`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<RwLockReadGuard<'static, Chapters>, TryLockError<RwLockReadGuard<'static, Chapters>>>
+    {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    // Only `Kernel` and `CCD` should be writing.
+    pub(crate) fn write(&'static self) -> RwLockWriteGuard<'static, Chapters> {
+        lockw!(self.0)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Chapters
+disk::bincode2!(
+    Chapters,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "chapters",
+    HEADER,
+    CHAPTERS_VERSION
+);
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+/// The chapters (or markers) of each [`Song`](crate::collection::Song) that has any.
+///
+/// Like [`crate::collection::FirstSeen`], this is keyed by the `Song`'s `PATH`.
+///
+/// `CCD` re-creates this when creating a new [`Collection`], only
+/// files that have at least 1 chapter get an entry. Each entry is sorted.
+pub struct Chapters(pub BTreeMap<PathBuf, Vec<Chapter>>);
+
+impl Chapters {
+    #[inline]
+    /// Creates an empty [`Chapters`].
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    #[inline]
+    /// Returns the chapters of the file at this `PATH`.
+    ///
+    /// This is empty if it has none.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> &[Chapter] {
+        self.0.get(path.as_ref()).map_or(&[], |c| c.as_slice())
+    }
+
+    #[inline]
+    /// INVARIANT: assumes key is valid.
+    ///
+    /// Returns the chapters of this [`Song`](crate::collection::Song).
+    pub fn song(&self, key: SongKey, collection: &Collection) -> &[Chapter] {
+        self.get(&collection.songs[key].path)
+    }
+
+    #[inline]
+    /// How many files have chapters?
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    /// Is this empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Chapter
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+)]
+/// A chapter (or marker) within a [`Song`](crate::collection::Song).
+pub struct Chapter {
+    /// The second this chapter starts at.
+    pub start: u32,
+    /// The title of this chapter, this may be empty.
+    pub title: String,
+}
+
+/// Parse the chapters of the audio file at this `PATH`.
+///
+/// Supported are:
+/// - ID3v2 `CHAP` frames (`mp3`)
+/// - MP4 `chpl` atoms (`m4a`, `m4b`)
+/// - QuickTime chapter tracks (`m4a`, `m4b`), if there is no `chpl`
+///
+/// Vorbis `CHAPTERxx` comments are read by `CCD` alongside
+/// the other tags instead, see [`Chapters`].
+///
+/// The returned chapters are sorted by their start.
+///
+/// This returns an empty `Vec` if the file has no chapters,
+/// isn't a supported format, or on any error.
+pub fn chapters<P: AsRef<Path>>(path: P) -> Vec<Chapter> {
+    let mut file = match File::open(path) {
+        Ok(f) => f,
+        Err(_) => return vec![],
+    };
+
+    let mut header = [0; 10];
+    if file.read_exact(&mut header).is_err() {
+        return vec![];
+    }
+
+    let mut chapters = if header.starts_with(b"ID3") {
+        id3_chapters(&mut file)
+    } else if &header[4..8] == b"ftyp" {
+        mp4_chapters(&mut file)
+    } else {
+        vec![]
+    };
+
+    chapters.sort();
+    chapters
+}
+
+/// The index of the chapter `elapsed` (seconds) is in.
+///
+/// `None` if there are no chapters, or `elapsed` is before the first.
+///
+/// `chapters` must be sorted.
+pub fn chapter_index(chapters: &[Chapter], elapsed: u32) -> Option<usize> {
+    chapters.iter().rposition(|c| c.start <= elapsed)
+}
+
+/// The start (seconds) of the chapter after the one `elapsed` is in.
+///
+/// `None` if `elapsed` is in the last chapter (or there are none).
+///
+/// `chapters` must be sorted.
+pub fn next_chapter(chapters: &[Chapter], elapsed: u32) -> Option<u32> {
+    chapters.iter().find(|c| c.start > elapsed).map(|c| c.start)
+}
+
+/// The start (seconds) to go back to from `elapsed`.
+///
+/// This is the start of the current chapter, or the one before it if
+/// `elapsed` is within [`CHAPTER_PREVIOUS_THRESHOLD`] of the current start.
+///
+/// `0` if there is no previous chapter.
+///
+/// `chapters` must be sorted.
+pub fn previous_chapter(chapters: &[Chapter], elapsed: u32) -> u32 {
+    match chapter_index(chapters, elapsed) {
+        Some(i) if elapsed >= chapters[i].start + CHAPTER_PREVIOUS_THRESHOLD => chapters[i].start,
+        Some(i) if i > 0 => chapters[i - 1].start,
+        _ => 0,
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- ID3
+/// Parse the `CHAP` frames of an ID3v2.3/ID3v2.4 tag.
+///
+/// The tag must start at the beginning of `reader`.
+///
+/// Only the frame headers and `CHAP` bodies are read,
+/// every other frame (cover art, lyrics, etc) is seeked past.
+pub(crate) fn id3_chapters<R: Read + Seek>(reader: &mut R) -> Vec<Chapter> {
+    let Some(header) = read(reader, 0, 10) else {
+        return vec![];
+    };
+    if !header.starts_with(b"ID3") {
+        return vec![];
+    }
+
+    let version = header[3];
+    if version != 3 && version != 4 {
+        return vec![];
+    }
+
+    // Syncsafe tag size, excluding the header.
+    let end = 10 + syncsafe(&header[6..10]) as u64;
+
+    // Skip the extended header.
+    let mut i = 10;
+    if header[5] & 0b0100_0000 != 0 {
+        let Some(b) = read(reader, 10, 14) else {
+            return vec![];
+        };
+        i += match version {
+            3 => 4 + u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            _ => syncsafe(&b),
+        } as u64;
+    }
+
+    let mut chapters = vec![];
+
+    while i + 10 <= end {
+        let Some(frame) = read(reader, i, i + 10) else {
+            break;
+        };
+        // Padding.
+        if frame[0] == 0 {
+            break;
+        }
+
+        let body = i + 10;
+        i = body + frame_size(&frame[4..8], version) as u64;
+
+        if &frame[..4] == b"CHAP" {
+            if let Some(c) = read(reader, body, i).and_then(|b| chap(&b, version)) {
+                chapters.push(c);
+            }
+        }
+    }
+
+    chapters
+}
+
+// Parse a single `CHAP` frame body.
+fn chap(body: &[u8], version: u8) -> Option<Chapter> {
+    // Element ID, null-terminated.
+    let id_end = body.iter().position(|b| *b == 0)?;
+    let times = body.get(id_end + 1..id_end + 17)?;
+    let start_ms = u32::from_be_bytes([times[0], times[1], times[2], times[3]]);
+
+    // Sub-frames, the title is `TIT2`.
+    let title = frames(&body[id_end + 17..], version)
+        .find(|(id, _)| id == b"TIT2")
+        .map(|(_, b)| text(b))
+        .unwrap_or_default();
+
+    Some(Chapter {
+        start: start_ms / 1000,
+        title,
+    })
+}
+
+// Iterate over the `(id, body)` of ID3v2 frames.
+fn frames(mut bytes: &[u8], version: u8) -> impl Iterator<Item = (&[u8], &[u8])> {
+    std::iter::from_fn(move || {
+        if bytes.len() < 10 || bytes[0] == 0 {
+            return None;
+        }
+
+        let id = &bytes[..4];
+        let size = frame_size(&bytes[4..8], version) as usize;
+        let body = bytes.get(10..10 + size)?;
+
+        bytes = &bytes[10 + size..];
+        Some((id, body))
+    })
+}
+
+// Decode the 4 byte size of an ID3v2 frame header,
+// plain in ID3v2.3, syncsafe in ID3v2.4.
+fn frame_size(b: &[u8], version: u8) -> u32 {
+    match version {
+        3 => u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+        _ => syncsafe(b),
+    }
+}
+
+// Decode an ID3v2 text frame body (encoding byte + text).
+fn text(body: &[u8]) -> String {
+    let Some((encoding, text)) = body.split_first() else {
+        return String::new();
+    };
+
+    let s = match encoding {
+        // ISO-8859-1.
+        0 => text.iter().map(|b| *b as char).collect(),
+        // UTF-16 with BOM, UTF-16BE.
+        1 | 2 => {
+            let (le, text) = match text {
+                [0xFF, 0xFE, rest @ ..] => (true, rest),
+                [0xFE, 0xFF, rest @ ..] => (false, rest),
+                _ => (false, text),
+            };
+            let units: Vec<u16> = text
+                .chunks_exact(2)
+                .map(|c| match le {
+                    true => u16::from_le_bytes([c[0], c[1]]),
+                    false => u16::from_be_bytes([c[0], c[1]]),
+                })
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        // UTF-8.
+        _ => String::from_utf8_lossy(text).into_owned(),
+    };
+
+    s.trim_end_matches('\0').to_string()
+}
+
+// Decode a 4 byte syncsafe integer.
+fn syncsafe(b: &[u8]) -> u32 {
+    b.iter()
+        .take(4)
+        .fold(0, |acc, b| (acc << 7) | (*b & 0x7F) as u32)
+}
+
+//---------------------------------------------------------------------------------------------------- Vorbis
+/// Parse Vorbis `CHAPTERxx` comments (`flac`, `ogg`, `opus`), given as `(key, value)` pairs.
+///
+/// `CHAPTERxx` holds the start as `HH:MM:SS.mmm`, `CHAPTERxxNAME` holds the title.
+/// Keys are case-insensitive, other comments are ignored.
+///
+/// The returned chapters are sorted by their start.
+pub(crate) fn vorbis_chapters<'a, I>(comments: I) -> Vec<Chapter>
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    // Chapter number -> (start, title).
+    let mut map: BTreeMap<&str, (Option<u32>, String)> = BTreeMap::new();
+
+    for (key, value) in comments {
+        let Some(rest) = key.get(..7).filter(|k| k.eq_ignore_ascii_case("CHAPTER")) else {
+            continue;
+        };
+        let rest = &key[rest.len()..];
+        let digits = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 {
+            continue;
+        }
+
+        let (number, suffix) = rest.split_at(digits);
+        let entry = map.entry(number).or_default();
+        if suffix.is_empty() {
+            entry.0 = vorbis_time(value);
+        } else if suffix.eq_ignore_ascii_case("NAME") {
+            entry.1 = value.trim().to_string();
+        }
+    }
+
+    let mut chapters: Vec<Chapter> = map
+        .into_values()
+        .filter_map(|(start, title)| {
+            Some(Chapter {
+                start: start?,
+                title,
+            })
+        })
+        .collect();
+
+    chapters.sort();
+    chapters
+}
+
+// Parse a `HH:MM:SS.mmm` timestamp into seconds.
+fn vorbis_time(s: &str) -> Option<u32> {
+    let s = s.trim();
+    let s = s.split_once('.').map_or(s, |(s, _)| s);
+
+    s.split(':').try_fold(0_u32, |acc, part| {
+        acc.checked_mul(60)?.checked_add(part.parse().ok()?)
+    })
+}
+
+//---------------------------------------------------------------------------------------------------- MP4
+/// Parse the body (after the size and `chpl` type) of an MP4 `chpl` (Nero chapter) atom.
+pub(crate) fn chpl_chapters(chpl: &[u8]) -> Vec<Chapter> {
+    // Version + flags, version `1` has 4 extra reserved bytes.
+    let Some(version) = chpl.first() else {
+        return vec![];
+    };
+    let mut i = if *version == 1 { 8 } else { 4 };
+
+    let Some(count) = chpl.get(i) else {
+        return vec![];
+    };
+    i += 1;
+
+    let mut chapters = Vec::with_capacity(*count as usize);
+    for _ in 0..*count {
+        let Some(start) = chpl.get(i..i + 8) else {
+            break;
+        };
+        let start = u64::from_be_bytes(start.try_into().unwrap_or_default());
+
+        let Some(len) = chpl.get(i + 8) else { break };
+        let Some(title) = chpl.get(i + 9..i + 9 + *len as usize) else {
+            break;
+        };
+        i += 9 + *len as usize;
+
+        chapters.push(Chapter {
+            start: (start / CHPL_TIMESCALE) as u32,
+            title: String::from_utf8_lossy(title).into_owned(),
+        });
+    }
+
+    chapters
+}
+
+/// Parse the chapters of an MP4 file.
+///
+/// The `moov/udta/chpl` atom is used if it exists,
+/// else the QuickTime chapter track, see [`qt_chapters`].
+pub(crate) fn mp4_chapters<R: Read + Seek>(file: &mut R) -> Vec<Chapter> {
+    let Some(len) = file.seek(SeekFrom::End(0)).ok() else {
+        return vec![];
+    };
+    let Some(moov) = find_atom(file, 0, len, b"moov") else {
+        return vec![];
+    };
+
+    let chapters = mp4_chpl(file, moov)
+        .map(|chpl| chpl_chapters(&chpl))
+        .unwrap_or_default();
+
+    if chapters.is_empty() {
+        qt_chapters(file, moov).unwrap_or_default()
+    } else {
+        chapters
+    }
+}
+
+// Find and read the `moov/udta/chpl` atom's body.
+fn mp4_chpl<R: Read + Seek>(file: &mut R, moov: (u64, u64)) -> Option<Vec<u8>> {
+    let udta = find_atom(file, moov.0, moov.1, b"udta")?;
+    let chpl = find_atom(file, udta.0, udta.1, b"chpl")?;
+    read(file, chpl.0, chpl.1)
+}
+
+/// Parse the QuickTime chapter track within this `moov` atom's body.
+///
+/// This is a text track another track references in its `tref/chap`
+/// atom (`m4b` audiobooks from iTunes, Audible, `ffmpeg`, etc).
+/// Each sample is a chapter, it starts at the sample's time and its text is the title.
+fn qt_chapters<R: Read + Seek>(file: &mut R, moov: (u64, u64)) -> Option<Vec<Chapter>> {
+    let traks: Vec<(u64, u64)> = atoms(file, moov.0, moov.1)
+        .into_iter()
+        .filter(|(kind, _)| kind == b"trak")
+        .map(|(_, body)| body)
+        .collect();
+
+    // The track IDs referenced as chapters.
+    let mut ids = vec![];
+    for trak in &traks {
+        let chap = find_atom(file, trak.0, trak.1, b"tref")
+            .and_then(|tref| find_atom(file, tref.0, tref.1, b"chap"))
+            .and_then(|chap| read(file, chap.0, chap.1));
+        if let Some(chap) = chap {
+            ids.extend(chap.chunks_exact(4).filter_map(|b| be_u32(b, 0)));
+        }
+    }
+    if ids.is_empty() {
+        return None;
+    }
+
+    let trak = traks.into_iter().find(|trak| {
+        find_atom(file, trak.0, trak.1, b"tkhd")
+            .and_then(|tkhd| read(file, tkhd.0, tkhd.1))
+            .and_then(|tkhd| match tkhd.first()? {
+                // Version 1 has 64-bit creation/modification times.
+                1 => be_u32(&tkhd, 20),
+                _ => be_u32(&tkhd, 12),
+            })
+            .is_some_and(|id| ids.contains(&id))
+    })?;
+
+    let mdia = find_atom(file, trak.0, trak.1, b"mdia")?;
+    let mdhd = find_atom(file, mdia.0, mdia.1, b"mdhd")?;
+    let mdhd = read(file, mdhd.0, mdhd.1)?;
+    let timescale = match mdhd.first()? {
+        1 => be_u32(&mdhd, 20)?,
+        _ => be_u32(&mdhd, 12)?,
+    } as u64;
+    if timescale == 0 {
+        return None;
+    }
+
+    let minf = find_atom(file, mdia.0, mdia.1, b"minf")?;
+    let stbl = find_atom(file, minf.0, minf.1, b"stbl")?;
+    let mut table = |kind: &[u8; 4]| {
+        find_atom(file, stbl.0, stbl.1, kind).and_then(|atom| read(file, atom.0, atom.1))
+    };
+    let stts = table(b"stts")?;
+    let stsz = table(b"stsz")?;
+    let stsc = table(b"stsc")?;
+    let (stco, offset_len) = match table(b"stco") {
+        Some(stco) => (stco, 4),
+        None => (table(b"co64")?, 8),
+    };
+
+    // Sample sizes.
+    let count = (be_u32(&stsz, 8)? as usize).min(MAX_QT_CHAPTERS);
+    let sizes: Vec<u64> = match be_u32(&stsz, 4)? {
+        0 => (0..count)
+            .map_while(|i| be_u32(&stsz, 12 + i * 4))
+            .map(u64::from)
+            .collect(),
+        size => vec![size as u64; count],
+    };
+
+    // Sample start times.
+    let mut starts = Vec::with_capacity(sizes.len());
+    let mut time = 0_u64;
+    for i in 0..be_u32(&stts, 4)? as usize {
+        let (Some(n), Some(delta)) = (be_u32(&stts, 8 + i * 8), be_u32(&stts, 12 + i * 8)) else {
+            break;
+        };
+        for _ in 0..n {
+            if starts.len() == sizes.len() {
+                break;
+            }
+            starts.push(time);
+            time += delta as u64;
+        }
+    }
+
+    // Sample offsets, chunks hold `samples_per_chunk`
+    // samples each, the last entry applies to the rest.
+    let chunks = be_u32(&stco, 4)? as usize;
+    let runs: Vec<(usize, usize)> = (0..be_u32(&stsc, 4)? as usize)
+        .map_while(|i| Some((be_u32(&stsc, 8 + i * 12)?, be_u32(&stsc, 12 + i * 12)?)))
+        .map(|(first, n)| (first as usize, n as usize))
+        .collect();
+    let mut offsets = Vec::with_capacity(sizes.len());
+    for chunk in 0..chunks {
+        let mut offset = match offset_len {
+            4 => be_u32(&stco, 8 + chunk * 4)? as u64,
+            _ => u64::from_be_bytes(stco.get(8 + chunk * 8..16 + chunk * 8)?.try_into().ok()?),
+        };
+        let samples = runs
+            .iter()
+            .rev()
+            .find(|(first, _)| *first <= chunk + 1)
+            .map_or(1, |(_, n)| *n);
+        for _ in 0..samples {
+            let Some(size) = sizes.get(offsets.len()) else {
+                break;
+            };
+            offsets.push((offset, *size));
+            offset += size;
+        }
+    }
+
+    let chapters = starts
+        .into_iter()
+        .zip(offsets)
+        .filter_map(|(start, (offset, size))| {
+            Some(Chapter {
+                start: (start / timescale) as u32,
+                title: qt_text(&read(file, offset, offset + size)?),
+            })
+        })
+        .collect();
+
+    Some(chapters)
+}
+
+// Decode a QuickTime text sample (16-bit length + text).
+fn qt_text(sample: &[u8]) -> String {
+    let len = sample
+        .get(..2)
+        .map_or(0, |b| u16::from_be_bytes([b[0], b[1]]));
+    let Some(text) = sample.get(2..2 + len as usize) else {
+        return String::new();
+    };
+
+    match text {
+        [0xFE, 0xFF, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => String::from_utf8_lossy(text).into_owned(),
+    }
+}
+
+// Read the big-endian `u32` at `i`.
+fn be_u32(b: &[u8], i: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(b.get(i..i + 4)?.try_into().ok()?))
+}
+
+// Find the atom with this type between `start..end`
+// of the file, returning the `(start, end)` of its body.
+fn find_atom<R: Read + Seek>(
+    file: &mut R,
+    start: u64,
+    end: u64,
+    kind: &[u8; 4],
+) -> Option<(u64, u64)> {
+    let mut found = None;
+    walk_atoms(file, start, end, |k, body| {
+        if k == kind {
+            found = Some(body);
+        }
+        found.is_none()
+    });
+    found
+}
+
+// The `(type, (start, end))` of every atom between `start..end`,
+// where `(start, end)` is the atom's body.
+fn atoms<R: Read + Seek>(file: &mut R, start: u64, end: u64) -> Vec<([u8; 4], (u64, u64))> {
+    let mut atoms = vec![];
+    walk_atoms(file, start, end, |k, body| {
+        atoms.push((*k, body));
+        true
+    });
+    atoms
+}
+
+// Call `f` with the type and body `(start, end)` of each atom
+// between `start..end`, until it returns `false` or on errors.
+fn walk_atoms<R, F>(file: &mut R, mut start: u64, end: u64, mut f: F) -> Option<()>
+where
+    R: Read + Seek,
+    F: FnMut(&[u8; 4], (u64, u64)) -> bool,
+{
+    while start + 8 <= end {
+        let header = read(file, start, start + 8)?;
+        let mut size = u32::from_be_bytes(header[..4].try_into().ok()?) as u64;
+        let mut body = start + 8;
+
+        match size {
+            // 64-bit size.
+            1 => {
+                let large = read(file, body, body + 8)?;
+                size = u64::from_be_bytes(large.try_into().ok()?);
+                body += 8;
+            }
+            // Extends to the end.
+            0 => size = end - start,
+            _ => (),
+        }
+
+        let atom_end = start.checked_add(size)?.min(end);
+        if atom_end <= start {
+            return None;
+        }
+
+        let kind: [u8; 4] = header[4..8].try_into().ok()?;
+        if !f(&kind, (body, atom_end)) {
+            return Some(());
+        }
+
+        start = atom_end;
+    }
+
+    Some(())
+}
+
+// Read the bytes at `start..end` of the file.
+fn read<R: Read + Seek>(file: &mut R, start: u64, end: u64) -> Option<Vec<u8>> {
+    let len = end.checked_sub(start)?;
+    if len > MAX_READ {
+        return None;
+    }
+
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut buf = vec![0; len as usize];
+    file.read_exact(&mut buf).ok()?;
+    Some(buf)
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    // An ID3v2.4 frame.
+    fn frame(id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let size = body.len() as u32;
+        let mut v = id.to_vec();
+        v.extend((0..4).rev().map(|i| ((size >> (i * 7)) & 0x7F) as u8));
+        v.extend([0, 0]);
+        v.extend(body);
+        v
+    }
+
+    // A `CHAP` frame titled `title`.
+    fn chap_frame(id: &str, start_ms: u32, title: &[u8]) -> Vec<u8> {
+        let mut body = id.as_bytes().to_vec();
+        body.push(0);
+        body.extend(start_ms.to_be_bytes());
+        body.extend([0; 4]);
+        body.extend([0xFF; 8]);
+        body.extend(frame(b"TIT2", title));
+        frame(b"CHAP", &body)
+    }
+
+    #[test]
+    fn id3() {
+        let mut frames = frame(b"TIT2", b"\x03song");
+        frames.extend(chap_frame("ch1", 61_500, b"\x03Second"));
+        frames.extend(chap_frame("ch0", 0, b"\x01\xFF\xFEF\0i\0r\0s\0t\0"));
+
+        let size = frames.len() as u32;
+        let mut tag = b"ID3\x04\x00\x00".to_vec();
+        tag.extend((0..4).rev().map(|i| ((size >> (i * 7)) & 0x7F) as u8));
+        tag.extend(frames);
+        // Padding.
+        tag.extend([0; 16]);
+
+        let mut chapters = id3_chapters(&mut Cursor::new(&tag));
+        chapters.sort();
+        assert_eq!(
+            chapters,
+            [
+                Chapter {
+                    start: 0,
+                    title: "First".into()
+                },
+                Chapter {
+                    start: 61,
+                    title: "Second".into()
+                },
+            ]
+        );
+
+        // Not a tag, or a truncated one.
+        assert!(id3_chapters(&mut Cursor::new(b"")).is_empty());
+        assert!(id3_chapters(&mut Cursor::new(&tag[..20])).is_empty());
+    }
+
+    #[test]
+    fn id3_skip() {
+        // ID3v2.3, plain frame sizes.
+        let frame = |id: &[u8; 4], body: &[u8]| {
+            let mut v = id.to_vec();
+            v.extend((body.len() as u32).to_be_bytes());
+            v.extend([0, 0]);
+            v.extend(body);
+            v
+        };
+
+        // Extended header, big cover art, then the chapter.
+        let mut frames = vec![0, 0, 0, 6, 0, 0, 0, 0, 0, 0];
+        frames.extend(frame(b"APIC", &vec![0xFF; 1024 * 1024]));
+        let mut chap = b"ch0\0".to_vec();
+        chap.extend(5_000_u32.to_be_bytes());
+        chap.extend([0; 12]);
+        chap.extend(frame(b"TIT2", b"\x00Intro"));
+        frames.extend(frame(b"CHAP", &chap));
+
+        let size = frames.len() as u32;
+        let mut tag = b"ID3\x03\x00\x40".to_vec();
+        tag.extend((0..4).rev().map(|i| ((size >> (i * 7)) & 0x7F) as u8));
+        tag.extend(frames);
+
+        assert_eq!(
+            id3_chapters(&mut Cursor::new(&tag)),
+            [Chapter {
+                start: 5,
+                title: "Intro".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn chpl() {
+        let mut chpl = vec![1, 0, 0, 0, 0, 0, 0, 0, 2];
+        chpl.extend((5 * CHPL_TIMESCALE).to_be_bytes());
+        chpl.push(3);
+        chpl.extend(b"one");
+        chpl.extend((90 * CHPL_TIMESCALE).to_be_bytes());
+        chpl.push(3);
+        chpl.extend(b"two");
+
+        assert_eq!(
+            chpl_chapters(&chpl),
+            [
+                Chapter {
+                    start: 5,
+                    title: "one".into()
+                },
+                Chapter {
+                    start: 90,
+                    title: "two".into()
+                },
+            ]
+        );
+
+        // Truncated.
+        assert_eq!(chpl_chapters(&chpl[..20]).len(), 0);
+        assert!(chpl_chapters(&[]).is_empty());
+    }
+
+    // An MP4 atom.
+    fn atom(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut v = (8 + body.len() as u32).to_be_bytes().to_vec();
+        v.extend(kind);
+        v.extend(body);
+        v
+    }
+
+    // An MP4 full atom body, `version + flags` then `u32`s.
+    fn full(fields: &[u32]) -> Vec<u8> {
+        let mut v = vec![0; 4];
+        v.extend(fields.iter().flat_map(|f| f.to_be_bytes()));
+        v
+    }
+
+    #[test]
+    // The audio track references a text track, its samples are the chapters.
+    fn qt() {
+        let ftyp = atom(b"ftyp", b"M4B \0\0\0\0");
+
+        // Text samples, 2 in the 1st chunk, 1 in the 2nd.
+        let samples: Vec<Vec<u8>> = [&b"Intro"[..], b"Middle", b"\xFE\xFF\0E\0n\0d"]
+            .into_iter()
+            .map(|t| {
+                let mut v = (t.len() as u16).to_be_bytes().to_vec();
+                v.extend(t);
+                v
+            })
+            .collect();
+        let mdat = atom(b"mdat", &samples.concat());
+        let first = (ftyp.len() + 8) as u32;
+        let third = first + (samples[0].len() + samples[1].len()) as u32;
+
+        let audio = atom(
+            b"trak",
+            &[
+                atom(b"tkhd", &full(&[0, 0, 1])),
+                atom(b"tref", &atom(b"chap", &2_u32.to_be_bytes())),
+            ]
+            .concat(),
+        );
+
+        // Timescale of 1000, chapters at 0s, 90.5s and 3600s.
+        let stbl = [
+            atom(b"stts", &full(&[3, 1, 90_500, 1, 3_509_500, 1, 1000])),
+            atom(
+                b"stsz",
+                &full(&[
+                    0,
+                    3,
+                    samples[0].len() as u32,
+                    samples[1].len() as u32,
+                    samples[2].len() as u32,
+                ]),
+            ),
+            atom(b"stsc", &full(&[2, 1, 2, 1, 2, 1, 1])),
+            atom(b"stco", &full(&[2, first, third])),
+        ]
+        .concat();
+        let mdia = [
+            atom(b"mdhd", &full(&[0, 0, 1000, 0])),
+            atom(b"minf", &atom(b"stbl", &stbl)),
+        ]
+        .concat();
+        let text = atom(
+            b"trak",
+            &[atom(b"tkhd", &full(&[0, 0, 2])), atom(b"mdia", &mdia)].concat(),
+        );
+
+        let moov = atom(b"moov", &[audio, text.clone()].concat());
+        let file = [ftyp.clone(), mdat.clone(), moov].concat();
+
+        assert_eq!(
+            mp4_chapters(&mut Cursor::new(&file)),
+            [
+                Chapter {
+                    start: 0,
+                    title: "Intro".into()
+                },
+                Chapter {
+                    start: 90,
+                    title: "Middle".into()
+                },
+                Chapter {
+                    start: 3600,
+                    title: "End".into()
+                },
+            ]
+        );
+
+        // Without the reference, there are no chapters.
+        let file = [ftyp, mdat, atom(b"moov", &text)].concat();
+        assert!(mp4_chapters(&mut Cursor::new(&file)).is_empty());
+    }
+
+    #[test]
+    fn vorbis() {
+        let comments = [
+            ("TITLE", "mix"),
+            ("CHAPTER002", "01:02:03.500"),
+            ("chapter002name", " Last "),
+            ("CHAPTER001", "00:00:30.000"),
+            ("CHAPTER001NAME", "First"),
+            // No start, or a broken one.
+            ("CHAPTER003NAME", "Missing"),
+            ("CHAPTER004", "xx:00"),
+            ("CHAPTERNAME", "?"),
+        ];
+
+        assert_eq!(
+            vorbis_chapters(comments),
+            [
+                Chapter {
+                    start: 30,
+                    title: "First".into()
+                },
+                Chapter {
+                    start: 3723,
+                    title: "Last".into()
+                },
+            ]
+        );
+
+        assert_eq!(vorbis_time("5"), Some(5));
+        assert_eq!(vorbis_time("1:05.9"), Some(65));
+        assert_eq!(vorbis_time(""), None);
+    }
+
+    #[test]
+    // Files without chapters return nothing.
+    fn chapters_get() {
+        let mut c = Chapters::new();
+        let chapter = Chapter {
+            start: 1,
+            title: String::new(),
+        };
+        c.0.insert(PathBuf::from("/a"), vec![chapter.clone()]);
+
+        assert_eq!(c.get("/a"), [chapter]);
+        assert!(c.get("/b").is_empty());
+        assert_eq!(c.len(), 1);
+    }
+
+    #[test]
+    // Next is the one after, previous is the start
+    // of the current one, or the one before it.
+    fn navigation() {
+        let c: Vec<Chapter> = [0, 60, 120]
+            .into_iter()
+            .map(|start| Chapter {
+                start,
+                title: String::new(),
+            })
+            .collect();
+
+        assert_eq!(chapter_index(&c, 0), Some(0));
+        assert_eq!(chapter_index(&c, 61), Some(1));
+        assert_eq!(chapter_index(&c, 999), Some(2));
+        assert_eq!(chapter_index(&[], 5), None);
+
+        assert_eq!(next_chapter(&c, 0), Some(60));
+        assert_eq!(next_chapter(&c, 60), Some(120));
+        assert_eq!(next_chapter(&c, 120), None);
+
+        assert_eq!(previous_chapter(&c, 90), 60);
+        assert_eq!(previous_chapter(&c, 61), 0);
+        assert_eq!(previous_chapter(&c, 1), 0);
+        assert_eq!(previous_chapter(&[], 50), 0);
+    }
+
+    #[test]
+    fn _syncsafe() {
+        assert_eq!(syncsafe(&[0, 0, 0x02, 0x01]), 257);
+        assert_eq!(syncsafe(&[0x7F, 0x7F, 0x7F, 0x7F]), 0x0FFF_FFFF);
+    }
+}
@@ -4,7 +4,7 @@ use crate::collection::{
     artist::Artist,
     plural::{Albums, Artists, Songs},
     song::Song,
-    AlbumKey, ArtistKey, Key, Map, SongKey,
+    AlbumKey, ArtistKey, Key, Map, SongKey, SortDateAdded, PLAY_STATS, RATINGS,
 };
 use crate::constants::{COLLECTION_VERSION, FESTIVAL, FRONTEND_SUB_DIR, HEADER, STATE_SUB_DIR};
 use crate::sort::{AlbumSort, ArtistSort, SongSort};
@@ -219,6 +219,13 @@ pub struct Collection {
     pub sort_song_title: Box<[SongKey]>,
     /// [`Song`] title longest to shortest.
     pub sort_song_title_rev: Box<[SongKey]>,
+
+    // Date added.
+    #[serde(skip)]
+    /// [`Album`] and [`Song`] sorted by when they were first seen.
+    ///
+    /// These are _not_ saved to disk, see [`SortDateAdded`].
+    pub sort_date_added: SortDateAdded,
 }
 
 impl Collection {
@@ -282,6 +289,8 @@ impl Collection {
             sort_song_runtime_rev: Box::new([]),
             sort_song_title: Box::new([]),
             sort_song_title_rev: Box::new([]),
+
+            sort_date_added: SortDateAdded::new(),
         }
     }
 
@@ -698,14 +707,20 @@ impl Collection {
             RuntimeRev => &self.sort_album_runtime_rev,
             Title => &self.sort_album_title,
             TitleRev => &self.sort_album_title_rev,
+            DateAdded => &self.sort_date_added.album,
+            DateAddedRev => &self.sort_date_added.album_rev,
         }
         .iter()
     }
 
     /// Access `sort_song` fields in the [`Collection`] as an iterator via a [`SongSort`].
-    pub fn song_iter(&self, sort: SongSort) -> std::slice::Iter<'_, SongKey> {
+    ///
+    /// The play statistic sorts (e.g [`SongSort::PlayCount`]) and rating sorts
+    /// change as `Song`'s are played and rated, so they are shared (not copied)
+    /// from [`PLAY_STATS`] and [`RATINGS`] instead, no lock is held while iterating.
+    pub fn song_iter(&self, sort: SongSort) -> SongIter<'_> {
         use SongSort::*;
-        match sort {
+        let keys = match sort {
             AlbumReleaseArtistLexi => &self.sort_song_album_release_artist_lexi,
             AlbumReleaseArtistLexiRev => &self.sort_song_album_release_artist_lexi_rev,
             AlbumReleaseRevArtistLexi => &self.sort_song_album_release_rev_artist_lexi,
@@ -722,8 +737,17 @@ impl Collection {
             RuntimeRev => &self.sort_song_runtime_rev,
             Title => &self.sort_song_title,
             TitleRev => &self.sort_song_title_rev,
-        }
-        .iter()
+            DateAdded => &self.sort_date_added.song,
+            DateAddedRev => &self.sort_date_added.song_rev,
+            PlayCount | PlayCountRev | LastPlayed | LastPlayedRev => {
+                return SongIter::new(SongIterKeys::Shared(PLAY_STATS.sorted(sort, self)));
+            }
+            Rating | RatingRev => {
+                return SongIter::new(SongIterKeys::Shared(RATINGS.read().sorted(sort, self)));
+            }
+        };
+
+        SongIter::new(SongIterKeys::Borrowed(keys))
     }
 
     //-------------------------------------------------- Random
@@ -1025,6 +1049,138 @@ impl Collection {
     }
 }
 
+//---------------------------------------------------------------------------------------------------- Test Collection
+#[cfg(test)]
+impl Collection {
+    // A `Collection` of these `Artist`'s, `Album`'s and `Song`'s, for tests.
+    //
+    // Every `sort_` field is filled with all keys in the order given
+    // (`0..len`) like a real `Collection` always has, so code that
+    // walks any sort (e.g. `song_iter()`) sees every key.
+    pub(crate) fn test(artists: Vec<Artist>, albums: Vec<Album>, songs: Vec<Song>) -> Self {
+        let artist: Box<[ArtistKey]> = (0..artists.len()).map(ArtistKey::from).collect();
+        let album: Box<[AlbumKey]> = (0..albums.len()).map(AlbumKey::from).collect();
+        let song: Box<[SongKey]> = (0..songs.len()).map(SongKey::from).collect();
+
+        let mut c = Self {
+            empty: songs.is_empty(),
+            count_artist: Unsigned::from(artists.len()),
+            count_album: Unsigned::from(albums.len()),
+            count_song: Unsigned::from(songs.len()),
+
+            artists: Artists(artists.into_boxed_slice()),
+            albums: Albums(albums.into_boxed_slice()),
+            songs: Songs(songs.into_boxed_slice()),
+
+            sort_artist_lexi: artist.clone(),
+            sort_artist_lexi_rev: artist.clone(),
+            sort_artist_album_count: artist.clone(),
+            sort_artist_album_count_rev: artist.clone(),
+            sort_artist_song_count: artist.clone(),
+            sort_artist_song_count_rev: artist.clone(),
+            sort_artist_runtime: artist.clone(),
+            sort_artist_runtime_rev: artist.clone(),
+            sort_artist_name: artist.clone(),
+            sort_artist_name_rev: artist.clone(),
+
+            sort_album_release_artist_lexi: album.clone(),
+            sort_album_release_artist_lexi_rev: album.clone(),
+            sort_album_release_rev_artist_lexi: album.clone(),
+            sort_album_release_rev_artist_lexi_rev: album.clone(),
+            sort_album_lexi_artist_lexi: album.clone(),
+            sort_album_lexi_artist_lexi_rev: album.clone(),
+            sort_album_lexi_rev_artist_lexi: album.clone(),
+            sort_album_lexi_rev_artist_lexi_rev: album.clone(),
+            sort_album_lexi: album.clone(),
+            sort_album_lexi_rev: album.clone(),
+            sort_album_release: album.clone(),
+            sort_album_release_rev: album.clone(),
+            sort_album_runtime: album.clone(),
+            sort_album_runtime_rev: album.clone(),
+            sort_album_title: album.clone(),
+            sort_album_title_rev: album.clone(),
+
+            sort_song_album_release_artist_lexi: song.clone(),
+            sort_song_album_release_artist_lexi_rev: song.clone(),
+            sort_song_album_release_rev_artist_lexi: song.clone(),
+            sort_song_album_release_rev_artist_lexi_rev: song.clone(),
+            sort_song_album_lexi_artist_lexi: song.clone(),
+            sort_song_album_lexi_artist_lexi_rev: song.clone(),
+            sort_song_album_lexi_rev_artist_lexi: song.clone(),
+            sort_song_album_lexi_rev_artist_lexi_rev: song.clone(),
+            sort_song_lexi: song.clone(),
+            sort_song_lexi_rev: song.clone(),
+            sort_song_release: song.clone(),
+            sort_song_release_rev: song.clone(),
+            sort_song_runtime: song.clone(),
+            sort_song_runtime_rev: song.clone(),
+            sort_song_title: song.clone(),
+            sort_song_title_rev: song.clone(),
+
+            ..Self::new()
+        };
+
+        c.sort_date_added =
+            SortDateAdded::from_collection(&c, &crate::collection::FirstSeen::new());
+        c
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SongIter
+#[derive(Clone, Debug)]
+/// An iterator over [`SongKey`]'s in a [`SongSort`] order, returned by [`Collection::song_iter`].
+pub struct SongIter<'a> {
+    keys: SongIterKeys<'a>,
+    index: usize,
+}
+
+#[derive(Clone, Debug)]
+// The keys a `SongIter` walks, either borrowed from
+// the `Collection`, or shared with `PLAY_STATS`/`RATINGS`.
+enum SongIterKeys<'a> {
+    Borrowed(&'a [SongKey]),
+    Shared(Arc<[SongKey]>),
+}
+
+impl std::ops::Deref for SongIterKeys<'_> {
+    type Target = [SongKey];
+
+    #[inline]
+    fn deref(&self) -> &[SongKey] {
+        match self {
+            Self::Borrowed(keys) => keys,
+            Self::Shared(keys) => keys,
+        }
+    }
+}
+
+impl<'a> SongIter<'a> {
+    #[inline]
+    // Iterate over `keys`, from the start.
+    fn new(keys: SongIterKeys<'a>) -> Self {
+        Self { keys, index: 0 }
+    }
+}
+
+impl Iterator for SongIter<'_> {
+    type Item = SongKey;
+
+    #[inline]
+    fn next(&mut self) -> Option<SongKey> {
+        let key = self.keys.get(self.index).copied()?;
+        self.index += 1;
+        Some(key)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.keys.len().saturating_sub(self.index);
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for SongIter<'_> {}
+
 //---------------------------------------------------------------------------------------------------- Display
 impl std::fmt::Display for Collection {
     /// Displays the [`Collection`] in a _slightly_ more human readable way.
@@ -1260,7 +1416,7 @@ mod tests {
 
         crate::assert_size_of! {
             // Collection
-            Collection       => 976,
+            Collection       => 1040,
             Unsigned         => 48,
             Map              => 48,
             Artists          => 16,
@@ -0,0 +1,342 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::collection::{AlbumKey, ArtistKey, Collection, MapKey, SongKey};
+use crate::state::RepairHint;
+use readable::{Date, Runtime};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+//---------------------------------------------------------------------------------------------------- CollectionDiff
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// The difference between an old and new [`Collection`]
+///
+/// This is created by `Kernel` after a [`Collection`] reset and
+/// sent to frontends with [`crate::kernel::KernelToFrontend::CollectionDiff`].
+///
+/// All keys refer to the _new_ [`Collection`], removed
+/// items no longer have keys, so their names are stored instead.
+///
+/// `Song`'s are matched by their `PATH` first, then by their
+/// [`MapKey`], so a file that was moved (but not re-tagged) is
+/// considered `modified`, not `removed` and `added`.
+///
+/// `Album`'s and `Artist`'s are matched by their title/name,
+/// and are `modified` if any of their `Song`'s were added, removed or modified.
+pub struct CollectionDiff {
+    /// The UNIX timestamp of the old [`Collection`]
+    pub timestamp_old: u64,
+    /// The UNIX timestamp of the new [`Collection`]
+    pub timestamp_new: u64,
+
+    /// New `Artist`'s
+    pub artists_added: Box<[ArtistKey]>,
+    /// Names of `Artist`'s that no longer exist
+    pub artists_removed: Box<[Arc<str>]>,
+    /// `Artist`'s that exist in both, but have changed
+    pub artists_modified: Box<[ArtistKey]>,
+
+    /// New `Album`'s
+    pub albums_added: Box<[AlbumKey]>,
+    /// `Album`'s that no longer exist
+    pub albums_removed: Box<[RemovedAlbum]>,
+    /// `Album`'s that exist in both, but have changed
+    pub albums_modified: Box<[AlbumKey]>,
+
+    /// New `Song`'s
+    pub songs_added: Box<[SongKey]>,
+    /// `Song`'s that no longer exist
+    pub songs_removed: Box<[RemovedSong]>,
+    /// `Song`'s that exist in both, but have changed (tags, or `PATH`)
+    pub songs_modified: Box<[SongKey]>,
+}
+
+impl CollectionDiff {
+    #[inline]
+    /// Returns `true` if nothing was added, removed or modified.
+    pub fn is_empty(&self) -> bool {
+        self.artists_added.is_empty()
+            && self.artists_removed.is_empty()
+            && self.artists_modified.is_empty()
+            && self.albums_added.is_empty()
+            && self.albums_removed.is_empty()
+            && self.albums_modified.is_empty()
+            && self.songs_added.is_empty()
+            && self.songs_removed.is_empty()
+            && self.songs_modified.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+/// An `Album` that existed in the old [`Collection`], but not the new one.
+pub struct RemovedAlbum {
+    /// Artist name
+    pub artist: Arc<str>,
+    /// Album title
+    pub album: Arc<str>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+/// A `Song` that existed in the old [`Collection`], but not the new one.
+pub struct RemovedSong {
+    /// Artist name
+    pub artist: Arc<str>,
+    /// Album title
+    pub album: Arc<str>,
+    /// Song title
+    pub song: Arc<str>,
+    /// The `PATH` the `Song` had
+    pub path: PathBuf,
+}
+
+//---------------------------------------------------------------------------------------------------- CollectionSnapshot
+// The parts of the old `Collection` needed to create a `CollectionDiff`.
+//
+// `CCD` destructs the old `Collection` during the reset,
+// so `Kernel` takes this snapshot before handing it over.
+pub(crate) struct CollectionSnapshot {
+    timestamp: u64,
+    artists: HashSet<Arc<str>>,
+    // `(artist, album)` -> `release`
+    albums: HashMap<(Arc<str>, Arc<str>), Date>,
+    songs: HashMap<PathBuf, SongSnapshot>,
+}
+
+// A single `Song` of the old `Collection`.
+#[derive(PartialEq)]
+struct SongSnapshot {
+    key: MapKey,
+    runtime: Runtime,
+    sample_rate: u32,
+    track: Option<u32>,
+    disc: Option<u32>,
+}
+
+impl CollectionSnapshot {
+    pub(crate) fn new(collection: &Arc<Collection>) -> Self {
+        let artists = collection
+            .artists
+            .iter()
+            .map(|a| Arc::clone(&a.name))
+            .collect();
+
+        let albums = collection
+            .albums
+            .iter()
+            .map(|a| {
+                let artist = &collection.artists[a.artist];
+                (
+                    (Arc::clone(&artist.name), Arc::clone(&a.title)),
+                    a.release.clone(),
+                )
+            })
+            .collect();
+
+        let songs = collection
+            .songs
+            .iter()
+            .map(|s| {
+                let snapshot = SongSnapshot {
+                    key: MapKey::from_song(s, collection),
+                    runtime: s.runtime.clone(),
+                    sample_rate: s.sample_rate,
+                    track: s.track,
+                    disc: s.disc,
+                };
+                (s.path.clone(), snapshot)
+            })
+            .collect();
+
+        Self {
+            timestamp: collection.timestamp,
+            artists,
+            albums,
+            songs,
+        }
+    }
+
+    // The `RepairHint` of every `Song` in the old `Collection`,
+    // used to repair playlist entries the new `Collection` invalidated.
+    pub(crate) fn repair_hints(&self) -> HashMap<MapKey, RepairHint> {
+        self.songs
+            .values()
+            .map(|s| {
+                let hint = RepairHint {
+                    runtime: Some(s.runtime.inner()),
+                    track: s.track,
+                };
+                (s.key.clone(), hint)
+            })
+            .collect()
+    }
+
+    // Create the `CollectionDiff` between this (old) snapshot and the new `Collection`.
+    pub(crate) fn diff(mut self, collection: &Arc<Collection>) -> CollectionDiff {
+        let mut songs_added = vec![];
+        let mut songs_modified = vec![];
+        let mut unmatched = vec![];
+
+        // `(artist, album)`'s that had `Song`'s added, removed or modified.
+        let mut touched: HashSet<(Arc<str>, Arc<str>)> = HashSet::new();
+
+        // Match by `PATH`.
+        for song in collection.songs.iter() {
+            let new = SongSnapshot {
+                key: MapKey::from_song(song, collection),
+                runtime: song.runtime.clone(),
+                sample_rate: song.sample_rate,
+                track: song.track,
+                disc: song.disc,
+            };
+
+            match self.songs.remove(&song.path) {
+                Some(old) if old == new => (),
+                Some(old) => {
+                    touched.insert((old.key.artist, old.key.album));
+                    touched.insert((Arc::clone(&new.key.artist), Arc::clone(&new.key.album)));
+                    songs_modified.push(song.key);
+                }
+                None => unmatched.push((song.key, new.key)),
+            }
+        }
+
+        // Match the rest by `MapKey`, these were moved.
+        let mut by_key: HashMap<MapKey, PathBuf> = self
+            .songs
+            .iter()
+            .map(|(path, s)| (s.key.clone(), path.clone()))
+            .collect();
+        for (key, map_key) in unmatched {
+            let album = (Arc::clone(&map_key.artist), Arc::clone(&map_key.album));
+            match by_key.remove(&map_key) {
+                Some(path) => {
+                    self.songs.remove(&path);
+                    songs_modified.push(key);
+                }
+                None => songs_added.push(key),
+            }
+            touched.insert(album);
+        }
+
+        // Whatever is left was removed.
+        let mut songs_removed: Vec<RemovedSong> = self
+            .songs
+            .into_iter()
+            .map(|(path, s)| {
+                touched.insert((Arc::clone(&s.key.artist), Arc::clone(&s.key.album)));
+                RemovedSong {
+                    artist: s.key.artist,
+                    album: s.key.album,
+                    song: s.key.song,
+                    path,
+                }
+            })
+            .collect();
+        songs_removed.sort();
+        songs_modified.sort();
+
+        // Albums.
+        let mut albums_added = vec![];
+        let mut albums_modified = vec![];
+        for album in collection.albums.iter() {
+            let artist = &collection.artists[album.artist];
+            let name = (Arc::clone(&artist.name), Arc::clone(&album.title));
+            match self.albums.remove(&name) {
+                Some(release) if release == album.release && !touched.contains(&name) => (),
+                Some(_) => {
+                    touched.insert(name);
+                    albums_modified.push(album.key);
+                }
+                None => {
+                    touched.insert(name);
+                    albums_added.push(album.key);
+                }
+            }
+        }
+        let mut albums_removed: Vec<RemovedAlbum> = self
+            .albums
+            .into_keys()
+            .map(|(artist, album)| {
+                touched.insert((Arc::clone(&artist), Arc::clone(&album)));
+                RemovedAlbum { artist, album }
+            })
+            .collect();
+        albums_removed.sort();
+
+        // Artists.
+        let touched: HashSet<Arc<str>> = touched.into_iter().map(|(artist, _)| artist).collect();
+        let mut artists_added = vec![];
+        let mut artists_modified = vec![];
+        for artist in collection.artists.iter() {
+            if !self.artists.remove(&artist.name) {
+                artists_added.push(artist.key);
+            } else if touched.contains(&artist.name) {
+                artists_modified.push(artist.key);
+            }
+        }
+        let mut artists_removed: Vec<Arc<str>> = self.artists.into_iter().collect();
+        artists_removed.sort();
+
+        CollectionDiff {
+            timestamp_old: self.timestamp,
+            timestamp_new: collection.timestamp,
+            artists_added: artists_added.into_boxed_slice(),
+            artists_removed: artists_removed.into_boxed_slice(),
+            artists_modified: artists_modified.into_boxed_slice(),
+            albums_added: albums_added.into_boxed_slice(),
+            albums_removed: albums_removed.into_boxed_slice(),
+            albums_modified: albums_modified.into_boxed_slice(),
+            songs_added: songs_added.into_boxed_slice(),
+            songs_removed: songs_removed.into_boxed_slice(),
+            songs_modified: songs_modified.into_boxed_slice(),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::COLLECTION_VERSION;
+    use disk::Bincode2;
+
+    fn collections() -> (Arc<Collection>, Arc<Collection>) {
+        let new = Collection::from_path(format!(
+            "../assets/shukusai/state/collection{COLLECTION_VERSION}_new.bin"
+        ))
+        .unwrap();
+        let real = Collection::from_path(format!(
+            "../assets/shukusai/state/collection{COLLECTION_VERSION}_real.bin"
+        ))
+        .unwrap();
+        (Arc::new(new), Arc::new(real))
+    }
+
+    #[test]
+    // Diffing a `Collection` against itself is empty.
+    fn same() {
+        let (_, c) = collections();
+        let diff = CollectionSnapshot::new(&c).diff(&c);
+        assert!(diff.is_empty());
+        assert_eq!(diff.timestamp_old, diff.timestamp_new);
+    }
+
+    #[test]
+    // Everything is added, then everything is removed.
+    fn added_removed() {
+        let (new, real) = collections();
+
+        let diff = CollectionSnapshot::new(&new).diff(&real);
+        assert_eq!(diff.artists_added.len(), real.artists.len());
+        assert_eq!(diff.albums_added.len(), real.albums.len());
+        assert_eq!(diff.songs_added.len(), real.songs.len());
+        assert!(diff.artists_modified.is_empty());
+        assert!(diff.songs_removed.is_empty());
+
+        let diff = CollectionSnapshot::new(&real).diff(&new);
+        assert_eq!(diff.artists_removed.len(), real.artists.len());
+        assert_eq!(diff.albums_removed.len(), real.albums.len());
+        assert_eq!(diff.songs_removed.len(), real.songs.len());
+        assert!(diff.songs_added.is_empty());
+    }
+}
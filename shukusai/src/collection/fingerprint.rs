@@ -0,0 +1,349 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{Collection, Song, SongKey},
+    constants::{FESTIVAL, FINGERPRINT_VERSION, FRONTEND_SUB_DIR, HEADER, STATE_SUB_DIR},
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicU32;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// A sane amount of seconds to set [`FINGERPRINT_SECONDS`] to when enabling fingerprinting.
+pub const FINGERPRINT_SECONDS_DEFAULT: u32 = 30;
+
+/// The minimum [`Fingerprint::similarity`] two [`Fingerprint`]'s
+/// must have for [`Fingerprint::matches`] to return `true`.
+pub const FINGERPRINT_SIMILARITY: f32 = 0.85;
+
+// How many sub-fingerprints (either direction) we slide
+// two fingerprints against each other when aligning them.
+const MAX_OFFSET: usize = 8;
+
+// Two fingerprints must overlap by at least
+// this many sub-fingerprints to be compared.
+const MIN_OVERLAP: usize = 16;
+
+// Songs with runtimes further apart than this (in seconds)
+// are never considered acoustic duplicates.
+const MAX_RUNTIME_DIFF: u32 = 3;
+
+//---------------------------------------------------------------------------------------------------- Statics
+/// How many seconds of audio `CCD` decodes per [`Song`] when fingerprinting.
+///
+/// If this is `0` (the default), the fingerprinting phase is
+/// skipped entirely when creating a new [`Collection`].
+///
+/// The `Frontend` should set this before sending [`crate::kernel::FrontendToKernel::NewCollection`].
+pub static FINGERPRINT_SECONDS: AtomicU32 = AtomicU32::new(0);
+
+/// This is the single, global copy of `Fingerprints` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `FINGERPRINTS.read()`.
+pub static FINGERPRINTS: FingerprintsLock = FingerprintsLock(RwLock::new(Fingerprints::new()));
+
+//---------------------------------------------------------------------------------------------------- FingerprintsLock
+/// There is only a single, global copy of `Fingerprints` that `Kernel` uses: [`FINGERPRINTS`].
+///
+/// To obtain a read-only lock, use `FINGERPRINTS.read()`.
+pub struct FingerprintsLock(RwLock<Fingerprints>);
+
+impl FingerprintsLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`Fingerprints`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, Fingerprints> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`Fingerprints`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<
+        RwLockReadGuard<'static, Fingerprints>,
+        TryLockError<RwLockReadGuard<'static, Fingerprints>>,
+    > {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    // Only `Kernel` and `CCD` should be writing.
+    pub(crate) fn write(&'static self) -> RwLockWriteGuard<'static, Fingerprints> {
+        lockw!(self.0)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Fingerprints
+disk::bincode2!(
+    Fingerprints,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "fingerprints",
+    HEADER,
+    FINGERPRINT_VERSION
+);
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+/// Cache of acoustic [`Fingerprint`]'s.
+///
+/// This is keyed by the [`Song`]'s `PATH`, not its [`SongKey`], so that
+/// entries survive [`Collection`] resets. `CCD` will only re-compute a
+/// [`Fingerprint`] if the file's modification time has changed.
+pub struct Fingerprints(pub BTreeMap<PathBuf, FingerprintEntry>);
+
+impl Fingerprints {
+    #[inline]
+    /// Creates an empty [`Fingerprints`].
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    #[inline]
+    /// Returns the [`Fingerprint`] of the file at this `PATH`, if it exists.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&Fingerprint> {
+        self.0.get(path.as_ref()).map(|e| &e.fingerprint)
+    }
+
+    #[inline]
+    /// How many [`Fingerprint`]'s are cached?
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    /// Is the cache empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+/// A single cached [`Fingerprint`] along with what it was computed from.
+pub struct FingerprintEntry {
+    /// The file's modification time (UNIX seconds) when this was computed.
+    pub mtime: u64,
+    /// How many seconds of audio this was computed from.
+    pub seconds: u32,
+    /// The actual [`Fingerprint`].
+    pub fingerprint: Fingerprint,
+}
+
+//---------------------------------------------------------------------------------------------------- Fingerprint
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+/// An acoustic fingerprint of a [`Song`].
+///
+/// This is a series of 32-bit sub-fingerprints, each one describing
+/// the chroma (pitch class energy) of a short frame of audio and how
+/// it changed since the previous frame.
+///
+/// Since it's derived from the audio itself, the same recording encoded
+/// in different formats, bitrates or with different tags will produce
+/// very similar [`Fingerprint`]'s.
+pub struct Fingerprint(pub Box<[u32]>);
+
+impl Fingerprint {
+    #[inline]
+    /// How many sub-fingerprints are there?
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    /// Is this [`Fingerprint`] empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns how similar two [`Fingerprint`]'s are, from `0.0..=1.0`.
+    ///
+    /// The two are slid against each other to find the best alignment,
+    /// the output is `1.0 - bit_error_rate` of that alignment.
+    ///
+    /// If they don't overlap enough to be compared, `0.0` is returned.
+    pub fn similarity(&self, other: &Self) -> f32 {
+        let (a, b) = (&self.0, &other.0);
+        let mut best = 0.0_f32;
+
+        for offset in 0..=MAX_OFFSET {
+            // Slide `b` forwards, then `a` forwards.
+            for (a, b) in [(a, b), (b, a)] {
+                if offset >= b.len() {
+                    continue;
+                }
+                let overlap = a.len().min(b.len() - offset);
+                if overlap < MIN_OVERLAP {
+                    continue;
+                }
+
+                let errors: u32 = a
+                    .iter()
+                    .zip(&b[offset..])
+                    .map(|(x, y)| (x ^ y).count_ones())
+                    .sum();
+                let score = 1.0 - (errors as f32 / (overlap * 32) as f32);
+                if score > best {
+                    best = score;
+                }
+            }
+        }
+
+        best
+    }
+
+    #[inline]
+    /// Are these two [`Fingerprint`]'s (most likely) the same recording?
+    ///
+    /// This returns `true` if their [`Fingerprint::similarity`]
+    /// is greater than or equal to [`FINGERPRINT_SIMILARITY`].
+    pub fn matches(&self, other: &Self) -> bool {
+        self.similarity(other) >= FINGERPRINT_SIMILARITY
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Song
+impl Song {
+    #[inline]
+    /// Returns this [`Song`]'s acoustic [`Fingerprint`], if one exists.
+    ///
+    /// This reads from the global [`FINGERPRINTS`] cache, which is only
+    /// filled if [`FINGERPRINT_SECONDS`] was non-zero during a [`Collection`] reset.
+    pub fn fingerprint(&self) -> Option<Fingerprint> {
+        FINGERPRINTS.read().get(&self.path).cloned()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Collection
+impl Collection {
+    /// Returns groups of [`Song`]'s that are acoustically identical.
+    ///
+    /// Each group is sorted by [`SongKey`] and contains at least 2 [`Song`]'s.
+    ///
+    /// [`Song`]'s without a [`Fingerprint`] are ignored, so this
+    /// will be empty if fingerprinting was never enabled.
+    pub fn acoustic_duplicates(&self) -> Vec<Box<[SongKey]>> {
+        let fingerprints = FINGERPRINTS.read();
+
+        // Only songs with a fingerprint, in runtime order so
+        // we only have to compare songs with similar runtimes.
+        let songs: Vec<(SongKey, u32, &Fingerprint)> = self
+            .sort_song_runtime
+            .iter()
+            .filter_map(|key| {
+                let song = &self.songs[key];
+                fingerprints
+                    .get(&song.path)
+                    .map(|f| (*key, song.runtime.inner(), f))
+            })
+            .collect();
+
+        // Union-find.
+        let mut parent: Vec<usize> = (0..songs.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for (i, (_, runtime, fingerprint)) in songs.iter().enumerate() {
+            for (j, (_, other_runtime, other)) in songs.iter().enumerate().skip(i + 1) {
+                if other_runtime.saturating_sub(*runtime) > MAX_RUNTIME_DIFF {
+                    break;
+                }
+                if fingerprint.matches(other) {
+                    let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                    if a != b {
+                        parent[b] = a;
+                    }
+                }
+            }
+        }
+
+        let mut groups: BTreeMap<usize, Vec<SongKey>> = BTreeMap::new();
+        for (i, (key, _, _)) in songs.iter().enumerate() {
+            let r = root(&mut parent, i);
+            groups.entry(r).or_default().push(*key);
+        }
+
+        let mut groups: Vec<Box<[SongKey]>> = groups
+            .into_values()
+            .filter(|g| g.len() > 1)
+            .map(|mut g| {
+                g.sort();
+                g.into_boxed_slice()
+            })
+            .collect();
+        groups.sort();
+        groups
+    }
+
+    /// Returns groups of acoustically identical [`Song`]'s whose tags differ.
+    ///
+    /// This is the same as [`Collection::acoustic_duplicates`], but only
+    /// groups where the [`Song`]'s title, [`Album`](crate::collection::Album)
+    /// title or [`Artist`](crate::collection::Artist) name (case-insensitive)
+    /// are not all the same are returned.
+    ///
+    /// These are likely to be mis-tagged files.
+    pub fn acoustic_mistags(&self) -> Vec<Box<[SongKey]>> {
+        self.acoustic_duplicates()
+            .into_iter()
+            .filter(|group| {
+                let tags = |key: &SongKey| {
+                    let (artist, album, song) = self.walk(*key);
+                    (
+                        artist.name_lowercase.clone(),
+                        album.title_lowercase.clone(),
+                        song.title_lowercase.clone(),
+                    )
+                };
+                let first = tags(&group[0]);
+                group.iter().skip(1).any(|k| tags(k) != first)
+            })
+            .collect()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Identical fingerprints are 100% similar,
+    // and an offset copy still matches.
+    fn similarity() {
+        let a: Box<[u32]> = (0..64_u32).map(|i| i.wrapping_mul(2654435761)).collect();
+        let a = Fingerprint(a);
+        assert_eq!(a.similarity(&a), 1.0);
+
+        let b = Fingerprint(a.0[3..].into());
+        assert_eq!(a.similarity(&b), 1.0);
+        assert_eq!(b.similarity(&a), 1.0);
+
+        let c = Fingerprint(a.0.iter().map(|x| !x).collect());
+        assert!(!a.matches(&c));
+    }
+
+    #[test]
+    // Fingerprints too short to overlap are never similar.
+    fn overlap() {
+        let a = Fingerprint(vec![0; MIN_OVERLAP - 1].into_boxed_slice());
+        assert_eq!(a.similarity(&a), 0.0);
+    }
+}
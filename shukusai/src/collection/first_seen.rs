@@ -0,0 +1,245 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{Album, AlbumKey, Collection, Song, SongKey},
+    constants::{FESTIVAL, FIRST_SEEN_VERSION, FRONTEND_SUB_DIR, HEADER, STATE_SUB_DIR},
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+//---------------------------------------------------------------------------------------------------- Statics
+/// This is the single, global copy of `FirstSeen` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `FIRST_SEEN.read()`.
+pub static FIRST_SEEN: FirstSeenLock = FirstSeenLock(RwLock::new(FirstSeen::new()));
+
+//---------------------------------------------------------------------------------------------------- FirstSeenLock
+/// There is only a single, global copy of `FirstSeen` that `Kernel` uses: [`FIRST_SEEN`].
+///
+/// To obtain a read-only lock, use `FIRST_SEEN.read()`.
+pub struct FirstSeenLock(RwLock<FirstSeen>);
+
+impl FirstSeenLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`FirstSeen`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, FirstSeen> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`FirstSeen`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<
+        RwLockReadGuard<'static, FirstSeen>,
+        TryLockError<RwLockReadGuard<'static, FirstSeen>>,
+    > {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    // Only `Kernel` and `CCD` should be writing.
+    pub(crate) fn write(&'static self) -> RwLockWriteGuard<'static, FirstSeen> {
+        lockw!(self.0)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- FirstSeen
+disk::bincode2!(
+    FirstSeen,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "first_seen",
+    HEADER,
+    FIRST_SEEN_VERSION
+);
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+/// The UNIX timestamp each [`Song`] was first seen in a [`Collection`].
+///
+/// This is keyed by the [`Song`]'s `PATH`, not its [`SongKey`], so that
+/// the timestamps survive [`Collection`] resets. `CCD` adds an entry for
+/// every new file it finds, and drops entries for files that no longer exist.
+///
+/// An [`Album`] doesn't have its own entry, it was first
+/// seen whenever the earliest of its [`Song`]'s was.
+pub struct FirstSeen(pub BTreeMap<PathBuf, u64>);
+
+impl FirstSeen {
+    #[inline]
+    /// Creates an empty [`FirstSeen`].
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    #[inline]
+    /// Returns the UNIX timestamp the file at this `PATH` was first seen, if it exists.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<u64> {
+        self.0.get(path.as_ref()).copied()
+    }
+
+    #[inline]
+    /// Returns the UNIX timestamp this [`Song`] was first seen.
+    ///
+    /// If it has no entry, the [`Collection`]'s `timestamp` is returned.
+    pub fn song(&self, key: SongKey, collection: &Collection) -> u64 {
+        self.get(&collection.songs[key].path)
+            .unwrap_or(collection.timestamp)
+    }
+
+    /// Returns the UNIX timestamp this [`Album`] was first seen.
+    ///
+    /// This is the earliest timestamp of all its [`Song`]'s.
+    ///
+    /// If none of them have an entry, the [`Collection`]'s `timestamp` is returned.
+    pub fn album(&self, key: AlbumKey, collection: &Collection) -> u64 {
+        self.album_timestamp(&collection.albums[key], collection)
+    }
+
+    #[inline]
+    /// How many files have a timestamp?
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    /// Is this empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Create the new `FirstSeen` for a set of `Song`'s.
+    //
+    // Files in `old` keep their timestamp, new ones are given `now`.
+    pub(crate) fn update(old: Self, songs: &[Song], now: u64) -> Self {
+        let map = songs
+            .iter()
+            .map(|s| (s.path.clone(), old.get(&s.path).unwrap_or(now)))
+            .collect();
+
+        Self(map)
+    }
+
+    #[inline]
+    // The earliest `Song` timestamp of an `Album`.
+    fn album_timestamp(&self, album: &Album, collection: &Collection) -> u64 {
+        album
+            .songs
+            .iter()
+            .map(|k| self.song(*k, collection))
+            .min()
+            .unwrap_or(collection.timestamp)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SortDateAdded
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+/// Pre-computed [`Album`] and [`Song`] keys sorted by when they were first seen.
+///
+/// Unlike the other `sort_` fields in [`Collection`], these are _not_ saved
+/// to disk with it (they encode to nothing). They are re-created from the
+/// [`FirstSeen`] timestamps by `Kernel` when the [`Collection`] is loaded,
+/// and by `CCD` when a new one is created.
+///
+/// Ties keep the order of [`Collection::sort_album_release_artist_lexi`]
+/// and [`Collection::sort_song_album_release_artist_lexi`], so `Song`'s
+/// added at the same time stay in track order.
+pub struct SortDateAdded {
+    /// [`Album`] oldest-newest.
+    pub album: Box<[AlbumKey]>,
+    /// [`Album`] newest-oldest.
+    pub album_rev: Box<[AlbumKey]>,
+    /// [`Song`] oldest-newest.
+    pub song: Box<[SongKey]>,
+    /// [`Song`] newest-oldest.
+    pub song_rev: Box<[SongKey]>,
+}
+
+impl SortDateAdded {
+    /// Creates an empty [`SortDateAdded`].
+    pub fn new() -> Self {
+        Self {
+            album: Box::new([]),
+            album_rev: Box::new([]),
+            song: Box::new([]),
+            song_rev: Box::new([]),
+        }
+    }
+
+    // Sort a `Collection`'s `Album`'s and `Song`'s by their `FirstSeen` timestamp.
+    pub(crate) fn from_collection(collection: &Collection, first_seen: &FirstSeen) -> Self {
+        let song_timestamp = |k: &SongKey| first_seen.song(*k, collection);
+        let album_timestamp =
+            |k: &AlbumKey| first_seen.album_timestamp(&collection.albums[k], collection);
+
+        let mut album = collection.sort_album_release_artist_lexi.to_vec();
+        let mut album_rev = album.clone();
+        album.sort_by_cached_key(album_timestamp);
+        album_rev.sort_by_cached_key(|k| Reverse(album_timestamp(k)));
+
+        let mut song = collection.sort_song_album_release_artist_lexi.to_vec();
+        let mut song_rev = song.clone();
+        song.sort_by_cached_key(song_timestamp);
+        song_rev.sort_by_cached_key(|k| Reverse(song_timestamp(k)));
+
+        Self {
+            album: album.into_boxed_slice(),
+            album_rev: album_rev.into_boxed_slice(),
+            song: song.into_boxed_slice(),
+            song_rev: song_rev.into_boxed_slice(),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SortDateAdded Bincode
+// These are re-created from `FirstSeen`, so
+// they are never actually written to disk.
+impl_bincode_skip!(SortDateAdded);
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Old timestamps are kept, new files get `now`, missing files are dropped.
+    fn update() {
+        let mut old = FirstSeen::new();
+        old.0.insert(PathBuf::from("/a"), 1);
+        old.0.insert(PathBuf::from("/gone"), 2);
+
+        let song = |p: &str| Song {
+            path: PathBuf::from(p),
+            ..Default::default()
+        };
+        let new = FirstSeen::update(old, &[song("/a"), song("/b")], 100);
+
+        assert_eq!(new.len(), 2);
+        assert_eq!(new.get("/a"), Some(1));
+        assert_eq!(new.get("/b"), Some(100));
+        assert_eq!(new.get("/gone"), None);
+    }
+
+    #[test]
+    // Encodes to nothing, decodes to empty.
+    fn sort_bincode() {
+        let sort = SortDateAdded {
+            album: Box::new([AlbumKey::zero()]),
+            ..SortDateAdded::new()
+        };
+        let config = bincode::config::standard();
+        let bytes = bincode::encode_to_vec(&sort, config).unwrap();
+        assert!(bytes.is_empty());
+        let (decoded, _): (SortDateAdded, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, SortDateAdded::new());
+    }
+}
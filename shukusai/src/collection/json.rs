@@ -1,5 +1,5 @@
 //---------------------------------------------------------------------------------------------------- Use
-use crate::collection::{AlbumKey, ArtistKey, SongKey};
+use crate::collection::{AlbumKey, ArtistKey, SongKey, SongStats};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
@@ -160,6 +160,7 @@ pub struct SongJson<'a> {
     pub mime: Cow<'a, str>,
     #[serde(borrow)]
     pub extension: Cow<'a, str>,
+    pub play_stats: SongStats,
 }
 
 //---------------------------------------------------------------------------------------------------- TESTS
@@ -252,7 +253,12 @@ mod tests {
   "track": null,
   "disc": null,
   "mime": "",
-  "extension": ""
+  "extension": "",
+  "play_stats": {
+    "play_count": 0,
+    "skip_count": 0,
+    "last_played": null
+  }
 }"#;
 
     fn serde_json_collection() {
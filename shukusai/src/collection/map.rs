@@ -6,7 +6,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 //---------------------------------------------------------------------------------------------------- MapEntry
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 /// An absolute "Key" for the [`Map`].
 pub struct MapKey {
     /// Artist name
@@ -1,22 +1,68 @@
+// Implement `bincode` for `sort_` structs that are re-created from
+// their own files when the `Collection` is loaded, instead of being
+// saved with it. They encode to nothing and decode as `$type::new()`.
+macro_rules! impl_bincode_skip {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl bincode::Encode for $type {
+                fn encode<E: bincode::enc::Encoder>(
+                    &self,
+                    _: &mut E,
+                ) -> std::result::Result<(), bincode::error::EncodeError> {
+                    Ok(())
+                }
+            }
+            impl bincode::Decode for $type {
+                fn decode<D: bincode::de::Decoder>(
+                    _: &mut D,
+                ) -> std::result::Result<Self, bincode::error::DecodeError> {
+                    Ok(Self::new())
+                }
+            }
+            impl<'de> bincode::BorrowDecode<'de> for $type {
+                fn borrow_decode<D: bincode::de::BorrowDecoder<'de>>(
+                    _: &mut D,
+                ) -> std::result::Result<Self, bincode::error::DecodeError> {
+                    Ok(Self::new())
+                }
+            }
+        )*
+    };
+}
+
 mod album;
 mod art;
 mod artist;
+mod chapter;
 mod collection;
+mod diff;
 mod entry;
+mod fingerprint;
+mod first_seen;
 mod image;
 mod key;
 mod map;
+mod play_stats;
 mod plural;
+mod ratings;
+mod resume;
 mod song;
 pub use crate::collection::image::*;
 pub use album::*;
 pub use art::*;
 pub use artist::*;
+pub use chapter::*;
 pub use collection::*;
+pub use diff::*;
 pub use entry::*;
+pub use fingerprint::*;
+pub use first_seen::*;
 pub use key::*;
 pub use map::*;
+pub use play_stats::*;
 pub use plural::*;
+pub use ratings::*;
+pub use resume::*;
 pub use song::*;
 
 mod metadata;
@@ -0,0 +1,415 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{Collection, Song, SongKey},
+    constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, PLAY_STATS_VERSION, STATE_SUB_DIR},
+    sort::SongSort,
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// A `Song` counts as played once this much of its runtime (percent) has been heard...
+pub const PLAY_THRESHOLD_PERCENT: u32 = 50;
+
+/// ...or once this many seconds of it have been heard, whichever comes first.
+pub const PLAY_THRESHOLD_SECONDS: u32 = 240;
+
+//---------------------------------------------------------------------------------------------------- Statics
+/// This is the single, global copy of `PlayStats` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `PLAY_STATS.read()`.
+// HACK:
+// This didn't have a `Lazy` before, but the sorts were changed
+// from `Vec` to `Arc<[SongKey]>` so `song_iter()` can share them
+// instead of copying, and `Arc` doesn't have a `const`
+// constructor so `Lazy` must be used.
+pub static PLAY_STATS: Lazy<PlayStatsLock> =
+    Lazy::new(|| PlayStatsLock(RwLock::new(PlayStats::new())));
+
+//---------------------------------------------------------------------------------------------------- PlayStatsLock
+/// There is only a single, global copy of `PlayStats` that `Kernel` uses: [`PLAY_STATS`].
+///
+/// To obtain a read-only lock, use `PLAY_STATS.read()`.
+pub struct PlayStatsLock(RwLock<PlayStats>);
+
+impl PlayStatsLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`PlayStats`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, PlayStats> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`PlayStats`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<
+        RwLockReadGuard<'static, PlayStats>,
+        TryLockError<RwLockReadGuard<'static, PlayStats>>,
+    > {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    // Only `Kernel`, `Audio` and `CCD` should be writing.
+    pub(crate) fn write(&'static self) -> RwLockWriteGuard<'static, PlayStats> {
+        lockw!(self.0)
+    }
+
+    /// Same as [`PlayStats::sorted`], but if the sorts are out of date
+    /// because of a counted play, they are re-created and saved first.
+    ///
+    /// This takes the write lock only if a re-sort is needed,
+    /// so do not hold a read lock to [`PLAY_STATS`] when calling this.
+    pub fn sorted(&'static self, sort: SongSort, collection: &Collection) -> Arc<[SongKey]> {
+        {
+            let play_stats = self.read();
+            if !play_stats.sort.dirty || !play_stats.sort.is_for(collection) {
+                return play_stats.sorted(sort, collection);
+            }
+        }
+
+        let mut play_stats = self.write();
+        if play_stats.sort.dirty && play_stats.sort.is_for(collection) {
+            play_stats.sort = SortPlayStats::from_collection(collection, &play_stats);
+        }
+        play_stats.sorted(sort, collection)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SongStats
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+)]
+/// Listening statistics of a single [`Song`].
+pub struct SongStats {
+    /// How many times this [`Song`] was played past the play threshold.
+    ///
+    /// See [`PlayStats::counts`].
+    pub play_count: u32,
+    /// How many times this [`Song`] was skipped before the play threshold.
+    pub skip_count: u32,
+    /// The UNIX timestamp of the last counted play, `None` if never played.
+    pub last_played: Option<u64>,
+}
+
+//---------------------------------------------------------------------------------------------------- PlayStats
+disk::bincode2!(
+    PlayStats,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "play_stats",
+    HEADER,
+    PLAY_STATS_VERSION
+);
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Encode, Decode)]
+/// The [`SongStats`] of every [`Song`] that was ever played or skipped.
+///
+/// Like [`crate::collection::FirstSeen`], this is keyed by the [`Song`]'s `PATH`,
+/// not its [`SongKey`], so that the statistics survive [`Collection`] resets.
+///
+/// Unlike `FirstSeen`, entries of files that no longer exist are kept,
+/// so that a temporarily missing file doesn't lose its statistics.
+pub struct PlayStats {
+    /// The statistics, keyed by `PATH`.
+    pub songs: BTreeMap<PathBuf, SongStats>,
+
+    #[serde(skip)]
+    /// [`Song`]'s sorted by their statistics.
+    ///
+    /// These are _not_ saved to disk, see [`SortPlayStats`].
+    pub sort: SortPlayStats,
+}
+
+impl PlayStats {
+    #[inline]
+    /// Creates an empty [`PlayStats`].
+    pub fn new() -> Self {
+        Self {
+            songs: BTreeMap::new(),
+            sort: SortPlayStats::new(),
+        }
+    }
+
+    #[inline]
+    /// Returns the statistics of the file at this `PATH`, if it has any.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&SongStats> {
+        self.songs.get(path.as_ref())
+    }
+
+    #[inline]
+    /// Returns the statistics of this [`Song`].
+    ///
+    /// If it has no entry, it was never played, so the default is returned.
+    pub fn song(&self, key: SongKey, collection: &Collection) -> SongStats {
+        self.get(&collection.songs[key].path)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    /// Has enough of a `Song` with this `runtime` been heard after `elapsed` seconds to count as a play?
+    ///
+    /// This is true after [`PLAY_THRESHOLD_PERCENT`] of
+    /// the runtime, or [`PLAY_THRESHOLD_SECONDS`].
+    pub const fn counts(elapsed: u32, runtime: u32) -> bool {
+        let percent = (runtime as u64 * PLAY_THRESHOLD_PERCENT as u64 / 100) as u32;
+        let threshold = if percent < PLAY_THRESHOLD_SECONDS {
+            percent
+        } else {
+            PLAY_THRESHOLD_SECONDS
+        };
+        elapsed >= threshold
+    }
+
+    #[inline]
+    /// How many files have statistics?
+    pub fn len(&self) -> usize {
+        self.songs.len()
+    }
+
+    #[inline]
+    /// Is this empty?
+    pub fn is_empty(&self) -> bool {
+        self.songs.is_empty()
+    }
+
+    /// Returns the keys of a [`SongSort::PlayCount`], [`SongSort::PlayCountRev`],
+    /// [`SongSort::LastPlayed`] or [`SongSort::LastPlayedRev`] sort of `collection`.
+    ///
+    /// If [`Self::sort`] was created for a different [`Collection`],
+    /// or is out of date, the sort is created on the spot instead.
+    ///
+    /// Any other [`SongSort`] returns an empty slice.
+    pub fn sorted(&self, sort: SongSort, collection: &Collection) -> Arc<[SongKey]> {
+        if !self.sort.dirty && self.sort.is_for(collection) {
+            self.sort.get(sort)
+        } else {
+            SortPlayStats::from_collection(collection, self).get(sort)
+        }
+    }
+
+    // INVARIANT: assumes key is valid.
+    //
+    // Count a play of this `Song` at `timestamp`.
+    //
+    // This is called from `Audio`, so instead of re-sorting
+    // here, the sorts are marked as out of date and are
+    // re-created on the next `PLAY_STATS.sorted()`.
+    pub(crate) fn played(&mut self, key: SongKey, timestamp: u64, collection: &Collection) {
+        let stats = self.entry(&collection.songs[key]);
+        stats.play_count = stats.play_count.saturating_add(1);
+        stats.last_played = Some(timestamp);
+
+        self.sort.dirty = true;
+    }
+
+    // INVARIANT: assumes key is valid.
+    //
+    // Count a skip of this `Song`.
+    //
+    // The skip count is not sorted on, so this doesn't re-sort.
+    pub(crate) fn skipped(&mut self, key: SongKey, collection: &Collection) {
+        let stats = self.entry(&collection.songs[key]);
+        stats.skip_count = stats.skip_count.saturating_add(1);
+    }
+
+    #[inline]
+    // The (maybe new) entry for this `Song`.
+    fn entry(&mut self, song: &Song) -> &mut SongStats {
+        self.songs.entry(song.path.clone()).or_default()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SortPlayStats
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+/// Pre-computed [`Song`] keys sorted by their [`SongStats`].
+///
+/// These are _not_ saved to disk (they encode to nothing), they are
+/// re-created when the [`Collection`] is loaded or reset, and lazily
+/// after a counted play, see [`PlayStatsLock::sorted`].
+///
+/// Ties keep the order of [`Collection::sort_song_album_release_artist_lexi`],
+/// `Song`'s that were never played are first in the non-`rev` sorts.
+pub struct SortPlayStats {
+    /// The `timestamp` of the [`Collection`] these were created from.
+    pub timestamp: u64,
+    /// [`Song`] least played-most played.
+    pub play_count: Arc<[SongKey]>,
+    /// [`Song`] most played-least played.
+    pub play_count_rev: Arc<[SongKey]>,
+    /// [`Song`] last played oldest-newest.
+    pub last_played: Arc<[SongKey]>,
+    /// [`Song`] last played newest-oldest.
+    pub last_played_rev: Arc<[SongKey]>,
+
+    #[serde(skip)]
+    // Was a play counted after these were created?
+    pub(crate) dirty: bool,
+}
+
+impl SortPlayStats {
+    /// Creates an empty [`SortPlayStats`].
+    pub fn new() -> Self {
+        Self {
+            timestamp: 0,
+            play_count: Arc::new([]),
+            play_count_rev: Arc::new([]),
+            last_played: Arc::new([]),
+            last_played_rev: Arc::new([]),
+            dirty: false,
+        }
+    }
+
+    // Sort a `Collection`'s `Song`'s by their `PlayStats`.
+    pub(crate) fn from_collection(collection: &Collection, stats: &PlayStats) -> Self {
+        let song_stats: Vec<SongStats> = collection
+            .songs
+            .iter()
+            .map(|s| stats.get(&s.path).copied().unwrap_or_default())
+            .collect();
+        let play_count = |k: &SongKey| song_stats[k.inner()].play_count;
+        let last_played = |k: &SongKey| song_stats[k.inner()].last_played;
+
+        let base = &collection.sort_song_album_release_artist_lexi;
+
+        let mut sort_play_count = base.to_vec();
+        let mut sort_play_count_rev = base.to_vec();
+        sort_play_count.sort_by_key(play_count);
+        sort_play_count_rev.sort_by_key(|k| Reverse(play_count(k)));
+
+        let mut sort_last_played = base.to_vec();
+        let mut sort_last_played_rev = base.to_vec();
+        sort_last_played.sort_by_key(last_played);
+        sort_last_played_rev.sort_by_key(|k| Reverse(last_played(k)));
+
+        Self {
+            timestamp: collection.timestamp,
+            play_count: sort_play_count.into(),
+            play_count_rev: sort_play_count_rev.into(),
+            last_played: sort_last_played.into(),
+            last_played_rev: sort_last_played_rev.into(),
+            dirty: false,
+        }
+    }
+
+    #[inline]
+    // Were these sorts created from this `Collection`?
+    fn is_for(&self, collection: &Collection) -> bool {
+        self.timestamp == collection.timestamp && self.play_count.len() == collection.songs.len()
+    }
+
+    #[inline]
+    // The field for this `SongSort`, or an empty slice.
+    fn get(&self, sort: SongSort) -> Arc<[SongKey]> {
+        match sort {
+            SongSort::PlayCount => Arc::clone(&self.play_count),
+            SongSort::PlayCountRev => Arc::clone(&self.play_count_rev),
+            SongSort::LastPlayed => Arc::clone(&self.last_played),
+            SongSort::LastPlayedRev => Arc::clone(&self.last_played_rev),
+            _ => Arc::new([]),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SortPlayStats Bincode
+// These are re-created from `PlayStats`, so
+// they are never actually written to disk.
+impl_bincode_skip!(SortPlayStats);
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Half the runtime, capped at `PLAY_THRESHOLD_SECONDS`.
+    fn counts() {
+        assert!(!PlayStats::counts(49, 100));
+        assert!(PlayStats::counts(50, 100));
+        assert!(!PlayStats::counts(239, 1000));
+        assert!(PlayStats::counts(240, 1000));
+        assert!(PlayStats::counts(0, 0));
+    }
+
+    #[test]
+    // Plays are counted and sorted, skips are only counted.
+    fn played_skipped() {
+        let c = Collection::test(
+            vec![],
+            vec![],
+            (0..3_usize)
+                .map(|i| Song {
+                    key: SongKey::from(i),
+                    path: PathBuf::from(format!("/{i}")),
+                    ..Default::default()
+                })
+                .collect(),
+        );
+
+        let mut p = PlayStats::new();
+        p.played(SongKey::from(2_usize), 10, &c);
+        p.played(SongKey::from(2_usize), 20, &c);
+        p.played(SongKey::from(0_usize), 30, &c);
+        p.skipped(SongKey::from(1_usize), &c);
+
+        let s = p.song(SongKey::from(2_usize), &c);
+        assert_eq!(
+            (s.play_count, s.skip_count, s.last_played),
+            (2, 0, Some(20))
+        );
+        let s = p.song(SongKey::from(1_usize), &c);
+        assert_eq!((s.play_count, s.skip_count, s.last_played), (0, 1, None));
+
+        let keys = |sort: SongSort| -> Vec<usize> {
+            p.sorted(sort, &c).iter().map(|k| k.inner()).collect()
+        };
+        assert_eq!(keys(SongSort::PlayCount), [1, 0, 2]);
+        assert_eq!(keys(SongSort::PlayCountRev), [2, 0, 1]);
+        assert_eq!(keys(SongSort::LastPlayed), [1, 2, 0]);
+        assert_eq!(keys(SongSort::LastPlayedRev), [0, 2, 1]);
+        assert!(keys(SongSort::Lexi).is_empty());
+
+        // Plays don't re-sort, but the out of date sort isn't used.
+        p.sort = SortPlayStats::from_collection(&c, &p);
+        p.played(SongKey::from(1_usize), 40, &c);
+        assert!(p.sort.dirty);
+        assert_eq!(p.sort.last_played_rev[..], [0, 2, 1]);
+        let sorted = p.sorted(SongSort::LastPlayedRev, &c);
+        assert_eq!(sorted[..], [1_usize, 0, 2].map(SongKey::from));
+    }
+
+    #[test]
+    // Encodes to nothing, decodes to empty.
+    fn sort_bincode() {
+        let sort = SortPlayStats {
+            play_count: Arc::new([SongKey::zero()]),
+            ..SortPlayStats::new()
+        };
+        let config = bincode::config::standard();
+        let bytes = bincode::encode_to_vec(&sort, config).unwrap();
+        assert!(bytes.is_empty());
+        let (decoded, _): (SortPlayStats, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, SortPlayStats::new());
+    }
+}
@@ -0,0 +1,447 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{
+        json::{AlbumJson, SongJson},
+        AlbumKey, Collection, SongKey,
+    },
+    constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, RATINGS_VERSION, STATE_SUB_DIR},
+    sort::SongSort,
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use disk::Bincode2;
+use log::{trace, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The highest rating a `Song` or `Album` can have.
+///
+/// `0` means unrated, anything above this is lowered to this.
+pub const RATING_MAX: u8 = 5;
+
+//---------------------------------------------------------------------------------------------------- Statics
+/// This is the single, global copy of `Ratings` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `RATINGS.read()`.
+// HACK:
+// This didn't have a `Lazy` before, but the sorts were changed
+// from `Vec` to `Arc<[SongKey]>` so `song_iter()` can share them
+// instead of copying, and `Arc` doesn't have a `const`
+// constructor so `Lazy` must be used.
+pub static RATINGS: Lazy<RatingsLock> = Lazy::new(|| RatingsLock(RwLock::new(Ratings::new())));
+
+//---------------------------------------------------------------------------------------------------- RatingsLock
+/// There is only a single, global copy of `Ratings` that `Kernel` uses: [`RATINGS`].
+///
+/// To obtain a read-only lock, use `RATINGS.read()`.
+pub struct RatingsLock(RwLock<Ratings>);
+
+impl RatingsLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`Ratings`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, Ratings> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`Ratings`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<RwLockReadGuard<'static, Ratings>, TryLockError<RwLockReadGuard<'static, Ratings>>>
+    {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    // Obtain a write lock to the global [`Ratings`].
+    pub(crate) fn write(&'static self) -> RwLockWriteGuard<'static, Ratings> {
+        lockw!(self.0)
+    }
+
+    /// INVARIANT: assumes key is valid.
+    ///
+    /// [`Ratings::rate_song`] on the global [`Ratings`], returning the previous rating.
+    ///
+    /// If the rating changed, the [`Ratings`] are saved to disk.
+    pub fn rate_song(&'static self, key: SongKey, rating: u8, collection: &Collection) -> u8 {
+        let previous = self.write().rate_song(key, rating, collection);
+        if previous != self.read().song(key, collection) {
+            self.save();
+        }
+        previous
+    }
+
+    /// INVARIANT: assumes key is valid.
+    ///
+    /// [`Ratings::rate_album`] on the global [`Ratings`], returning the previous rating.
+    ///
+    /// If the rating changed, the [`Ratings`] are saved to disk.
+    pub fn rate_album(&'static self, key: AlbumKey, rating: u8, collection: &Collection) -> u8 {
+        let previous = self.write().rate_album(key, rating, collection);
+        if previous != self.read().album(key, collection) {
+            self.save();
+        }
+        previous
+    }
+
+    // Save the global `Ratings` to disk, `Kernel` saves them again on exit.
+    fn save(&'static self) {
+        match self.read().save_atomic() {
+            Ok(_) => trace!("Ratings{RATINGS_VERSION} - save ... OK"),
+            Err(e) => warn!("Ratings{RATINGS_VERSION} - save ... {e}"),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Ratings
+disk::bincode2!(
+    Ratings,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "ratings",
+    HEADER,
+    RATINGS_VERSION
+);
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Encode, Decode)]
+/// The `0..=5` ratings of `Song`'s and `Album`'s.
+///
+/// These are kept separately from the [`Collection`] so that they survive resets:
+/// - `Song` ratings are keyed by the `Song`'s `PATH`
+/// - `Album` ratings are keyed by the `Artist` name, then the `Album` title
+///
+/// An unrated (`0`) `Song` or `Album` has no entry.
+///
+/// Entries of `Song`'s or `Album`'s that no longer exist are kept.
+pub struct Ratings {
+    /// `Song` ratings, keyed by `PATH`.
+    pub songs: BTreeMap<PathBuf, u8>,
+    /// `Album` ratings, keyed by `Artist` name, then `Album` title.
+    pub albums: BTreeMap<Arc<str>, BTreeMap<Arc<str>, u8>>,
+
+    #[serde(skip)]
+    /// `Song`'s sorted by their rating.
+    ///
+    /// These are _not_ saved to disk, see [`SortRatings`].
+    pub sort: SortRatings,
+}
+
+impl Ratings {
+    #[inline]
+    /// Creates an empty [`Ratings`].
+    pub fn new() -> Self {
+        Self {
+            songs: BTreeMap::new(),
+            albums: BTreeMap::new(),
+            sort: SortRatings::new(),
+        }
+    }
+
+    #[inline]
+    /// Returns the rating of the file at this `PATH`, `0` if unrated.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> u8 {
+        self.songs.get(path.as_ref()).copied().unwrap_or(0)
+    }
+
+    #[inline]
+    /// Returns the rating of this `Song`, `0` if unrated.
+    pub fn song(&self, key: SongKey, collection: &Collection) -> u8 {
+        self.get(&collection.songs[key].path)
+    }
+
+    #[inline]
+    /// Returns the rating of this `Album`, `0` if unrated.
+    pub fn album(&self, key: AlbumKey, collection: &Collection) -> u8 {
+        let album = &collection.albums[key];
+        self.get_album(&collection.artists[album.artist].name, &album.title)
+    }
+
+    #[inline]
+    /// Returns the rating of the `Album` with this `Artist` name and `Album` title, `0` if unrated.
+    pub fn get_album(&self, artist: &str, album: &str) -> u8 {
+        self.albums
+            .get(artist)
+            .and_then(|albums| albums.get(album))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// INVARIANT: assumes key is valid.
+    ///
+    /// Rate a `Song`, returning its previous rating.
+    ///
+    /// `rating` is lowered to [`RATING_MAX`], `0` removes the rating.
+    pub fn rate_song(&mut self, key: SongKey, rating: u8, collection: &Collection) -> u8 {
+        let path = &collection.songs[key].path;
+        let rating = rating.min(RATING_MAX);
+
+        let previous = if rating == 0 {
+            self.songs.remove(path)
+        } else {
+            self.songs.insert(path.clone(), rating)
+        }
+        .unwrap_or(0);
+
+        if previous != rating {
+            self.sort = SortRatings::from_collection(collection, self);
+        }
+
+        previous
+    }
+
+    /// INVARIANT: assumes key is valid.
+    ///
+    /// Rate an `Album`, returning its previous rating.
+    ///
+    /// `rating` is lowered to [`RATING_MAX`], `0` removes the rating.
+    pub fn rate_album(&mut self, key: AlbumKey, rating: u8, collection: &Collection) -> u8 {
+        let album = &collection.albums[key];
+        let artist = &collection.artists[album.artist].name;
+        let rating = rating.min(RATING_MAX);
+
+        if rating == 0 {
+            let Some(albums) = self.albums.get_mut(artist) else {
+                return 0;
+            };
+            let previous = albums.remove(&album.title).unwrap_or(0);
+            if albums.is_empty() {
+                self.albums.remove(artist);
+            }
+            previous
+        } else {
+            self.albums
+                .entry(Arc::clone(artist))
+                .or_default()
+                .insert(Arc::clone(&album.title), rating)
+                .unwrap_or(0)
+        }
+    }
+
+    /// Returns all rated `Song`'s that are in `collection`, highest rating first.
+    ///
+    /// Ties keep the order of [`Collection::sort_song_album_release_artist_lexi`].
+    pub fn favorite_songs(&self, collection: &Collection) -> Vec<(SongKey, u8)> {
+        let mut songs: Vec<(SongKey, u8)> = collection
+            .sort_song_album_release_artist_lexi
+            .iter()
+            .map(|k| (*k, self.song(*k, collection)))
+            .filter(|(_, rating)| *rating != 0)
+            .collect();
+
+        songs.sort_by_key(|(_, rating)| Reverse(*rating));
+        songs
+    }
+
+    /// Returns all rated `Album`'s that are in `collection`, highest rating first.
+    ///
+    /// Ties keep the order of [`Collection::sort_album_release_artist_lexi`].
+    pub fn favorite_albums(&self, collection: &Collection) -> Vec<(AlbumKey, u8)> {
+        let mut albums: Vec<(AlbumKey, u8)> = collection
+            .sort_album_release_artist_lexi
+            .iter()
+            .map(|k| (*k, self.album(*k, collection)))
+            .filter(|(_, rating)| *rating != 0)
+            .collect();
+
+        albums.sort_by_key(|(_, rating)| Reverse(*rating));
+        albums
+    }
+
+    /// Returns the keys of a [`SongSort::Rating`] or [`SongSort::RatingRev`] sort of `collection`.
+    ///
+    /// If [`Self::sort`] was created for a different [`Collection`],
+    /// the sort is created on the spot instead.
+    ///
+    /// Any other [`SongSort`] returns an empty slice.
+    pub fn sorted(&self, sort: SongSort, collection: &Collection) -> Arc<[SongKey]> {
+        if self.sort.is_for(collection) {
+            self.sort.get(sort)
+        } else {
+            SortRatings::from_collection(collection, self).get(sort)
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SortRatings
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+/// Pre-computed [`Song`](crate::collection::Song) keys sorted by their rating.
+///
+/// These are _not_ saved to disk (they encode to nothing), they are
+/// re-created when the [`Collection`] is loaded or reset, and after
+/// every rating change.
+///
+/// Ties keep the order of [`Collection::sort_song_album_release_artist_lexi`],
+/// unrated `Song`'s are first in the non-`rev` sort.
+pub struct SortRatings {
+    /// The `timestamp` of the [`Collection`] these were created from.
+    pub timestamp: u64,
+    /// `Song` lowest rated-highest rated.
+    pub rating: Arc<[SongKey]>,
+    /// `Song` highest rated-lowest rated.
+    pub rating_rev: Arc<[SongKey]>,
+}
+
+impl SortRatings {
+    /// Creates an empty [`SortRatings`].
+    pub fn new() -> Self {
+        Self {
+            timestamp: 0,
+            rating: Arc::new([]),
+            rating_rev: Arc::new([]),
+        }
+    }
+
+    // Sort a `Collection`'s `Song`'s by their `Ratings`.
+    pub(crate) fn from_collection(collection: &Collection, ratings: &Ratings) -> Self {
+        let song_ratings: Vec<u8> = collection
+            .songs
+            .iter()
+            .map(|s| ratings.get(&s.path))
+            .collect();
+        let rating = |k: &SongKey| song_ratings[k.inner()];
+
+        let base = &collection.sort_song_album_release_artist_lexi;
+
+        let mut sort_rating = base.to_vec();
+        let mut sort_rating_rev = base.to_vec();
+        sort_rating.sort_by_key(rating);
+        sort_rating_rev.sort_by_key(|k| Reverse(rating(k)));
+
+        Self {
+            timestamp: collection.timestamp,
+            rating: sort_rating.into(),
+            rating_rev: sort_rating_rev.into(),
+        }
+    }
+
+    #[inline]
+    // Were these sorts created from this `Collection`?
+    fn is_for(&self, collection: &Collection) -> bool {
+        self.timestamp == collection.timestamp && self.rating.len() == collection.songs.len()
+    }
+
+    #[inline]
+    // The field for this `SongSort`, or an empty slice.
+    fn get(&self, sort: SongSort) -> Arc<[SongKey]> {
+        match sort {
+            SongSort::Rating => Arc::clone(&self.rating),
+            SongSort::RatingRev => Arc::clone(&self.rating_rev),
+            _ => Arc::new([]),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SortRatings Bincode
+// These are re-created from `Ratings`, so
+// they are never actually written to disk.
+impl_bincode_skip!(SortRatings);
+
+//---------------------------------------------------------------------------------------------------- JSON Representation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// Stable `JSON` representation of a rated `Song`.
+pub struct SongRatingJson<'a> {
+    /// The rating, `1..=5`
+    pub rating: u8,
+    #[serde(borrow)]
+    /// The `Song`
+    pub song: SongJson<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// Stable `JSON` representation of a rated `Album`.
+pub struct AlbumRatingJson<'a> {
+    /// The rating, `1..=5`
+    pub rating: u8,
+    #[serde(borrow)]
+    /// The `Album`
+    pub album: AlbumJson<'a>,
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::{Album, Artist, Song};
+
+    // 3 `Song`'s in 2 `Album`'s by 1 `Artist`.
+    fn collection() -> Collection {
+        Collection::test(
+            vec![Artist {
+                name: "artist".into(),
+                ..Default::default()
+            }],
+            ["a", "b"]
+                .into_iter()
+                .map(|title| Album {
+                    title: title.into(),
+                    ..Default::default()
+                })
+                .collect(),
+            (0..3_usize)
+                .map(|i| Song {
+                    key: SongKey::from(i),
+                    path: PathBuf::from(format!("/{i}")),
+                    ..Default::default()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    // Ratings are clamped, `0` removes, the previous rating is returned.
+    fn rate_song() {
+        let c = collection();
+        let mut r = Ratings::new();
+        let key = SongKey::from(1_usize);
+
+        assert_eq!(r.rate_song(key, 3, &c), 0);
+        assert_eq!(r.rate_song(key, 200, &c), 3);
+        assert_eq!(r.song(key, &c), RATING_MAX);
+        assert_eq!(r.rate_song(key, 0, &c), RATING_MAX);
+        assert!(r.songs.is_empty());
+    }
+
+    #[test]
+    fn rate_album() {
+        let c = collection();
+        let mut r = Ratings::new();
+        let key = AlbumKey::from(1_usize);
+
+        assert_eq!(r.rate_album(key, 4, &c), 0);
+        assert_eq!(r.album(key, &c), 4);
+        assert_eq!(r.get_album("artist", "b"), 4);
+        assert_eq!(r.album(AlbumKey::from(0_usize), &c), 0);
+        assert_eq!(r.rate_album(key, 0, &c), 4);
+        assert!(r.albums.is_empty());
+    }
+
+    #[test]
+    // Highest first, unrated are left out.
+    fn favorites_sorted() {
+        let c = collection();
+        let mut r = Ratings::new();
+        r.rate_song(SongKey::from(0_usize), 2, &c);
+        r.rate_song(SongKey::from(2_usize), 5, &c);
+        r.rate_album(AlbumKey::from(0_usize), 1, &c);
+
+        let songs: Vec<(usize, u8)> = r
+            .favorite_songs(&c)
+            .into_iter()
+            .map(|(k, r)| (k.inner(), r))
+            .collect();
+        assert_eq!(songs, [(2, 5), (0, 2)]);
+        assert_eq!(r.favorite_albums(&c), [(AlbumKey::from(0_usize), 1)]);
+
+        let keys = |sort: SongSort| -> Vec<usize> {
+            r.sorted(sort, &c).iter().map(|k| k.inner()).collect()
+        };
+        assert_eq!(keys(SongSort::Rating), [1, 0, 2]);
+        assert_eq!(keys(SongSort::RatingRev), [2, 0, 1]);
+    }
+}
@@ -0,0 +1,361 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{Collection, SongKey},
+    constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, RESUME_VERSION, STATE_SUB_DIR},
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The default [`Resume::threshold`], `Song`'s at least this long (seconds) resume automatically.
+///
+/// This is 20 minutes.
+pub const RESUME_THRESHOLD: u32 = 60 * 20;
+
+/// The genre (case-insensitive) that makes a `Song` resume automatically, regardless of runtime.
+pub const RESUME_GENRE: &str = "Audiobook";
+
+/// Positions this close (seconds) to either end of a `Song` are not remembered.
+///
+/// Resuming a few seconds in is pointless, and a
+/// `Song` that is this close to its end is considered finished.
+pub const RESUME_MARGIN: u32 = 10;
+
+/// [`ResumePolicy::Auto`]
+const RESUME_AUTO: &str = "Remember the position of long songs and audiobooks";
+/// [`ResumePolicy::Always`]
+const RESUME_ALWAYS: &str = "Always remember the position of this song";
+/// [`ResumePolicy::Never`]
+const RESUME_NEVER: &str = "Never remember the position of this song";
+
+//---------------------------------------------------------------------------------------------------- Statics
+/// This is the single, global copy of `Resume` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `RESUME.read()`.
+pub static RESUME: ResumeLock = ResumeLock(RwLock::new(Resume::new()));
+
+//---------------------------------------------------------------------------------------------------- ResumeLock
+/// There is only a single, global copy of `Resume` that `Kernel` uses: [`RESUME`].
+///
+/// To obtain a read-only lock, use `RESUME.read()`.
+pub struct ResumeLock(RwLock<Resume>);
+
+impl ResumeLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`Resume`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, Resume> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`Resume`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<RwLockReadGuard<'static, Resume>, TryLockError<RwLockReadGuard<'static, Resume>>>
+    {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    // Only `Kernel` and `Audio` should be writing.
+    pub(crate) fn write(&'static self) -> RwLockWriteGuard<'static, Resume> {
+        lockw!(self.0)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- ResumePolicy
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    AsRefStr,
+    Display,
+    EnumCount,
+    EnumIter,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+/// Should the playback position of a [`Song`](crate::collection::Song) be remembered?
+pub enum ResumePolicy {
+    #[default]
+    /// Only if its runtime is at least [`Resume::threshold`],
+    /// or its [`Album`](crate::collection::Album)'s genre is [`RESUME_GENRE`].
+    Auto,
+    /// Always remember its position.
+    Always,
+    /// Never remember its position.
+    Never,
+}
+
+impl ResumePolicy {
+    #[inline]
+    /// Returns the default, [`Self::Auto`].
+    pub const fn new() -> Self {
+        Self::Auto
+    }
+
+    #[inline]
+    /// Returns formatted, human readable versions.
+    pub const fn human(&self) -> &'static str {
+        match self {
+            Self::Auto => RESUME_AUTO,
+            Self::Always => RESUME_ALWAYS,
+            Self::Never => RESUME_NEVER,
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Resume
+disk::bincode2!(
+    Resume,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "resume",
+    HEADER,
+    RESUME_VERSION
+);
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+/// The last playback position of long files (audiobooks, podcasts, lectures, etc).
+///
+/// When a [`Song`](crate::collection::Song) that resumes (see [`Self::resumes`])
+/// is played again, playback starts from where it was left off, instead of from `0`.
+///
+/// Like [`crate::collection::PlayStats`], this is keyed by the `Song`'s `PATH`,
+/// not its [`SongKey`], so that the positions survive [`Collection`] resets.
+pub struct Resume {
+    /// `Song`'s with a runtime (seconds) of at least this many seconds resume with [`ResumePolicy::Auto`].
+    pub threshold: u32,
+    /// The [`ResumePolicy`] of `Song`'s that don't use [`ResumePolicy::Auto`], keyed by `PATH`.
+    pub policies: BTreeMap<PathBuf, ResumePolicy>,
+    /// The last playback position (seconds), keyed by `PATH`.
+    pub positions: BTreeMap<PathBuf, u32>,
+}
+
+impl Resume {
+    #[inline]
+    /// Creates an empty [`Resume`], with a [`RESUME_THRESHOLD`] threshold.
+    pub const fn new() -> Self {
+        Self {
+            threshold: RESUME_THRESHOLD,
+            policies: BTreeMap::new(),
+            positions: BTreeMap::new(),
+        }
+    }
+
+    #[inline]
+    /// Returns the [`ResumePolicy`] of the file at this `PATH`.
+    pub fn policy<P: AsRef<Path>>(&self, path: P) -> ResumePolicy {
+        self.policies
+            .get(path.as_ref())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// INVARIANT: assumes key is valid.
+    ///
+    /// Should the position of this [`Song`](crate::collection::Song) be remembered?
+    pub fn resumes(&self, key: SongKey, collection: &Collection) -> bool {
+        let song = &collection.songs[key];
+        match self.policy(&song.path) {
+            ResumePolicy::Always => true,
+            ResumePolicy::Never => false,
+            ResumePolicy::Auto => {
+                song.runtime.inner() >= self.threshold
+                    || collection.albums[song.album]
+                        .genre
+                        .as_deref()
+                        .is_some_and(|g| g.trim().eq_ignore_ascii_case(RESUME_GENRE))
+            }
+        }
+    }
+
+    /// INVARIANT: assumes key is valid.
+    ///
+    /// Returns the position (seconds) this [`Song`](crate::collection::Song)
+    /// should resume at, if it resumes and has one.
+    pub fn position(&self, key: SongKey, collection: &Collection) -> Option<u32> {
+        if !self.resumes(key, collection) {
+            return None;
+        }
+        self.positions.get(&collection.songs[key].path).copied()
+    }
+
+    #[inline]
+    /// How many files have a remembered position?
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    #[inline]
+    /// Is this empty?
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    // INVARIANT: assumes key is valid.
+    //
+    // Remember `elapsed` as this `Song`'s position, if it resumes.
+    //
+    // Positions within `RESUME_MARGIN` of either end forget the position instead.
+    pub(crate) fn remember(&mut self, key: SongKey, elapsed: u32, collection: &Collection) {
+        if !self.resumes(key, collection) {
+            return;
+        }
+
+        let song = &collection.songs[key];
+        let runtime = song.runtime.inner();
+        if elapsed < RESUME_MARGIN || elapsed.saturating_add(RESUME_MARGIN) >= runtime {
+            self.positions.remove(&song.path);
+        } else {
+            self.positions.insert(song.path.clone(), elapsed);
+        }
+    }
+
+    // INVARIANT: assumes key is valid.
+    //
+    // Forget this `Song`'s position, it finished playing.
+    pub(crate) fn finish(&mut self, key: SongKey, collection: &Collection) {
+        self.positions.remove(&collection.songs[key].path);
+    }
+
+    // INVARIANT: assumes key is valid.
+    //
+    // Set this `Song`'s `ResumePolicy`.
+    //
+    // `Never` also forgets its position.
+    pub(crate) fn set_policy(
+        &mut self,
+        key: SongKey,
+        policy: ResumePolicy,
+        collection: &Collection,
+    ) {
+        let path = &collection.songs[key].path;
+        match policy {
+            ResumePolicy::Auto => {
+                self.policies.remove(path);
+            }
+            ResumePolicy::Never => {
+                self.policies.insert(path.clone(), policy);
+                self.positions.remove(path);
+            }
+            ResumePolicy::Always => {
+                self.policies.insert(path.clone(), policy);
+            }
+        }
+    }
+}
+
+impl Default for Resume {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::{Album, AlbumKey, Song};
+    use readable::Runtime;
+
+    // Song `0` is short, `1` is long, `2` is a short audiobook.
+    fn collection() -> Collection {
+        Collection::test(
+            vec![],
+            [None, None, Some(" audiobook ".to_string())]
+                .into_iter()
+                .map(|genre| Album {
+                    genre,
+                    ..Default::default()
+                })
+                .collect(),
+            [10 * 60, RESUME_THRESHOLD, 60]
+                .into_iter()
+                .enumerate()
+                .map(|(i, runtime)| Song {
+                    key: SongKey::from(i),
+                    album: AlbumKey::from(i),
+                    runtime: Runtime::from(runtime),
+                    path: PathBuf::from(format!("/{i}")),
+                    ..Default::default()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    // Long songs and audiobooks resume, unless overridden.
+    fn resumes() {
+        let c = collection();
+        let mut r = Resume::new();
+        let k = |i: usize| SongKey::from(i);
+
+        assert!(!r.resumes(k(0), &c));
+        assert!(r.resumes(k(1), &c));
+        assert!(r.resumes(k(2), &c));
+
+        r.set_policy(k(0), ResumePolicy::Always, &c);
+        r.set_policy(k(1), ResumePolicy::Never, &c);
+        assert!(r.resumes(k(0), &c));
+        assert!(!r.resumes(k(1), &c));
+
+        r.set_policy(k(1), ResumePolicy::Auto, &c);
+        assert!(r.resumes(k(1), &c));
+        assert_eq!(r.policies.len(), 1);
+
+        r.threshold = 1;
+        r.set_policy(k(0), ResumePolicy::Auto, &c);
+        assert!(r.resumes(k(0), &c));
+    }
+
+    #[test]
+    // Positions near either end are forgotten.
+    fn remember() {
+        let c = collection();
+        let mut r = Resume::new();
+        let k = SongKey::from(1_usize);
+
+        r.remember(k, 100, &c);
+        assert_eq!(r.position(k, &c), Some(100));
+
+        r.remember(k, RESUME_MARGIN - 1, &c);
+        assert_eq!(r.position(k, &c), None);
+
+        r.remember(k, 100, &c);
+        r.remember(k, RESUME_THRESHOLD - RESUME_MARGIN, &c);
+        assert_eq!(r.position(k, &c), None);
+
+        r.remember(k, 100, &c);
+        r.finish(k, &c);
+        assert!(r.is_empty());
+
+        // Short songs are ignored.
+        r.remember(SongKey::zero(), 100, &c);
+        assert!(r.is_empty());
+
+        // `Never` forgets.
+        r.remember(k, 100, &c);
+        r.set_policy(k, ResumePolicy::Never, &c);
+        assert!(r.is_empty());
+    }
+}
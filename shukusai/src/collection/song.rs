@@ -1,22 +1,25 @@
 //---------------------------------------------------------------------------------------------------- Use
-use crate::collection::{AlbumKey, SongKey};
+use crate::collection::{json::SongJson, AlbumKey, SongKey, PLAY_STATS};
 use bincode::{Decode, Encode};
 use readable::Runtime;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 //----------------------------------------------------------------------------------------------------
-#[derive(Clone, Debug, Hash, PartialEq, PartialOrd, Serialize, Encode, Decode)]
+#[derive(Clone, Debug, Hash, PartialEq, PartialOrd, Encode, Decode)]
 /// Struct holding [`Song`] metadata, with a pointer to the [`Album`] it belongs to
 ///
 /// This struct holds all the metadata about a particular [`Song`].
 ///
 /// It contains a [`SongKey`] that is the index of the owning [`Album`], in the [`Collection`].
+///
+/// This serializes as a [`SongJson`], which includes the [`Song`]'s
+/// [`SongStats`](crate::collection::SongStats) from [`PLAY_STATS`].
 pub struct Song {
     /// Title of the [`Song`].
     pub title: Arc<str>,
-    #[serde(skip)]
     /// Title of the [`Song`] in "Unicode Derived Core Property" lowercase.
     pub title_lowercase: Arc<str>,
 
@@ -25,7 +28,6 @@ pub struct Song {
     /// Key to the [`Album`].
     pub album: AlbumKey,
 
-    #[serde(serialize_with = "crate::serde::runtime")]
     /// Total runtime of this [`Song`].
     pub runtime: Runtime,
     /// Sample rate of this [`Song`].
@@ -40,11 +42,35 @@ pub struct Song {
     /// The file extension of this [`Song`].
     pub extension: Arc<str>,
 
-    #[serde(skip)]
     /// The [`PathBuf`] this [`Song`] is located at.
     pub path: PathBuf,
 }
 
+//---------------------------------------------------------------------------------------------------- Serialize
+impl Serialize for Song {
+    // The `PATH` and lowercase title are not serialized,
+    // the `SongStats` keyed by that `PATH` are added instead.
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        SongJson {
+            title: Cow::Borrowed(&self.title),
+            key: self.key,
+            album: self.album,
+            runtime: self.runtime.inner(),
+            sample_rate: self.sample_rate,
+            track: self.track,
+            disc: self.disc,
+            mime: Cow::Borrowed(&self.mime),
+            extension: Cow::Borrowed(&self.extension),
+            play_stats: PLAY_STATS
+                .read()
+                .get(&self.path)
+                .copied()
+                .unwrap_or_default(),
+        }
+        .serialize(s)
+    }
+}
+
 impl Default for Song {
     fn default() -> Self {
         Self {
@@ -272,6 +272,8 @@ impl Into<crate::collection::Collection> for Collection {
             sort_song_runtime_rev,
             sort_song_title,
             sort_song_title_rev,
+
+            sort_date_added: crate::collection::SortDateAdded::new(),
         }
     }
 }
@@ -142,10 +142,40 @@ pub const HEADER_STR: &str = match std::str::from_utf8(&HEADER) {
 pub const COLLECTION_VERSION: u8 = 3;
 
 /// Current major version of the [`AudioState`]
-pub const AUDIO_VERSION: u8 = 0;
+pub const AUDIO_VERSION: u8 = 5;
 
 /// Current major version of the [`Playlists`]
-pub const PLAYLIST_VERSION: u8 = 0;
+pub const PLAYLIST_VERSION: u8 = 1;
+
+/// Current major version of the [`SmartPlaylists`](crate::state::SmartPlaylists)
+pub const SMART_PLAYLIST_VERSION: u8 = 0;
+
+/// Current major version of the [`Fingerprints`](crate::collection::Fingerprints)
+pub const FINGERPRINT_VERSION: u8 = 0;
+
+/// Current major version of the [`FirstSeen`](crate::collection::FirstSeen)
+pub const FIRST_SEEN_VERSION: u8 = 0;
+
+/// Current major version of the [`PlayStats`](crate::collection::PlayStats)
+pub const PLAY_STATS_VERSION: u8 = 0;
+
+/// Current major version of the [`Ratings`](crate::collection::Ratings)
+pub const RATINGS_VERSION: u8 = 0;
+
+/// Current major version of the [`History`](crate::state::History)
+pub const HISTORY_VERSION: u8 = 0;
+
+/// Current major version of the [`QueueSnapshots`](crate::state::QueueSnapshots)
+pub const QUEUE_SNAPSHOT_VERSION: u8 = 0;
+
+/// Current major version of the [`Resume`](crate::collection::Resume)
+pub const RESUME_VERSION: u8 = 0;
+
+/// Current major version of the [`Chapters`](crate::collection::Chapters)
+pub const CHAPTERS_VERSION: u8 = 0;
+
+/// Current major version of the [`AudioErrors`](crate::state::AudioErrors)
+pub const AUDIO_ERRORS_VERSION: u8 = 0;
 
 #[cfg(target_os = "windows")]
 #[cfg(target_arch = "x86_64")]
@@ -0,0 +1,280 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{Collection, SongKey},
+    sort::SongSort,
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::Path;
+use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The header row [`csv()`] writes before any [`Song`](crate::collection::Song)'s.
+pub const CSV_HEADER: &str =
+    "key,artist,album,title,track,disc,runtime,sample_rate,mime,extension,path";
+
+//---------------------------------------------------------------------------------------------------- ExportFormat
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    Display,
+    EnumCount,
+    EnumIter,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+/// The formats the [`Collection`] can be exported as.
+pub enum ExportFormat {
+    #[default]
+    /// Comma-separated values, a header row and then one row per `Song`.
+    Csv,
+    /// An extended `M3U` playlist (UTF-8) of every `Song`.
+    M3u8,
+    /// JSON Lines, one [`SongExport`] object per line.
+    Jsonl,
+}
+
+impl ExportFormat {
+    #[inline]
+    /// Returns the file extension of this format, e.g: `csv`.
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::M3u8 => "m3u8",
+            Self::Jsonl => "jsonl",
+        }
+    }
+
+    #[inline]
+    /// Returns the `MIME` type of this format, e.g: `text/csv`.
+    pub const fn mime(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::M3u8 => "audio/x-mpegurl",
+            Self::Jsonl => "application/x-ndjson",
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SongExport
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// A flat representation of a single `Song`, used by all export formats
+///
+/// This is what each line of a [`jsonl()`] export is,
+/// and what each row of a [`csv()`] export contains.
+pub struct SongExport<'a> {
+    /// Song key
+    pub key: SongKey,
+    #[serde(borrow)]
+    /// Artist name
+    pub artist: Cow<'a, str>,
+    #[serde(borrow)]
+    /// Album title
+    pub album: Cow<'a, str>,
+    #[serde(borrow)]
+    /// Song title
+    pub title: Cow<'a, str>,
+    /// Track number
+    pub track: Option<u32>,
+    /// Disc number
+    pub disc: Option<u32>,
+    /// Runtime in seconds
+    pub runtime: u32,
+    /// Sample rate
+    pub sample_rate: u32,
+    #[serde(borrow)]
+    /// `MIME` type
+    pub mime: Cow<'a, str>,
+    #[serde(borrow)]
+    /// File extension
+    pub extension: Cow<'a, str>,
+    #[serde(borrow)]
+    /// The `PATH` of the underlying audio file
+    pub path: Cow<'a, Path>,
+}
+
+impl<'a> SongExport<'a> {
+    /// INVARIANT: assumes key is valid.
+    pub fn from_song(key: SongKey, collection: &'a Collection) -> Self {
+        let (artist, album, song) = collection.walk(key);
+        Self {
+            key: song.key,
+            artist: Cow::Borrowed(&artist.name),
+            album: Cow::Borrowed(&album.title),
+            title: Cow::Borrowed(&song.title),
+            track: song.track,
+            disc: song.disc,
+            runtime: song.runtime.inner(),
+            sample_rate: song.sample_rate,
+            mime: Cow::Borrowed(&song.mime),
+            extension: Cow::Borrowed(&song.extension),
+            path: Cow::Borrowed(&song.path),
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Export
+/// Write every `Song` in the [`Collection`] in `format`, ordered by `sort`
+///
+/// The writer is not buffered internally, so wrapping
+/// it with a [`std::io::BufWriter`] is recommended.
+pub fn export<W: Write>(
+    collection: &Collection,
+    format: ExportFormat,
+    sort: SongSort,
+    writer: W,
+) -> std::io::Result<()> {
+    match format {
+        ExportFormat::Csv => csv(collection, sort, writer),
+        ExportFormat::M3u8 => m3u8(collection, sort, writer),
+        ExportFormat::Jsonl => jsonl(collection, sort, writer),
+    }
+}
+
+/// Write the [`Collection`] as CSV, one row per `Song`, ordered by `sort`
+///
+/// The first row is always [`CSV_HEADER`].
+///
+/// Fields containing a `,`, `"` or newline are quoted as per `RFC 4180`.
+pub fn csv<W: Write>(
+    collection: &Collection,
+    sort: SongSort,
+    mut writer: W,
+) -> std::io::Result<()> {
+    writeln!(writer, "{CSV_HEADER}")?;
+
+    for key in collection.song_iter(sort) {
+        let s = SongExport::from_song(key, collection);
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            s.key,
+            csv_field(&s.artist),
+            csv_field(&s.album),
+            csv_field(&s.title),
+            s.track.map_or(String::new(), |t| t.to_string()),
+            s.disc.map_or(String::new(), |d| d.to_string()),
+            s.runtime,
+            s.sample_rate,
+            csv_field(&s.mime),
+            csv_field(&s.extension),
+            csv_field(&s.path.to_string_lossy()),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Write the [`Collection`] as a single `M3U8` playlist, ordered by `sort`
+///
+/// Each `Song` gets an `#EXTINF` line with its
+/// runtime and `Artist - Title`, followed by its `PATH`.
+pub fn m3u8<W: Write>(
+    collection: &Collection,
+    sort: SongSort,
+    mut writer: W,
+) -> std::io::Result<()> {
+    writeln!(writer, "#EXTM3U")?;
+
+    for key in collection.song_iter(sort) {
+        let (artist, _, song) = collection.walk(key);
+        writeln!(
+            writer,
+            "#EXTINF:{},{} - {}\n{}",
+            song.runtime.inner(),
+            m3u8_field(&artist.name),
+            m3u8_field(&song.title),
+            song.path.display(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Write the [`Collection`] as JSON Lines, one [`SongExport`] per line, ordered by `sort`
+///
+/// Each line is written as soon as it is serialized, so
+/// consumers can stream the output instead of parsing a single blob.
+pub fn jsonl<W: Write>(
+    collection: &Collection,
+    sort: SongSort,
+    mut writer: W,
+) -> std::io::Result<()> {
+    for key in collection.song_iter(sort) {
+        serde_json::to_writer(&mut writer, &SongExport::from_song(key, collection))?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+//---------------------------------------------------------------------------------------------------- Private functions
+// Quote a CSV field if needed.
+fn csv_field(s: &str) -> Cow<'_, str> {
+    if s.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", s.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+// `M3U8` is line-based, newlines would break the entry.
+pub(crate) fn m3u8_field(s: &str) -> Cow<'_, str> {
+    if s.contains(['\n', '\r']) {
+        Cow::Owned(s.replace(['\n', '\r'], " "))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_quote() {
+        assert_eq!(csv_field("hello"), "hello");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn m3u8_newline() {
+        assert_eq!(m3u8_field("a\nb\rc"), "a b c");
+        assert_eq!(m3u8_field("abc"), "abc");
+    }
+
+    #[test]
+    // An empty `Collection` outputs only the headers.
+    fn empty() {
+        let c = Collection::new();
+
+        let mut vec = vec![];
+        csv(&c, SongSort::default(), &mut vec).unwrap();
+        assert_eq!(vec, format!("{CSV_HEADER}\n").as_bytes());
+
+        let mut vec = vec![];
+        m3u8(&c, SongSort::default(), &mut vec).unwrap();
+        assert_eq!(vec, b"#EXTM3U\n");
+
+        let mut vec = vec![];
+        jsonl(&c, SongSort::default(), &mut vec).unwrap();
+        assert!(vec.is_empty());
+    }
+}
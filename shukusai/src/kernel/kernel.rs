@@ -3,13 +3,23 @@ use super::{FrontendToKernel, KernelToFrontend};
 use crate::{
     audio::{Audio, AudioToKernel, KernelToAudio, Volume},
     ccd::{Ccd, CcdToKernel},
-    collection::{Collection, SongKey, DUMMY_COLLECTION},
-    constants::{AUDIO_VERSION, COLLECTION_VERSION, PLAYLIST_VERSION},
+    collection::{
+        AlbumKey, Chapters, Collection, CollectionSnapshot, Fingerprints, FirstSeen, PlayStats,
+        Ratings, Resume, SongKey, SortDateAdded, SortPlayStats, SortRatings, CHAPTERS,
+        DUMMY_COLLECTION, FINGERPRINTS, FIRST_SEEN, PLAY_STATS, RATINGS, RESUME,
+    },
+    constants::{
+        AUDIO_ERRORS_VERSION, AUDIO_VERSION, CHAPTERS_VERSION, COLLECTION_VERSION,
+        FINGERPRINT_VERSION, FIRST_SEEN_VERSION, HISTORY_VERSION, PLAYLIST_VERSION,
+        PLAY_STATS_VERSION, QUEUE_SNAPSHOT_VERSION, RATINGS_VERSION, RESUME_VERSION,
+        SMART_PLAYLIST_VERSION,
+    },
     logger::INIT_INSTANT,
     search::{KernelToSearch, Search, SearchToKernel},
     state::{
-        AudioState, AudioStateRestore, Phase, Playlists, AUDIO_STATE, PLAYLISTS, RESETTING,
-        RESET_STATE,
+        AudioErrors, AudioState, AudioStateRestore, History, Phase, Playlists, QueueSnapshots,
+        SmartPlaylists, AUDIO_ERRORS, AUDIO_STATE, HISTORY, PLAYLISTS, QUEUE_SNAPSHOTS,
+        REPAIR_THRESHOLD, RESETTING, RESET_STATE, SMART_PLAYLISTS,
     },
     watch::{Watch, WatchToKernel},
 };
@@ -157,6 +167,75 @@ impl Kernel {
         // Create `ResetState`, send to `Frontend`.
         RESET_STATE.write().disk();
 
+        // Read the `FirstSeen` timestamps, if any.
+        //
+        // This is read before the `Collection` so that it is
+        // there for `CCD` even if the `Collection` is missing.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { FirstSeen::from_file_memmap() } {
+            Ok(f) => {
+                debug!("Kernel Init ... FirstSeen{FIRST_SEEN_VERSION} found");
+                *FIRST_SEEN.write() = f;
+            }
+            Err(_) => debug!("Kernel Init ... FirstSeen{FIRST_SEEN_VERSION} NOT found"),
+        };
+
+        // Read the `Chapters`, if any.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { Chapters::from_file_memmap() } {
+            Ok(c) => {
+                debug!("Kernel Init ... Chapters{CHAPTERS_VERSION} found");
+                *CHAPTERS.write() = c;
+            }
+            Err(_) => debug!("Kernel Init ... Chapters{CHAPTERS_VERSION} NOT found"),
+        };
+
+        // Read the `PlayStats`, if any.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { PlayStats::from_file_memmap() } {
+            Ok(p) => {
+                debug!("Kernel Init ... PlayStats{PLAY_STATS_VERSION} found");
+                *PLAY_STATS.write() = p;
+            }
+            Err(_) => debug!("Kernel Init ... PlayStats{PLAY_STATS_VERSION} NOT found"),
+        };
+
+        // Read the `Resume` positions, if any.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { Resume::from_file_memmap() } {
+            Ok(r) => {
+                debug!("Kernel Init ... Resume{RESUME_VERSION} found");
+                *RESUME.write() = r;
+            }
+            Err(_) => debug!("Kernel Init ... Resume{RESUME_VERSION} NOT found"),
+        };
+
+        // Read the `AudioErrors`, if any.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { AudioErrors::from_file_memmap() } {
+            Ok(a) => {
+                debug!("Kernel Init ... AudioErrors{AUDIO_ERRORS_VERSION} found");
+                *AUDIO_ERRORS.write() = a;
+            }
+            Err(_) => debug!("Kernel Init ... AudioErrors{AUDIO_ERRORS_VERSION} NOT found"),
+        };
+
+        // Read the `Ratings`, if any.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { Ratings::from_file_memmap() } {
+            Ok(r) => {
+                debug!("Kernel Init ... Ratings{RATINGS_VERSION} found");
+                *RATINGS.write() = r;
+            }
+            Err(_) => debug!("Kernel Init ... Ratings{RATINGS_VERSION} NOT found"),
+        };
+
         // Attempt to load `Collection` from file.
         debug!("Kernel Init ... Reading Collection{COLLECTION_VERSION} from disk...");
         let now = now!();
@@ -212,8 +291,7 @@ impl Kernel {
                 warn!("Kernel Init ... Collection{COLLECTION_VERSION} from file error: {e}");
 
                 // Read `Playlist`'s anyway, and turn all entries into `invalid`.
-                // SAFETY: memmap is used.
-                let playlists = unsafe { Playlists::from_file_memmap() };
+                let playlists = Self::playlists_from_disk();
                 let playlists = match playlists {
                     Ok(mut p) => {
                         p.all_invalid();
@@ -238,7 +316,7 @@ impl Kernel {
 
     //-------------------------------------------------- boot_loader()
     fn boot_loader(
-        collection: Collection,
+        mut collection: Collection,
         to_frontend: Sender<KernelToFrontend>,
         from_frontend: Receiver<FrontendToKernel>,
         beginning: std::time::Instant,
@@ -248,6 +326,21 @@ impl Kernel {
     ) {
         debug!("Kernel Init [2/13] ... entering boot_loader()");
 
+        // The date added sorts are not saved with the
+        // `Collection`, re-create them from `FirstSeen`.
+        collection.sort_date_added =
+            SortDateAdded::from_collection(&collection, &FIRST_SEEN.read());
+
+        // Same for the play statistic sorts, from `PlayStats`.
+        {
+            let mut play_stats = PLAY_STATS.write();
+            play_stats.sort = SortPlayStats::from_collection(&collection, &play_stats);
+        }
+        {
+            let mut ratings = RATINGS.write();
+            ratings.sort = SortRatings::from_collection(&collection, &ratings);
+        }
+
         // If the `Collection` got upgraded, that means
         // we need to save the new version to disk.
         if COLLECTION_VERSION != version {
@@ -290,14 +383,12 @@ impl Kernel {
         // Before hanging on `CCD`, read `AudioState` file.
         // Note: This is a `Result`.
         debug!("Kernel Init [4/13] ... reading AudioState");
-        // SAFETY: memmap is used.
-        let state = unsafe { AudioState::from_file_memmap() };
+        let state = Self::audio_from_disk();
 
         // Before hanging on `CCD`, read `Playlists` file.
         // Note: This is a `Result`.
         debug!("Kernel Init [5/13] ... reading Playlists");
-        // SAFETY: memmap is used.
-        let playlists = unsafe { Playlists::from_file_memmap() };
+        let playlists = Self::playlists_from_disk();
 
         // Set `ResetState` to `Start` + `Art` phase.
         {
@@ -381,6 +472,9 @@ impl Kernel {
             audio.song = None;
         }
 
+        // Sleep timers don't survive restarts.
+        audio.sleep = crate::audio::SleepTimer::Off;
+
         // Check if `AUDIO_STATE` indices into itself are in-bounds.
         if let Some(idx) = audio.queue_idx {
             if audio.queue.get(idx).is_none() {
@@ -418,6 +512,44 @@ impl Kernel {
         );
     }
 
+    //-------------------------------------------------- audio_from_disk()
+    // Read `AudioState` from disk, converting older versions if needed.
+    fn audio_from_disk() -> Result<AudioState, anyhow::Error> {
+        AudioState::from_versions(&[
+            // SAFETY: memmap is used.
+            (AUDIO_VERSION, || unsafe { AudioState::from_file_memmap() }),
+            (4, crate::state::v4::AudioState::disk_into),
+            (3, crate::state::v3::AudioState::disk_into),
+            (2, crate::state::v2::AudioState::disk_into),
+            (1, crate::state::v1::AudioState::disk_into),
+            (0, crate::state::v0::AudioState::disk_into),
+        ])
+        .map(|(v, audio)| {
+            if v != AUDIO_VERSION {
+                info!("Kernel Init ... AudioState{v} converted to AudioState{AUDIO_VERSION}");
+            }
+            audio
+        })
+    }
+
+    //-------------------------------------------------- playlists_from_disk()
+    // Read `Playlists` from disk, converting older versions if needed.
+    fn playlists_from_disk() -> Result<Playlists, anyhow::Error> {
+        Playlists::from_versions(&[
+            // SAFETY: memmap is used.
+            (PLAYLIST_VERSION, || unsafe {
+                Playlists::from_file_memmap()
+            }),
+            (0, crate::state::v0::Playlists::disk_into),
+        ])
+        .map(|(v, playlists)| {
+            if v != PLAYLIST_VERSION {
+                info!("Kernel Init ... Playlists{v} converted to Playlists{PLAYLIST_VERSION}");
+            }
+            playlists
+        })
+    }
+
     //-------------------------------------------------- init()
     fn init(
         collection: Option<Arc<Collection>>,
@@ -464,6 +596,54 @@ impl Kernel {
             None => debug!("Kernel Init [10/13] ... Playlists NOT found"),
         };
 
+        // Read the `Fingerprints` cache, if any.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { Fingerprints::from_file_memmap() } {
+            Ok(f) => {
+                debug!("Kernel Init ... Fingerprints{FINGERPRINT_VERSION} found");
+                *FINGERPRINTS.write() = f;
+            }
+            Err(_) => debug!("Kernel Init ... Fingerprints{FINGERPRINT_VERSION} NOT found"),
+        };
+
+        // Read the `History`, if any, and check its validity.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { History::from_file_memmap() } {
+            Ok(mut h) => {
+                debug!("Kernel Init ... History{HISTORY_VERSION} found");
+                h.validate(&collection);
+                *HISTORY.write() = h;
+            }
+            Err(_) => debug!("Kernel Init ... History{HISTORY_VERSION} NOT found"),
+        };
+
+        // Read the `QueueSnapshots`, if any, and check their validity.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { QueueSnapshots::from_file_memmap() } {
+            Ok(mut q) => {
+                debug!("Kernel Init ... QueueSnapshots{QUEUE_SNAPSHOT_VERSION} found");
+                q.validate(&collection);
+                *QUEUE_SNAPSHOTS.write() = q;
+            }
+            Err(_) => debug!("Kernel Init ... QueueSnapshots{QUEUE_SNAPSHOT_VERSION} NOT found"),
+        };
+
+        // Read the `SmartPlaylists`, if any, and
+        // re-create their playlists for this `Collection`.
+        //
+        // SAFETY: `memmap` used.
+        match unsafe { SmartPlaylists::from_file_memmap() } {
+            Ok(s) => {
+                debug!("Kernel Init ... SmartPlaylists{SMART_PLAYLIST_VERSION} found");
+                s.refresh_all(&mut PLAYLISTS.write(), &collection);
+                *SMART_PLAYLISTS.write() = s;
+            }
+            Err(_) => debug!("Kernel Init ... SmartPlaylists{SMART_PLAYLIST_VERSION} NOT found"),
+        };
+
         // Send `Collection` to `Frontend`.
         send!(
             to_frontend,
@@ -592,10 +772,21 @@ impl Kernel {
             Pause => send!(self.to_audio, KernelToAudio::Pause),
             Next => send!(self.to_audio, KernelToAudio::Next),
             Previous(threshold) => send!(self.to_audio, KernelToAudio::Previous(threshold)),
+            NextChapter => send!(
+                self.to_audio,
+                KernelToAudio::Seek((crate::audio::Seek::NextChapter, 0))
+            ),
+            PreviousChapter => send!(
+                self.to_audio,
+                KernelToAudio::Seek((crate::audio::Seek::PreviousChapter, 0))
+            ),
             Stop => send!(self.to_audio, KernelToAudio::Clear(false)),
             // Audio settings.
             Repeat(r) => send!(self.to_audio, KernelToAudio::Repeat(r)),
             Volume(volume) => send!(self.to_audio, KernelToAudio::Volume(volume.check())),
+            AutoDj(b) => send!(self.to_audio, KernelToAudio::AutoDj(b)),
+            Sleep(s) => send!(self.to_audio, KernelToAudio::Sleep(s)),
+            ChannelMix(mix) => send!(self.to_audio, KernelToAudio::ChannelMix(mix.check())),
             Seek(tuple) => send!(self.to_audio, KernelToAudio::Seek(tuple)),
 
             // Queue.
@@ -603,7 +794,7 @@ impl Kernel {
             QueueAddAlbum(tuple) => send!(self.to_audio, KernelToAudio::QueueAddAlbum(tuple)),
             QueueAddArtist(tuple) => send!(self.to_audio, KernelToAudio::QueueAddArtist(tuple)),
             QueueAddPlaylist(tuple) => send!(self.to_audio, KernelToAudio::QueueAddPlaylist(tuple)),
-            Shuffle => send!(self.to_audio, KernelToAudio::Shuffle),
+            Shuffle(mode) => send!(self.to_audio, KernelToAudio::Shuffle(mode)),
             Clear(play) => send!(self.to_audio, KernelToAudio::Clear(play)),
             Skip(num) => send!(self.to_audio, KernelToAudio::Skip(num)),
             Back(num) => send!(self.to_audio, KernelToAudio::Back(num)),
@@ -611,6 +802,14 @@ impl Kernel {
             // Queue Index.
             QueueSetIndex(q_key) => send!(self.to_audio, KernelToAudio::QueueSetIndex(q_key)),
             QueueRemoveRange(tuple) => send!(self.to_audio, KernelToAudio::QueueRemoveRange(tuple)),
+            QueueMove(tuple) => send!(self.to_audio, KernelToAudio::QueueMove(tuple)),
+            QueueInsert(tuple) => send!(self.to_audio, KernelToAudio::QueueInsert(tuple)),
+            QueueSwap(tuple) => send!(self.to_audio, KernelToAudio::QueueSwap(tuple)),
+
+            // Queue Snapshots.
+            QueueSave(name) => self.queue_save(name),
+            QueueRestore(name) => send!(self.to_audio, KernelToAudio::QueueRestore(name)),
+            QueueSnapshotDelete(name) => self.queue_snapshot_delete(name),
 
             // Audio State.
             RestoreAudioState => send!(self.to_audio, KernelToAudio::RestoreAudioState),
@@ -620,6 +819,14 @@ impl Kernel {
             CachePath(paths) => Self::cache_path(paths),
             Search(string) => send!(self.to_search, KernelToSearch::Search(string)),
 
+            // Ratings.
+            RateSong((key, rating)) => self.rate_song(key, rating),
+            RateAlbum((key, rating)) => self.rate_album(key, rating),
+
+            // Resume.
+            ResumePolicy((key, policy)) => self.resume_policy(key, policy),
+            ResumeThreshold(seconds) => RESUME.write().threshold = seconds,
+
             // Exit.
             Exit => self.exit(),
         }
@@ -655,13 +862,17 @@ impl Kernel {
                 self.to_frontend,
                 KernelToFrontend::PathError((song_key, string.to_string()))
             ),
+            DecodeError((song_key, string)) => send!(
+                self.to_frontend,
+                KernelToFrontend::DecodeError((song_key, string.to_string()))
+            ),
         }
     }
 
     #[inline(always)]
     // We got a message from `Watch`.
     fn msg_watch(&self, msg: WatchToKernel) {
-        use crate::audio::{Repeat, Seek};
+        use crate::audio::{Repeat, Seek, ShuffleMode, SleepTimer};
         use crate::watch::WatchToKernel::*;
         match msg {
             Toggle => send!(self.to_audio, KernelToAudio::Toggle),
@@ -670,10 +881,16 @@ impl Kernel {
             Next => send!(self.to_audio, KernelToAudio::Next),
             Previous => send!(self.to_audio, KernelToAudio::Previous(None)),
             Stop => send!(self.to_audio, KernelToAudio::Clear(false)),
-            Shuffle => send!(self.to_audio, KernelToAudio::Shuffle),
+            Shuffle => send!(self.to_audio, KernelToAudio::Shuffle(ShuffleMode::new())),
             RepeatSong => send!(self.to_audio, KernelToAudio::Repeat(Repeat::Song)),
             RepeatQueue => send!(self.to_audio, KernelToAudio::Repeat(Repeat::Queue)),
             RepeatOff => send!(self.to_audio, KernelToAudio::Repeat(Repeat::Off)),
+            RepeatAlbum => send!(self.to_audio, KernelToAudio::Repeat(Repeat::Album)),
+            RepeatArtist => send!(self.to_audio, KernelToAudio::Repeat(Repeat::Artist)),
+            SleepSong => send!(self.to_audio, KernelToAudio::Sleep(SleepTimer::Song)),
+            SleepAlbum => send!(self.to_audio, KernelToAudio::Sleep(SleepTimer::Album)),
+            SleepQueue => send!(self.to_audio, KernelToAudio::Sleep(SleepTimer::Queue)),
+            SleepOff => send!(self.to_audio, KernelToAudio::Sleep(SleepTimer::Off)),
 
             // Content signals.
             Volume(v) => send!(self.to_audio, KernelToAudio::Volume(v.check())),
@@ -684,6 +901,12 @@ impl Kernel {
             Index(s) => send!(self.to_audio, KernelToAudio::QueueSetIndex(s)),
             Skip(s) => send!(self.to_audio, KernelToAudio::Skip(s)),
             Back(s) => send!(self.to_audio, KernelToAudio::Back(s)),
+            RepeatCount(c) => send!(self.to_audio, KernelToAudio::Repeat(Repeat::Count(c))),
+            RepeatAb((a, b)) => send!(self.to_audio, KernelToAudio::Repeat(Repeat::Ab { a, b })),
+            SleepMinutes(m) => send!(
+                self.to_audio,
+                KernelToAudio::Sleep(SleepTimer::minutes(m, 0, benri::unix!()))
+            ),
             //			ArtistKey(k)    => send!(self.to_audio, KernelToAudio::ArtistKey(k)),
             //			AlbumKey(k)     => send!(self.to_audio, KernelToAudio::AlbumKey(k)),
             //			SongKey(k)      => send!(self.to_audio, KernelToAudio::SongKey(k)),
@@ -725,6 +948,79 @@ impl Kernel {
             }
         }
 
+        // Save `SmartPlaylists`.
+        match SMART_PLAYLISTS.read().save_atomic() {
+            Ok(o) => ok!("Kernel - SmartPlaylists{SMART_PLAYLIST_VERSION} save: {o}"),
+            Err(e) => {
+                fail!("Kernel - SmartPlaylists{SMART_PLAYLIST_VERSION} save: {e}");
+                err = Some(e.to_string());
+            }
+        }
+
+        // Save `History`.
+        match HISTORY.read().save_atomic() {
+            Ok(o) => ok!("Kernel - History{HISTORY_VERSION} save: {o}"),
+            Err(e) => {
+                fail!("Kernel - History{HISTORY_VERSION} save: {e}");
+                err = Some(e.to_string());
+            }
+        }
+
+        // Save `QueueSnapshots`.
+        match QUEUE_SNAPSHOTS.read().save_atomic() {
+            Ok(o) => ok!("Kernel - QueueSnapshots{QUEUE_SNAPSHOT_VERSION} save: {o}"),
+            Err(e) => {
+                fail!("Kernel - QueueSnapshots{QUEUE_SNAPSHOT_VERSION} save: {e}");
+                err = Some(e.to_string());
+            }
+        }
+
+        // Save `PlayStats`.
+        match PLAY_STATS.read().save_atomic() {
+            Ok(o) => ok!("Kernel - PlayStats{PLAY_STATS_VERSION} save: {o}"),
+            Err(e) => {
+                fail!("Kernel - PlayStats{PLAY_STATS_VERSION} save: {e}");
+                err = Some(e.to_string());
+            }
+        }
+
+        // Save `Ratings`.
+        match RATINGS.read().save_atomic() {
+            Ok(o) => ok!("Kernel - Ratings{RATINGS_VERSION} save: {o}"),
+            Err(e) => {
+                fail!("Kernel - Ratings{RATINGS_VERSION} save: {e}");
+                err = Some(e.to_string());
+            }
+        }
+
+        // Save `Resume`, with the current position.
+        //
+        // `Audio` only remembers it every so often while playing.
+        {
+            let state = AUDIO_STATE.read();
+            if let Some(key) = state.song {
+                RESUME
+                    .write()
+                    .remember(key, state.elapsed.inner(), &self.collection);
+            }
+        }
+        match RESUME.read().save_atomic() {
+            Ok(o) => ok!("Kernel - Resume{RESUME_VERSION} save: {o}"),
+            Err(e) => {
+                fail!("Kernel - Resume{RESUME_VERSION} save: {e}");
+                err = Some(e.to_string());
+            }
+        }
+
+        // Save `AudioErrors`.
+        match AUDIO_ERRORS.read().save_atomic() {
+            Ok(o) => ok!("Kernel - AudioErrors{AUDIO_ERRORS_VERSION} save: {o}"),
+            Err(e) => {
+                fail!("Kernel - AudioErrors{AUDIO_ERRORS_VERSION} save: {e}");
+                err = Some(e.to_string());
+            }
+        }
+
         if let Some(err) = err {
             send!(self.to_frontend, KernelToFrontend::Exit(Err(err)));
         } else {
@@ -741,6 +1037,66 @@ impl Kernel {
         }
     }
 
+    //-------------------------------------------------- Ratings.
+    #[inline(always)]
+    fn rate_song(&self, key: SongKey, rating: u8) {
+        if self.collection.songs.get(key).is_none() {
+            warn!("Kernel - RateSong: {key:?} is out of bounds");
+            return;
+        }
+
+        RATINGS.rate_song(key, rating, &self.collection);
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
+    #[inline(always)]
+    fn rate_album(&self, key: AlbumKey, rating: u8) {
+        if self.collection.albums.get(key).is_none() {
+            warn!("Kernel - RateAlbum: {key:?} is out of bounds");
+            return;
+        }
+
+        RATINGS.rate_album(key, rating, &self.collection);
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
+    //-------------------------------------------------- Resume.
+    #[inline(always)]
+    fn resume_policy(&self, key: SongKey, policy: crate::collection::ResumePolicy) {
+        if self.collection.songs.get(key).is_none() {
+            warn!("Kernel - ResumePolicy: {key:?} is out of bounds");
+            return;
+        }
+
+        RESUME.write().set_policy(key, policy, &self.collection);
+    }
+
+    //-------------------------------------------------- Queue Snapshots.
+    #[inline(always)]
+    fn queue_save(&self, name: Arc<str>) {
+        QUEUE_SNAPSHOTS
+            .write()
+            .insert(name, &AUDIO_STATE.read(), benri::unix!(), &self.collection);
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
+    #[inline(always)]
+    fn queue_snapshot_delete(&self, name: Arc<str>) {
+        if QUEUE_SNAPSHOTS.write().remove(&name).is_none() {
+            warn!("Kernel - QueueSnapshotDelete: {name} doesn't exist");
+            return;
+        }
+
+        #[cfg(feature = "gui")]
+        gui_request_update();
+    }
+
     //-------------------------------------------------- CachePath.
     // A separate thread is responsible for walking these
     // directories since `Kernel` really shouldn't be blocked
@@ -830,6 +1186,10 @@ impl Kernel {
         };
         self.collection = Collection::dummy();
 
+        // `CCD` destructs the old `Collection`,
+        // so take what we need for the diff now.
+        let snapshot = CollectionSnapshot::new(&old_collection);
+
         // If there is another `CCD` still alive
         // saving, wait for it to finish.
         if crate::state::saving() {
@@ -895,7 +1255,33 @@ impl Kernel {
         let audio_state = audio_state_restore.into_audio_state(&self.collection);
         *AUDIO_STATE.write() = audio_state;
 
+        // Repair the playlist entries the new `Collection`
+        // invalidated, e.g `Song`'s that were re-tagged.
+        let hints = snapshot.repair_hints();
+        let repairs = PLAYLISTS
+            .write()
+            .repair(REPAIR_THRESHOLD, &hints, &self.collection);
+        drop(hints);
+        debug!(
+            "Kernel - Playlist repair: {} applied, {} left for review",
+            repairs.iter().filter(|r| r.applied).count(),
+            repairs.iter().filter(|r| !r.applied).count(),
+        );
+
+        // Diff the old and new `Collection`.
+        let diff = snapshot.diff(&self.collection);
+        debug!(
+            "Kernel - Collection diff: {} songs added, {} removed, {} modified",
+            diff.songs_added.len(),
+            diff.songs_removed.len(),
+            diff.songs_modified.len(),
+        );
+
         // Send new pointers to everyone.
+        send!(
+            self.to_frontend,
+            KernelToFrontend::CollectionDiff(Arc::new(diff))
+        );
         send!(
             self.to_audio,
             KernelToAudio::NewCollection(Arc::clone(&self.collection))
@@ -1,7 +1,9 @@
 //---------------------------------------------------------------------------------------------------- Use
 use crate::{
-    audio::{Append, Repeat, Seek, Volume},
-    collection::{AlbumKey, ArtistKey, Collection, Keychain, SongKey},
+    audio::{Append, ChannelMix, Repeat, Seek, ShuffleMode, SleepTimer, Volume},
+    collection::{
+        AlbumKey, ArtistKey, Collection, CollectionDiff, Keychain, ResumePolicy, SongKey,
+    },
     search::SearchKind,
 };
 use std::path::PathBuf;
@@ -31,6 +33,18 @@ pub enum FrontendToKernel {
     ///   If `None` is passed, `audio::PREVIOUS_THRESHOLD` will be used.
     ///   If `Some(0)` is passed, we will always skip to the previous song.
     Previous(Option<u32>),
+    /// Seek to the start of the next chapter in the current song.
+    ///
+    /// If the current song is in its last chapter (or has none), this plays the next song in queue.
+    ///
+    /// This is the same as `Self::Seek((Seek::NextChapter, 0))`.
+    NextChapter,
+    /// Seek to the start of the current chapter in the current song,
+    /// or the previous chapter if the current one started less than
+    /// [`crate::collection::CHAPTER_PREVIOUS_THRESHOLD`] seconds ago.
+    ///
+    /// This is the same as `Self::Seek((Seek::PreviousChapter, 0))`.
+    PreviousChapter,
     /// Clear the queue and stop playback.
     ///
     /// This is the same as `Self::Clear(false)`.
@@ -46,6 +60,20 @@ pub enum FrontendToKernel {
     ///
     /// Use [`crate::state::VOLUME`] instead.
     Volume(Volume),
+    /// Turn Auto-DJ on/off.
+    ///
+    /// If on, related `Song`'s are appended when the queue ends.
+    ///
+    /// See [`crate::audio::auto_dj`] for how they are picked.
+    AutoDj(bool),
+    /// Set (or cancel with [`SleepTimer::Off`]) the sleep timer.
+    ///
+    /// Use [`SleepTimer::minutes`] to create a timer that goes off in `x` minutes.
+    Sleep(SleepTimer),
+    /// Change how the left/right channels are mixed (mono, balance, swap).
+    ///
+    /// [`ChannelMix::balance`] is clamped to `-100..=100`.
+    ChannelMix(ChannelMix),
 
     // Queue.
     /// - [`SongKey`]: add this `Song` to the queue.
@@ -87,8 +115,8 @@ pub enum FrontendToKernel {
     QueueAddArtist((ArtistKey, Append, bool, bool, usize)),
     /// Add the playlist with this name to the queue.
     QueueAddPlaylist((Arc<str>, Append, bool, bool, usize)),
-    /// Shuffle the _current_ queue.
-    Shuffle,
+    /// Shuffle the _current_ queue with this [`ShuffleMode`].
+    Shuffle(ShuffleMode),
     /// Clear the entire queue.
     /// - [`bool`]: should we still continue playback on the current song?
     Clear(bool),
@@ -123,6 +151,54 @@ pub enum FrontendToKernel {
     ///
     /// This will do nothing if the start or end is out of bounds.
     QueueRemoveRange((std::ops::Range<usize>, bool)),
+    /// Move the `Song` at a queue index to another index.
+    ///
+    /// - [`usize`]: the index to move from
+    /// - [`usize`]: the index to move to
+    ///
+    /// The indices refer to the queue _before_ the move,
+    /// e.g, moving `0` to `2` in `[a, b, c]` results in `[b, c, a]`.
+    ///
+    /// The current `Song` keeps playing, and the queue index
+    /// follows it if it moved.
+    ///
+    /// This will do nothing if either index is out of bounds.
+    QueueMove((usize, usize)),
+    /// Insert `Song`'s into the queue at an index.
+    ///
+    /// - [`usize`]: the index to insert at, this may be the queue length to append
+    /// - [`Box<[SongKey]>`]: the `Song`'s to insert, in order
+    ///
+    /// Unlike the `QueueAdd*` messages, this never changes
+    /// playback, even if the queue was empty.
+    ///
+    /// This will do nothing if the index is out of bounds.
+    QueueInsert((usize, Box<[SongKey]>)),
+    /// Swap the `Song`'s at two queue indices.
+    ///
+    /// The current `Song` keeps playing, and the queue index
+    /// follows it if it was swapped.
+    ///
+    /// This will do nothing if either index is out of bounds.
+    QueueSwap((usize, usize)),
+
+    // Queue Snapshots.
+    /// Save the current queue, queue index and elapsed
+    /// time as a [`crate::state::QueueSnapshot`] with this name.
+    ///
+    /// A snapshot with the same name is overwritten.
+    QueueSave(Arc<str>),
+    /// Replace the current queue with the snapshot with this name,
+    /// starting at the `Song` and time it was saved at.
+    ///
+    /// Playback continues if it was playing, the snapshot is kept.
+    ///
+    /// If the snapshot has no valid `Song`'s, this clears the queue.
+    ///
+    /// This will do nothing if the snapshot doesn't exist.
+    QueueRestore(Arc<str>),
+    /// Delete the snapshot with this name.
+    QueueSnapshotDelete(Arc<str>),
 
     // Audio State.
     /// We just started up, restore the previous audio
@@ -142,6 +218,30 @@ pub enum FrontendToKernel {
     /// reset benefits of the OS hopefully indexing them into cache.
     CachePath(Vec<PathBuf>),
 
+    // Ratings.
+    /// Rate this [`Song`] from `1..=RATING_MAX`.
+    ///
+    /// Ratings above [`crate::collection::RATING_MAX`] are clamped, `0` clears the rating.
+    ///
+    /// This will do nothing if the [`SongKey`] is out of bounds.
+    RateSong((SongKey, u8)),
+    /// Rate this [`Album`] from `1..=RATING_MAX`.
+    ///
+    /// Ratings above [`crate::collection::RATING_MAX`] are clamped, `0` clears the rating.
+    ///
+    /// This will do nothing if the [`AlbumKey`] is out of bounds.
+    RateAlbum((AlbumKey, u8)),
+
+    // Resume.
+    /// Set the [`ResumePolicy`] of this [`Song`].
+    ///
+    /// This will do nothing if the [`SongKey`] is out of bounds.
+    ResumePolicy((SongKey, ResumePolicy)),
+    /// `Song`'s at least this many seconds long remember their playback position.
+    ///
+    /// See [`crate::collection::Resume::threshold`].
+    ResumeThreshold(u32),
+
     // Exiting.
     /// I'm exiting, save everything.
     ///
@@ -184,6 +284,12 @@ pub enum KernelToFrontend {
     NewCollection(Arc<Collection>),
     /// Creating the new [`Collection`] failed, here's the old pointer and error message.
     Failed((Arc<Collection>, String)),
+    /// Here's what changed between the old and new [`Collection`].
+    ///
+    /// # Notes
+    /// This is sent right before [`KernelToFrontend::NewCollection`]
+    /// after a successful [`FrontendToKernel::NewCollection`].
+    CollectionDiff(Arc<CollectionDiff>),
 
     // Audio error.
     /// The device error'ed during initialization.
@@ -194,6 +300,11 @@ pub enum KernelToFrontend {
     SeekError(String),
     /// Attempting to play this [`SongKey`] has errored (probably doesn't exist).
     PathError((SongKey, String)),
+    /// This [`SongKey`] had more corrupt packets than
+    /// [`crate::audio::DECODE_ERROR_THRESHOLD`], so playback of it was given up on.
+    ///
+    /// Each error is recorded in [`crate::state::AUDIO_ERRORS`].
+    DecodeError((SongKey, String)),
 
     // Search.
     /// Here's a (similarity) search result.
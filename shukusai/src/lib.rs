@@ -160,6 +160,16 @@ pub mod signal;
 /// `Frontend`-specific compatibility layers
 pub mod frontend;
 
+/// `Collection` export formats
+///
+/// These functions write every `Song` in a `Collection` to
+/// any `std::io::Write` in a plain format that other programs
+/// (spreadsheets, shell scripts, media players) can consume:
+/// - [`export::csv()`]
+/// - [`export::m3u8()`]
+/// - [`export::jsonl()`]
+pub mod export;
+
 /// Ancillary `Collection` data validation
 ///
 /// Since the `Collection` uses indices instead of references,
@@ -65,33 +65,45 @@ impl Search {
     fn search_sim(&self, input: &str, sim: f64) -> Keychain {
         let mut artists: Vec<(f64, ArtistKey)> = self
             .collection
-            .artists
-            .0
+            .sort_artist_lexi
             .par_iter()
-            .enumerate()
-            .map(|(i, x)| (strsim::jaro(&x.name_lowercase, input), ArtistKey::from(i)))
+            .map(|k| {
+                (
+                    strsim::jaro(&self.collection.artists[k].name_lowercase, input),
+                    *k,
+                )
+            })
             .filter(|(f, _)| *f >= sim)
             .collect();
         let mut albums: Vec<(f64, AlbumKey)> = self
             .collection
-            .albums
-            .0
+            .sort_album_lexi
             .par_iter()
-            .enumerate()
-            .map(|(i, x)| (strsim::jaro(&x.title_lowercase, input), AlbumKey::from(i)))
+            .map(|k| {
+                (
+                    strsim::jaro(&self.collection.albums[k].title_lowercase, input),
+                    *k,
+                )
+            })
             .filter(|(f, _)| *f >= sim)
             .collect();
         let mut songs: Vec<(f64, SongKey)> = self
             .collection
-            .songs
-            .0
+            .sort_song_lexi
             .par_iter()
-            .enumerate()
-            .map(|(i, x)| (strsim::jaro(&x.title_lowercase, input), SongKey::from(i)))
+            .map(|k| {
+                (
+                    strsim::jaro(&self.collection.songs[k].title_lowercase, input),
+                    *k,
+                )
+            })
             .filter(|(f, _)| *f >= sim)
             .collect();
 
         // Sort by highest-to-lowest similarity value.
+        //
+        // The sort is stable and the input is in `Lexi` order,
+        // so equally similar results stay in `Lexi` order.
         artists.par_sort_by(|a, b| Self::cmp_f64(b.0, a.0));
         albums.par_sort_by(|a, b| Self::cmp_f64(b.0, a.0));
         songs.par_sort_by(|a, b| Self::cmp_f64(b.0, a.0));
@@ -150,30 +162,42 @@ impl Search {
     ) {
         let mut artists: Vec<(f64, ArtistKey)> = self
             .collection
-            .artists
-            .0
+            .sort_artist_lexi
             .par_iter()
-            .enumerate()
-            .map(|(i, x)| (strsim::jaro(&x.name_lowercase, input), ArtistKey::from(i)))
+            .map(|k| {
+                (
+                    strsim::jaro(&self.collection.artists[k].name_lowercase, input),
+                    *k,
+                )
+            })
             .collect();
         let mut albums: Vec<(f64, AlbumKey)> = self
             .collection
-            .albums
-            .0
+            .sort_album_lexi
             .par_iter()
-            .enumerate()
-            .map(|(i, x)| (strsim::jaro(&x.title_lowercase, input), AlbumKey::from(i)))
+            .map(|k| {
+                (
+                    strsim::jaro(&self.collection.albums[k].title_lowercase, input),
+                    *k,
+                )
+            })
             .collect();
         let mut songs: Vec<(f64, SongKey)> = self
             .collection
-            .songs
-            .0
+            .sort_song_lexi
             .par_iter()
-            .enumerate()
-            .map(|(i, x)| (strsim::jaro(&x.title_lowercase, input), SongKey::from(i)))
+            .map(|k| {
+                (
+                    strsim::jaro(&self.collection.songs[k].title_lowercase, input),
+                    *k,
+                )
+            })
             .collect();
 
         // Sort by highest-to-lowest similarity value.
+        //
+        // The sort is stable and the input is in `Lexi` order,
+        // so equally similar results stay in `Lexi` order.
         artists.par_sort_by(|a, b| Self::cmp_f64(b.0, a.0));
         albums.par_sort_by(|a, b| Self::cmp_f64(b.0, a.0));
         songs.par_sort_by(|a, b| Self::cmp_f64(b.0, a.0));
@@ -426,6 +450,11 @@ mod tests {
         c.albums.0 = Box::new([album0, album1, album2, album3, album4]);
         c.songs.0 = Box::new([song0, song1, song2, song3, song4]);
 
+        // `Search` walks the A-Z sorts, which these already are.
+        c.sort_artist_lexi = (0..5_usize).map(ArtistKey::from).collect();
+        c.sort_album_lexi = (0..5_usize).map(AlbumKey::from).collect();
+        c.sort_song_lexi = (0..5_usize).map(SongKey::from).collect();
+
         // Spawn `Search`
         let c = Arc::new(c);
         let (to_kernel, from_search) = crossbeam::channel::unbounded::<SearchToKernel>();
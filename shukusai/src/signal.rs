@@ -62,6 +62,12 @@ impl_signal_empty! {
     RepeatSong,    "repeat_song",
     RepeatQueue,   "repeat_queue",
     RepeatOff,     "repeat_off",
+    RepeatAlbum,   "repeat_album",
+    RepeatArtist,  "repeat_artist",
+    SleepSong,     "sleep_song",
+    SleepAlbum,    "sleep_album",
+    SleepQueue,    "sleep_queue",
+    SleepOff,      "sleep_off",
 }
 
 impl_signal_content! {
@@ -89,6 +95,15 @@ impl_signal_content! {
     Back, usize, "back",
     "Contents should be a [`usize`]. This is the same as `Skip`, although it skips backwards.",
 
+    RepeatCount, u8, "repeat_count",
+    "Contents should be a [`u8`]. This repeats the current [`Song`] this many more times, then turns off repeating.",
+
+    RepeatAb, String, "repeat_ab",
+    "Contents should be two [`u32`]'s separated by a `-`, e.g. `30-45`. This loops between these two seconds (A-B) of the current [`Song`].",
+
+    SleepMinutes, u32, "sleep_minutes",
+    "Contents should be a [`u32`]. This sets a sleep timer that pauses playback after this many minutes.",
+
     // SOMEDAY
 //	ArtistKey, usize, "artist_key",
 //	"Contents should be a [`usize`] representing an [`ArtistKey`]. This will add the [`Artist`]'s songs to the queue",
@@ -1,2 +1,5 @@
 mod sort;
 pub use sort::*;
+
+mod natural;
+pub use natural::*;
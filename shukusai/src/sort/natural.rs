@@ -0,0 +1,175 @@
+//---------------------------------------------------------------------------------------------------- Use
+use once_cell::sync::Lazy;
+use std::cmp::Ordering;
+use std::sync::RwLock;
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The default [`SORT_ARTICLES`].
+pub const SORT_ARTICLES_DEFAULT: [&str; 4] = ["The", "A", "Die", "Le"];
+
+//---------------------------------------------------------------------------------------------------- Statics
+/// Leading articles that are ignored when sorting
+///
+/// e.g, with `The`, `The Beatles` sorts under `B`.
+///
+/// Articles are matched case-insensitively and only if followed
+/// by whitespace, so `Theory` and `Apple` are left untouched.
+///
+/// The `Frontend` may replace these before sending
+/// [`crate::kernel::FrontendToKernel::NewCollection`],
+/// the default is [`SORT_ARTICLES_DEFAULT`].
+pub static SORT_ARTICLES: Lazy<RwLock<Vec<String>>> = Lazy::new(|| {
+    RwLock::new(
+        SORT_ARTICLES_DEFAULT
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+    )
+});
+
+//---------------------------------------------------------------------------------------------------- Natural sorting
+/// Returns the string `Lexi` sorts compare for `s`
+///
+/// This is `s` in lowercase, with the first matching leading article in
+/// `articles` removed. If removing the article would leave nothing
+/// (e.g, an `Artist` named just `The`), it is kept.
+///
+/// ```rust,ignore
+/// let articles = ["The".to_string(), "A".to_string()];
+/// assert_eq!(sort_name("The Beatles", &articles), "beatles");
+/// assert_eq!(sort_name("Theory", &articles), "theory");
+/// assert_eq!(sort_name("The", &articles), "the");
+/// ```
+pub fn sort_name(s: &str, articles: &[String]) -> String {
+    let lowercase = s.to_lowercase();
+
+    for article in articles {
+        if let Some(rest) = lowercase.strip_prefix(&*article.to_lowercase()) {
+            let trimmed = rest.trim_start();
+            if trimmed.len() < rest.len() && !trimmed.is_empty() {
+                return trimmed.to_string();
+            }
+        }
+    }
+
+    lowercase
+}
+
+/// Compare two strings "naturally"
+///
+/// Runs of ASCII digits are compared by their numeric value,
+/// everything else is compared as per the `std` [`Ord`] implementation.
+///
+/// ```rust,ignore
+/// assert_eq!(cmp_natural("track 2", "track 10"), Ordering::Less);
+/// assert_eq!(cmp_natural("track 02", "track 2"), Ordering::Equal);
+/// assert_eq!(cmp_natural("a", "b"), Ordering::Less);
+/// ```
+pub fn cmp_natural(a: &str, b: &str) -> Ordering {
+    // Digits are ASCII, so comparing bytes is fine, UTF-8
+    // bytes sort the same as the `char`'s they encode.
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            let (a_start, b_start) = (i, j);
+            while i < a.len() && a[i].is_ascii_digit() {
+                i += 1;
+            }
+            while j < b.len() && b[j].is_ascii_digit() {
+                j += 1;
+            }
+
+            // Without leading zeros, a longer number is always bigger.
+            let x = trim_zeros(&a[a_start..i]);
+            let y = trim_zeros(&b[b_start..j]);
+            match x.len().cmp(&y.len()).then_with(|| x.cmp(y)) {
+                Ordering::Equal => (),
+                o => return o,
+            }
+        } else {
+            match a[i].cmp(&b[j]) {
+                Ordering::Equal => (),
+                o => return o,
+            }
+            i += 1;
+            j += 1;
+        }
+    }
+
+    (a.len() - i).cmp(&(b.len() - j))
+}
+
+#[inline(always)]
+// Strip the leading `0`'s of a run of digits.
+fn trim_zeros(digits: &[u8]) -> &[u8] {
+    match digits.iter().position(|b| *b != b'0') {
+        Some(i) => &digits[i..],
+        None => &[],
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural() {
+        let mut vec = vec![
+            "track 10",
+            "track 2",
+            "track 1",
+            "track 02b",
+            "track",
+            "a10b2",
+            "a10b10",
+            "a9",
+        ];
+        vec.sort_by(|a, b| cmp_natural(a, b));
+        assert_eq!(
+            vec,
+            [
+                "a9",
+                "a10b2",
+                "a10b10",
+                "track",
+                "track 1",
+                "track 2",
+                "track 02b",
+                "track 10"
+            ]
+        );
+    }
+
+    #[test]
+    fn natural_unicode() {
+        assert_eq!(cmp_natural("日本 2", "日本 10"), Ordering::Less);
+        assert_eq!(cmp_natural("über", "uber"), Ordering::Greater);
+        assert_eq!(cmp_natural("", ""), Ordering::Equal);
+        assert_eq!(cmp_natural("", "0"), Ordering::Less);
+    }
+
+    #[test]
+    fn articles() {
+        let articles: Vec<String> = SORT_ARTICLES_DEFAULT
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(sort_name("The Beatles", &articles), "beatles");
+        assert_eq!(
+            sort_name("A Tribe Called Quest", &articles),
+            "tribe called quest"
+        );
+        assert_eq!(sort_name("Die Ärzte", &articles), "ärzte");
+        assert_eq!(sort_name("Le Tigre", &articles), "tigre");
+        assert_eq!(sort_name("THE   National", &articles), "national");
+        assert_eq!(sort_name("Theory", &articles), "theory");
+        assert_eq!(sort_name("Abba", &articles), "abba");
+        assert_eq!(sort_name("The", &articles), "the");
+        assert_eq!(sort_name("The ", &articles), "the ");
+        assert_eq!(sort_name("The Beatles", &[]), "the beatles");
+    }
+}
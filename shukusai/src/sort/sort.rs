@@ -59,6 +59,10 @@ pub const ALBUM_RUNTIME_REV: &str = "Albums longest-shortest";
 pub const ALBUM_TITLE: &str = "Album title shortest-longest";
 /// [`AlbumSort::TitleRev`]
 pub const ALBUM_TITLE_REV: &str = "Album title longest-shortest";
+/// [`AlbumSort::DateAdded`]
+pub const ALBUM_DATE_ADDED: &str = "Albums added oldest-newest";
+/// [`AlbumSort::DateAddedRev`]
+pub const ALBUM_DATE_ADDED_REV: &str = "Albums added newest-oldest";
 
 /// [`SongSort::AlbumReleaseArtistLexi`]
 pub const SONG_ALBUM_RELEASE_ARTIST_LEXI: &str =
@@ -99,6 +103,39 @@ pub const SONG_RUNTIME_REV: &str = "Songs longest-shortest";
 pub const SONG_TITLE: &str = "Song title shortest-longest";
 /// [`SongSort::TitleRev`]
 pub const SONG_TITLE_REV: &str = "Song title longest-shortest";
+/// [`SongSort::DateAdded`]
+pub const SONG_DATE_ADDED: &str = "Songs added oldest-newest";
+/// [`SongSort::DateAddedRev`]
+pub const SONG_DATE_ADDED_REV: &str = "Songs added newest-oldest";
+/// [`SongSort::PlayCount`]
+pub const SONG_PLAY_COUNT: &str = "Songs played least-most";
+/// [`SongSort::PlayCountRev`]
+pub const SONG_PLAY_COUNT_REV: &str = "Songs played most-least";
+/// [`SongSort::LastPlayed`]
+pub const SONG_LAST_PLAYED: &str = "Songs last played oldest-newest";
+/// [`SongSort::LastPlayedRev`]
+pub const SONG_LAST_PLAYED_REV: &str = "Songs last played newest-oldest";
+/// [`SongSort::Rating`]
+pub const SONG_RATING: &str = "Songs rated lowest-highest";
+/// [`SongSort::RatingRev`]
+pub const SONG_RATING_REV: &str = "Songs rated highest-lowest";
+
+/// [`PlaylistSort::Artist`]
+pub const PLAYLIST_ARTIST: &str = "Artists A-Z";
+/// [`PlaylistSort::ArtistRev`]
+pub const PLAYLIST_ARTIST_REV: &str = "Artists Z-A";
+/// [`PlaylistSort::Album`]
+pub const PLAYLIST_ALBUM: &str = "Albums A-Z";
+/// [`PlaylistSort::AlbumRev`]
+pub const PLAYLIST_ALBUM_REV: &str = "Albums Z-A";
+/// [`PlaylistSort::Title`]
+pub const PLAYLIST_TITLE: &str = "Songs A-Z";
+/// [`PlaylistSort::TitleRev`]
+pub const PLAYLIST_TITLE_REV: &str = "Songs Z-A";
+/// [`PlaylistSort::Runtime`]
+pub const PLAYLIST_RUNTIME: &str = "Songs shortest-longest";
+/// [`PlaylistSort::RuntimeRev`]
+pub const PLAYLIST_RUNTIME_REV: &str = "Songs longest-shortest";
 
 //---------------------------------------------------------------------------------------------------- Sort
 #[derive(
@@ -127,7 +164,11 @@ pub const SONG_TITLE_REV: &str = "Song title longest-shortest";
 #[serde(rename_all = "snake_case")]
 /// All the ways to sort the [`Collection`]'s [`Artist`]'s.
 ///
-/// String sorting is done lexicographically as per the `std` [`Ord` implementation.](https://doc.rust-lang.org/std/primitive.str.html#impl-Ord)
+/// String sorting is done lexicographically as per the `std` [`Ord` implementation.](https://doc.rust-lang.org/std/primitive.str.html#impl-Ord),
+/// except numbers are compared by value (`Track 2` comes before `Track 10`), see [`cmp_natural`](crate::sort::cmp_natural).
+///
+/// `ARTISTSORT`, `ALBUMSORT` and `TITLESORT` tags are used instead of the
+/// name/title if they exist, and leading [`SORT_ARTICLES`](crate::sort::SORT_ARTICLES) are ignored.
 ///
 /// `lexi` is shorthand for `lexicographically`.
 pub enum ArtistSort {
@@ -180,7 +221,11 @@ pub enum ArtistSort {
 #[serde(rename_all = "snake_case")]
 /// All the ways to sort the [`Collection`]'s [`Album`]'s.
 ///
-/// String sorting is done lexicographically as per the `std` [`Ord` implementation.](https://doc.rust-lang.org/std/primitive.str.html#impl-Ord)
+/// String sorting is done lexicographically as per the `std` [`Ord` implementation.](https://doc.rust-lang.org/std/primitive.str.html#impl-Ord),
+/// except numbers are compared by value (`Track 2` comes before `Track 10`), see [`cmp_natural`](crate::sort::cmp_natural).
+///
+/// `ARTISTSORT`, `ALBUMSORT` and `TITLESORT` tags are used instead of the
+/// name/title if they exist, and leading [`SORT_ARTICLES`](crate::sort::SORT_ARTICLES) are ignored.
 ///
 /// `lexi` is shorthand for `lexicographically`.
 pub enum AlbumSort {
@@ -217,6 +262,10 @@ pub enum AlbumSort {
     Title,
     /// [`Album`] longest title, shortest title. Field: [`Collection::sort_album_title_rev`].
     TitleRev,
+    /// [`Album`] added oldest-newest. Field: [`SortDateAdded::album`](crate::collection::SortDateAdded::album).
+    DateAdded,
+    /// [`Album`] added newest-oldest. Field: [`SortDateAdded::album_rev`](crate::collection::SortDateAdded::album_rev).
+    DateAddedRev,
 }
 
 #[derive(
@@ -245,7 +294,11 @@ pub enum AlbumSort {
 #[serde(rename_all = "snake_case")]
 /// All the ways to sort the [`Collection`]'s [`Song`]'s.
 ///
-/// String sorting is done lexicographically as per the `std` [`Ord` implementation.](https://doc.rust-lang.org/std/primitive.str.html#impl-Ord)
+/// String sorting is done lexicographically as per the `std` [`Ord` implementation.](https://doc.rust-lang.org/std/primitive.str.html#impl-Ord),
+/// except numbers are compared by value (`Track 2` comes before `Track 10`), see [`cmp_natural`](crate::sort::cmp_natural).
+///
+/// `ARTISTSORT`, `ALBUMSORT` and `TITLESORT` tags are used instead of the
+/// name/title if they exist, and leading [`SORT_ARTICLES`](crate::sort::SORT_ARTICLES) are ignored.
 ///
 /// `lexi` is shorthand for `lexicographically`.
 pub enum SongSort {
@@ -282,6 +335,73 @@ pub enum SongSort {
     Title,
     /// [`Song`] longest title, shortest title. Field: [`Collection::sort_song_title_rev`].
     TitleRev,
+    /// [`Song`] added oldest-newest. Field: [`SortDateAdded::song`](crate::collection::SortDateAdded::song).
+    DateAdded,
+    /// [`Song`] added newest-oldest. Field: [`SortDateAdded::song_rev`](crate::collection::SortDateAdded::song_rev).
+    DateAddedRev,
+    /// [`Song`] least played, most played. Field: [`SortPlayStats::play_count`](crate::collection::SortPlayStats::play_count).
+    PlayCount,
+    /// [`Song`] most played, least played. Field: [`SortPlayStats::play_count_rev`](crate::collection::SortPlayStats::play_count_rev).
+    PlayCountRev,
+    /// [`Song`] last played oldest-newest. Field: [`SortPlayStats::last_played`](crate::collection::SortPlayStats::last_played).
+    LastPlayed,
+    /// [`Song`] last played newest-oldest. Field: [`SortPlayStats::last_played_rev`](crate::collection::SortPlayStats::last_played_rev).
+    LastPlayedRev,
+    /// [`Song`] lowest rated, highest rated. Field: [`SortRatings::rating`](crate::collection::SortRatings::rating).
+    Rating,
+    /// [`Song`] highest rated, lowest rated. Field: [`SortRatings::rating_rev`](crate::collection::SortRatings::rating_rev).
+    RatingRev,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    AsRefStr,
+    Display,
+    EnumCount,
+    EnumIter,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+/// All the ways to sort the entries of a single playlist.
+///
+/// See [`Playlists::playlist_sort`](crate::state::Playlists::playlist_sort).
+///
+/// Strings are compared the same way as [`SongSort`], entries that compare
+/// equal keep their current order, e.g [`PlaylistSort::Artist`] keeps each
+/// `Artist`'s `Album`'s in the order they were in.
+pub enum PlaylistSort {
+    /// [`Artist`] A-Z, then [`Album`] A-Z.
+    #[default]
+    Artist,
+    /// [`Artist`] Z-A, then [`Album`] Z-A.
+    ArtistRev,
+    /// [`Album`] A-Z.
+    Album,
+    /// [`Album`] Z-A.
+    AlbumRev,
+    /// [`Song`] title A-Z.
+    Title,
+    /// [`Song`] title Z-A.
+    TitleRev,
+    /// [`Song`] shortest-longest, missing `Song`'s are last.
+    Runtime,
+    /// [`Song`] longest-shortest, missing `Song`'s are last.
+    RuntimeRev,
 }
 
 impl ArtistSort {
@@ -366,6 +486,8 @@ impl AlbumSort {
             RuntimeRev => ALBUM_RUNTIME_REV,
             Title => ALBUM_TITLE,
             TitleRev => ALBUM_TITLE_REV,
+            DateAdded => ALBUM_DATE_ADDED,
+            DateAddedRev => ALBUM_DATE_ADDED_REV,
         }
     }
 
@@ -389,7 +511,9 @@ impl AlbumSort {
             Self::Runtime => Self::RuntimeRev,
             Self::RuntimeRev => Self::Title,
             Self::Title => Self::TitleRev,
-            Self::TitleRev => Self::ReleaseArtistLexi,
+            Self::TitleRev => Self::DateAdded,
+            Self::DateAdded => Self::DateAddedRev,
+            Self::DateAddedRev => Self::ReleaseArtistLexi,
         }
     }
 
@@ -398,7 +522,7 @@ impl AlbumSort {
     /// This returns the _last_ if at the _first_.
     pub const fn previous(&self) -> Self {
         match self {
-            Self::ReleaseArtistLexi => Self::TitleRev,
+            Self::ReleaseArtistLexi => Self::DateAddedRev,
             Self::ReleaseArtistLexiRev => Self::ReleaseArtistLexi,
             Self::ReleaseRevArtistLexi => Self::ReleaseArtistLexiRev,
             Self::ReleaseRevArtistLexiRev => Self::ReleaseRevArtistLexi,
@@ -414,6 +538,8 @@ impl AlbumSort {
             Self::RuntimeRev => Self::Runtime,
             Self::Title => Self::RuntimeRev,
             Self::TitleRev => Self::Title,
+            Self::DateAdded => Self::TitleRev,
+            Self::DateAddedRev => Self::DateAdded,
         }
     }
 }
@@ -442,6 +568,14 @@ impl SongSort {
             RuntimeRev => SONG_RUNTIME_REV,
             Title => SONG_TITLE,
             TitleRev => SONG_TITLE_REV,
+            DateAdded => SONG_DATE_ADDED,
+            DateAddedRev => SONG_DATE_ADDED_REV,
+            PlayCount => SONG_PLAY_COUNT,
+            PlayCountRev => SONG_PLAY_COUNT_REV,
+            LastPlayed => SONG_LAST_PLAYED,
+            LastPlayedRev => SONG_LAST_PLAYED_REV,
+            Rating => SONG_RATING,
+            RatingRev => SONG_RATING_REV,
         }
     }
 
@@ -465,7 +599,15 @@ impl SongSort {
             Self::Runtime => Self::RuntimeRev,
             Self::RuntimeRev => Self::Title,
             Self::Title => Self::TitleRev,
-            Self::TitleRev => Self::AlbumReleaseArtistLexi,
+            Self::TitleRev => Self::DateAdded,
+            Self::DateAdded => Self::DateAddedRev,
+            Self::DateAddedRev => Self::PlayCount,
+            Self::PlayCount => Self::PlayCountRev,
+            Self::PlayCountRev => Self::LastPlayed,
+            Self::LastPlayed => Self::LastPlayedRev,
+            Self::LastPlayedRev => Self::Rating,
+            Self::Rating => Self::RatingRev,
+            Self::RatingRev => Self::AlbumReleaseArtistLexi,
         }
     }
 
@@ -474,7 +616,7 @@ impl SongSort {
     /// This returns the _last_ if at the _first_.
     pub const fn previous(&self) -> Self {
         match self {
-            Self::AlbumReleaseArtistLexi => Self::TitleRev,
+            Self::AlbumReleaseArtistLexi => Self::RatingRev,
             Self::AlbumReleaseArtistLexiRev => Self::AlbumReleaseArtistLexi,
             Self::AlbumReleaseRevArtistLexi => Self::AlbumReleaseArtistLexiRev,
             Self::AlbumReleaseRevArtistLexiRev => Self::AlbumReleaseRevArtistLexi,
@@ -490,6 +632,65 @@ impl SongSort {
             Self::RuntimeRev => Self::Runtime,
             Self::Title => Self::RuntimeRev,
             Self::TitleRev => Self::Title,
+            Self::DateAdded => Self::TitleRev,
+            Self::DateAddedRev => Self::DateAdded,
+            Self::PlayCount => Self::DateAddedRev,
+            Self::PlayCountRev => Self::PlayCount,
+            Self::LastPlayed => Self::PlayCountRev,
+            Self::LastPlayedRev => Self::LastPlayed,
+            Self::Rating => Self::LastPlayedRev,
+            Self::RatingRev => Self::Rating,
+        }
+    }
+}
+
+impl PlaylistSort {
+    #[inline]
+    /// Returns formatted, human readable versions.
+    ///
+    /// e.g: [`PlaylistSort::Artist`] returns [`PLAYLIST_ARTIST`]
+    pub const fn human(&self) -> &'static str {
+        match self {
+            Self::Artist => PLAYLIST_ARTIST,
+            Self::ArtistRev => PLAYLIST_ARTIST_REV,
+            Self::Album => PLAYLIST_ALBUM,
+            Self::AlbumRev => PLAYLIST_ALBUM_REV,
+            Self::Title => PLAYLIST_TITLE,
+            Self::TitleRev => PLAYLIST_TITLE_REV,
+            Self::Runtime => PLAYLIST_RUNTIME,
+            Self::RuntimeRev => PLAYLIST_RUNTIME_REV,
+        }
+    }
+
+    /// Returns the next sequential [`PlaylistSort`] variant.
+    ///
+    /// This returns the _first_ if at the _last_.
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Artist => Self::ArtistRev,
+            Self::ArtistRev => Self::Album,
+            Self::Album => Self::AlbumRev,
+            Self::AlbumRev => Self::Title,
+            Self::Title => Self::TitleRev,
+            Self::TitleRev => Self::Runtime,
+            Self::Runtime => Self::RuntimeRev,
+            Self::RuntimeRev => Self::Artist,
+        }
+    }
+
+    /// Returns the previous sequential [`PlaylistSort`] variant.
+    ///
+    /// This returns the _last_ if at the _first_.
+    pub const fn previous(&self) -> Self {
+        match self {
+            Self::Artist => Self::RuntimeRev,
+            Self::ArtistRev => Self::Artist,
+            Self::Album => Self::ArtistRev,
+            Self::AlbumRev => Self::Album,
+            Self::Title => Self::AlbumRev,
+            Self::TitleRev => Self::Title,
+            Self::Runtime => Self::TitleRev,
+            Self::RuntimeRev => Self::Runtime,
         }
     }
 }
@@ -542,4 +743,17 @@ mod tests {
             assert!(set3.insert(i.previous()));
         }
     }
+
+    #[test]
+    fn playlist_diff() {
+        let mut set1 = std::collections::HashSet::new();
+        let mut set2 = std::collections::HashSet::new();
+        let mut set3 = std::collections::HashSet::new();
+
+        for i in PlaylistSort::iter() {
+            assert!(set1.insert(i.human()));
+            assert!(set2.insert(i.next()));
+            assert!(set3.insert(i.previous()));
+        }
+    }
 }
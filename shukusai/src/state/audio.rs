@@ -1,6 +1,6 @@
 //---------------------------------------------------------------------------------------------------- Use
-use crate::audio::{Repeat, Volume};
-use crate::collection::{Collection, MapKey, SongKey};
+use crate::audio::{ChannelMix, OutputPath, Repeat, SleepTimer, Volume};
+use crate::collection::{chapter_index, Collection, MapKey, SongKey, CHAPTERS};
 use crate::constants::{AUDIO_VERSION, FESTIVAL, FRONTEND_SUB_DIR, HEADER, STATE_SUB_DIR};
 use benri::ok;
 use benri::sync::*;
@@ -106,6 +106,38 @@ pub struct AudioState {
     /// It does not represent the current volume.
     /// See [`crate::state::VOLUME`] for more info.
     pub volume: Volume,
+
+    // Auto-DJ.
+    /// Should we append related `Song`'s when the queue ends?
+    ///
+    /// See [`crate::audio::auto_dj`] for how they are picked.
+    pub auto_dj: bool,
+    /// The `queue` indices (`start..end`) of the `Song`'s Auto-DJ appended, if any.
+    ///
+    /// This is forgotten when the queue is edited
+    /// in any way other than appending to the back.
+    pub auto_dj_span: Option<(usize, usize)>,
+
+    /// The currently set sleep timer.
+    ///
+    /// This is saved to disk, but is always [`SleepTimer::Off`]
+    /// after a restart or a [`Collection`] reset.
+    pub sleep: SleepTimer,
+
+    /// The index of the chapter of the current song we are in, if it has any.
+    ///
+    /// This indexes into the song's [`crate::collection::Chapters`].
+    pub chapter: Option<usize>,
+
+    /// How the left/right channels are mixed before playback.
+    pub channel_mix: ChannelMix,
+
+    /// How samples are currently getting to the audio device.
+    ///
+    /// This is [`OutputPath::None`] until something is played.
+    pub output_path: OutputPath,
+    /// The sample rate the audio device is currently opened at, `0` if [`OutputPath::None`].
+    pub output_rate: u32,
 }
 
 impl AudioState {
@@ -121,9 +153,27 @@ impl AudioState {
             runtime: Runtime::zero(),
             repeat: Repeat::new(),
             volume: Volume::const_default(),
+
+            auto_dj: false,
+            auto_dj_span: None,
+
+            sleep: SleepTimer::new(),
+
+            chapter: None,
+
+            channel_mix: ChannelMix::new(),
+
+            output_path: OutputPath::None,
+            output_rate: 0,
         }
     }
 
+    /// Is the `Song` at `index` in the `queue` from Auto-DJ?
+    pub fn is_auto_dj(&self, index: usize) -> bool {
+        self.auto_dj_span
+            .is_some_and(|(start, end)| (start..end).contains(&index))
+    }
+
     /// Clone `Self`, conditionally.
     ///
     /// - If `self` and `dst` are the same, this does nothing
@@ -150,6 +200,23 @@ impl AudioState {
         self.song = None;
         self.elapsed = Runtime::zero();
         self.runtime = Runtime::zero();
+        self.auto_dj_span = None;
+        self.chapter = None;
+    }
+
+    // Append Auto-DJ picked `keys` to the back of the queue.
+    //
+    // The span grows if the queue
+    // already ended in Auto-DJ `Song`'s.
+    pub(crate) fn auto_dj_append(&mut self, keys: Vec<SongKey>) {
+        let len = self.queue.len();
+        self.queue.extend(keys);
+
+        let start = match self.auto_dj_span {
+            Some((start, end)) if end == len => start,
+            _ => len,
+        };
+        self.auto_dj_span = Some((start, self.queue.len()));
     }
 
     // - Increments the `queue_idx`
@@ -189,6 +256,101 @@ impl AudioState {
 
         None
     }
+
+    // Move the entry at `from` to `to`.
+    //
+    // `queue_idx` follows the `Song` it was pointing to.
+    //
+    // Returns `false` and does nothing if either index is out of bounds.
+    pub(crate) fn queue_move(&mut self, from: usize, to: usize) -> bool {
+        let len = self.queue.len();
+        if from >= len || to >= len {
+            return false;
+        }
+
+        if let Some(key) = self.queue.remove(from) {
+            self.queue.insert(to, key);
+        }
+
+        if let Some(i) = self.queue_idx {
+            self.queue_idx = Some(if i == from {
+                to
+            } else if from < i && i <= to {
+                i - 1
+            } else if to <= i && i < from {
+                i + 1
+            } else {
+                i
+            });
+        }
+
+        true
+    }
+
+    // Insert `keys` starting at `index`, shifting everything after it.
+    //
+    // `index` may be equal to the queue length, which appends.
+    // `queue_idx` follows the `Song` it was pointing to.
+    //
+    // Returns `false` and does nothing if `index` is out of bounds.
+    pub(crate) fn queue_insert(&mut self, index: usize, keys: &[SongKey]) -> bool {
+        if index > self.queue.len() {
+            return false;
+        }
+
+        for (i, key) in keys.iter().enumerate() {
+            self.queue.insert(index + i, *key);
+        }
+
+        if let Some(i) = self.queue_idx {
+            if i >= index {
+                self.queue_idx = Some(i + keys.len());
+            }
+        }
+
+        true
+    }
+
+    // Swap the entries at `a` and `b`.
+    //
+    // `queue_idx` follows the `Song` it was pointing to.
+    //
+    // Returns `false` and does nothing if either index is out of bounds.
+    pub(crate) fn queue_swap(&mut self, a: usize, b: usize) -> bool {
+        let len = self.queue.len();
+        if a >= len || b >= len {
+            return false;
+        }
+
+        self.queue.swap(a, b);
+
+        if self.queue_idx == Some(a) {
+            self.queue_idx = Some(b);
+        } else if self.queue_idx == Some(b) {
+            self.queue_idx = Some(a);
+        }
+
+        true
+    }
+
+    // Point `queue_idx` at `key`, a `Song` going back in `HISTORY`.
+    //
+    // If `key` is the current `Song` or the one right before it, that
+    // entry is used, else `key` is inserted at the current index, so
+    // the `Song` we were on is up next. Walking back through songs
+    // that were played from the queue doesn't grow it.
+    pub(crate) fn queue_back_to(&mut self, key: SongKey) {
+        let index = self.queue_idx.unwrap_or(0).min(self.queue.len());
+
+        if self.queue.get(index) == Some(&key) {
+            self.queue_idx = Some(index);
+        } else if index > 0 && self.queue[index - 1] == key {
+            self.queue_idx = Some(index - 1);
+        } else {
+            self.queue.insert(index, key);
+            self.queue_idx = Some(index);
+        }
+    }
 }
 
 impl Default for AudioState {
@@ -217,6 +379,9 @@ pub(crate) struct AudioStateRestore {
     pub(crate) runtime: Runtime,
     pub(crate) repeat: Repeat,
     pub(crate) volume: Volume,
+    pub(crate) auto_dj: bool,
+    pub(crate) auto_dj_span: Option<(usize, usize)>,
+    pub(crate) channel_mix: ChannelMix,
 
     // extra info
     pub(crate) queue_len: usize,
@@ -259,6 +424,19 @@ impl AudioStateRestore {
                             runtime: self.runtime,
                             repeat: self.repeat,
                             volume: self.volume,
+                            auto_dj: self.auto_dj,
+                            auto_dj_span: self.auto_dj_span,
+                            // Sleep timers don't survive a `Collection` reset.
+                            sleep: SleepTimer::Off,
+                            // The chapters may have changed with the `Collection`.
+                            chapter: chapter_index(
+                                CHAPTERS.read().get(&song.path),
+                                self.elapsed.inner(),
+                            ),
+                            channel_mix: self.channel_mix,
+                            // `Audio` reports this again on the next write.
+                            output_path: OutputPath::None,
+                            output_rate: 0,
                         };
                     }
                 }
@@ -275,6 +453,13 @@ impl AudioStateRestore {
             runtime: Runtime::zero(),
             repeat: self.repeat,
             volume: self.volume,
+            auto_dj: self.auto_dj,
+            auto_dj_span: None,
+            sleep: SleepTimer::Off,
+            chapter: None,
+            channel_mix: self.channel_mix,
+            output_path: OutputPath::None,
+            output_rate: 0,
         }
     }
 
@@ -296,6 +481,9 @@ impl AudioStateRestore {
             runtime: a.runtime,
             repeat: a.repeat,
             volume: Volume::new(atomic_load!(crate::state::VOLUME)),
+            auto_dj: a.auto_dj,
+            auto_dj_span: a.auto_dj_span,
+            channel_mix: a.channel_mix,
 
             queue_len: a.queue.len(),
         }
@@ -312,10 +500,10 @@ mod tests {
 
     // Empty new `AudioState`.
     const A1: Lazy<AudioState> =
-        Lazy::new(|| AudioState::from_path("../assets/shukusai/state/audio0_new.bin").unwrap());
+        Lazy::new(|| AudioState::from_path("../assets/shukusai/state/audio5_new.bin").unwrap());
     // Filled, user `AudioState`.
     const A2: Lazy<AudioState> =
-        Lazy::new(|| AudioState::from_path("../assets/shukusai/state/audio0_real.bin").unwrap());
+        Lazy::new(|| AudioState::from_path("../assets/shukusai/state/audio5_real.bin").unwrap());
     const SONG: SongKey = SongKey::new();
 
     #[test]
@@ -446,6 +634,151 @@ mod tests {
         assert!(a.prev().is_none());
     }
 
+    // An `AudioState` with a queue of `0..len`, playing `idx`.
+    fn queue(len: usize, idx: usize) -> AudioState {
+        let mut a = AudioState::new();
+        a.queue = (0..len).map(SongKey::from).collect();
+        a.queue_idx = Some(idx);
+        a
+    }
+
+    // The queue as `usize`'s.
+    fn keys(a: &AudioState) -> Vec<usize> {
+        a.queue.iter().map(|k| k.inner()).collect()
+    }
+
+    #[test]
+    // Tests `queue_move()`, `queue_idx` must follow the current `Song`.
+    fn queue_move() {
+        // Moving the current `Song`.
+        let mut a = queue(5, 1);
+        assert!(a.queue_move(1, 3));
+        assert_eq!(keys(&a), [0, 2, 3, 1, 4]);
+        assert_eq!(a.queue_idx, Some(3));
+
+        // Moving from before to after the current `Song`.
+        let mut a = queue(5, 2);
+        assert!(a.queue_move(0, 4));
+        assert_eq!(keys(&a), [1, 2, 3, 4, 0]);
+        assert_eq!(a.queue_idx, Some(1));
+
+        // Moving from after to before the current `Song`.
+        let mut a = queue(5, 2);
+        assert!(a.queue_move(4, 0));
+        assert_eq!(keys(&a), [4, 0, 1, 2, 3]);
+        assert_eq!(a.queue_idx, Some(3));
+
+        // Moving around the current `Song`, not across it.
+        let mut a = queue(5, 0);
+        assert!(a.queue_move(3, 1));
+        assert_eq!(keys(&a), [0, 3, 1, 2, 4]);
+        assert_eq!(a.queue_idx, Some(0));
+
+        // Out of bounds.
+        let mut a = queue(5, 0);
+        assert!(!a.queue_move(5, 0));
+        assert!(!a.queue_move(0, 5));
+        assert_eq!(keys(&a), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    // Tests `queue_insert()`, `queue_idx` must follow the current `Song`.
+    fn queue_insert() {
+        let new = [SongKey::from(8_usize), SongKey::from(9_usize)];
+
+        // Before the current `Song`.
+        let mut a = queue(3, 1);
+        assert!(a.queue_insert(1, &new));
+        assert_eq!(keys(&a), [0, 8, 9, 1, 2]);
+        assert_eq!(a.queue_idx, Some(3));
+
+        // After the current `Song`.
+        let mut a = queue(3, 1);
+        assert!(a.queue_insert(2, &new));
+        assert_eq!(keys(&a), [0, 1, 8, 9, 2]);
+        assert_eq!(a.queue_idx, Some(1));
+
+        // Appending.
+        let mut a = queue(3, 1);
+        assert!(a.queue_insert(3, &new));
+        assert_eq!(keys(&a), [0, 1, 2, 8, 9]);
+        assert_eq!(a.queue_idx, Some(1));
+
+        // Out of bounds.
+        let mut a = queue(3, 1);
+        assert!(!a.queue_insert(4, &new));
+        assert_eq!(keys(&a), [0, 1, 2]);
+    }
+
+    #[test]
+    // Tests `auto_dj_append()`, the span grows
+    // only if the queue ended in Auto-DJ `Song`'s.
+    fn auto_dj_append() {
+        let new = || vec![SongKey::from(8_usize), SongKey::from(9_usize)];
+
+        let mut a = queue(3, 1);
+        a.auto_dj_append(new());
+        assert_eq!(keys(&a), [0, 1, 2, 8, 9]);
+        assert_eq!(a.auto_dj_span, Some((3, 5)));
+
+        a.auto_dj_append(new());
+        assert_eq!(a.auto_dj_span, Some((3, 7)));
+
+        // The user appended after the Auto-DJ `Song`'s.
+        a.queue.push_back(SONG);
+        a.auto_dj_append(new());
+        assert_eq!(a.auto_dj_span, Some((8, 10)));
+
+        a.finish();
+        assert_eq!(a.auto_dj_span, None);
+    }
+
+    #[test]
+    // Tests `queue_swap()`, `queue_idx` must follow the current `Song`.
+    fn queue_swap() {
+        let mut a = queue(4, 1);
+        assert!(a.queue_swap(1, 3));
+        assert_eq!(keys(&a), [0, 3, 2, 1]);
+        assert_eq!(a.queue_idx, Some(3));
+
+        assert!(a.queue_swap(0, 2));
+        assert_eq!(keys(&a), [2, 3, 0, 1]);
+        assert_eq!(a.queue_idx, Some(3));
+
+        assert!(!a.queue_swap(0, 4));
+        assert_eq!(keys(&a), [2, 3, 0, 1]);
+    }
+
+    #[test]
+    // Tests repeated `queue_back_to()`'s, like pressing `Back(1)` over and over.
+    fn queue_back_to() {
+        // Walking back over `Song`'s played from the queue.
+        let mut a = queue(3, 2);
+        for key in [1_usize, 0] {
+            a.queue_back_to(SongKey::from(key));
+        }
+        assert_eq!(keys(&a), [0, 1, 2]);
+        assert_eq!(a.queue_idx, Some(0));
+
+        // `Song`'s that aren't there (cleared, shuffled) are inserted once each.
+        let mut a = queue(3, 0);
+        a.queue
+            .iter_mut()
+            .for_each(|k| *k = SongKey::from(k.inner() + 5));
+        for key in [1_usize, 0, 0] {
+            a.queue_back_to(SongKey::from(key));
+        }
+        assert_eq!(keys(&a), [0, 1, 5, 6, 7]);
+        assert_eq!(a.queue_idx, Some(0));
+
+        // Nothing after the queue finished.
+        let mut a = AudioState::new();
+        a.queue_back_to(SongKey::from(3_usize));
+        a.queue_back_to(SongKey::from(3_usize));
+        assert_eq!(keys(&a), [3]);
+        assert_eq!(a.queue_idx, Some(0));
+    }
+
     #[test]
     // Compares `AudioState::new()` against A1 & A2.
     fn cmp() {
@@ -457,6 +790,17 @@ mod tests {
         assert_ne!(b1, b2);
     }
 
+    #[test]
+    // Sleep timers are turned `Off` across a `Collection` reset.
+    fn restore_sleep() {
+        let c = Collection::dummy();
+        let mut a = AudioState::new();
+        a.sleep = SleepTimer::Song;
+
+        let a = AudioStateRestore::from_audio_state(&a, &c).into_audio_state(&c);
+        assert_eq!(a.sleep, SleepTimer::Off);
+    }
+
     #[test]
     // Attempts to deserialize a non-empty `AudioState`.
     fn real() {
@@ -470,6 +814,24 @@ mod tests {
         assert_eq!(A2.runtime, Runtime::from(321_u16));
         assert_eq!(A2.repeat, Repeat::Queue);
         assert!(A2.playing);
+        assert!(A2.auto_dj);
+        assert_eq!(A2.auto_dj_span, Some((1, 3)));
+        assert!(!A2.is_auto_dj(0));
+        assert!(A2.is_auto_dj(1));
+        assert!(A2.is_auto_dj(2));
+        // Saved, but not restored, see `restore_sleep()`.
+        assert_eq!(A2.sleep, SleepTimer::Song);
+        assert_eq!(A2.chapter, Some(1));
+        assert_eq!(
+            A2.channel_mix,
+            ChannelMix {
+                mono: true,
+                balance: -20,
+                swap: true,
+            }
+        );
+        assert_eq!(A2.output_path, OutputPath::Resampled);
+        assert_eq!(A2.output_rate, 48_000);
     }
 
     #[test]
@@ -483,7 +845,18 @@ mod tests {
   "elapsed": 0,
   "runtime": 0,
   "repeat": "off",
-  "volume": 25
+  "volume": 25,
+  "auto_dj": false,
+  "auto_dj_span": null,
+  "sleep": "off",
+  "chapter": null,
+  "channel_mix": {
+    "mono": false,
+    "balance": 0,
+    "swap": false
+  },
+  "output_path": "none",
+  "output_rate": 0
 }"#;
 
         assert_eq!(
@@ -0,0 +1,264 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{Collection, SongKey},
+    constants::{AUDIO_ERRORS_VERSION, FESTIVAL, FRONTEND_SUB_DIR, HEADER, STATE_SUB_DIR},
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The maximum amount of [`AudioError`]'s kept per `Song`.
+///
+/// Once this is reached, the oldest error of that `Song` is dropped for every new one.
+pub const AUDIO_ERRORS_MAX: usize = 32;
+
+//---------------------------------------------------------------------------------------------------- Lazy
+/// This is the single, global copy of `AudioErrors` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `AUDIO_ERRORS.read()`.
+pub static AUDIO_ERRORS: AudioErrorsLock = AudioErrorsLock(RwLock::new(AudioErrors::new()));
+
+//---------------------------------------------------------------------------------------------------- AudioErrorsLock
+/// There is only a single, global copy of `AudioErrors` that `Kernel` uses: [`AUDIO_ERRORS`].
+///
+/// To obtain a read-only lock, use `AUDIO_ERRORS.read()`.
+pub struct AudioErrorsLock(RwLock<AudioErrors>);
+
+impl AudioErrorsLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`AudioErrors`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, AudioErrors> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`AudioErrors`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<
+        RwLockReadGuard<'static, AudioErrors>,
+        TryLockError<RwLockReadGuard<'static, AudioErrors>>,
+    > {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    // Only `Kernel` and `Audio` should be writing.
+    pub(crate) fn write(&'static self) -> RwLockWriteGuard<'static, AudioErrors> {
+        lockw!(self.0)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- AudioErrors
+disk::bincode2!(
+    AudioErrors,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "audio_errors",
+    HEADER,
+    AUDIO_ERRORS_VERSION
+);
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+/// Every decoding/demuxing incident `Audio` ran into while playing, per `Song`.
+///
+/// Like [`crate::collection::Chapters`], this is keyed by the `Song`'s `PATH`,
+/// so it survives [`Collection`] resets. Each entry is oldest first
+/// and holds at most [`AUDIO_ERRORS_MAX`] errors.
+///
+/// Only `Song`'s that had at least 1 error have an entry.
+pub struct AudioErrors(pub BTreeMap<PathBuf, Vec<AudioError>>);
+
+impl AudioErrors {
+    #[inline]
+    /// Creates an empty [`AudioErrors`].
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    #[inline]
+    /// Returns the errors of the file at this `PATH`, oldest first.
+    ///
+    /// This is empty if it has none.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> &[AudioError] {
+        self.0.get(path.as_ref()).map_or(&[], |e| e.as_slice())
+    }
+
+    #[inline]
+    /// INVARIANT: assumes key is valid.
+    ///
+    /// Returns the errors of this [`Song`](crate::collection::Song), oldest first.
+    pub fn song(&self, key: SongKey, collection: &Collection) -> &[AudioError] {
+        self.get(&collection.songs[key].path)
+    }
+
+    #[inline]
+    /// How many files have errors?
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    /// Is this empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the [`AudioErrorsJson`] of every file, sorted by `PATH`.
+    ///
+    /// Files that are in this [`Collection`] get their [`SongKey`].
+    pub fn json<'a>(&'a self, collection: &Collection) -> Vec<AudioErrorsJson<'a>> {
+        let keys: HashMap<&Path, SongKey> = collection
+            .songs
+            .iter()
+            .map(|s| (s.path.as_path(), s.key))
+            .collect();
+
+        self.0
+            .iter()
+            .map(|(path, errors)| AudioErrorsJson {
+                path: Cow::Borrowed(path.as_path()),
+                key: keys.get(path.as_path()).copied(),
+                errors: Cow::Borrowed(errors.as_slice()),
+            })
+            .collect()
+    }
+
+    // Record an error for the file at this `PATH`.
+    //
+    // The oldest errors of the file are dropped if it is over `AUDIO_ERRORS_MAX`.
+    pub(crate) fn push(&mut self, path: &Path, error: AudioError) {
+        let errors = match self.0.get_mut(path) {
+            Some(e) => e,
+            None => self.0.entry(path.to_path_buf()).or_default(),
+        };
+
+        errors.push(error);
+
+        if errors.len() > AUDIO_ERRORS_MAX {
+            let over = errors.len() - AUDIO_ERRORS_MAX;
+            errors.drain(..over);
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- AudioError
+#[derive(
+    Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+/// A single decoding/demuxing incident within a `Song`.
+pub struct AudioError {
+    /// The UNIX timestamp this happened at
+    pub timestamp: u64,
+    /// The second within the `Song` this happened at
+    pub elapsed: u32,
+    /// What happened
+    pub kind: AudioErrorKind,
+    /// The error message
+    pub error: String,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Hash,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    AsRefStr,
+    Display,
+    EnumCount,
+    EnumIter,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+/// The kind of [`AudioError`].
+pub enum AudioErrorKind {
+    /// A packet could not be decoded, it was skipped.
+    Decode,
+    /// The next packet could not be read, the demuxer
+    /// was re-synced by seeking past the corrupt data.
+    Demux,
+    /// There were more errors than [`crate::audio::DECODE_ERROR_THRESHOLD`]
+    /// (or re-syncing failed), so playback of the `Song` was given up on.
+    Abort,
+}
+
+//---------------------------------------------------------------------------------------------------- JSON Representation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// Stable `JSON` representation of the [`AudioError`]'s of a file.
+pub struct AudioErrorsJson<'a> {
+    #[serde(borrow)]
+    /// The `PATH` of the file
+    pub path: Cow<'a, Path>,
+    /// The `Song` key of the file, `None` if it isn't in the current `Collection`
+    pub key: Option<SongKey>,
+    /// The errors, oldest first
+    pub errors: Cow<'a, [AudioError]>,
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(elapsed: u32) -> AudioError {
+        AudioError {
+            timestamp: 0,
+            elapsed,
+            kind: AudioErrorKind::Decode,
+            error: String::new(),
+        }
+    }
+
+    #[test]
+    // Errors are kept per file, oldest first, and capped.
+    fn push() {
+        let mut e = AudioErrors::new();
+        let (a, b) = (Path::new("/a"), Path::new("/b"));
+
+        e.push(a, error(0));
+        e.push(b, error(1));
+        assert_eq!(e.len(), 2);
+        assert_eq!(e.get(a), [error(0)]);
+        assert_eq!(e.get(b), [error(1)]);
+        assert!(e.get("/c").is_empty());
+
+        for i in 1..=AUDIO_ERRORS_MAX as u32 {
+            e.push(a, error(i));
+        }
+        let a = e.get(a);
+        assert_eq!(a.len(), AUDIO_ERRORS_MAX);
+        assert_eq!(a[0], error(1));
+        assert_eq!(a[AUDIO_ERRORS_MAX - 1], error(AUDIO_ERRORS_MAX as u32));
+    }
+
+    #[test]
+    fn kind() {
+        assert_eq!(AudioErrorKind::Abort.as_ref(), "abort");
+        assert_eq!(
+            serde_json::to_string(&AudioErrorKind::Decode).unwrap(),
+            r#""decode""#
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{Collection, SongKey},
+    constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, HISTORY_VERSION, STATE_SUB_DIR},
+    state::{Entry, EntryJson},
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The maximum amount of [`HistoryEntry`]'s kept in [`History`].
+///
+/// Once this is reached, the oldest entry is dropped for every new one.
+pub const HISTORY_MAX: usize = 1000;
+
+//---------------------------------------------------------------------------------------------------- Lazy
+/// This is the single, global copy of `History` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `HISTORY.read()`.
+pub static HISTORY: HistoryLock = HistoryLock(RwLock::new(History::new()));
+
+//---------------------------------------------------------------------------------------------------- HistoryLock
+/// There is only a single, global copy of `History` that `Kernel` uses: [`HISTORY`].
+///
+/// To obtain a read-only lock, use `HISTORY.read()`.
+pub struct HistoryLock(RwLock<History>);
+
+impl HistoryLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`History`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, History> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`History`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<RwLockReadGuard<'static, History>, TryLockError<RwLockReadGuard<'static, History>>>
+    {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    // Only `Kernel`, `Audio` and `CCD` should be writing.
+    pub(crate) fn write(&'static self) -> RwLockWriteGuard<'static, History> {
+        lockw!(self.0)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- History
+disk::bincode2!(
+    History,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "history",
+    HEADER,
+    HISTORY_VERSION
+);
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+/// A log of every `Song` that started playing, oldest first.
+///
+/// `Audio` pushes a [`HistoryEntry`] every time a `Song` is set,
+/// regardless of how (next, previous, queue index, etc).
+///
+/// This holds at most [`HISTORY_MAX`] entries.
+///
+/// Like [`crate::state::Playlists`], each entry is an [`Entry`], so the
+/// history survives [`Collection`] resets, `Song`'s that no longer
+/// exist are kept as [`Entry::Invalid`].
+pub struct History(pub VecDeque<HistoryEntry>);
+
+#[derive(
+    Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+/// A single `Song` in the [`History`].
+pub struct HistoryEntry {
+    /// The `Song` that was played
+    pub entry: Entry,
+    /// The UNIX timestamp it started playing at
+    pub timestamp: u64,
+}
+
+impl std::ops::Deref for History {
+    type Target = VecDeque<HistoryEntry>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl History {
+    #[inline]
+    /// Create an empty `Self` with no allocation.
+    pub const fn new() -> Self {
+        Self(VecDeque::new())
+    }
+
+    /// INVARIANT: assumes key is valid.
+    ///
+    /// Push a `Song` that started playing at `timestamp`.
+    ///
+    /// The oldest entries are dropped if we are over [`HISTORY_MAX`].
+    pub(crate) fn push(&mut self, key: SongKey, timestamp: u64, collection: &Arc<Collection>) {
+        self.0.push_back(HistoryEntry {
+            entry: Entry::valid_from_song(key, collection),
+            timestamp,
+        });
+
+        while self.0.len() > HISTORY_MAX {
+            self.0.pop_front();
+        }
+    }
+
+    /// Returns an iterator over the [`HistoryEntry`]'s, newest first.
+    pub fn iter_newest(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.0.iter().rev()
+    }
+
+    /// Returns up to `len` [`HistoryEntry`]'s, newest first, skipping the `offset` newest.
+    ///
+    /// This is empty if `offset` is past the end.
+    pub fn page(&self, offset: usize, len: usize) -> Vec<&HistoryEntry> {
+        self.iter_newest().skip(offset).take(len).collect()
+    }
+
+    /// Returns the `offset`'th newest `Song` that is still valid, and its offset.
+    ///
+    /// `0` is the newest entry, i.e, usually the `Song` currently playing.
+    ///
+    /// [`Entry::Invalid`]'s are skipped, so the returned offset may be bigger.
+    pub fn valid_from(&self, offset: usize) -> Option<(usize, SongKey)> {
+        self.iter_newest()
+            .enumerate()
+            .skip(offset)
+            .find_map(|(i, h)| match h.entry {
+                Entry::Valid { key_song, .. } => Some((i, key_song)),
+                Entry::Invalid { .. } => None,
+            })
+    }
+
+    /// Validate all entries against `collection`, see [`Entry::validate`].
+    pub fn validate(&mut self, collection: &Arc<Collection>) {
+        self.0
+            .par_iter_mut()
+            .for_each(|h| h.entry.validate(collection));
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- JSON Representation
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+/// Stable `JSON` representation of [`HistoryEntry`].
+pub struct HistoryEntryJson<'a> {
+    #[serde(borrow)]
+    /// The `Song` that was played
+    pub entry: EntryJson<'a>,
+    /// The UNIX timestamp it started playing at
+    pub timestamp: u64,
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An `Invalid` entry, at `timestamp`.
+    fn invalid(timestamp: u64) -> HistoryEntry {
+        HistoryEntry {
+            entry: Entry::Invalid {
+                artist: "artist".into(),
+                album: "album".into(),
+                song: timestamp.to_string().into(),
+            },
+            timestamp,
+        }
+    }
+
+    // A `Valid` entry of `key`, at `timestamp`.
+    fn valid(key: usize, timestamp: u64) -> HistoryEntry {
+        HistoryEntry {
+            entry: Entry::Valid {
+                key_artist: Default::default(),
+                key_album: Default::default(),
+                key_song: SongKey::from(key),
+                artist: "artist".into(),
+                album: "album".into(),
+                song: key.to_string().into(),
+            },
+            timestamp,
+        }
+    }
+
+    #[test]
+    // Pages are newest first.
+    fn page() {
+        let h = History((0..10).map(invalid).collect());
+
+        let page: Vec<u64> = h.page(0, 3).iter().map(|h| h.timestamp).collect();
+        assert_eq!(page, [9, 8, 7]);
+
+        let page: Vec<u64> = h.page(8, 3).iter().map(|h| h.timestamp).collect();
+        assert_eq!(page, [1, 0]);
+
+        assert!(h.page(10, 3).is_empty());
+    }
+
+    #[test]
+    // `Invalid` entries are skipped.
+    fn valid_from() {
+        let h = History([valid(1, 0), invalid(1), valid(2, 2), invalid(3)].into());
+
+        assert_eq!(h.valid_from(0), Some((1, SongKey::from(2_usize))));
+        assert_eq!(h.valid_from(2), Some((3, SongKey::from(1_usize))));
+        assert_eq!(h.valid_from(4), None);
+        assert_eq!(History::new().valid_from(0), None);
+    }
+}
@@ -12,3 +12,20 @@ pub use phase::*;
 
 mod playlist;
 pub use playlist::*;
+pub(crate) mod v0;
+pub(crate) mod v1;
+pub(crate) mod v2;
+pub(crate) mod v3;
+pub(crate) mod v4;
+
+mod smart_playlist;
+pub use smart_playlist::*;
+
+mod history;
+pub use history::*;
+
+mod queue_snapshot;
+pub use queue_snapshot::*;
+
+mod audio_error;
+pub use audio_error::*;
@@ -11,6 +11,7 @@ const START: &str = "Starting";
 const DECONSTRUCT: &str = "Deconstructing Old Collection";
 const WALKDIR: &str = "Walking Directories";
 const PARSE: &str = "Parsing Metadata";
+const FINGERPRINT: &str = "Fingerprinting Audio";
 const FIX: &str = "Fixing Metadata";
 const SORT: &str = "Sorting";
 const SEARCH: &str = "Creating Search Engine";
@@ -67,6 +68,7 @@ const FINALIZE: &str = "Finalizing Collection";
 /// assert_eq!(Phase::Deconstruct.as_str(), "Deconstructing Old Collection");
 /// assert_eq!(Phase::WalkDir.as_str(),     "Walking Directories");
 /// assert_eq!(Phase::Parse.as_str(),       "Parsing Metadata");
+/// assert_eq!(Phase::Fingerprint.as_str(), "Fingerprinting Audio");
 /// assert_eq!(Phase::Fix.as_str(),         "Fixing Metadata");
 /// assert_eq!(Phase::Sort.as_str(),        "Sorting");
 /// assert_eq!(Phase::Search.as_str(),      "Creating Search Engine");
@@ -94,6 +96,8 @@ pub enum Phase {
     WalkDir,
     /// Phase 4
     Parse,
+    /// Phase 4.5 (optional)
+    Fingerprint,
     /// Phase 5
     Fix,
     /// Phase 6
@@ -124,6 +128,7 @@ impl Phase {
             Self::Deconstruct => DECONSTRUCT,
             Self::WalkDir => WALKDIR,
             Self::Parse => PARSE,
+            Self::Fingerprint => FINGERPRINT,
             Self::Fix => FIX,
             Self::Sort => SORT,
             Self::Search => SEARCH,
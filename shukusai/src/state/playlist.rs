@@ -1,9 +1,12 @@
 //---------------------------------------------------------------------------------------------------- Use
 use crate::{
     audio::Append,
-    collection::{AlbumKey, ArtistKey, Collection, SongKey},
+    collection::{AlbumKey, ArtistKey, Collection, MapKey, SongKey},
     constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, PLAYLIST_VERSION, STATE_SUB_DIR},
+    export::m3u8_field,
+    sort::{cmp_natural, sort_name, PlaylistSort, SORT_ARTICLES},
 };
+use anyhow::bail;
 use benri::{lockr, lockw};
 use bincode::{Decode, Encode};
 use const_format::formatcp;
@@ -12,9 +15,13 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
 use std::{
-    collections::{BTreeMap, VecDeque},
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    io::Write,
+    path::{Component, Path, PathBuf},
     sync::Arc,
 };
+use strum::{AsRefStr, Display, EnumCount, EnumIter, EnumString, EnumVariantNames, IntoStaticStr};
 
 //---------------------------------------------------------------------------------------------------- Lazy
 /// This is the single, global copy of `Playlists` that `Kernel` uses.
@@ -89,8 +96,6 @@ disk::bincode2!(
     Decode,
 )]
 #[serde(rename_all = "snake_case")]
-#[serde(transparent)]
-#[repr(transparent)]
 /// Playlist implementation.
 ///
 /// Contains all user playlists, ordering via `BTreeMap`.
@@ -98,13 +103,21 @@ disk::bincode2!(
 /// Each node in the `BTreeMap` is a `(String, VecDeque)` where
 /// the `String` is the name of the playlist, and the `VecDeque`
 /// contains [`Entry`]'s.
-pub struct Playlists(pub PlaylistsInner);
+///
+/// The 2nd field holds the [`PlaylistMetadata`] of each playlist,
+/// keyed by the same name.
+pub struct Playlists(pub PlaylistsInner, pub PlaylistsMetadata);
 
 /// The internal type of [`Playlists`].
 ///
 /// [`Playlists`] is just a wrapper so methods/traits can be implemented on it.
 pub type PlaylistsInner = BTreeMap<Arc<str>, VecDeque<Entry>>;
 
+/// The metadata half of [`Playlists`].
+///
+/// Keys are the same playlist names found in [`PlaylistsInner`].
+pub type PlaylistsMetadata = BTreeMap<Arc<str>, PlaylistMetadata>;
+
 #[derive(
     Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
 )]
@@ -203,6 +216,75 @@ impl Entry {
             song: Arc::clone(&song.title),
         }
     }
+
+    /// Validate the key (and strings), replace with `Invalid` if needed.
+    ///
+    /// `Invalid` entries that exist in `collection` are turned `Valid`.
+    ///
+    /// Also, clone the `Arc`'s from the `Collection` as to not use more space.
+    pub fn validate(&mut self, collection: &Arc<Collection>) {
+        match self {
+            Entry::Valid {
+                artist,
+                album,
+                song,
+                ..
+            } => {
+                let Some((s, _)) = collection.song(&artist, &album, &song) else {
+                    *self = Entry::Invalid {
+                        artist: Arc::clone(artist),
+                        album: Arc::clone(album),
+                        song: Arc::clone(song),
+                    };
+                    return;
+                };
+
+                // FIXME:
+                // This will cause songs that have the same name
+                // to be invalidated. Songs with the same name in the
+                // same album is not compatible `shukusai` in general.
+                //
+                // These are quite common with `interlude` type of songs
+                // so multiple songs with the same name should be supported...
+                // somehow... eventually... SOMEDAY.
+                //							if *key != s.key {
+                //								*self = Entry::Invalid {
+                //									artist: Arc::clone(artist),
+                //									album: Arc::clone(album),
+                //									song: Arc::clone(song),
+                //								};
+                //								return;
+                //							}
+
+                let (artist, album, song) = collection.walk(s.key);
+                *self = Entry::Valid {
+                    key_artist: artist.key,
+                    key_album: album.key,
+                    key_song: s.key,
+                    artist: Arc::clone(&artist.name),
+                    album: Arc::clone(&album.title),
+                    song: Arc::clone(&song.title),
+                };
+            }
+            Entry::Invalid {
+                artist,
+                album,
+                song,
+            } => {
+                if let Some((s, _)) = collection.song(&artist, &album, &song) {
+                    let (artist, album, song) = collection.walk(s.key);
+                    *self = Entry::Valid {
+                        key_artist: artist.key,
+                        key_album: album.key,
+                        key_song: s.key,
+                        artist: Arc::clone(&artist.name),
+                        album: Arc::clone(&album.title),
+                        song: Arc::clone(&song.title),
+                    };
+                }
+            }
+        }
+    }
 }
 
 impl std::ops::Deref for Playlists {
@@ -223,17 +305,36 @@ impl Playlists {
     //-------------------------------------------------- Construction.
     /// Create an empty `Self` with no allocation.
     pub const fn new() -> Self {
-        Self(BTreeMap::new())
+        Self(BTreeMap::new(), BTreeMap::new())
     }
 
     //-------------------------------------------------- Playlist handling.
     /// Create a new playlist with this name, overwriting if it already exists.
+    ///
+    /// The playlist's [`PlaylistMetadata`] is reset as well.
     pub fn playlist_new(&mut self, s: &str) -> Option<VecDeque<Entry>> {
-        self.insert(s.into(), VecDeque::with_capacity(8))
+        let name: Arc<str> = s.into();
+        self.1
+            .insert(Arc::clone(&name), PlaylistMetadata::new(benri::unix!()));
+        self.insert(name, VecDeque::with_capacity(8))
+    }
+
+    /// Set the playlist with this name to `entries`, overwriting if it already exists.
+    ///
+    /// Unlike [`Playlists::playlist_new`], existing [`PlaylistMetadata`] is kept.
+    pub fn playlist_set(
+        &mut self,
+        s: Arc<str>,
+        entries: VecDeque<Entry>,
+    ) -> Option<VecDeque<Entry>> {
+        let old = self.insert(Arc::clone(&s), entries);
+        self.touch(&s);
+        old
     }
 
     /// Remove the playlist with this name.
     pub fn playlist_remove(&mut self, s: Arc<str>) -> Option<VecDeque<Entry>> {
+        self.1.remove(&s);
         self.remove(&s)
     }
 
@@ -249,7 +350,18 @@ impl Playlists {
     ) -> Result<Option<VecDeque<Entry>>, ()> {
         let vec = self.get(&from).map(|v| v.clone());
         if let Some(vec) = vec {
-            Ok(self.insert(into.into(), vec))
+            let into: Arc<str> = into.into();
+            let now = benri::unix!();
+            let metadata = match self.1.get(&from) {
+                Some(m) => PlaylistMetadata {
+                    created: now,
+                    modified: now,
+                    ..m.clone()
+                },
+                None => PlaylistMetadata::new(now),
+            };
+            self.1.insert(Arc::clone(&into), metadata);
+            Ok(self.insert(into, vec))
         } else {
             Err(())
         }
@@ -283,12 +395,196 @@ impl Playlists {
         playlist: Arc<str>,
     ) -> Result<Option<Entry>, ()> {
         if let Some(p) = self.get_mut(&playlist) {
-            Ok(p.remove(index))
+            let entry = p.remove(index);
+            if entry.is_some() {
+                self.touch(&playlist);
+            }
+            Ok(entry)
+        } else {
+            Err(())
+        }
+    }
+
+    /// Rename the playlist from the 1st input, into the 2nd input.
+    ///
+    /// `Ok(Some(_))` => from existed, into was overwritten
+    /// `Ok(None)`    => from existed, into was created
+    /// `Err(())`     => from did not exist, nothing was renamed
+    pub fn playlist_rename(
+        &mut self,
+        from: Arc<str>,
+        into: &str,
+    ) -> Result<Option<VecDeque<Entry>>, ()> {
+        if let Some(vec) = self.remove(&from) {
+            let into: Arc<str> = into.into();
+            match self.1.remove(&from) {
+                Some(m) => self.1.insert(Arc::clone(&into), m),
+                None => self.1.remove(&into),
+            };
+            let old = self.insert(Arc::clone(&into), vec);
+            self.touch(&into);
+            Ok(old)
         } else {
             Err(())
         }
     }
 
+    /// Move the [`Entry`] at index `from` to index `to` within the playlist `playlist`.
+    ///
+    /// Both indices are relative to the playlist _before_ the move,
+    /// e.g moving `0` to `len - 1` moves the 1st entry to the back.
+    ///
+    /// `Ok(true)`  => playlist existed, entry was moved
+    /// `Ok(false)` => playlist existed, an index was out-of-bounds, nothing was moved
+    /// `Err(())`   => playlist did not exist
+    pub fn playlist_move_index(
+        &mut self,
+        from: usize,
+        to: usize,
+        playlist: Arc<str>,
+    ) -> Result<bool, ()> {
+        let Some(p) = self.get_mut(&playlist) else {
+            return Err(());
+        };
+
+        if from >= p.len() || to >= p.len() {
+            return Ok(false);
+        }
+
+        if let Some(entry) = p.remove(from) {
+            p.insert(to, entry);
+        }
+
+        self.touch(&playlist);
+        Ok(true)
+    }
+
+    /// Insert these songs at index `index` within the playlist `playlist`.
+    ///
+    /// `index` may be equal to the playlist length, which appends to the back.
+    ///
+    /// `Ok(true)`  => playlist existed, songs were inserted
+    /// `Ok(false)` => playlist existed, index was out-of-bounds, nothing was inserted
+    /// `Err(())`   => playlist did not exist
+    ///
+    /// # INVARIANT
+    /// - Assumes keys are not out-of-bounds
+    pub fn playlist_insert_songs(
+        &mut self,
+        index: usize,
+        keys: &[SongKey],
+        playlist: Arc<str>,
+        collection: &Arc<Collection>,
+    ) -> Result<bool, ()> {
+        let Some(p) = self.get_mut(&playlist) else {
+            return Err(());
+        };
+
+        if index > p.len() {
+            return Ok(false);
+        }
+
+        for (i, key) in keys.iter().enumerate() {
+            p.insert(index + i, Entry::valid_from_song(*key, collection));
+        }
+
+        self.touch(&playlist);
+        Ok(true)
+    }
+
+    /// Remove duplicate [`Entry`]'s within the playlist `playlist`.
+    ///
+    /// The first occurrence of each entry is kept, the order is not changed.
+    ///
+    /// `Some(usize)` => playlist existed, this many entries were removed
+    /// `None`        => playlist did not exist
+    pub fn playlist_dedup(&mut self, playlist: Arc<str>) -> Option<usize> {
+        let p = self.get_mut(&playlist)?;
+
+        let old_len = p.len();
+        let mut seen = HashSet::with_capacity(old_len);
+        p.retain(|e| seen.insert(e.clone()));
+
+        let removed = old_len - p.len();
+        if removed != 0 {
+            self.touch(&playlist);
+        }
+        Some(removed)
+    }
+
+    /// Sort the playlist `playlist` with `sort`.
+    ///
+    /// The sort is stable, entries that compare equal keep their order.
+    ///
+    /// Returns `false` if the playlist did not exist.
+    ///
+    /// # INVARIANT
+    /// - Assumes the playlist's validity is already correct
+    pub fn playlist_sort(
+        &mut self,
+        sort: PlaylistSort,
+        playlist: Arc<str>,
+        collection: &Arc<Collection>,
+    ) -> bool {
+        let Some(p) = self.get_mut(&playlist) else {
+            return false;
+        };
+
+        let articles = lockr!(SORT_ARTICLES);
+        let name = |s: &str| sort_name(s, &articles);
+
+        // Compute the keys once, not on every comparison.
+        let mut keyed: Vec<(String, String, Option<u32>, Entry)> = p
+            .drain(..)
+            .map(|e| {
+                let (a, b, runtime) = match (&e, sort) {
+                    (
+                        Entry::Valid { artist, album, .. } | Entry::Invalid { artist, album, .. },
+                        PlaylistSort::Artist | PlaylistSort::ArtistRev,
+                    ) => (name(artist), name(album), None),
+                    (
+                        Entry::Valid { album, .. } | Entry::Invalid { album, .. },
+                        PlaylistSort::Album | PlaylistSort::AlbumRev,
+                    ) => (name(album), String::new(), None),
+                    (
+                        Entry::Valid { song, .. } | Entry::Invalid { song, .. },
+                        PlaylistSort::Title | PlaylistSort::TitleRev,
+                    ) => (name(song), String::new(), None),
+                    (Entry::Valid { key_song, .. }, _) => (
+                        String::new(),
+                        String::new(),
+                        Some(collection.songs[*key_song].runtime.inner()),
+                    ),
+                    (Entry::Invalid { .. }, _) => (String::new(), String::new(), None),
+                };
+                (a, b, runtime, e)
+            })
+            .collect();
+        drop(articles);
+
+        use PlaylistSort as S;
+        match sort {
+            S::Artist | S::Album | S::Title => {
+                keyed.sort_by(|x, y| cmp_natural(&x.0, &y.0).then_with(|| cmp_natural(&x.1, &y.1)))
+            }
+            S::ArtistRev | S::AlbumRev | S::TitleRev => {
+                keyed.sort_by(|x, y| cmp_natural(&y.0, &x.0).then_with(|| cmp_natural(&y.1, &x.1)))
+            }
+            // `None` (missing `Song`'s) are always last.
+            S::Runtime | S::RuntimeRev => keyed.sort_by(|x, y| match (x.2, y.2) {
+                (Some(x), Some(y)) if sort == S::Runtime => x.cmp(&y),
+                (Some(x), Some(y)) => y.cmp(&x),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }),
+        }
+
+        p.extend(keyed.into_iter().map(|(_, _, _, e)| e));
+        self.touch(&playlist);
+        true
+    }
+
     /// Add this artist to this playlist.
     ///
     /// Creates playlist if it did not exist.
@@ -313,7 +609,7 @@ impl Playlists {
 
         let mut existed = true;
 
-        let v = self.entry(playlist).or_insert_with(|| {
+        let v = self.entry(Arc::clone(&playlist)).or_insert_with(|| {
             existed = false;
             VecDeque::with_capacity(keys.len())
         });
@@ -360,7 +656,9 @@ impl Playlists {
             }),
         }
 
-        (existed, old_len, v.len())
+        let new_len = v.len();
+        self.touch(&playlist);
+        (existed, old_len, new_len)
     }
 
     /// Add this album to this playlist.
@@ -386,7 +684,7 @@ impl Playlists {
         let iter = keys.iter();
 
         let mut existed = true;
-        let v = self.entry(playlist).or_insert_with(|| {
+        let v = self.entry(Arc::clone(&playlist)).or_insert_with(|| {
             existed = false;
             VecDeque::with_capacity(keys.len())
         });
@@ -433,7 +731,9 @@ impl Playlists {
             }),
         }
 
-        (existed, old_len, v.len())
+        let new_len = v.len();
+        self.touch(&playlist);
+        (existed, old_len, new_len)
     }
 
     /// Add this song to this playlist.
@@ -467,7 +767,7 @@ impl Playlists {
         };
 
         let mut existed = true;
-        let v = self.entry(playlist).or_insert_with(|| {
+        let v = self.entry(Arc::clone(&playlist)).or_insert_with(|| {
             existed = false;
             VecDeque::with_capacity(8)
         });
@@ -480,7 +780,9 @@ impl Playlists {
             Append::Index(i) => v.insert(i, entry),
         }
 
-        (existed, old_len, v.len())
+        let new_len = v.len();
+        self.touch(&playlist);
+        (existed, old_len, new_len)
     }
 
     //-------------------------------------------------- Misc.
@@ -519,69 +821,9 @@ impl Playlists {
     /// Also, clone the `Arc`'s from the `Collection` as to not use more space.
     pub fn validate(&mut self, collection: &Arc<Collection>) {
         self.0.par_iter_mut().for_each(|(_, entry)| {
-            entry.par_iter_mut().for_each(|entry| {
-                match entry {
-                    Entry::Valid {
-                        artist,
-                        album,
-                        song,
-                        ..
-                    } => {
-                        let Some((s, _)) = collection.song(&artist, &album, &song) else {
-                            *entry = Entry::Invalid {
-                                artist: Arc::clone(artist),
-                                album: Arc::clone(album),
-                                song: Arc::clone(song),
-                            };
-                            return;
-                        };
-
-                        // FIXME:
-                        // This will cause songs that have the same name
-                        // to be invalidated. Songs with the same name in the
-                        // same album is not compatible `shukusai` in general.
-                        //
-                        // These are quite common with `interlude` type of songs
-                        // so multiple songs with the same name should be supported...
-                        // somehow... eventually... SOMEDAY.
-                        //							if *key != s.key {
-                        //								*entry = Entry::Invalid {
-                        //									artist: Arc::clone(artist),
-                        //									album: Arc::clone(album),
-                        //									song: Arc::clone(song),
-                        //								};
-                        //								return;
-                        //							}
-
-                        let (artist, album, song) = collection.walk(s.key);
-                        *entry = Entry::Valid {
-                            key_artist: artist.key,
-                            key_album: album.key,
-                            key_song: s.key,
-                            artist: Arc::clone(&artist.name),
-                            album: Arc::clone(&album.title),
-                            song: Arc::clone(&song.title),
-                        };
-                    }
-                    Entry::Invalid {
-                        artist,
-                        album,
-                        song,
-                    } => {
-                        if let Some((s, _)) = collection.song(&artist, &album, &song) {
-                            let (artist, album, song) = collection.walk(s.key);
-                            *entry = Entry::Valid {
-                                key_artist: artist.key,
-                                key_album: album.key,
-                                key_song: s.key,
-                                artist: Arc::clone(&artist.name),
-                                album: Arc::clone(&album.title),
-                                song: Arc::clone(&song.title),
-                            };
-                        }
-                    }
-                }
-            });
+            entry
+                .par_iter_mut()
+                .for_each(|entry| entry.validate(collection));
         });
     }
 
@@ -665,6 +907,156 @@ impl Playlists {
     }
 }
 
+//---------------------------------------------------------------------------------------------------- Metadata
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+)]
+#[serde(rename_all = "snake_case")]
+/// Metadata about a single playlist.
+///
+/// This lives next to the [`Entry`]'s in [`Playlists`], keyed by the same playlist name.
+pub struct PlaylistMetadata {
+    /// User description of the playlist, empty if none.
+    pub description: String,
+    /// UNIX timestamp of when the playlist was created.
+    pub created: u64,
+    /// UNIX timestamp of the last time the playlist was changed.
+    pub modified: u64,
+    /// Path to an image file used as the playlist's cover art.
+    pub art: Option<PathBuf>,
+    /// Should this playlist be shown before all others?
+    pub pinned: bool,
+    /// The `/` separated folder path this playlist is in, e.g `Rock/80s`.
+    ///
+    /// Empty means the top-level.
+    pub folder: String,
+}
+
+impl PlaylistMetadata {
+    /// Create new metadata, created and modified at the UNIX timestamp `now`.
+    pub fn new(now: u64) -> Self {
+        Self {
+            created: now,
+            modified: now,
+            ..Default::default()
+        }
+    }
+
+    /// Normalize a folder path.
+    ///
+    /// Whitespace around each `/` separated component
+    /// is trimmed and empty components are removed,
+    /// e.g ` Rock// 80s /` => `Rock/80s`.
+    pub fn folder_normalize(folder: &str) -> String {
+        folder
+            .split('/')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<&str>>()
+            .join("/")
+    }
+
+    /// Iterate over the `/` separated components of [`Self::folder`].
+    ///
+    /// The top-level folder has no components.
+    pub fn folder_components(&self) -> impl Iterator<Item = &str> {
+        self.folder.split('/').filter(|s| !s.is_empty())
+    }
+}
+
+impl Playlists {
+    //-------------------------------------------------- Metadata.
+    /// Get the [`PlaylistMetadata`] of the playlist `playlist`.
+    ///
+    /// `None` if the playlist does not exist or has no metadata yet.
+    pub fn metadata(&self, playlist: &str) -> Option<&PlaylistMetadata> {
+        self.1.get(playlist)
+    }
+
+    /// Get mutable access to the [`PlaylistMetadata`] of the playlist `playlist`.
+    ///
+    /// Metadata is created if the playlist exists but has none yet.
+    ///
+    /// This does not update [`PlaylistMetadata::modified`], see [`Playlists::touch`].
+    ///
+    /// `None` if the playlist does not exist.
+    pub fn metadata_mut(&mut self, playlist: &str) -> Option<&mut PlaylistMetadata> {
+        let (name, _) = self.0.get_key_value(playlist)?;
+        Some(
+            self.1
+                .entry(Arc::clone(name))
+                .or_insert_with(|| PlaylistMetadata::new(benri::unix!())),
+        )
+    }
+
+    /// Set [`PlaylistMetadata::modified`] of the playlist `playlist` to now.
+    ///
+    /// Does nothing if the playlist does not exist.
+    pub fn touch(&mut self, playlist: &str) {
+        if let Some(metadata) = self.metadata_mut(playlist) {
+            metadata.modified = benri::unix!();
+        }
+    }
+
+    /// Create [`PlaylistMetadata`] for every playlist that has none, and
+    /// remove the metadata of playlists that no longer exist.
+    pub fn metadata_sync(&mut self) {
+        let now = benri::unix!();
+        let Self(playlists, metadata) = self;
+
+        metadata.retain(|name, _| playlists.contains_key(name));
+        for name in playlists.keys() {
+            metadata
+                .entry(Arc::clone(name))
+                .or_insert_with(|| PlaylistMetadata::new(now));
+        }
+    }
+
+    /// Returns all playlist names in the order they should be displayed.
+    ///
+    /// Pinned playlists come first, the rest are ordered by
+    /// their folder's components, then by their name.
+    ///
+    /// # Return
+    /// - `bool`     => is the playlist pinned?
+    /// - `&str`     => the playlist's [`PlaylistMetadata::folder`]
+    /// - `Arc<str>` => the playlist name
+    pub fn name_arcs_by_folder(&self) -> Vec<(bool, &str, Arc<str>)> {
+        let mut vec: Vec<(bool, Vec<&str>, &str, Arc<str>)> = self
+            .0
+            .keys()
+            .map(|name| match self.1.get(name) {
+                Some(m) if m.pinned => (true, vec![], m.folder.as_str(), Arc::clone(name)),
+                Some(m) => (
+                    false,
+                    m.folder_components().collect(),
+                    m.folder.as_str(),
+                    Arc::clone(name),
+                ),
+                None => (false, vec![], "", Arc::clone(name)),
+            })
+            .collect();
+
+        // Stable, names are already sorted by the `BTreeMap`.
+        vec.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+
+        vec.into_iter()
+            .map(|(pinned, _, folder, name)| (pinned, folder, name))
+            .collect()
+    }
+}
+
 //---------------------------------------------------------------------------------------------------- JSON Representation
 #[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -710,10 +1102,1280 @@ pub enum EntryJson<'a> {
     },
 }
 
+//---------------------------------------------------------------------------------------------------- Import/Export
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    Debug,
+    Hash,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    Display,
+    EnumCount,
+    EnumIter,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+/// Playlist file formats that can be imported and exported.
+///
+/// See [`Playlists::import`] and [`Playlists::export`].
+pub enum PlaylistFormat {
+    #[default]
+    #[strum(to_string = "m3u8", serialize = "m3u")]
+    #[serde(alias = "m3u")]
+    /// `M3U`/`M3U8`, with `#EXTINF` metadata.
+    ///
+    /// `M3U8` is just `M3U` in `UTF-8`, which is what is always written.
+    M3u8,
+    /// `PLS`
+    Pls,
+    /// `XSPF` (XML Shareable Playlist Format)
+    Xspf,
+}
+
+impl PlaylistFormat {
+    /// Guess the format from a file's extension (ASCII case-insensitive).
+    ///
+    /// Both `.m3u` and `.m3u8` map to [`Self::M3u8`].
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "m3u" | "m3u8" => Some(Self::M3u8),
+            "pls" => Some(Self::Pls),
+            "xspf" => Some(Self::Xspf),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    /// The file extension, without the `.`
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::M3u8 => "m3u8",
+            Self::Pls => "pls",
+            Self::Xspf => "xspf",
+        }
+    }
+
+    #[inline]
+    /// The MIME type.
+    pub const fn mime(&self) -> &'static str {
+        match self {
+            Self::M3u8 => "audio/x-mpegurl",
+            Self::Pls => "audio/x-scpls",
+            Self::Xspf => "application/xspf+xml",
+        }
+    }
+}
+
+// A single playlist file entry, before it is resolved against a `Collection`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct FileEntry {
+    path: Option<PathBuf>,
+    artist: Option<String>,
+    album: Option<String>,
+    title: Option<String>,
+}
+
+impl FileEntry {
+    // Set the artist and title from an `Artist - Title` display string.
+    fn display(&mut self, s: &str) {
+        let s = s.trim();
+        if s.is_empty() {
+            return;
+        }
+
+        match s.split_once(" - ") {
+            Some((artist, title)) => {
+                self.artist = Some(artist.trim().to_string());
+                self.title = Some(title.trim().to_string());
+            }
+            None => self.title = Some(s.to_string()),
+        }
+    }
+}
+
+impl Playlists {
+    //-------------------------------------------------- Import/Export.
+    /// Parse the contents of a playlist file and resolve the entries against `collection`.
+    ///
+    /// Each entry is matched by:
+    /// 1. Its path, relative paths are joined onto `base` (usually the playlist file's directory)
+    /// 2. Its `Artist`/`Album`/`Song` metadata if the path did not match
+    ///
+    /// Entries that cannot be found become [`Entry::Invalid`] with whatever metadata
+    /// the file had, or the file name as the `Song` title if it had none.
+    ///
+    /// Entries that are not local files (e.g. `http://` streams) are ignored.
+    pub fn import(
+        s: &str,
+        format: PlaylistFormat,
+        base: Option<&Path>,
+        collection: &Arc<Collection>,
+    ) -> VecDeque<Entry> {
+        let entries = match format {
+            PlaylistFormat::M3u8 => parse_m3u(s),
+            PlaylistFormat::Pls => parse_pls(s),
+            PlaylistFormat::Xspf => parse_xspf(s),
+        };
+
+        let paths: HashMap<&Path, SongKey> = collection
+            .songs
+            .iter()
+            .map(|s| (s.path.as_path(), s.key))
+            .collect();
+
+        entries
+            .into_iter()
+            .filter_map(|e| resolve(e, &paths, base, collection))
+            .collect()
+    }
+
+    /// Read and [`Self::import`] the playlist file at `path`.
+    ///
+    /// The format is guessed from the file extension, relative
+    /// entries are resolved against the file's directory.
+    ///
+    /// # Errors
+    /// This errors if the extension is unknown or the file could not be read.
+    pub fn import_path(
+        path: &Path,
+        collection: &Arc<Collection>,
+    ) -> Result<VecDeque<Entry>, anyhow::Error> {
+        let Some(format) = PlaylistFormat::from_path(path) else {
+            bail!("unknown playlist format: {}", path.display());
+        };
+
+        let bytes = std::fs::read(path)?;
+        let s = String::from_utf8_lossy(&bytes);
+
+        Ok(Self::import(&s, format, path.parent(), collection))
+    }
+
+    /// Write `playlist` as a playlist file in `format`.
+    ///
+    /// - `name` is written as the playlist title where the format supports it
+    /// - If `relative_to` is `Some`, `Song` paths are written relative to it
+    ///   (usually the directory the file will be saved in), else they are absolute
+    ///
+    /// [`Entry::Invalid`]'s have no path, so they are only written in
+    /// [`PlaylistFormat::Xspf`], as tracks with metadata and no location.
+    ///
+    /// The writer is not buffered internally, so wrapping
+    /// it with a [`std::io::BufWriter`] is recommended.
+    pub fn export<W: Write>(
+        name: &str,
+        playlist: &VecDeque<Entry>,
+        format: PlaylistFormat,
+        relative_to: Option<&Path>,
+        collection: &Arc<Collection>,
+        mut writer: W,
+    ) -> std::io::Result<()> {
+        // Returns `(runtime, path)` of a valid entry.
+        let song = |key: &SongKey| {
+            let song = &collection.songs[key];
+            let path = match relative_to {
+                Some(base) => relative_path(&song.path, base),
+                None => song.path.clone(),
+            };
+            (song.runtime.inner(), path)
+        };
+
+        match format {
+            PlaylistFormat::M3u8 => {
+                writeln!(writer, "#EXTM3U\n#PLAYLIST:{}", m3u8_field(name))?;
+                for entry in playlist {
+                    if let Entry::Valid {
+                        key_song,
+                        artist,
+                        album,
+                        song: title,
+                        ..
+                    } = entry
+                    {
+                        let (runtime, path) = song(key_song);
+                        writeln!(
+                            writer,
+                            "#EXTINF:{runtime},{} - {}\n#EXTALB:{}\n{}",
+                            m3u8_field(artist),
+                            m3u8_field(title),
+                            m3u8_field(album),
+                            path.display(),
+                        )?;
+                    }
+                }
+            }
+
+            PlaylistFormat::Pls => {
+                writeln!(writer, "[playlist]")?;
+                let mut i = 0;
+                for entry in playlist {
+                    if let Entry::Valid {
+                        key_song,
+                        artist,
+                        song: title,
+                        ..
+                    } = entry
+                    {
+                        i += 1;
+                        let (runtime, path) = song(key_song);
+                        writeln!(
+                            writer,
+                            "File{i}={}\nTitle{i}={} - {}\nLength{i}={runtime}",
+                            path.display(),
+                            m3u8_field(artist),
+                            m3u8_field(title),
+                        )?;
+                    }
+                }
+                writeln!(writer, "NumberOfEntries={i}\nVersion=2")?;
+            }
+
+            PlaylistFormat::Xspf => {
+                writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+                writeln!(
+                    writer,
+                    r#"<playlist version="1" xmlns="http://xspf.org/ns/0/">"#
+                )?;
+                writeln!(writer, "  <title>{}</title>", xml_escape(name))?;
+                writeln!(writer, "  <trackList>")?;
+                for entry in playlist {
+                    writeln!(writer, "    <track>")?;
+                    let (artist, album, title) = match entry {
+                        Entry::Valid {
+                            key_song,
+                            artist,
+                            album,
+                            song: title,
+                            ..
+                        } => {
+                            let (runtime, path) = song(key_song);
+                            writeln!(
+                                writer,
+                                "      <location>{}</location>\n      <duration>{}</duration>",
+                                xml_escape(&path_to_uri(&path)),
+                                u64::from(runtime) * 1000,
+                            )?;
+                            (artist, album, title)
+                        }
+                        Entry::Invalid {
+                            artist,
+                            album,
+                            song: title,
+                        } => (artist, album, title),
+                    };
+                    writeln!(
+                        writer,
+                        "      <creator>{}</creator>\n      <album>{}</album>\n      <title>{}</title>",
+                        xml_escape(artist),
+                        xml_escape(album),
+                        xml_escape(title),
+                    )?;
+                    writeln!(writer, "    </track>")?;
+                }
+                writeln!(writer, "  </trackList>\n</playlist>")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Find the `Song` a `FileEntry` points to, by path, then by metadata.
+fn resolve(
+    e: FileEntry,
+    paths: &HashMap<&Path, SongKey>,
+    base: Option<&Path>,
+    collection: &Arc<Collection>,
+) -> Option<Entry> {
+    // By path.
+    if let Some(path) = &e.path {
+        let path = match base {
+            Some(base) if path.is_relative() => normalize_path(&base.join(path)),
+            _ => normalize_path(path),
+        };
+        if let Some(key) = paths.get(path.as_path()) {
+            return Some(Entry::valid_from_song(*key, collection));
+        }
+    }
+
+    // By metadata.
+    let key = match (&e.artist, &e.album, &e.title) {
+        (Some(artist), Some(album), Some(title)) => collection
+            .song(artist.as_str(), album.as_str(), title.as_str())
+            .map(|(_, key)| key),
+        (Some(artist), None, Some(title)) => collection.artist(artist).and_then(|(artist, _)| {
+            artist
+                .songs
+                .iter()
+                .find(|key| &*collection.songs[*key].title == title)
+                .copied()
+        }),
+        _ => None,
+    };
+    if let Some(key) = key {
+        return Some(Entry::valid_from_song(key, collection));
+    }
+
+    // Not found.
+    let title = e.title.or_else(|| {
+        e.path
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| s.to_string_lossy().into_owned())
+    })?;
+
+    Some(Entry::Invalid {
+        artist: e.artist.unwrap_or_default().into(),
+        album: e.album.unwrap_or_default().into(),
+        song: title.into(),
+    })
+}
+
+// Parse `M3U`/`M3U8`.
+//
+// `#EXTINF`, `#EXTART` and `#EXTALB` apply to the next path.
+fn parse_m3u(s: &str) -> Vec<FileEntry> {
+    let mut entries = vec![];
+    let mut entry = FileEntry::default();
+
+    for line in s.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+
+        if let Some(info) = line.strip_prefix("#EXTINF:") {
+            // `#EXTINF:<seconds> [attributes],<display>`
+            if let Some((_, display)) = info.split_once(',') {
+                entry.display(display);
+            }
+        } else if let Some(artist) = line.strip_prefix("#EXTART:") {
+            entry.artist = Some(artist.trim().to_string());
+        } else if let Some(album) = line.strip_prefix("#EXTALB:") {
+            entry.album = Some(album.trim().to_string());
+        } else if line.is_empty() || line.starts_with('#') {
+            continue;
+        } else {
+            entry.path = location(line, false);
+            if entry.path.is_some() {
+                entries.push(std::mem::take(&mut entry));
+            } else {
+                entry = FileEntry::default();
+            }
+        }
+    }
+
+    entries
+}
+
+// Parse `PLS`.
+//
+// Entries are ordered by their `N` in `FileN`, not by line order.
+fn parse_pls(s: &str) -> Vec<FileEntry> {
+    let mut entries: BTreeMap<usize, FileEntry> = BTreeMap::new();
+
+    for line in s.trim_start_matches('\u{feff}').lines() {
+        let Some((key, value)) = line.trim().split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+
+        let (field, index) = if let Some(i) = key.strip_prefix("file") {
+            ("file", i)
+        } else if let Some(i) = key.strip_prefix("title") {
+            ("title", i)
+        } else {
+            continue;
+        };
+        let Ok(index) = index.parse::<usize>() else {
+            continue;
+        };
+
+        let entry = entries.entry(index).or_default();
+        match field {
+            "file" => entry.path = location(value.trim(), false),
+            _ => entry.display(value),
+        }
+    }
+
+    entries.into_values().filter(|e| e.path.is_some()).collect()
+}
+
+// Parse `XSPF`.
+//
+// This is not a real XML parser, it only looks at
+// the `<location>`, `<creator>`, `<album>` and `<title>`
+// of each `<track>`, which is all we need.
+fn parse_xspf(s: &str) -> Vec<FileEntry> {
+    let mut entries = vec![];
+    let mut rest = s;
+
+    while let Some(start) = rest.find("<track>") {
+        rest = &rest[start + "<track>".len()..];
+        let end = rest.find("</track>").unwrap_or(rest.len());
+        let track = &rest[..end];
+        rest = &rest[end..];
+
+        let path = match xml_tag(track, "location") {
+            Some(l) => match location(&l, true) {
+                Some(p) => Some(p),
+                // Not a local file.
+                None => continue,
+            },
+            None => None,
+        };
+
+        entries.push(FileEntry {
+            path,
+            artist: xml_tag(track, "creator"),
+            album: xml_tag(track, "album"),
+            title: xml_tag(track, "title"),
+        });
+    }
+
+    entries
+}
+
+// The unescaped, trimmed text inside the first `<tag>...</tag>` in `s`.
+fn xml_tag(s: &str, tag: &str) -> Option<String> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let start = s.find(&open)? + open.len();
+    let end = start + s[start..].find(&close)?;
+    let text = xml_unescape(s[start..end].trim());
+    (!text.is_empty()).then_some(text)
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out += "&amp;",
+            '<' => out += "&lt;",
+            '>' => out += "&gt;",
+            '"' => out += "&quot;",
+            '\'' => out += "&apos;",
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// Turn a playlist file location (a path or a URI) into a path.
+//
+// `uri` means relative locations are (percent-encoded) URIs as well.
+//
+// Returns `None` for non-local URIs, e.g. `http://`.
+fn location(s: &str, uri: bool) -> Option<PathBuf> {
+    if let Some(uri) = s.strip_prefix("file://") {
+        // `file://localhost/path` == `file:///path`
+        let uri = uri.strip_prefix("localhost").unwrap_or(uri);
+        let path = percent_decode(uri);
+
+        // `file:///C:/path` on Windows.
+        #[cfg(windows)]
+        let path = match path.as_bytes() {
+            [b'/', _, b':', ..] => path[1..].to_string(),
+            _ => path,
+        };
+
+        return Some(PathBuf::from(path));
+    }
+
+    // Some other scheme, e.g. `http://`.
+    if s.contains("://") {
+        return None;
+    }
+
+    if uri {
+        Some(PathBuf::from(percent_decode(s)))
+    } else {
+        Some(PathBuf::from(s))
+    }
+}
+
+// Turn a path into a `file://` URI (or a relative URI).
+fn path_to_uri(path: &Path) -> String {
+    let s = path.to_string_lossy().replace('\\', "/");
+    let encoded = percent_encode(&s);
+
+    if !path.is_absolute() {
+        encoded
+    } else if encoded.starts_with('/') {
+        format!("file://{encoded}")
+    } else {
+        // `C:/path` on Windows.
+        format!("file:///{encoded}")
+    }
+}
+
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                out.push(b as char)
+            }
+            b => out += &format!("%{b:02X}"),
+        }
+    }
+    out
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .and_then(|h| std::str::from_utf8(h).ok())
+                .and_then(|h| u8::from_str_radix(h, 16).ok());
+            if let Some(b) = hex {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// Remove `.` and resolve `..` without touching the filesystem.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push(c);
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// `path` relative to the directory `base`.
+//
+// If they share no root (e.g. different Windows drives), `path` is returned as is.
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let mut path_iter = path.components().peekable();
+    let mut base_iter = base.components().peekable();
+
+    match (path_iter.peek(), base_iter.peek()) {
+        (Some(a), Some(b)) if a == b => (),
+        _ => return path.to_path_buf(),
+    }
+
+    while let (Some(a), Some(b)) = (path_iter.peek(), base_iter.peek()) {
+        if a != b {
+            break;
+        }
+        path_iter.next();
+        base_iter.next();
+    }
+
+    let mut out = PathBuf::new();
+    for _ in base_iter {
+        out.push(Component::ParentDir);
+    }
+    for c in path_iter {
+        out.push(c);
+    }
+    out
+}
+
+//---------------------------------------------------------------------------------------------------- Repair
+/// The default [`Playlists::repair`] confidence threshold.
+///
+/// Matches scoring at least this are applied automatically.
+pub const REPAIR_THRESHOLD: f64 = 0.9;
+
+// Matches scoring less than this are not proposed at all.
+const REPAIR_MINIMUM: f64 = 0.5;
+
+// How many seconds of runtime difference it takes
+// for a [`RepairHint::runtime`] to not match at all.
+const REPAIR_RUNTIME_SECONDS: f64 = 30.0;
+
+// The weights of each part of a [`Playlists::repair`] score.
+const REPAIR_WEIGHT_TITLE: f64 = 4.0;
+const REPAIR_WEIGHT_ALBUM: f64 = 2.0;
+const REPAIR_WEIGHT_ARTIST: f64 = 2.0;
+const REPAIR_WEIGHT_RUNTIME: f64 = 1.0;
+const REPAIR_WEIGHT_TRACK: f64 = 1.0;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+/// What an [`Entry::Invalid`] used to be, other than its strings.
+///
+/// These make [`Playlists::repair`] more accurate, if known,
+/// e.g when the `Song` was re-tagged during a `Collection` reset.
+pub struct RepairHint {
+    /// The `Song`'s runtime in seconds.
+    pub runtime: Option<u32>,
+    /// The `Song`'s track number.
+    pub track: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A proposed match for an [`Entry::Invalid`], from [`Playlists::repair`].
+pub struct Repair {
+    /// The name of the playlist the entry is in.
+    pub playlist: Arc<str>,
+    /// The index of the entry within the playlist.
+    pub index: usize,
+    /// The [`Entry::Invalid`] being repaired.
+    pub from: Entry,
+    /// The best matching [`Entry::Valid`].
+    pub to: Entry,
+    /// How confident this match is, `0.0..=1.0`.
+    pub score: f64,
+    /// Was this repair already applied?
+    pub applied: bool,
+}
+
+impl Playlists {
+    //-------------------------------------------------- Repair.
+    /// Find the best matching `Song` for every [`Entry::Invalid`] in every playlist.
+    ///
+    /// Matches are scored by how similar the `Artist` name, `Album` title and `Song`
+    /// title are (the same way `Search` is), and the runtime and track number if
+    /// there is a [`RepairHint`] for the entry in `hints`.
+    ///
+    /// Matches with a score of at least `threshold` are applied.
+    ///
+    /// Every match is returned, in playlist order, with [`Repair::applied`]
+    /// set for the applied ones, the rest are left for review, see [`Self::repair_apply`].
+    pub fn repair(
+        &mut self,
+        threshold: f64,
+        hints: &HashMap<MapKey, RepairHint>,
+        collection: &Arc<Collection>,
+    ) -> Vec<Repair> {
+        let names = self.name_arcs();
+        let mut repairs = vec![];
+        for name in names {
+            repairs.append(&mut self.repair_playlist(threshold, hints, name, collection));
+        }
+        repairs
+    }
+
+    /// Same as [`Self::repair`], but only for the playlist `playlist`.
+    ///
+    /// This returns an empty `Vec` if the playlist does not exist.
+    pub fn repair_playlist(
+        &mut self,
+        threshold: f64,
+        hints: &HashMap<MapKey, RepairHint>,
+        playlist: Arc<str>,
+        collection: &Arc<Collection>,
+    ) -> Vec<Repair> {
+        let Some(p) = self.get_mut(&playlist) else {
+            return vec![];
+        };
+
+        // The same `Song` is usually invalid many times
+        // across playlists, only find its match once.
+        let mut cache: HashMap<MapKey, Option<(SongKey, f64)>> = HashMap::new();
+
+        let mut repairs = vec![];
+        for (index, entry) in p.iter_mut().enumerate() {
+            let Entry::Invalid {
+                artist,
+                album,
+                song,
+            } = entry
+            else {
+                continue;
+            };
+
+            let key = MapKey {
+                artist: Arc::clone(artist),
+                album: Arc::clone(album),
+                song: Arc::clone(song),
+            };
+
+            let found = match cache.get(&key) {
+                Some(found) => *found,
+                None => {
+                    let found = repair_match(&key, hints.get(&key), collection);
+                    cache.insert(key, found);
+                    found
+                }
+            };
+
+            let Some((key, score)) = found.filter(|(_, score)| *score >= REPAIR_MINIMUM) else {
+                continue;
+            };
+
+            let to = Entry::valid_from_song(key, collection);
+            let applied = score >= threshold;
+            let from = if applied {
+                std::mem::replace(entry, to.clone())
+            } else {
+                entry.clone()
+            };
+
+            repairs.push(Repair {
+                playlist: Arc::clone(&playlist),
+                index,
+                from,
+                to,
+                score,
+                applied,
+            });
+        }
+
+        repairs
+    }
+
+    /// Replace the [`Entry::Invalid`] at index `index` within the playlist `playlist` with the `Song` `key`.
+    ///
+    /// This is used to accept a [`Repair`] that was not applied.
+    ///
+    /// `Ok(true)`  => playlist existed, entry was replaced
+    /// `Ok(false)` => playlist existed, index was out-of-bounds or the entry was not `Invalid`
+    /// `Err(())`   => playlist did not exist
+    ///
+    /// # INVARIANT
+    /// - Assumes key is not out-of-bounds
+    pub fn repair_apply(
+        &mut self,
+        index: usize,
+        key: SongKey,
+        playlist: Arc<str>,
+        collection: &Arc<Collection>,
+    ) -> Result<bool, ()> {
+        let Some(p) = self.get_mut(&playlist) else {
+            return Err(());
+        };
+
+        match p.get_mut(index) {
+            Some(entry @ Entry::Invalid { .. }) => {
+                *entry = Entry::valid_from_song(key, collection);
+                self.touch(&playlist);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+// Find the best matching `Song` for an invalid entry, and its score.
+//
+// `None` if the `Collection` is empty.
+fn repair_match(
+    key: &MapKey,
+    hint: Option<&RepairHint>,
+    collection: &Arc<Collection>,
+) -> Option<(SongKey, f64)> {
+    let artist = key.artist.to_lowercase();
+    let album = key.album.to_lowercase();
+    let song = key.song.to_lowercase();
+
+    // Empty strings (e.g imported without an `Album`)
+    // say nothing about the match, so they are skipped.
+    let score = |input: &str, other: &str| -> Option<f64> {
+        if input.is_empty() {
+            None
+        } else {
+            Some(strsim::jaro(input, other))
+        }
+    };
+
+    // Score each `Artist`/`Album` once, not once per `Song`.
+    let artists: Vec<Option<f64>> = collection
+        .artists
+        .iter()
+        .map(|a| score(&artist, &a.name_lowercase))
+        .collect();
+    let albums: Vec<Option<f64>> = collection
+        .albums
+        .iter()
+        .map(|a| score(&album, &a.title_lowercase))
+        .collect();
+
+    collection
+        .songs
+        .0
+        .par_iter()
+        .map(|s| {
+            let album = &collection.albums[s.album];
+
+            let parts = [
+                (REPAIR_WEIGHT_TITLE, score(&song, &s.title_lowercase)),
+                (REPAIR_WEIGHT_ALBUM, albums[s.album.inner()]),
+                (REPAIR_WEIGHT_ARTIST, artists[album.artist.inner()]),
+                (
+                    REPAIR_WEIGHT_RUNTIME,
+                    hint.and_then(|h| h.runtime).map(|r| {
+                        let diff = (f64::from(r) - f64::from(s.runtime.inner())).abs();
+                        1.0 - (diff / REPAIR_RUNTIME_SECONDS).min(1.0)
+                    }),
+                ),
+                (
+                    REPAIR_WEIGHT_TRACK,
+                    hint.and_then(|h| h.track)
+                        .map(|t| if Some(t) == s.track { 1.0 } else { 0.0 }),
+                ),
+            ];
+
+            let (total, weights) = parts
+                .into_iter()
+                .filter_map(|(w, s)| s.map(|s| (w * s, w)))
+                .fold((0.0, 0.0), |(t, w), (s, ws)| (t + s, w + ws));
+
+            let score = if weights == 0.0 { 0.0 } else { total / weights };
+            (s.key, score)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+}
+
 //---------------------------------------------------------------------------------------------------- TESTS
-//#[cfg(test)]
-//mod tests {
-//	#[test]
-//		fn __TEST__() {
-//	}
-//}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn m3u() {
+        let s = "\
+#EXTM3U
+#EXTINF:252,Artist - Title
+#EXTALB:Album
+/music/a.flac
+
+#EXTINF:-1 tvg-id=\"x\",Only Title
+relative/b.mp3
+http://example.com/stream
+file:///music/c%20d.ogg
+";
+        assert_eq!(
+            parse_m3u(s),
+            [
+                FileEntry {
+                    path: Some("/music/a.flac".into()),
+                    artist: Some("Artist".into()),
+                    album: Some("Album".into()),
+                    title: Some("Title".into()),
+                },
+                FileEntry {
+                    path: Some("relative/b.mp3".into()),
+                    title: Some("Only Title".into()),
+                    ..Default::default()
+                },
+                FileEntry {
+                    path: Some("/music/c d.ogg".into()),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn pls() {
+        let s = "\
+[playlist]
+File2=/music/b.mp3
+File1=/music/a.flac
+Title1=Artist - Title
+Length1=252
+NumberOfEntries=2
+Version=2
+";
+        assert_eq!(
+            parse_pls(s),
+            [
+                FileEntry {
+                    path: Some("/music/a.flac".into()),
+                    artist: Some("Artist".into()),
+                    title: Some("Title".into()),
+                    ..Default::default()
+                },
+                FileEntry {
+                    path: Some("/music/b.mp3".into()),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn xspf() {
+        let s = r#"<?xml version="1.0" encoding="UTF-8"?>
+<playlist version="1" xmlns="http://xspf.org/ns/0/">
+  <title>Playlist</title>
+  <trackList>
+    <track>
+      <location>file:///music/a%20b.flac</location>
+      <creator>Artist &amp; Artist</creator>
+      <album>Album</album>
+      <title>Title</title>
+    </track>
+    <track>
+      <location>http://example.com/stream</location>
+    </track>
+    <track>
+      <creator>Artist</creator>
+      <title>Missing</title>
+    </track>
+  </trackList>
+</playlist>"#;
+        assert_eq!(
+            parse_xspf(s),
+            [
+                FileEntry {
+                    path: Some("/music/a b.flac".into()),
+                    artist: Some("Artist & Artist".into()),
+                    album: Some("Album".into()),
+                    title: Some("Title".into()),
+                },
+                FileEntry {
+                    artist: Some("Artist".into()),
+                    title: Some("Missing".into()),
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            normalize_path(Path::new("/music/./a/../b.flac")),
+            Path::new("/music/b.flac")
+        );
+        assert_eq!(
+            relative_path(Path::new("/music/a/b.flac"), Path::new("/music/c")),
+            Path::new("../a/b.flac")
+        );
+        assert_eq!(
+            relative_path(Path::new("/music/a/b.flac"), Path::new("/music")),
+            Path::new("a/b.flac")
+        );
+        assert_eq!(
+            path_to_uri(Path::new("/a b/ü.flac")),
+            "file:///a%20b/%C3%BC.flac"
+        );
+        assert_eq!(
+            location("file:///a%20b/%C3%BC.flac", false),
+            Some("/a b/ü.flac".into())
+        );
+    }
+
+    #[test]
+    // Entries are found by path, or become `Invalid`.
+    fn import_export() {
+        use crate::collection::{Album, Artist, Song};
+
+        let c = Arc::new(Collection::test(
+            vec![Artist {
+                name: "Artist".into(),
+                ..Default::default()
+            }],
+            vec![Album {
+                title: "Album".into(),
+                ..Default::default()
+            }],
+            vec![Song {
+                title: "Title".into(),
+                path: "/music/a.flac".into(),
+                ..Default::default()
+            }],
+        ));
+
+        let s = "../music/./a.flac\n#EXTINF:1,Missing Artist - Missing\n/music/missing.flac\n";
+        let playlist = Playlists::import(s, PlaylistFormat::M3u8, Some(Path::new("/other")), &c);
+        assert_eq!(
+            playlist,
+            [
+                Entry::valid_from_song(SongKey::zero(), &c),
+                Entry::Invalid {
+                    artist: "Missing Artist".into(),
+                    album: "".into(),
+                    song: "Missing".into(),
+                },
+            ]
+        );
+
+        let mut s = vec![];
+        Playlists::export(
+            "",
+            &playlist,
+            PlaylistFormat::M3u8,
+            Some(Path::new("/other")),
+            &c,
+            &mut s,
+        )
+        .unwrap();
+        let s = String::from_utf8(s).unwrap();
+        assert!(s.contains("#EXTINF:0,Artist - Title\n#EXTALB:Album\n../music/a.flac\n"));
+        assert!(!s.contains("Missing"));
+    }
+
+    #[test]
+    fn edit() {
+        let c = Arc::new(Collection::new());
+        let e = |song: &str| Entry::Invalid {
+            artist: "".into(),
+            album: "".into(),
+            song: song.into(),
+        };
+
+        let mut p = Playlists::new();
+        let name: Arc<str> = "a".into();
+        p.insert(Arc::clone(&name), [e("3"), e("1"), e("10"), e("1")].into());
+
+        // Dedup.
+        assert_eq!(p.playlist_dedup(Arc::clone(&name)), Some(1));
+        assert_eq!(p[&name], [e("3"), e("1"), e("10")]);
+        assert_eq!(p.playlist_dedup("b".into()), None);
+
+        // Move.
+        assert_eq!(p.playlist_move_index(0, 2, Arc::clone(&name)), Ok(true));
+        assert_eq!(p[&name], [e("1"), e("10"), e("3")]);
+        assert_eq!(p.playlist_move_index(0, 3, Arc::clone(&name)), Ok(false));
+        assert_eq!(p.playlist_move_index(0, 0, "b".into()), Err(()));
+
+        // Sort, natural and stable.
+        assert!(p.playlist_sort(PlaylistSort::Title, Arc::clone(&name), &c));
+        assert_eq!(p[&name], [e("1"), e("3"), e("10")]);
+        assert!(p.playlist_sort(PlaylistSort::TitleRev, Arc::clone(&name), &c));
+        assert_eq!(p[&name], [e("10"), e("3"), e("1")]);
+        assert!(p.playlist_sort(PlaylistSort::Runtime, Arc::clone(&name), &c));
+        assert_eq!(p[&name], [e("10"), e("3"), e("1")]);
+        assert!(!p.playlist_sort(PlaylistSort::Title, "b".into(), &c));
+
+        // Insert.
+        assert_eq!(
+            p.playlist_insert_songs(4, &[], Arc::clone(&name), &c),
+            Ok(false)
+        );
+        assert_eq!(
+            p.playlist_insert_songs(3, &[], Arc::clone(&name), &c),
+            Ok(true)
+        );
+        assert_eq!(p.playlist_insert_songs(0, &[], "b".into(), &c), Err(()));
+
+        // Rename.
+        assert_eq!(p.playlist_rename(Arc::clone(&name), "b"), Ok(None));
+        assert!(!p.contains_key(&name));
+        assert_eq!(p["b"], [e("10"), e("3"), e("1")]);
+        assert_eq!(p.playlist_rename(name, "b"), Err(()));
+    }
+
+    #[test]
+    fn metadata() {
+        let mut p = Playlists::new();
+        let a: Arc<str> = "a".into();
+
+        // New playlists get metadata.
+        p.playlist_new("a");
+        assert_eq!(
+            p.metadata("a").unwrap().created,
+            p.metadata("a").unwrap().modified
+        );
+        let m = p.metadata_mut("a").unwrap();
+        m.description = "desc".into();
+        m.folder = PlaylistMetadata::folder_normalize(" Rock// 80s /");
+        assert_eq!(m.folder, "Rock/80s");
+        assert_eq!(PlaylistMetadata::folder_normalize(" / "), "");
+        assert!(p.metadata_mut("b").is_none());
+
+        // Clone copies it, rename moves it, remove drops it.
+        p.playlist_clone(Arc::clone(&a), "b").unwrap();
+        assert_eq!(p.metadata("b").unwrap().description, "desc");
+        p.playlist_rename("b".into(), "c").unwrap();
+        assert!(p.metadata("b").is_none());
+        assert_eq!(p.metadata("c").unwrap().folder, "Rock/80s");
+        p.playlist_remove("c".into());
+        assert!(p.metadata("c").is_none());
+
+        // Sync fills missing and drops orphaned metadata.
+        p.insert("d".into(), VecDeque::new());
+        p.1.insert("e".into(), PlaylistMetadata::default());
+        p.metadata_sync();
+        assert!(p.metadata("d").is_some());
+        assert!(p.metadata("e").is_none());
+
+        // Pinned first, then by folder, then by name.
+        p.playlist_new("f");
+        p.metadata_mut("f").unwrap().folder = "Rock".into();
+        p.playlist_new("g");
+        p.metadata_mut("g").unwrap().folder = "Rock 2".into();
+        p.playlist_new("h");
+        p.metadata_mut("h").unwrap().pinned = true;
+        let arc = |s: &str| -> Arc<str> { s.into() };
+        assert_eq!(
+            p.name_arcs_by_folder(),
+            [
+                (true, "", arc("h")),
+                (false, "", arc("d")),
+                (false, "Rock", arc("f")),
+                (false, "Rock/80s", arc("a")),
+                (false, "Rock 2", arc("g")),
+            ]
+        );
+
+        // `v0` has no metadata, it is created on conversion.
+        let v0 = crate::state::v0::Playlists([(Arc::clone(&a), VecDeque::new())].into());
+        let p: Playlists = v0.into();
+        assert!(p.contains_key(&a));
+        assert!(p.metadata(&a).is_some());
+    }
+
+    #[test]
+    fn repair() {
+        use crate::collection::{Album, Artist, Song};
+
+        let song = |i: usize, title: &str, track: u32| Song {
+            key: SongKey::from(i),
+            title: title.into(),
+            title_lowercase: title.to_lowercase().into(),
+            track: Some(track),
+            ..Default::default()
+        };
+        let c = Arc::new(Collection::test(
+            vec![Artist {
+                name: "Artist".into(),
+                name_lowercase: "artist".into(),
+                ..Default::default()
+            }],
+            vec![Album {
+                title: "Album".into(),
+                title_lowercase: "album".into(),
+                ..Default::default()
+            }],
+            vec![
+                song(0, "Title", 1),
+                song(1, "Intro", 1),
+                song(2, "Intro", 2),
+            ],
+        ));
+
+        let e = |artist: &str, album: &str, song: &str| Entry::Invalid {
+            artist: artist.into(),
+            album: album.into(),
+            song: song.into(),
+        };
+
+        let mut p = Playlists::new();
+        p.insert(
+            "a".into(),
+            [
+                e("Artist", "Album", "Titel"),
+                e("Nobody", "Nothing", "Zzz"),
+                e("Artist", "Album", "Intro"),
+            ]
+            .into(),
+        );
+
+        // A typo is applied, no match at all is not proposed,
+        // equal matches pick the first `Song`.
+        let repairs = p.repair(1.0, &HashMap::new(), &c);
+        assert_eq!(repairs.len(), 2);
+        assert_eq!(repairs[0].index, 0);
+        assert_eq!(
+            repairs[0].to,
+            Entry::valid_from_song(SongKey::from(0_usize), &c)
+        );
+        assert!(repairs[0].score > 0.9 && repairs[0].score < 1.0);
+        assert!(!repairs[0].applied);
+        assert_eq!(repairs[1].index, 2);
+        assert_eq!(
+            repairs[1].to,
+            Entry::valid_from_song(SongKey::from(1_usize), &c)
+        );
+        assert!(repairs[1].applied);
+        assert_eq!(
+            p["a"][2],
+            Entry::valid_from_song(SongKey::from(1_usize), &c)
+        );
+
+        // Accept the one left for review.
+        assert_eq!(
+            p.repair_apply(0, SongKey::from(0_usize), "a".into(), &c),
+            Ok(true)
+        );
+        assert_eq!(
+            p["a"][0],
+            Entry::valid_from_song(SongKey::from(0_usize), &c)
+        );
+        assert_eq!(
+            p.repair_apply(0, SongKey::from(0_usize), "a".into(), &c),
+            Ok(false)
+        );
+        assert_eq!(
+            p.repair_apply(3, SongKey::from(0_usize), "a".into(), &c),
+            Ok(false)
+        );
+        assert_eq!(
+            p.repair_apply(0, SongKey::from(0_usize), "b".into(), &c),
+            Err(())
+        );
+
+        // Hints pick between equal matches.
+        p.insert("b".into(), [e("Artist", "Album", "Intro")].into());
+        let key = MapKey {
+            artist: "Artist".into(),
+            album: "Album".into(),
+            song: "Intro".into(),
+        };
+        let hint = RepairHint {
+            runtime: None,
+            track: Some(2),
+        };
+        let repairs = p.repair_playlist(
+            REPAIR_THRESHOLD,
+            &HashMap::from([(key, hint)]),
+            "b".into(),
+            &c,
+        );
+        assert_eq!(repairs.len(), 1);
+        assert_eq!(repairs[0].score, 1.0);
+        assert_eq!(
+            p["b"][0],
+            Entry::valid_from_song(SongKey::from(2_usize), &c)
+        );
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("a.M3U")),
+            Some(PlaylistFormat::M3u8)
+        );
+        assert_eq!(
+            PlaylistFormat::from_path(Path::new("a.xspf")),
+            Some(PlaylistFormat::Xspf)
+        );
+        assert_eq!(PlaylistFormat::from_path(Path::new("a.txt")), None);
+        assert_eq!(
+            "m3u".parse::<PlaylistFormat>().unwrap(),
+            PlaylistFormat::M3u8
+        );
+        assert_eq!(PlaylistFormat::M3u8.to_string(), "m3u8");
+    }
+}
@@ -0,0 +1,286 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{Collection, SongKey},
+    constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, QUEUE_SNAPSHOT_VERSION, STATE_SUB_DIR},
+    state::{AudioState, Entry, EntryJson},
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+//---------------------------------------------------------------------------------------------------- Lazy
+/// This is the single, global copy of `QueueSnapshots` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `QUEUE_SNAPSHOTS.read()`.
+pub static QUEUE_SNAPSHOTS: QueueSnapshotsLock =
+    QueueSnapshotsLock(RwLock::new(QueueSnapshots::new()));
+
+//---------------------------------------------------------------------------------------------------- QueueSnapshotsLock
+/// There is only a single, global copy of `QueueSnapshots` that `Kernel` uses: [`QUEUE_SNAPSHOTS`].
+///
+/// To obtain a read-only lock, use `QUEUE_SNAPSHOTS.read()`.
+pub struct QueueSnapshotsLock(RwLock<QueueSnapshots>);
+
+impl QueueSnapshotsLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`QueueSnapshots`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, QueueSnapshots> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`QueueSnapshots`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<
+        RwLockReadGuard<'static, QueueSnapshots>,
+        TryLockError<RwLockReadGuard<'static, QueueSnapshots>>,
+    > {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    /// Obtain a write lock to the global [`QueueSnapshots`].
+    pub fn write(&'static self) -> RwLockWriteGuard<'static, QueueSnapshots> {
+        lockw!(self.0)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- QueueSnapshots
+disk::bincode2!(
+    QueueSnapshots,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "queue_snapshots",
+    HEADER,
+    QUEUE_SNAPSHOT_VERSION
+);
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+)]
+#[serde(transparent)]
+#[repr(transparent)]
+/// Named copies of the queue, so it can be parked and come back to later.
+///
+/// Each node in the `BTreeMap` is the name of
+/// the snapshot and the [`QueueSnapshot`] itself.
+///
+/// Like [`crate::state::Playlists`], each `Song` is an [`Entry`], so
+/// snapshots survive [`Collection`] resets, `Song`'s that no longer
+/// exist are kept as [`Entry::Invalid`] and skipped when restoring.
+pub struct QueueSnapshots(pub BTreeMap<Arc<str>, QueueSnapshot>);
+
+#[derive(
+    Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+/// A saved copy of the [`AudioState`] queue.
+pub struct QueueSnapshot {
+    /// The `Song`'s in the queue
+    pub queue: VecDeque<Entry>,
+    /// The index of the `Song` that was playing, if any
+    pub queue_idx: Option<usize>,
+    /// How many seconds into that `Song` we were
+    pub elapsed: u32,
+    /// The UNIX timestamp this snapshot was saved at
+    pub created: u64,
+}
+
+impl std::ops::Deref for QueueSnapshots {
+    type Target = BTreeMap<Arc<str>, QueueSnapshot>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl QueueSnapshots {
+    #[inline]
+    /// Create an empty `Self` with no allocation.
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// INVARIANT: assumes the keys in `state` are valid.
+    ///
+    /// Save the queue, index and elapsed time of `state` as `name`.
+    ///
+    /// This overwrites any snapshot with the same name, and returns it.
+    pub fn insert(
+        &mut self,
+        name: Arc<str>,
+        state: &AudioState,
+        created: u64,
+        collection: &Arc<Collection>,
+    ) -> Option<QueueSnapshot> {
+        let snapshot = QueueSnapshot {
+            queue: state
+                .queue
+                .iter()
+                .map(|k| Entry::valid_from_song(*k, collection))
+                .collect(),
+            queue_idx: state.queue_idx,
+            elapsed: state.elapsed.inner(),
+            created,
+        };
+
+        self.0.insert(name, snapshot)
+    }
+
+    /// Delete the snapshot `name`, returning it if it existed.
+    pub fn remove(&mut self, name: &str) -> Option<QueueSnapshot> {
+        self.0.remove(name)
+    }
+
+    /// Validate all entries against `collection`, see [`Entry::validate`].
+    pub fn validate(&mut self, collection: &Arc<Collection>) {
+        self.0
+            .par_iter_mut()
+            .for_each(|(_, s)| s.queue.iter_mut().for_each(|e| e.validate(collection)));
+    }
+}
+
+impl QueueSnapshot {
+    /// Returns the valid `Song`'s in this snapshot, the index
+    /// to start at within them, and the elapsed seconds.
+    ///
+    /// [`Entry::Invalid`]'s are skipped, the index is remapped to
+    /// point at the same `Song` it did before. If that `Song` is
+    /// the one that went invalid, the index points to the next valid
+    /// `Song` (or the last one) and the elapsed time is reset to `0`.
+    ///
+    /// The index is `None` if there are no valid `Song`'s.
+    pub fn restore(&self) -> (VecDeque<SongKey>, Option<usize>, u32) {
+        let mut queue = VecDeque::with_capacity(self.queue.len());
+        let mut queue_idx = None;
+        let mut elapsed = 0;
+
+        for (i, entry) in self.queue.iter().enumerate() {
+            let is_current = self.queue_idx == Some(i);
+
+            if let Entry::Valid { key_song, .. } = entry {
+                if is_current {
+                    queue_idx = Some(queue.len());
+                    elapsed = self.elapsed;
+                }
+                queue.push_back(*key_song);
+            } else if is_current {
+                queue_idx = Some(queue.len());
+            }
+        }
+
+        let queue_idx = match queue.len() {
+            0 => None,
+            len => queue_idx.map(|i: usize| i.min(len - 1)),
+        };
+
+        (queue, queue_idx, elapsed)
+    }
+
+    /// How many `Song`'s in this snapshot are [`Entry::Valid`].
+    pub fn valid_len(&self) -> usize {
+        crate::state::Playlists::valid_len(&self.queue)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- JSON Representation
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+#[repr(transparent)]
+/// Stable `JSON` representation of [`QueueSnapshots`].
+pub struct QueueSnapshotsJson<'a>(
+    #[serde(borrow)] pub BTreeMap<Cow<'a, str>, QueueSnapshotJson<'a>>,
+);
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+/// Stable `JSON` representation of [`QueueSnapshot`].
+pub struct QueueSnapshotJson<'a> {
+    #[serde(borrow)]
+    /// The `Song`'s in the queue
+    pub queue: VecDeque<EntryJson<'a>>,
+    /// The index of the `Song` that was playing, if any
+    pub queue_idx: Option<usize>,
+    /// How many seconds into that `Song` we were
+    pub elapsed: u32,
+    /// The UNIX timestamp this snapshot was saved at
+    pub created: u64,
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An `Invalid` entry.
+    fn invalid() -> Entry {
+        Entry::Invalid {
+            artist: "artist".into(),
+            album: "album".into(),
+            song: "song".into(),
+        }
+    }
+
+    // A `Valid` entry of `key`.
+    fn valid(key: usize) -> Entry {
+        Entry::Valid {
+            key_artist: Default::default(),
+            key_album: Default::default(),
+            key_song: SongKey::from(key),
+            artist: "artist".into(),
+            album: "album".into(),
+            song: key.to_string().into(),
+        }
+    }
+
+    // A snapshot 60 seconds into `queue_idx`.
+    fn snapshot(queue: Vec<Entry>, queue_idx: Option<usize>) -> QueueSnapshot {
+        QueueSnapshot {
+            queue: queue.into(),
+            queue_idx,
+            elapsed: 60,
+            created: 0,
+        }
+    }
+
+    // The `SongKey`'s of `keys`.
+    fn keys(keys: &[usize]) -> VecDeque<SongKey> {
+        keys.iter().map(|k| SongKey::from(*k)).collect()
+    }
+
+    #[test]
+    // The index follows its `Song` past `Invalid` entries.
+    fn restore() {
+        let s = snapshot(vec![invalid(), valid(1), invalid(), valid(3)], Some(3));
+        assert_eq!(s.restore(), (keys(&[1, 3]), Some(1), 60));
+        assert_eq!(s.valid_len(), 2);
+    }
+
+    #[test]
+    // If the current `Song` went invalid, start at the next valid one.
+    fn restore_current_invalid() {
+        let s = snapshot(vec![valid(0), invalid(), valid(2)], Some(1));
+        assert_eq!(s.restore(), (keys(&[0, 2]), Some(1), 0));
+
+        let s = snapshot(vec![valid(0), valid(1), invalid()], Some(2));
+        assert_eq!(s.restore(), (keys(&[0, 1]), Some(1), 0));
+
+        let s = snapshot(vec![invalid(), invalid()], Some(0));
+        assert_eq!(s.restore(), (keys(&[]), None, 0));
+    }
+}
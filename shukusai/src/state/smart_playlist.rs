@@ -0,0 +1,489 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::{
+    collection::{
+        Collection, FirstSeen, PlayStats, Ratings, SongKey, FIRST_SEEN, PLAY_STATS, RATINGS,
+    },
+    constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, SMART_PLAYLIST_VERSION, STATE_SUB_DIR},
+    sort::SongSort,
+    state::{Entry, Playlists},
+};
+use benri::{lockr, lockw};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+//---------------------------------------------------------------------------------------------------- Constants
+// Seconds in a day, for the `*_days` rules.
+const DAY_SECONDS: u64 = 60 * 60 * 24;
+
+//---------------------------------------------------------------------------------------------------- Lazy
+/// This is the single, global copy of `SmartPlaylists` that `Kernel` uses.
+///
+/// To obtain a read-only lock, use `SMART_PLAYLISTS.read()`.
+pub static SMART_PLAYLISTS: SmartPlaylistsLock =
+    SmartPlaylistsLock(RwLock::new(SmartPlaylists::new()));
+
+//---------------------------------------------------------------------------------------------------- SmartPlaylistsLock
+/// There is only a single, global copy of `SmartPlaylists` that `Kernel` uses: [`SMART_PLAYLISTS`].
+///
+/// To obtain a read-only lock, use `SMART_PLAYLISTS.read()`.
+///
+/// If both are needed, [`crate::state::PLAYLISTS`] must be locked first.
+pub struct SmartPlaylistsLock(RwLock<SmartPlaylists>);
+
+impl SmartPlaylistsLock {
+    #[inline(always)]
+    /// Obtain a read-only lock to the global [`SmartPlaylists`].
+    pub fn read(&'static self) -> RwLockReadGuard<'static, SmartPlaylists> {
+        lockr!(self.0)
+    }
+
+    #[inline(always)]
+    /// Call the non-blocking `.try_read()` on the global [`SmartPlaylists`].
+    pub fn try_read(
+        &'static self,
+    ) -> Result<
+        RwLockReadGuard<'static, SmartPlaylists>,
+        TryLockError<RwLockReadGuard<'static, SmartPlaylists>>,
+    > {
+        self.0.try_read()
+    }
+
+    #[inline(always)]
+    /// Obtain a write lock to the global [`SmartPlaylists`].
+    pub fn write(&'static self) -> RwLockWriteGuard<'static, SmartPlaylists> {
+        lockw!(self.0)
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SmartPlaylists
+disk::bincode2!(
+    SmartPlaylists,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "smart_playlists",
+    HEADER,
+    SMART_PLAYLIST_VERSION
+);
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+#[serde(transparent)]
+#[repr(transparent)]
+/// The rules of all smart playlists, keyed by playlist name.
+///
+/// A smart playlist is a normal playlist in [`crate::state::Playlists`]
+/// whose entries are (re-)created from its [`SmartPlaylist`] rules.
+///
+/// This happens when the rules are set, after every [`Collection`]
+/// reset, and whenever a frontend asks for it, so anything that
+/// works with a normal playlist (queueing, viewing, etc) also works
+/// with a smart one.
+///
+/// Manual changes to a smart playlist's entries are
+/// overwritten the next time it gets re-created.
+pub struct SmartPlaylists(pub BTreeMap<Arc<str>, SmartPlaylist>);
+
+impl std::ops::Deref for SmartPlaylists {
+    type Target = BTreeMap<Arc<str>, SmartPlaylist>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for SmartPlaylists {
+    fn deref_mut(&mut self) -> &mut BTreeMap<Arc<str>, SmartPlaylist> {
+        &mut self.0
+    }
+}
+
+impl SmartPlaylists {
+    #[inline]
+    /// Create an empty `Self` with no allocation.
+    pub const fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Re-create the playlist `name` in `playlists` from its rules.
+    ///
+    /// Returns the new playlist length, or `None` if
+    /// `name` is not a smart playlist (nothing is touched).
+    pub fn refresh(
+        &self,
+        name: &str,
+        playlists: &mut Playlists,
+        collection: &Arc<Collection>,
+    ) -> Option<usize> {
+        let (name, smart) = self.0.get_key_value(name)?;
+        let entries = smart.evaluate(collection);
+        let len = entries.len();
+        playlists.playlist_set(Arc::clone(name), entries);
+        Some(len)
+    }
+
+    /// Re-create all smart playlists in `playlists` from their rules.
+    pub fn refresh_all(&self, playlists: &mut Playlists, collection: &Arc<Collection>) {
+        for (name, smart) in self.0.iter() {
+            playlists.playlist_set(Arc::clone(name), smart.evaluate(collection));
+        }
+    }
+
+    /// Move the rules of the playlist `from` to `into`,
+    /// after [`Playlists::playlist_rename`] succeeded.
+    ///
+    /// Like the playlist itself, any rules `into` had are overwritten,
+    /// and if `from` wasn't a smart playlist, `into` no longer is either.
+    ///
+    /// Renaming a playlist to its own name does nothing.
+    pub fn rename(&mut self, from: &str, into: &str) {
+        if from == into {
+            return;
+        }
+
+        match self.0.remove(from) {
+            Some(smart) => self.0.insert(into.into(), smart),
+            None => self.0.remove(into),
+        };
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- SmartPlaylist
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+/// The rules of a single smart playlist.
+pub struct SmartPlaylist {
+    /// Every rule must match for a `Song` to be included.
+    ///
+    /// No rules means every `Song` in the [`Collection`] matches.
+    pub rules: Vec<Rule>,
+    /// The maximum amount of `Song`'s, `None` means no limit.
+    pub limit: Option<usize>,
+    /// How the matching `Song`'s are ordered, the `limit` is applied after sorting.
+    pub sort: SongSort,
+}
+
+impl SmartPlaylist {
+    /// Evaluate the rules against `collection`, returning the playlist entries.
+    ///
+    /// This uses the current [`RATINGS`], [`PLAY_STATS`] and [`FIRST_SEEN`].
+    pub fn evaluate(&self, collection: &Arc<Collection>) -> VecDeque<Entry> {
+        // `song_iter()` may need to lock these
+        // as well, so collect the keys beforehand.
+        let keys: Vec<SongKey> = collection.song_iter(self.sort).collect();
+
+        let ratings = RATINGS.read();
+        let play_stats = PLAY_STATS.read();
+        let first_seen = FIRST_SEEN.read();
+        let state = RuleState {
+            ratings: &ratings,
+            play_stats: &play_stats,
+            first_seen: &first_seen,
+            now: benri::unix!(),
+        };
+
+        self.filter(keys, collection, &state)
+            .into_iter()
+            .map(|k| Entry::valid_from_song(k, collection))
+            .collect()
+    }
+
+    // Filter the (already sorted) `keys` by our rules and limit.
+    fn filter(
+        &self,
+        keys: Vec<SongKey>,
+        collection: &Collection,
+        state: &RuleState<'_>,
+    ) -> Vec<SongKey> {
+        keys.into_iter()
+            .filter(|k| self.rules.iter().all(|r| r.matches(*k, collection, state)))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- Rule
+#[derive(
+    Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
+#[serde(rename_all = "snake_case")]
+/// A single [`SmartPlaylist`] rule.
+pub enum Rule {
+    /// The `Song`'s `Album` genre is this (ASCII case-insensitive).
+    Genre(String),
+    /// The `Song`'s `Album` was released in between these years (inclusive).
+    ///
+    /// `Album`'s with an unknown release date never match.
+    Year {
+        /// The earliest year
+        from: u16,
+        /// The latest year
+        to: u16,
+    },
+    /// The `Song`'s runtime is less than this many seconds.
+    RuntimeUnder(u32),
+    /// The `Song`'s `Artist` is one of these (ASCII case-insensitive).
+    Artists(Vec<String>),
+    /// The `Song` is rated at least this.
+    RatingAtLeast(u8),
+    /// The `Song` has not been played in this many days, never played `Song`'s match.
+    NotPlayedDays(u64),
+    /// The `Song` was first seen in the last this many days.
+    AddedDays(u64),
+}
+
+// Everything outside the `Collection` that the rules look at.
+struct RuleState<'a> {
+    ratings: &'a Ratings,
+    play_stats: &'a PlayStats,
+    first_seen: &'a FirstSeen,
+    now: u64,
+}
+
+impl Rule {
+    /// One of each [`Rule`], with some default value.
+    pub const DEFAULTS: [Self; 7] = [
+        Self::Genre(String::new()),
+        Self::Year {
+            from: 2000,
+            to: 2009,
+        },
+        Self::RuntimeUnder(240),
+        Self::Artists(Vec::new()),
+        Self::RatingAtLeast(4),
+        Self::NotPlayedDays(30),
+        Self::AddedDays(30),
+    ];
+
+    #[inline]
+    /// Returns a formatted, human readable description of this kind of rule.
+    pub const fn human(&self) -> &'static str {
+        match self {
+            Self::Genre(_) => "Genre is",
+            Self::Year { .. } => "Released between years",
+            Self::RuntimeUnder(_) => "Shorter than (seconds)",
+            Self::Artists(_) => "Artist is one of",
+            Self::RatingAtLeast(_) => "Rated at least",
+            Self::NotPlayedDays(_) => "Not played in (days)",
+            Self::AddedDays(_) => "Added in the last (days)",
+        }
+    }
+
+    // INVARIANT: assumes `key` is valid.
+    //
+    // Does the `Song` at `key` match this rule?
+    fn matches(&self, key: SongKey, collection: &Collection, state: &RuleState<'_>) -> bool {
+        let (artist, album, song) = collection.walk(key);
+
+        match self {
+            Self::Genre(genre) => album
+                .genre
+                .as_ref()
+                .is_some_and(|g| g.eq_ignore_ascii_case(genre)),
+            Self::Year { from, to } => {
+                let year = album.release.year();
+                year != 0 && (*from..=*to).contains(&year)
+            }
+            Self::RuntimeUnder(seconds) => song.runtime.usize() < *seconds as usize,
+            Self::Artists(names) => names.iter().any(|n| n.eq_ignore_ascii_case(&artist.name)),
+            Self::RatingAtLeast(rating) => state.ratings.get(&song.path) >= *rating,
+            Self::NotPlayedDays(days) => {
+                match state.play_stats.get(&song.path).and_then(|s| s.last_played) {
+                    Some(t) => state.now.saturating_sub(t) >= days.saturating_mul(DAY_SECONDS),
+                    None => true,
+                }
+            }
+            Self::AddedDays(days) => {
+                let first_seen = state.first_seen.song(key, collection);
+                state.now.saturating_sub(first_seen) <= days.saturating_mul(DAY_SECONDS)
+            }
+        }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::{Album, AlbumKey, Artist, ArtistKey, Song};
+    use readable::{Date, Runtime};
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    const NOW: u64 = 100 * DAY_SECONDS;
+
+    // 4 `Song`'s, 1 per `Album`, 2 `Album`'s per `Artist`.
+    //
+    // `Song` `i` is `i * 60` seconds long, its `Album` is
+    // released in `2000 + i` with genre "rock" or "jazz".
+    fn collection() -> Collection {
+        let mut c = Collection::test(
+            ["a", "b"]
+                .into_iter()
+                .map(|name| Artist {
+                    name: name.into(),
+                    ..Default::default()
+                })
+                .collect(),
+            (0..4_usize)
+                .map(|i| Album {
+                    title: i.to_string().into(),
+                    artist: ArtistKey::from(i / 2),
+                    release: Date::from_str(&(2000 + i).to_string()).unwrap(),
+                    genre: Some(if i % 2 == 0 { "Rock" } else { "Jazz" }.into()),
+                    ..Default::default()
+                })
+                .collect(),
+            (0..4_usize)
+                .map(|i| Song {
+                    key: SongKey::from(i),
+                    album: AlbumKey::from(i),
+                    runtime: Runtime::from(i as u32 * 60),
+                    path: PathBuf::from(format!("/{i}")),
+                    ..Default::default()
+                })
+                .collect(),
+        );
+        c.timestamp = NOW;
+        c
+    }
+
+    // The `SongKey`'s (in order) matching these rules.
+    fn matching(rules: Vec<Rule>, limit: Option<usize>, state: &RuleState<'_>) -> Vec<usize> {
+        let smart = SmartPlaylist {
+            rules,
+            limit,
+            sort: SongSort::default(),
+        };
+        smart
+            .filter(
+                (0..4_usize).map(SongKey::from).collect(),
+                &collection(),
+                state,
+            )
+            .into_iter()
+            .map(|k| k.inner())
+            .collect()
+    }
+
+    #[test]
+    // Each rule on its own, and all rules together.
+    fn rules() {
+        let c = collection();
+        let mut ratings = Ratings::new();
+        ratings.rate_song(SongKey::from(1_usize), 4, &c);
+        ratings.rate_song(SongKey::from(2_usize), 5, &c);
+        let mut play_stats = PlayStats::new();
+        play_stats.played(SongKey::from(2_usize), NOW - DAY_SECONDS, &c);
+        play_stats.played(SongKey::from(3_usize), NOW - 40 * DAY_SECONDS, &c);
+        let mut first_seen = FirstSeen::new();
+        first_seen
+            .0
+            .insert(PathBuf::from("/0"), NOW - 60 * DAY_SECONDS);
+        first_seen
+            .0
+            .insert(PathBuf::from("/1"), NOW - 5 * DAY_SECONDS);
+        let state = RuleState {
+            ratings: &ratings,
+            play_stats: &play_stats,
+            first_seen: &first_seen,
+            now: NOW,
+        };
+
+        assert_eq!(matching(vec![], None, &state), [0, 1, 2, 3]);
+        assert_eq!(
+            matching(vec![Rule::Genre("rock".into())], None, &state),
+            [0, 2]
+        );
+        assert_eq!(
+            matching(
+                vec![Rule::Year {
+                    from: 2001,
+                    to: 2002
+                }],
+                None,
+                &state
+            ),
+            [1, 2]
+        );
+        assert_eq!(
+            matching(vec![Rule::RuntimeUnder(120)], None, &state),
+            [0, 1]
+        );
+        assert_eq!(
+            matching(vec![Rule::Artists(vec!["B".into()])], None, &state),
+            [2, 3]
+        );
+        assert_eq!(matching(vec![Rule::RatingAtLeast(4)], None, &state), [1, 2]);
+        assert_eq!(
+            matching(vec![Rule::NotPlayedDays(30)], None, &state),
+            [0, 1, 3]
+        );
+        assert_eq!(matching(vec![Rule::AddedDays(30)], None, &state), [1, 2, 3]);
+
+        let rules = vec![Rule::RatingAtLeast(1), Rule::NotPlayedDays(30)];
+        assert_eq!(matching(rules, None, &state), [1]);
+    }
+
+    #[test]
+    // The limit is applied after filtering.
+    fn limit() {
+        let (ratings, play_stats, first_seen) =
+            (Ratings::new(), PlayStats::new(), FirstSeen::new());
+        let state = RuleState {
+            ratings: &ratings,
+            play_stats: &play_stats,
+            first_seen: &first_seen,
+            now: NOW,
+        };
+
+        let rules = vec![Rule::Genre("jazz".into())];
+        assert_eq!(matching(rules.clone(), Some(1), &state), [1]);
+        assert_eq!(matching(rules, Some(0), &state), [0_usize; 0]);
+    }
+
+    #[test]
+    // Rules follow the playlist, renaming to the same name keeps them.
+    fn rename() {
+        let smart = |limit| SmartPlaylist {
+            limit: Some(limit),
+            ..Default::default()
+        };
+        let mut s = SmartPlaylists::new();
+        s.insert("a".into(), smart(1));
+        s.insert("b".into(), smart(2));
+
+        s.rename("a", "a");
+        assert_eq!(s.get("a"), Some(&smart(1)));
+
+        s.rename("a", "b");
+        assert_eq!(s.get("a"), None);
+        assert_eq!(s.get("b"), Some(&smart(1)));
+
+        // A normal playlist overwriting a smart one.
+        s.rename("c", "b");
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn json() {
+        let smart = SmartPlaylist {
+            rules: vec![
+                Rule::Genre("Rock".into()),
+                Rule::Year {
+                    from: 2000,
+                    to: 2010,
+                },
+                Rule::RatingAtLeast(4),
+            ],
+            limit: Some(50),
+            sort: SongSort::Rating,
+        };
+        let json = serde_json::to_string(&smart).unwrap();
+        assert_eq!(
+            json,
+            r#"{"rules":[{"genre":"Rock"},{"year":{"from":2000,"to":2010}},{"rating_at_least":4}],"limit":50,"sort":"rating"}"#
+        );
+        assert_eq!(serde_json::from_str::<SmartPlaylist>(&json).unwrap(), smart);
+    }
+}
@@ -60,6 +60,10 @@ pub fn resetting() -> bool {
 /// If this value is > 100, it will be set to 100.
 pub static VOLUME: AtomicU8 = AtomicU8::new(Volume::const_default().inner());
 
+// The `%` (`0..=100`) the [`VOLUME`] is multiplied by
+// while a [`crate::audio::SleepTimer`] fades out.
+pub(crate) static SLEEP_FADE: AtomicU8 = AtomicU8::new(100);
+
 //---------------------------------------------------------------------------------------------------- Media Controls
 pub(crate) static MEDIA_CONTROLS_RAISE: AtomicBool = AtomicBool::new(false);
 #[inline(always)]
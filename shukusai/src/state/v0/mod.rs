@@ -0,0 +1,5 @@
+mod playlist;
+pub(crate) use playlist::*;
+
+mod audio;
+pub(crate) use audio::*;
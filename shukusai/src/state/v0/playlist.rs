@@ -0,0 +1,40 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, STATE_SUB_DIR};
+use crate::state::Entry;
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+
+//---------------------------------------------------------------------------------------------------- Playlists
+disk::bincode2!(
+    Playlists,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "playlists",
+    HEADER,
+    0
+);
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+#[repr(transparent)]
+/// Version 0 of `Playlists`.
+pub struct Playlists(pub BTreeMap<Arc<str>, VecDeque<Entry>>);
+
+impl Into<crate::state::Playlists> for Playlists {
+    fn into(self) -> crate::state::Playlists {
+        // `v0` had no metadata, create it for every playlist.
+        let mut playlists = crate::state::Playlists(self.0, BTreeMap::new());
+        playlists.metadata_sync();
+        playlists
+    }
+}
+
+impl Playlists {
+    //-------------------------------------------------- Converts v0 from disk into current.
+    pub(crate) fn disk_into() -> Result<crate::state::Playlists, anyhow::Error> {
+        use disk::Bincode2;
+        // SAFETY: memmap is used.
+        unsafe { Self::from_file_memmap().map(Into::into) }
+    }
+}
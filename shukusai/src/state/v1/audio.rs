@@ -0,0 +1,101 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::audio::{Repeat, Volume};
+use crate::collection::SongKey;
+use crate::constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, STATE_SUB_DIR};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use readable::Runtime;
+use std::collections::VecDeque;
+
+//---------------------------------------------------------------------------------------------------- AudioState
+disk::bincode2!(
+    AudioState,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "audio",
+    HEADER,
+    1
+);
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+/// Version 1 of `AudioState`.
+pub struct AudioState {
+    pub queue: VecDeque<SongKey>,
+    pub queue_idx: Option<usize>,
+    pub playing: bool,
+    pub song: Option<SongKey>,
+    pub elapsed: Runtime,
+    pub runtime: Runtime,
+    pub repeat: Repeat,
+    pub volume: Volume,
+    pub auto_dj: bool,
+    pub auto_dj_span: Option<(usize, usize)>,
+}
+
+impl Into<crate::state::AudioState> for AudioState {
+    fn into(self) -> crate::state::AudioState {
+        // `v1` had no sleep timer, chapter, channel mix or output path.
+        crate::state::AudioState {
+            queue: self.queue,
+            queue_idx: self.queue_idx,
+            playing: self.playing,
+            song: self.song,
+            elapsed: self.elapsed,
+            runtime: self.runtime,
+            repeat: self.repeat,
+            volume: self.volume,
+            auto_dj: self.auto_dj,
+            auto_dj_span: self.auto_dj_span,
+            sleep: crate::audio::SleepTimer::Off,
+            chapter: None,
+            channel_mix: crate::audio::ChannelMix::new(),
+            output_path: crate::audio::OutputPath::None,
+            output_rate: 0,
+        }
+    }
+}
+
+impl AudioState {
+    //-------------------------------------------------- Converts v1 from disk into current.
+    pub(crate) fn disk_into() -> Result<crate::state::AudioState, anyhow::Error> {
+        use disk::Bincode2;
+        // SAFETY: memmap is used.
+        unsafe { Self::from_file_memmap().map(Into::into) }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use disk::Bincode2;
+
+    #[test]
+    // Converts both `v1` files, the fields added after `v1` should be their defaults.
+    fn into() {
+        let a1: crate::state::AudioState =
+            AudioState::from_path("../assets/shukusai/state/audio1_new.bin")
+                .unwrap()
+                .into();
+        assert_eq!(a1, crate::state::AudioState::new());
+
+        let a2: crate::state::AudioState =
+            AudioState::from_path("../assets/shukusai/state/audio1_real.bin")
+                .unwrap()
+                .into();
+        assert_eq!(a2.queue.len(), 3);
+        assert_eq!(a2.queue_idx, Some(2));
+        assert_eq!(a2.song, Some(SongKey::from(100_u8)));
+        assert_eq!(a2.elapsed, Runtime::from(123_u16));
+        assert_eq!(a2.runtime, Runtime::from(321_u16));
+        assert_eq!(a2.repeat, Repeat::Queue);
+        assert!(a2.playing);
+        assert!(a2.auto_dj);
+        assert_eq!(a2.auto_dj_span, Some((1, 3)));
+        assert_eq!(a2.sleep, crate::audio::SleepTimer::Off);
+        assert_eq!(a2.chapter, None);
+        assert!(a2.channel_mix.is_passthrough());
+        assert_eq!(a2.output_path, crate::audio::OutputPath::None);
+        assert_eq!(a2.output_rate, 0);
+    }
+}
@@ -0,0 +1,2 @@
+mod audio;
+pub(crate) use audio::*;
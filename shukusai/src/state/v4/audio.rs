@@ -0,0 +1,111 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::audio::{ChannelMix, Repeat, SleepTimer, Volume};
+use crate::collection::SongKey;
+use crate::constants::{FESTIVAL, FRONTEND_SUB_DIR, HEADER, STATE_SUB_DIR};
+use bincode::{Decode, Encode};
+use const_format::formatcp;
+use readable::Runtime;
+use std::collections::VecDeque;
+
+//---------------------------------------------------------------------------------------------------- AudioState
+disk::bincode2!(
+    AudioState,
+    disk::Dir::Data,
+    FESTIVAL,
+    formatcp!("{FRONTEND_SUB_DIR}/{STATE_SUB_DIR}"),
+    "audio",
+    HEADER,
+    4
+);
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+/// Version 4 of `AudioState`.
+pub struct AudioState {
+    pub queue: VecDeque<SongKey>,
+    pub queue_idx: Option<usize>,
+    pub playing: bool,
+    pub song: Option<SongKey>,
+    pub elapsed: Runtime,
+    pub runtime: Runtime,
+    pub repeat: Repeat,
+    pub volume: Volume,
+    pub auto_dj: bool,
+    pub auto_dj_span: Option<(usize, usize)>,
+    pub sleep: SleepTimer,
+    pub chapter: Option<usize>,
+    pub channel_mix: ChannelMix,
+}
+
+impl Into<crate::state::AudioState> for AudioState {
+    fn into(self) -> crate::state::AudioState {
+        // `v4` had no output path.
+        crate::state::AudioState {
+            queue: self.queue,
+            queue_idx: self.queue_idx,
+            playing: self.playing,
+            song: self.song,
+            elapsed: self.elapsed,
+            runtime: self.runtime,
+            repeat: self.repeat,
+            volume: self.volume,
+            auto_dj: self.auto_dj,
+            auto_dj_span: self.auto_dj_span,
+            sleep: self.sleep,
+            chapter: self.chapter,
+            channel_mix: self.channel_mix,
+            output_path: crate::audio::OutputPath::None,
+            output_rate: 0,
+        }
+    }
+}
+
+impl AudioState {
+    //-------------------------------------------------- Converts v4 from disk into current.
+    pub(crate) fn disk_into() -> Result<crate::state::AudioState, anyhow::Error> {
+        use disk::Bincode2;
+        // SAFETY: memmap is used.
+        unsafe { Self::from_file_memmap().map(Into::into) }
+    }
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use disk::Bincode2;
+
+    #[test]
+    // Converts both `v4` files, the fields added after `v4` should be their defaults.
+    fn into() {
+        let a1: crate::state::AudioState =
+            AudioState::from_path("../assets/shukusai/state/audio4_new.bin")
+                .unwrap()
+                .into();
+        assert_eq!(a1, crate::state::AudioState::new());
+
+        let a2: crate::state::AudioState =
+            AudioState::from_path("../assets/shukusai/state/audio4_real.bin")
+                .unwrap()
+                .into();
+        assert_eq!(a2.queue.len(), 3);
+        assert_eq!(a2.queue_idx, Some(2));
+        assert_eq!(a2.song, Some(SongKey::from(100_u8)));
+        assert_eq!(a2.elapsed, Runtime::from(123_u16));
+        assert_eq!(a2.runtime, Runtime::from(321_u16));
+        assert_eq!(a2.repeat, Repeat::Queue);
+        assert!(a2.playing);
+        assert!(a2.auto_dj);
+        assert_eq!(a2.auto_dj_span, Some((1, 3)));
+        assert_eq!(a2.sleep, SleepTimer::Song);
+        assert_eq!(a2.chapter, Some(1));
+        assert_eq!(
+            a2.channel_mix,
+            ChannelMix {
+                mono: true,
+                balance: -20,
+                swap: true,
+            }
+        );
+        assert_eq!(a2.output_path, crate::audio::OutputPath::None);
+        assert_eq!(a2.output_rate, 0);
+    }
+}
@@ -18,6 +18,14 @@ pub(crate) enum WatchToKernel {
     RepeatSong,
     RepeatQueue,
     RepeatOff,
+    RepeatAlbum,
+    RepeatArtist,
+
+    // Sleep timers.
+    SleepSong,
+    SleepAlbum,
+    SleepQueue,
+    SleepOff,
 
     // Content signals.
     Volume(Volume),
@@ -28,6 +36,9 @@ pub(crate) enum WatchToKernel {
     Index(usize),
     Skip(usize),
     Back(usize),
+    RepeatCount(u8),
+    RepeatAb((u32, u32)),
+    SleepMinutes(u32),
     //	ArtistKey(usize),
     //	AlbumKey(usize),
     //	SongKey(usize),
@@ -98,6 +98,24 @@ impl Watch {
         if let Err(e) = RepeatOff::rm() {
             error!("Watch - RepeatOff: {e}");
         }
+        if let Err(e) = RepeatAlbum::rm() {
+            error!("Watch - RepeatAlbum: {e}");
+        }
+        if let Err(e) = RepeatArtist::rm() {
+            error!("Watch - RepeatArtist: {e}");
+        }
+        if let Err(e) = SleepSong::rm() {
+            error!("Watch - SleepSong: {e}");
+        }
+        if let Err(e) = SleepAlbum::rm() {
+            error!("Watch - SleepAlbum: {e}");
+        }
+        if let Err(e) = SleepQueue::rm() {
+            error!("Watch - SleepQueue: {e}");
+        }
+        if let Err(e) = SleepOff::rm() {
+            error!("Watch - SleepOff: {e}");
+        }
 
         // Content files.
         if let Err(e) = Volume::rm() {
@@ -124,6 +142,15 @@ impl Watch {
         if let Err(e) = Back::rm() {
             error!("Watch - Back: {e}");
         }
+        if let Err(e) = RepeatCount::rm() {
+            error!("Watch - RepeatCount: {e}");
+        }
+        if let Err(e) = RepeatAb::rm() {
+            error!("Watch - RepeatAb: {e}");
+        }
+        if let Err(e) = SleepMinutes::rm() {
+            error!("Watch - SleepMinutes: {e}");
+        }
         //		if let Err(e) = ArtistKey::rm()    { error!("Watch - ArtistKey: {e}"); }
         //		if let Err(e) = AlbumKey::rm()     { error!("Watch - AlbumKey: {e}"); }
         //		if let Err(e) = SongKey::rm()      { error!("Watch - SongKey: {e}"); }
@@ -200,6 +227,26 @@ impl Watch {
             if RepeatOff::exists().is_ok() {
                 self.send(WatchToKernel::RepeatOff);
             }
+            if RepeatAlbum::exists().is_ok() {
+                self.send(WatchToKernel::RepeatAlbum);
+            }
+            if RepeatArtist::exists().is_ok() {
+                self.send(WatchToKernel::RepeatArtist);
+            }
+
+            // Sleep timers.
+            if SleepSong::exists().is_ok() {
+                self.send(WatchToKernel::SleepSong);
+            }
+            if SleepAlbum::exists().is_ok() {
+                self.send(WatchToKernel::SleepAlbum);
+            }
+            if SleepQueue::exists().is_ok() {
+                self.send(WatchToKernel::SleepQueue);
+            }
+            if SleepOff::exists().is_ok() {
+                self.send(WatchToKernel::SleepOff);
+            }
 
             // Content signals.
             if let Ok(v) = Volume::from_file() {
@@ -226,6 +273,18 @@ impl Watch {
             if let Ok(s) = Back::from_file() {
                 self.send(WatchToKernel::Back(s.0));
             }
+            if let Ok(s) = RepeatCount::from_file() {
+                self.send(WatchToKernel::RepeatCount(s.0));
+            }
+            if let Ok(s) = RepeatAb::from_file() {
+                match parse_ab(&s.0) {
+                    Some(ab) => self.send(WatchToKernel::RepeatAb(ab)),
+                    None => debug!("Watch - invalid RepeatAb: {}", s.0),
+                }
+            }
+            if let Ok(s) = SleepMinutes::from_file() {
+                self.send(WatchToKernel::SleepMinutes(s.0));
+            }
 
             // Clean folder.
             Self::clean();
@@ -233,11 +292,30 @@ impl Watch {
     }
 }
 
+// Parse the `A-B` seconds of a `RepeatAb` signal.
+//
+// `None` if malformed or `A` is not less than `B`.
+fn parse_ab(s: &str) -> Option<(u32, u32)> {
+    let (a, b) = s.trim().split_once('-')?;
+    let (a, b) = (a.trim().parse().ok()?, b.trim().parse().ok()?);
+    (a < b).then_some((a, b))
+}
+
 //---------------------------------------------------------------------------------------------------- TESTS
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn _parse_ab() {
+        assert_eq!(parse_ab("30-45"), Some((30, 45)));
+        assert_eq!(parse_ab(" 0 - 1 "), Some((0, 1)));
+        assert_eq!(parse_ab("45-30"), None);
+        assert_eq!(parse_ab("30-30"), None);
+        assert_eq!(parse_ab("30"), None);
+        assert_eq!(parse_ab("a-b"), None);
+    }
+
     #[test]
     #[ignore]
     // Tests if all files being created
@@ -316,6 +394,45 @@ mod tests {
         );
         sleep!(S);
 
+        RepeatAlbum::touch().unwrap();
+        assert_eq!(
+            from_watch.recv_timeout(T).unwrap(),
+            WatchToKernel::RepeatAlbum
+        );
+        sleep!(S);
+
+        RepeatArtist::touch().unwrap();
+        assert_eq!(
+            from_watch.recv_timeout(T).unwrap(),
+            WatchToKernel::RepeatArtist
+        );
+        sleep!(S);
+
+        SleepSong::touch().unwrap();
+        assert_eq!(
+            from_watch.recv_timeout(T).unwrap(),
+            WatchToKernel::SleepSong
+        );
+        sleep!(S);
+
+        SleepAlbum::touch().unwrap();
+        assert_eq!(
+            from_watch.recv_timeout(T).unwrap(),
+            WatchToKernel::SleepAlbum
+        );
+        sleep!(S);
+
+        SleepQueue::touch().unwrap();
+        assert_eq!(
+            from_watch.recv_timeout(T).unwrap(),
+            WatchToKernel::SleepQueue
+        );
+        sleep!(S);
+
+        SleepOff::touch().unwrap();
+        assert_eq!(from_watch.recv_timeout(T).unwrap(), WatchToKernel::SleepOff);
+        sleep!(S);
+
         // Content signals.
         // Should be 0..=100
         for i in [0, 50, 100, 101, u8::MAX] {
@@ -373,5 +490,31 @@ mod tests {
             );
             sleep!(S);
         }
+
+        for i in [0, 3, u8::MAX] {
+            RepeatCount(i).save().unwrap();
+            assert_eq!(
+                from_watch.recv_timeout(T).unwrap(),
+                WatchToKernel::RepeatCount(i)
+            );
+            sleep!(S);
+        }
+
+        RepeatAb("30-45".into()).save().unwrap();
+        assert_eq!(
+            from_watch.recv_timeout(T).unwrap(),
+            WatchToKernel::RepeatAb((30, 45))
+        );
+        sleep!(S);
+
+        for i in [0, 30, u32::MAX] {
+            SleepMinutes(i).save().unwrap();
+            assert_eq!(
+                from_watch.recv_timeout(T).unwrap(),
+                WatchToKernel::SleepMinutes(i)
+            );
+            sleep!(S);
+        }
+        sleep!(S);
     }
 }